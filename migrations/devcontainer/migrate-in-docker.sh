@@ -10,6 +10,14 @@ mongoimport \
   --collection=mountains \
   --file="/docker-entrypoint-initdb.d/mountix_db-mountains.json"
 
+mongoimport \
+  --username="$MONGO_INITDB_ROOT_USERNAME" \
+  --password="$MONGO_INITDB_ROOT_PASSWORD" \
+  --authenticationDatabase=admin \
+  --db=mountix_db \
+  --collection=tags \
+  --file="/docker-entrypoint-initdb.d/mountix_db-tags.json"
+
 echo "Creating geospatial index on mountains collection..."
 
 mongosh \
@@ -19,4 +27,13 @@ mongosh \
   mountix_db \
   --eval "db.mountains.createIndex({ location: '2dsphere' });"
 
+echo "Creating name prefix indexes on mountains collection..."
+
+mongosh \
+  --username="$MONGO_INITDB_ROOT_USERNAME" \
+  --password="$MONGO_INITDB_ROOT_PASSWORD" \
+  --authenticationDatabase=admin \
+  mountix_db \
+  --eval "['name_folded', 'name_kana', 'name_romaji', 'aliases.name_folded', 'aliases.name_kana', 'aliases.name_romaji'].forEach((field) => db.mountains.createIndex({ [field]: 1 }));"
+
 echo "Completed migration"
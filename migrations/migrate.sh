@@ -12,6 +12,11 @@ mongoimport $DATABASE_URL \
   --db=mountix_db \
   --file="./data/mountix_db-mountains.json"
 
+mongoimport $DATABASE_URL \
+  --collection=tags \
+  --db=mountix_db \
+  --file="./data/mountix_db-tags.json"
+
 echo "Creating geospatial index on mountains collection..."
 
 mongosh \
@@ -21,6 +26,15 @@ mongosh \
   mountix_db \
   --eval "db.mountains.createIndex({ location: '2dsphere' });"
 
+echo "Creating name prefix indexes on mountains collection..."
+
+mongosh \
+  --username="$MONGO_INITDB_ROOT_USERNAME" \
+  --password="$MONGO_INITDB_ROOT_PASSWORD" \
+  --authenticationDatabase=admin \
+  mountix_db \
+  --eval "['name_folded', 'name_kana', 'name_romaji', 'aliases.name_folded', 'aliases.name_kana', 'aliases.name_romaji'].forEach((field) => db.mountains.createIndex({ [field]: 1 }));"
+
 echo "Completed migration"
 
 brew remove mongodb-database-tools
@@ -2,3 +2,4 @@ pub mod model;
 pub mod modules;
 pub mod persistence;
 pub mod repository;
+pub mod search;
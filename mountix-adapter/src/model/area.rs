@@ -0,0 +1,85 @@
+use mongodb::bson::{doc, Document};
+use mountix_kernel::model::area::Area;
+use mountix_kernel::model::mountain::MountainBoxCoordinates;
+use serde::Deserialize;
+
+/// Result document of `area_stats_pipeline`
+#[derive(Debug, Deserialize)]
+pub struct AreaStatsDocument {
+    #[serde(rename = "_id")]
+    pub name: String,
+    pub mountain_count: u64,
+    pub elevation_min: u32,
+    pub elevation_max: u32,
+    pub prefectures: Vec<Vec<String>>,
+    pub longitude_min: f64,
+    pub latitude_min: f64,
+    pub longitude_max: f64,
+    pub latitude_max: f64,
+}
+
+impl From<AreaStatsDocument> for Area {
+    fn from(stats_doc: AreaStatsDocument) -> Self {
+        Area::new(
+            stats_doc.name,
+            stats_doc.mountain_count,
+            (stats_doc.elevation_min, stats_doc.elevation_max),
+            stats_doc.prefectures.into_iter().flatten().collect(),
+            MountainBoxCoordinates {
+                bottom_left: (stats_doc.longitude_min, stats_doc.latitude_min),
+                upper_right: (stats_doc.longitude_max, stats_doc.latitude_max),
+            },
+        )
+    }
+}
+
+/// Aggregation pipeline grouping mountains by area
+///
+/// 山域ごとに山岳の件数、標高の範囲、都道府県、範囲を集計するパイプライン
+pub fn area_stats_pipeline() -> Vec<Document> {
+    vec![
+        doc! {"$group": {
+            "_id": "$area",
+            "mountain_count": {"$sum": 1},
+            "elevation_min": {"$min": "$elevation"},
+            "elevation_max": {"$max": "$elevation"},
+            "prefectures": {"$addToSet": "$prefectures"},
+            "longitude_min": {"$min": {"$arrayElemAt": ["$location.coordinates", 0]}},
+            "latitude_min": {"$min": {"$arrayElemAt": ["$location.coordinates", 1]}},
+            "longitude_max": {"$max": {"$arrayElemAt": ["$location.coordinates", 0]}},
+            "latitude_max": {"$max": {"$arrayElemAt": ["$location.coordinates", 1]}},
+        }},
+        doc! {"$sort": {"_id": 1}},
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_stats_document_to_area() {
+        let stats_doc: AreaStatsDocument = mongodb::bson::from_document(doc! {
+            "_id": "富士山",
+            "mountain_count": 2,
+            "elevation_min": 1504,
+            "elevation_max": 3776,
+            "prefectures": [["静岡県", "山梨県"], ["静岡県"]],
+            "longitude_min": 138.7275,
+            "latitude_min": 35.2325,
+            "longitude_max": 138.80666666666667,
+            "latitude_max": 35.36083333333333,
+        })
+        .unwrap();
+
+        let area: Area = stats_doc.into();
+        assert_eq!(area.name, "富士山");
+        assert_eq!(area.mountain_count, 2);
+        assert_eq!(area.prefectures, vec!["山梨県", "静岡県"]);
+        assert_eq!(area.bounding_box.bottom_left, (138.7275, 35.2325));
+        assert_eq!(
+            area.bounding_box.upper_right,
+            (138.80666666666667, 35.36083333333333)
+        );
+    }
+}
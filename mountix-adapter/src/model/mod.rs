@@ -1,2 +1,5 @@
+pub mod area;
 pub mod mountain;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
@@ -1,26 +1,135 @@
-use mongodb::bson::{doc, Document};
-use mongodb::options::FindOptions;
+use crate::search::MountainRelevanceRanking;
+use mongodb::bson::{doc, Bson, Document};
+use mountix_kernel::model::geo::{GeoPoint, GeoPolygon, EARTH_RADIUS};
+use mountix_kernel::model::kana::{fold_name_variants, hiragana_to_romaji};
 use mountix_kernel::model::mountain::{
-    Mountain, MountainBoxSearchCondition, MountainData, MountainLocation, MountainSearchCondition,
+    Mountain, MountainAlias, MountainAliasType, MountainBoxSearchCondition, MountainCursor,
+    MountainCursorDirection, MountainCursorValue, MountainData, MountainElevationRange,
+    MountainFacet, MountainFacetCount, MountainFacetKey, MountainLocation, MountainNameCondition,
+    MountainNameMatchType, MountainOrderType, MountainPage, MountainPolygonSearchCondition,
+    MountainPrefecture, MountainSearchCondition, MountainSort, MountainSortKey,
+    MountainSuggestCondition, MountainSuggestMatch, MountainSuggestion, MountainTag,
+    MountainValuesCondition, MountainValuesMode, ELEVATION_BAND_WIDTH, SUGGEST_PEAK_BRACKETS,
+    SUGGEST_PREFERRED_TAG,
+};
+use mountix_kernel::model::mountain_filter::{
+    MountainFilterComparison, MountainFilterCondition, MountainFilterExpr, MountainFilterText,
 };
 use mountix_kernel::model::Id;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MountainDocument {
     #[serde(rename = "_id")]
     pub id: i32,
     pub name: String,
+    /// Name whose orthographic variants are folded, which is generated from `name` when the document is stored
+    #[serde(default)]
+    pub name_folded: String,
     pub name_kana: String,
+    /// Hepburn romaji of the kana name, which is generated from `name_kana` when the document is stored
+    #[serde(default)]
+    pub name_romaji: String,
     pub area: String,
     pub prefectures: Vec<String>,
     pub elevation: u32,
     pub tags: Vec<String>,
     pub location: MountainLocationDocument,
     pub gsi_url: String,
+    #[serde(default)]
+    pub aliases: Vec<MountainAliasDocument>,
+    /// Version for optimistic locking, which is absent in imported documents
+    #[serde(default)]
+    pub version: u32,
+}
+
+impl MountainDocument {
+    pub fn new(id: i32, data: MountainData, version: u32) -> Self {
+        Self {
+            id,
+            name_folded: fold_name_variants(&data.name),
+            name: data.name,
+            name_romaji: hiragana_to_romaji(&data.name_kana),
+            name_kana: data.name_kana,
+            area: data.area,
+            prefectures: data.prefectures,
+            elevation: data.elevation,
+            tags: data.tags,
+            // MongoDBの地理的データ形式: [longitude, latitude]
+            location: MountainLocationDocument {
+                r#type: "Point".to_string(),
+                coordinates: [data.location.longitude, data.location.latitude],
+            },
+            gsi_url: data.location.gsi_url,
+            aliases: data
+                .aliases
+                .into_iter()
+                .map(MountainAliasDocument::new)
+                .collect(),
+            version,
+        }
+    }
+
+    /// Generates the search names which are absent in the imported documents
+    ///
+    /// 取り込んだドキュメントにない検索用の山名を生成します
+    pub(crate) fn fill_search_names(&mut self) {
+        if self.name_folded.is_empty() {
+            self.name_folded = fold_name_variants(&self.name);
+        }
+        if self.name_romaji.is_empty() {
+            self.name_romaji = hiragana_to_romaji(&self.name_kana);
+        }
+        for alias in self.aliases.iter_mut() {
+            if alias.name_folded.is_empty() {
+                alias.name_folded = fold_name_variants(&alias.name);
+            }
+            if alias.name_romaji.is_empty() {
+                alias.name_romaji = hiragana_to_romaji(&alias.name_kana);
+            }
+        }
+    }
+}
+
+/// Alternate name of a mountain with the search names generated like `MountainDocument`
+///
+/// 山の別名。`MountainDocument` と同様に検索用の山名を持ちます
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MountainAliasDocument {
+    pub name: String,
+    #[serde(default)]
+    pub name_folded: String,
+    pub name_kana: String,
+    #[serde(default)]
+    pub name_romaji: String,
+    pub r#type: String,
+}
+
+impl MountainAliasDocument {
+    pub fn new(alias: MountainAlias) -> Self {
+        Self {
+            name_folded: fold_name_variants(&alias.name),
+            name: alias.name,
+            name_romaji: hiragana_to_romaji(&alias.name_kana),
+            name_kana: alias.name_kana,
+            r#type: alias.alias_type.to_key(),
+        }
+    }
+}
+
+impl TryFrom<MountainAliasDocument> for MountainAlias {
+    type Error = anyhow::Error;
+    fn try_from(alias_doc: MountainAliasDocument) -> Result<Self, Self::Error> {
+        Ok(MountainAlias {
+            name: alias_doc.name,
+            name_kana: alias_doc.name_kana,
+            alias_type: MountainAliasType::try_from(alias_doc.r#type)?,
+        })
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MountainLocationDocument {
     pub r#type: String,
     pub coordinates: [f64; 2],
@@ -47,87 +156,744 @@ impl TryFrom<MountainDocument> for Mountain {
             elevation: mountain_doc.elevation,
             location: mountain_location,
             tags: mountain_doc.tags,
+            aliases: mountain_doc
+                .aliases
+                .into_iter()
+                .map(MountainAlias::try_from)
+                .collect::<anyhow::Result<_>>()?,
         };
-        Ok(Mountain::new(mountain_id, data))
+        Ok(Mountain::new(mountain_id, data).with_version(mountain_doc.version))
     }
 }
 
+/// Commands to search mountains
+///
+/// `filter` counts all the mountains matching the condition, and `pipeline` returns a page of them
+/// together with the count and the facet counts in a single `$facet` aggregation.
+/// When sorted by relevance, `relevance_pipeline` replaces `pipeline`, which is empty
 pub struct MountainFindCommand {
     pub(crate) filter: Document,
-    pub(crate) options: FindOptions,
+    pub(crate) pipeline: Vec<Document>,
+    /// Whether the found documents are in reverse of the requested order
+    pub(crate) reversed: bool,
+    pub(crate) facets: Vec<MountainFacetKey>,
+    /// Ranking of the candidates in the search index when sorted by relevance
+    pub(crate) relevance: Option<MountainRelevanceRanking>,
 }
 
 impl TryFrom<MountainSearchCondition> for MountainFindCommand {
     type Error = anyhow::Error;
 
     fn try_from(sc: MountainSearchCondition) -> Result<Self, Self::Error> {
+        let relevance = MountainRelevanceRanking::new(&sc);
         let mut filter = Document::new();
         let mut and_doc: Vec<Document> = Vec::new();
 
         if let Some(name) = sc.name {
-            and_doc.push(doc! {"$or": [{"name": {"$regex": &name, "$options": "i"}}, {"name_kana": {"$regex": &name, "$options": "i"}}]});
+            and_doc.push(name_filter(&name));
+        }
+
+        if let Some(prefecture_doc) =
+            values_filter("prefectures", &sc.prefecture.map(|p| p.name.clone()))
+        {
+            and_doc.push(prefecture_doc);
         }
 
-        if let Some(pref) = sc.prefecture {
-            let pref_name = pref.name;
-            and_doc.push(doc! {"prefectures": &pref_name});
+        if let Some(tag_doc) = values_filter("tags", &sc.tag.map(|t| t.name.clone())) {
+            and_doc.push(tag_doc);
         }
 
-        if let Some(tag) = sc.tag {
-            let tag_name = tag.name;
-            and_doc.push(doc! {"tags": &tag_name});
+        if let Some(area) = sc.area {
+            and_doc.push(doc! {"area": area});
+        }
+
+        if let Some(elevation_doc) = elevation_filter(&sc.elevation) {
+            and_doc.push(elevation_doc);
+        }
+
+        if let Some(expr) = &sc.filter {
+            and_doc.push(filter_expression(expr));
         }
 
         if !and_doc.is_empty() {
             filter.insert("$and", and_doc);
         }
 
-        let key = sc.sort.key.to_key();
-        let value = sc.sort.order.to_value();
-        let sort_doc = doc! {key: value};
+        // 前のページは逆順に検索し、リポジトリで要求された順序に戻す
+        let reversed = sc
+            .cursor
+            .as_ref()
+            .is_some_and(|cursor| cursor.direction == MountainCursorDirection::Prev);
+        let sort = if reversed { sc.sort.reverse() } else { sc.sort };
+
+        // 件数はカーソルの位置によらず検索条件全体で数えるため、カーソルはページの取得にのみ適用する
+        // 関連度順では索引の候補で絞り込む必要があるため、リポジトリで `relevance_pipeline` を生成する
+        let pipeline = if relevance.is_some() {
+            Vec::new()
+        } else {
+            let page_pipeline = page_stages(&sort, sc.cursor.as_ref(), sc.skip, sc.limit)?;
+            facet_pipeline(filter.clone(), page_pipeline, &sc.facets)
+        };
+
+        Ok(MountainFindCommand {
+            filter,
+            pipeline,
+            reversed,
+            facets: sc.facets,
+            relevance,
+        })
+    }
+}
+
+impl MountainFindCommand {
+    /// Returns the pipeline returning the ids of the mountains matching both the filter and the candidates
+    /// of the relevance ranking, together with the count and the facet counts
+    ///
+    /// 検索条件と関連度の候補の両方に一致した山岳の ID を、件数とファセットの件数とともに返すパイプラインを生成します
+    pub(crate) fn relevance_pipeline(&self, candidates: Vec<i32>) -> Vec<Document> {
+        let filter = doc! {"$and": [self.filter.clone(), {"_id": {"$in": candidates}}]};
+        facet_pipeline(filter, vec![doc! {"$project": {"_id": 1}}], &self.facets)
+    }
+}
+
+/// Result of the `$facet` stage built by `facet_pipeline`
+#[derive(Debug, Deserialize)]
+pub struct MountainFacetDocument<T = MountainDocument> {
+    pub mountains: Vec<T>,
+    pub total: Vec<MountainCountDocument>,
+    #[serde(default)]
+    pub prefecture: Vec<MountainFacetValueDocument<String>>,
+    #[serde(default)]
+    pub tag: Vec<MountainFacetValueDocument<String>>,
+    #[serde(default)]
+    pub area: Vec<MountainFacetValueDocument<String>>,
+    #[serde(default)]
+    pub elevation_band: Vec<MountainFacetValueDocument<u32>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MountainCountDocument {
+    pub count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MountainFacetValueDocument<T> {
+    #[serde(rename = "_id")]
+    pub value: T,
+    pub count: u64,
+}
+
+/// Document holding only `_id`, which the relevance pipeline returns
+#[derive(Debug, Deserialize)]
+pub struct MountainIdDocument {
+    #[serde(rename = "_id")]
+    pub id: i32,
+}
+
+impl<T> MountainFacetDocument<T> {
+    /// Returns the count, which is absent when no mountain matches the condition
+    ///
+    /// 件数を返します。条件に一致する山岳がない場合 `$count` は結果を返さないため 0 とします
+    pub fn total(&self) -> u64 {
+        self.total.first().map(|total| total.count).unwrap_or(0)
+    }
 
-        let options = FindOptions::builder()
-            .sort(sort_doc)
-            .skip(sc.skip)
-            .limit(sc.limit)
-            .build();
+    /// Returns the facets in the requested order
+    ///
+    /// 要求された順序のファセットを返します
+    pub fn facets(&self, facets: &[MountainFacetKey]) -> Vec<MountainFacet> {
+        let value_counts = |counts: &[MountainFacetValueDocument<String>]| {
+            counts
+                .iter()
+                .map(|c| MountainFacetCount {
+                    value: c.value.clone(),
+                    count: c.count,
+                })
+                .collect()
+        };
+        facets
+            .iter()
+            .map(|facet| MountainFacet {
+                key: *facet,
+                counts: match facet {
+                    MountainFacetKey::Prefecture => value_counts(&self.prefecture),
+                    MountainFacetKey::Tag => value_counts(&self.tag),
+                    MountainFacetKey::Area => value_counts(&self.area),
+                    MountainFacetKey::ElevationBand => self
+                        .elevation_band
+                        .iter()
+                        .map(|c| MountainFacetCount::elevation_band(c.value, c.count))
+                        .collect(),
+                },
+            })
+            .collect()
+    }
+}
+
+impl MountainFacetDocument {
+    /// Converts to a page of mountains with the facets in the requested order
+    ///
+    /// 要求された順序のファセットを含む山岳のページに変換します
+    pub fn into_page(
+        self,
+        facets: &[MountainFacetKey],
+        reversed: bool,
+    ) -> anyhow::Result<MountainPage> {
+        let total = self.total();
+        let facets = self.facets(facets);
+
+        let mut mountains: Vec<Mountain> = Vec::new();
+        for md in self.mountains {
+            mountains.push(md.try_into()?);
+        }
+        if reversed {
+            mountains.reverse();
+        }
 
-        Ok(MountainFindCommand { filter, options })
+        Ok(MountainPage {
+            mountains,
+            total,
+            facets,
+            relevance: BTreeMap::new(),
+        })
     }
 }
 
+/// Commands to search mountains inside the box
+///
+/// `pipeline` returns a page of them together with the count and the facet counts
+/// in a single `$facet` aggregation
 pub struct MountainFindBoxCommand {
-    pub(crate) filter: Document,
-    pub(crate) options: FindOptions,
+    pub(crate) pipeline: Vec<Document>,
+    pub(crate) facets: Vec<MountainFacetKey>,
+}
+
+/// Returns the stages to sort and page the documents after the cursor, using `_id` as the tiebreaker
+///
+/// ドキュメントを並び替えてカーソルより後ろの取得範囲を切り出すステージを生成します。並び順が一意に定まるよう _id を最後のキーにします
+pub(crate) fn page_stages(
+    sort: &MountainSort,
+    cursor: Option<&MountainCursor>,
+    skip: u64,
+    limit: Option<i64>,
+) -> anyhow::Result<Vec<Document>> {
+    let mut stages: Vec<Document> = Vec::new();
+    let fields = sort_fields(sort)?;
+    if !fields.is_empty() {
+        stages.push(doc! {"$addFields": fields});
+    }
+    // 計算したキーの値でも絞り込めるよう、カーソルは項目を追加した後に適用する
+    if let Some(cursor) = cursor {
+        stages.push(doc! {"$match": cursor_filter(cursor, sort)});
+    }
+
+    let mut sort_doc = Document::new();
+    for condition in &sort.conditions {
+        sort_doc.insert(condition.key.to_key(), condition.order.to_value());
+    }
+    if !sort.contains(MountainSortKey::Id) {
+        sort_doc.insert("_id", sort.primary().order.to_value());
+    }
+
+    stages.push(doc! {"$sort": sort_doc});
+    stages.push(doc! {"$skip": skip as i64});
+    // MongoDB の find と同様に limit が 0 の場合は上限なしとして扱う
+    if let Some(limit) = limit.filter(|limit| *limit != 0) {
+        stages.push(doc! {"$limit": limit.abs()});
+    }
+    Ok(stages)
+}
+
+/// Returns the fields to add for the sort keys which are not stored in the documents
+///
+/// ドキュメントに保存されていない並び替えのキーの値を計算する項目を生成します
+pub(crate) fn sort_fields(sort: &MountainSort) -> anyhow::Result<Document> {
+    let mut fields = Document::new();
+    for condition in &sort.conditions {
+        let field = match condition.key {
+            MountainSortKey::Prefecture => {
+                // 都道府県コードは都道府県コード順の一覧での位置から求める。一覧にない都道府県と都道府県がない場合は 0 とする
+                let names: Vec<String> = MountainPrefecture::all()
+                    .into_iter()
+                    .map(|prefecture| prefecture.name)
+                    .collect();
+                doc! {"$ifNull": [
+                    {"$min": {"$map": {
+                        "input": "$prefectures",
+                        "in": {"$add": [{"$indexOfArray": [names, "$$this"]}, 1]},
+                    }}},
+                    0,
+                ]}
+            }
+            MountainSortKey::Latitude => doc! {"$arrayElemAt": ["$location.coordinates", 1]},
+            MountainSortKey::Distance => {
+                let point = sort.point.ok_or(anyhow::anyhow!(
+                    "The point to sort by the distance is not set."
+                ))?;
+                distance_expression(&point)
+            }
+            _ => continue,
+        };
+        fields.insert(condition.key.to_key(), field);
+    }
+    Ok(fields)
+}
+
+/// Returns an expression of the great-circle distance from the point in meters like `GeoPoint::distance_to`
+///
+/// 地点からの大圏距離 (m) を `GeoPoint::distance_to` と同様に計算する式を生成します
+fn distance_expression(point: &GeoPoint) -> Document {
+    let (from_lat, from_lng) = (point.latitude.to_radians(), point.longitude.to_radians());
+    // GeoJSON の座標は [longitude, latitude] の順序
+    let lat = doc! {"$degreesToRadians": {"$arrayElemAt": ["$location.coordinates", 1]}};
+    let lng = doc! {"$degreesToRadians": {"$arrayElemAt": ["$location.coordinates", 0]}};
+    let half_sin_squared = |delta: Document| doc! {"$pow": [{"$sin": {"$divide": [delta, 2]}}, 2]};
+
+    let a = doc! {"$add": [
+        half_sin_squared(doc! {"$subtract": [lat.clone(), from_lat]}),
+        {"$multiply": [
+            from_lat.cos(),
+            {"$cos": lat},
+            half_sin_squared(doc! {"$subtract": [lng, from_lng]}),
+        ]},
+    ]};
+    doc! {"$multiply": [2.0 * EARTH_RADIUS, {"$asin": {"$min": [{"$sqrt": a}, 1]}}]}
+}
+
+/// Returns the pipeline counting the documents per value of the facet
+///
+/// ファセットの値ごとにドキュメントの件数を数えるパイプラインを生成します
+pub(crate) fn facet_count_stages(facet: MountainFacetKey) -> Vec<Document> {
+    let group_by = |field: &str| {
+        vec![
+            doc! {"$group": {"_id": field, "count": {"$sum": 1}}},
+            doc! {"$sort": {"count": -1, "_id": 1}},
+        ]
+    };
+
+    match facet {
+        MountainFacetKey::Prefecture => {
+            let mut stages = vec![doc! {"$unwind": "$prefectures"}];
+            stages.extend(group_by("$prefectures"));
+            stages
+        }
+        MountainFacetKey::Tag => {
+            let mut stages = vec![doc! {"$unwind": "$tags"}];
+            stages.extend(group_by("$tags"));
+            stages
+        }
+        MountainFacetKey::Area => group_by("$area"),
+        MountainFacetKey::ElevationBand => vec![
+            doc! {"$group": {
+                "_id": {"$subtract": ["$elevation", {"$mod": ["$elevation", ELEVATION_BAND_WIDTH as i64]}]},
+                "count": {"$sum": 1},
+            }},
+            doc! {"$sort": {"_id": 1}},
+        ],
+    }
+}
+
+/// Returns the pipeline returning a page of the matched documents, their count and the facet counts
+///
+/// 条件に一致したドキュメントの取得範囲、件数、ファセットごとの件数を 1 つの `$facet` で返すパイプラインを生成します
+pub(crate) fn facet_pipeline(
+    filter: Document,
+    page_pipeline: Vec<Document>,
+    facets: &[MountainFacetKey],
+) -> Vec<Document> {
+    let mut facet_doc = doc! {
+        "mountains": page_pipeline,
+        "total": [{"$count": "count"}],
+    };
+    for facet in facets {
+        facet_doc.insert(facet.to_key(), facet_count_stages(*facet));
+    }
+
+    vec![doc! {"$match": filter}, doc! {"$facet": facet_doc}]
+}
+
+/// Returns a filter matching the name, the folded name, the kana name or the romaji name of the mountain
+/// or its aliases with the name condition
+///
+/// 山岳名、表記ゆれをまとめた山岳名、山岳名 (かな)、山岳名 (ローマ字) または別名のいずれかが山名の条件に一致するフィルタを生成します
+pub(crate) fn name_filter(name: &MountainNameCondition) -> Document {
+    let patterns = name.to_patterns();
+    let mut fields = vec![("name", &patterns.name), ("name_kana", &patterns.kana)];
+    if let Some(folded) = &patterns.folded {
+        fields.push(("name_folded", folded));
+    }
+    if let Some(romaji) = &patterns.romaji {
+        fields.push(("name_romaji", romaji));
+    }
+
+    // 別名も山名と同様に照合する
+    let or_doc: Vec<Document> = ["", "aliases."]
+        .iter()
+        .flat_map(|prefix| {
+            fields.iter().map(move |(field, pattern)| {
+                doc! {format!("{}{}", prefix, field): {"$regex": *pattern, "$options": "i"}}
+            })
+        })
+        .collect();
+    doc! {"$or": or_doc}
+}
+
+/// Compiles the filter expression of the `q` parameter into a filter
+///
+/// `q` パラメータのフィルタ式をフィルタに変換します
+pub(crate) fn filter_expression(expr: &MountainFilterExpr) -> Document {
+    let compile_all =
+        |exprs: &[MountainFilterExpr]| exprs.iter().map(filter_expression).collect::<Vec<_>>();
+    match expr {
+        MountainFilterExpr::And(exprs) => doc! {"$and": compile_all(exprs)},
+        MountainFilterExpr::Or(exprs) => doc! {"$or": compile_all(exprs)},
+        MountainFilterExpr::Not(expr) => doc! {"$nor": [filter_expression(expr)]},
+        MountainFilterExpr::Condition(condition) => match condition {
+            MountainFilterCondition::Elevation(comparison, elevation) => {
+                let op = match comparison {
+                    MountainFilterComparison::Eq => "$eq",
+                    MountainFilterComparison::Gt => "$gt",
+                    MountainFilterComparison::Gte => "$gte",
+                    MountainFilterComparison::Lt => "$lt",
+                    MountainFilterComparison::Lte => "$lte",
+                };
+                doc! {"elevation": {op: elevation}}
+            }
+            MountainFilterCondition::Tag(tag) => doc! {"tags": tag},
+            MountainFilterCondition::Prefecture(prefecture) => doc! {"prefectures": prefecture},
+            MountainFilterCondition::Area(MountainFilterText::Exact(area)) => doc! {"area": area},
+            MountainFilterCondition::Area(text) => doc! {"area": {"$regex": text.to_pattern()}},
+            MountainFilterCondition::Name(name) => name_filter(name),
+        },
+    }
+}
+
+/// Returns a keyset filter matching the documents after the cursor in the sort,
+/// comparing the sort keys and `_id` lexicographically
+///
+/// 並び順でカーソルより後ろにあるドキュメントに一致するフィルタを生成します。
+/// ソートキーと _id を辞書式に比較し、手前のキーがすべて等しい場合に次のキーで比較します
+pub(crate) fn cursor_filter(cursor: &MountainCursor, sort: &MountainSort) -> Document {
+    let op = |order: MountainOrderType| match order {
+        MountainOrderType::Asc => "$gt",
+        MountainOrderType::Desc => "$lt",
+    };
+
+    let mut keys: Vec<(String, Bson, MountainOrderType)> = cursor
+        .values
+        .iter()
+        .zip(&sort.conditions)
+        .map(|(value, condition)| {
+            let value = match value {
+                MountainCursorValue::Id => Bson::from(cursor.id),
+                MountainCursorValue::Elevation(elevation) => Bson::from(*elevation),
+                MountainCursorValue::Name(name) => Bson::from(name),
+                MountainCursorValue::Prefecture(code) => Bson::from(*code as i64),
+                MountainCursorValue::Area(area) => Bson::from(area),
+                MountainCursorValue::Latitude(latitude) => Bson::from(*latitude),
+                MountainCursorValue::Distance(distance) => Bson::from(*distance),
+            };
+            (condition.key.to_key(), value, condition.order)
+        })
+        .collect();
+    if !sort.contains(MountainSortKey::Id) {
+        keys.push((
+            "_id".to_string(),
+            Bson::from(cursor.id),
+            sort.primary().order,
+        ));
+    }
+
+    let mut terms: Vec<Document> = (0..keys.len())
+        .map(|i| {
+            let mut term = Document::new();
+            for (key, value, _) in &keys[..i] {
+                term.insert(key, value.clone());
+            }
+            let (key, value, order) = &keys[i];
+            term.insert(key, doc! {op(*order): value.clone()});
+            term
+        })
+        .collect();
+    let filter = if terms.len() == 1 {
+        terms.remove(0)
+    } else {
+        doc! {"$or": terms}
+    };
+
+    // 距離はデータベースで計算するため、誤差でカーソルの山岳自身が一致しないよう除外する
+    if sort.contains(MountainSortKey::Distance) {
+        doc! {"$and": [filter, {"_id": {"$ne": cursor.id}}]}
+    } else {
+        filter
+    }
+}
+
+/// Returns a filter on the array field with `$in` or `$all` by the mode,
+/// or `None` if there are no values
+///
+/// 配列の項目を結合方法に応じて `$in` または `$all` で絞り込むフィルタを生成します
+pub(crate) fn values_filter(
+    field: &str,
+    condition: &MountainValuesCondition<String>,
+) -> Option<Document> {
+    match condition.values.as_slice() {
+        [] => None,
+        [value] => Some(doc! {field: value}),
+        values => {
+            let operator = match condition.mode {
+                MountainValuesMode::Any => "$in",
+                MountainValuesMode::All => "$all",
+            };
+            Some(doc! {field: {operator: values}})
+        }
+    }
+}
+
+/// Returns a range filter on the elevation, or `None` if the range is unbounded
+///
+/// 標高の範囲で絞り込むフィルタを生成します
+pub(crate) fn elevation_filter(elevation: &MountainElevationRange) -> Option<Document> {
+    if elevation.is_unbounded() {
+        return None;
+    }
+
+    let mut range_doc = Document::new();
+    if let Some(min) = elevation.min {
+        range_doc.insert("$gte", min);
+    }
+    if let Some(max) = elevation.max {
+        range_doc.insert("$lte", max);
+    }
+    Some(doc! {"elevation": range_doc})
+}
+
+/// Returns a filter matching the mountain with the version
+///
+/// バージョンを指定して山岳情報を特定するフィルタを生成します
+pub(crate) fn version_filter(id: i32, version: u32) -> Document {
+    if version == 0 {
+        // インポートされたドキュメントには version が存在しない
+        doc! {"_id": id, "version": {"$in": [0, null]}}
+    } else {
+        doc! {"_id": id, "version": version}
+    }
+}
+
+/// Returns a filter of the box and polygon searches, which narrows the mountains inside the area
+/// with the same conditions as the search
+///
+/// 範囲検索とポリゴン検索のフィルタを生成します。範囲内の山岳を検索と同じ条件で絞り込みます
+pub(crate) fn geo_search_filter(
+    location: Document,
+    name: Option<&MountainNameCondition>,
+    tag: &MountainValuesCondition<MountainTag>,
+    area: Option<&str>,
+    elevation: &MountainElevationRange,
+) -> Document {
+    let mut and_doc = vec![location];
+
+    if let Some(name) = name {
+        and_doc.push(name_filter(name));
+    }
+
+    if let Some(tag_doc) = values_filter("tags", &tag.map(|t| t.name.clone())) {
+        and_doc.push(tag_doc);
+    }
+
+    if let Some(area) = area {
+        and_doc.push(doc! {"area": area});
+    }
+
+    if let Some(elevation_doc) = elevation_filter(elevation) {
+        and_doc.push(elevation_doc);
+    }
+
+    doc! {"$and": and_doc}
 }
 
 impl TryFrom<MountainBoxSearchCondition> for MountainFindBoxCommand {
     type Error = anyhow::Error;
 
     fn try_from(sc: MountainBoxSearchCondition) -> Result<Self, Self::Error> {
-        let mut filter = Document::new();
         // MongoDBの地理的ボックス検索では [longitude, latitude] の順序が必要
-        let mut and_doc = vec![
-            doc! {"location": {"$geoWithin": {"$box": [[sc.box_coordinates.bottom_left.0,sc.box_coordinates.bottom_left.1], [sc.box_coordinates.upper_right.0,sc.box_coordinates.upper_right.1]]}}},
-        ];
+        let location = doc! {"location": {"$geoWithin": {"$box": [[sc.box_coordinates.bottom_left.0,sc.box_coordinates.bottom_left.1], [sc.box_coordinates.upper_right.0,sc.box_coordinates.upper_right.1]]}}};
+        let filter = geo_search_filter(
+            location,
+            sc.name.as_ref(),
+            &sc.tag,
+            sc.area.as_deref(),
+            &sc.elevation,
+        );
 
-        if let Some(name) = sc.name {
-            and_doc.push(doc! {"$or": [{"name": {"$regex": &name, "$options": "i"}}, {"name_kana": {"$regex": &name, "$options": "i"}}]});
+        let page_pipeline = page_stages(&sc.sort, None, sc.skip, sc.limit)?;
+        let pipeline = facet_pipeline(filter, page_pipeline, &sc.facets);
+
+        Ok(MountainFindBoxCommand {
+            pipeline,
+            facets: sc.facets,
+        })
+    }
+}
+
+/// Commands to search mountains inside the polygon
+///
+/// `pipeline` returns a page of them together with the count in a single `$facet` aggregation
+pub struct MountainFindPolygonCommand {
+    pub(crate) pipeline: Vec<Document>,
+}
+
+/// Returns a GeoJSON geometry document of the polygon
+///
+/// ポリゴンの GeoJSON ジオメトリのドキュメントを生成します
+pub(crate) fn polygon_geometry(polygon: &GeoPolygon) -> Document {
+    // GeoJSON の座標は [longitude, latitude] の順序
+    let polygons: Vec<Vec<Vec<Vec<f64>>>> = polygon
+        .polygons
+        .iter()
+        .map(|rings| {
+            rings
+                .iter()
+                .map(|ring| ring.iter().map(|p| vec![p.longitude, p.latitude]).collect())
+                .collect()
+        })
+        .collect();
+
+    if polygon.is_multi() {
+        doc! {"type": "MultiPolygon", "coordinates": polygons}
+    } else {
+        doc! {"type": "Polygon", "coordinates": polygons.into_iter().next().unwrap_or_default()}
+    }
+}
+
+impl TryFrom<MountainPolygonSearchCondition> for MountainFindPolygonCommand {
+    type Error = anyhow::Error;
+
+    fn try_from(sc: MountainPolygonSearchCondition) -> Result<Self, Self::Error> {
+        let location =
+            doc! {"location": {"$geoWithin": {"$geometry": polygon_geometry(&sc.polygon)}}};
+        let filter = geo_search_filter(
+            location,
+            sc.name.as_ref(),
+            &sc.tag,
+            sc.area.as_deref(),
+            &sc.elevation,
+        );
+
+        let page_pipeline = page_stages(&sc.sort, None, sc.skip, sc.limit)?;
+        let pipeline = facet_pipeline(filter, page_pipeline, &[]);
+
+        Ok(MountainFindPolygonCommand { pipeline })
+    }
+}
+
+/// Aggregation of the suggestions, which ranks the candidates in the same order as
+/// `MountainSuggestCondition::rank` and returns only the fields shown in the search box
+///
+/// 候補の山岳を `MountainSuggestCondition::rank` と同じ順序で並べ、検索ボックスに表示する項目のみを返す集計コマンド
+pub struct MountainSuggestCommand {
+    pub(crate) pipeline: Vec<Document>,
+}
+
+/// Document of a suggestion, which has only the projected fields
+///
+/// 候補の山岳のドキュメント。射影した項目のみを持ちます
+#[derive(Debug, Deserialize)]
+pub struct MountainSuggestionDocument {
+    #[serde(rename = "_id")]
+    pub id: i32,
+    pub name: String,
+    pub name_kana: String,
+    pub prefectures: Vec<String>,
+}
+
+impl From<MountainSuggestionDocument> for MountainSuggestion {
+    fn from(sd: MountainSuggestionDocument) -> Self {
+        Self {
+            id: Id::new(sd.id),
+            name: sd.name,
+            name_kana: sd.name_kana,
+            prefectures: sd.prefectures,
         }
+    }
+}
 
-        if let Some(tag) = sc.tag {
-            let tag_name = tag.name;
-            and_doc.push(doc! {"tags": &tag_name});
+impl From<&MountainSuggestCondition> for MountainSuggestCommand {
+    fn from(sc: &MountainSuggestCondition) -> Self {
+        let prefixes = sc.prefixes();
+        let mut fields = vec![
+            ("name_folded", prefixes.folded),
+            ("name_kana", prefixes.kana),
+        ];
+        if let Some(romaji) = prefixes.romaji {
+            fields.push(("name_romaji", romaji));
         }
+        let patterns: Vec<(&str, String)> = fields
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(field, value)| {
+                let pattern = MountainNameCondition {
+                    value,
+                    match_type: MountainNameMatchType::Prefix,
+                }
+                .to_pattern();
+                (field, pattern)
+            })
+            .collect();
 
-        filter.insert("$and", and_doc);
+        // 大文字小文字を区別しない正規表現はインデックスの範囲検索にならないため、正規化済みの値に前方一致で照合する
+        let or_doc: Vec<Document> = ["", "aliases."]
+            .iter()
+            .flat_map(|prefix| {
+                patterns.iter().map(move |(field, pattern)| {
+                    doc! {format!("{}{}", prefix, field): {"$regex": pattern}}
+                })
+            })
+            .collect();
+        let filter = doc! {"$or": or_doc};
 
-        let key = sc.sort.key.to_key();
-        let value = sc.sort.order.to_value();
-        let sort_doc = doc! {key: value};
+        // 入力値全体への一致は、入力値の後が末尾または山頂名の括弧であるものとする
+        let brackets: String = SUGGEST_PEAK_BRACKETS.iter().collect();
+        let matches = |variable: &str, whole: bool| -> Document {
+            let conditions: Vec<Document> = patterns
+                .iter()
+                .map(|(field, pattern)| {
+                    let regex = if whole {
+                        format!("{}(?:$|[{}])", pattern, brackets)
+                    } else {
+                        pattern.clone()
+                    };
+                    doc! {"$regexMatch": {"input": format!("{}.{}", variable, field), "regex": regex}}
+                })
+                .collect();
+            doc! {"$or": conditions}
+        };
+        let alias_matches = |whole: bool| {
+            doc! {"$anyElementTrue": [{"$map": {
+                "input": {"$ifNull": ["$aliases", []]},
+                "in": matches("$$this", whole),
+            }}]}
+        };
+        let match_quality = doc! {"$switch": {
+            "branches": [
+                {"case": matches("$$ROOT", true), "then": MountainSuggestMatch::Name as i32},
+                {"case": alias_matches(true), "then": MountainSuggestMatch::Alias as i32},
+                {"case": matches("$$ROOT", false), "then": MountainSuggestMatch::NamePrefix as i32},
+            ],
+            "default": MountainSuggestMatch::AliasPrefix as i32,
+        }};
 
-        let options = FindOptions::builder().sort(sort_doc).build();
+        let pipeline = vec![
+            doc! {"$match": filter},
+            doc! {"$addFields": {
+                "suggest_match": match_quality,
+                "suggest_preferred": {"$in": [SUGGEST_PREFERRED_TAG, {"$ifNull": ["$tags", []]}]},
+            }},
+            doc! {"$sort": {"suggest_match": 1, "suggest_preferred": -1, "elevation": -1, "_id": 1}},
+            doc! {"$limit": sc.limit as i64},
+            doc! {"$project": {"name": 1, "name_kana": 1, "prefectures": 1}},
+        ];
 
-        Ok(MountainFindBoxCommand { filter, options })
+        MountainSuggestCommand { pipeline }
     }
 }
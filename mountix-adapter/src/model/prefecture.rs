@@ -0,0 +1,69 @@
+use mongodb::bson::{doc, Document};
+use mountix_kernel::model::prefecture::{PrefectureHighestPeak, PrefectureMountainStats};
+use serde::Deserialize;
+
+/// Result document of `prefecture_stats_pipeline`
+#[derive(Debug, Deserialize)]
+pub struct PrefectureStatsDocument {
+    #[serde(rename = "_id")]
+    pub name: String,
+    pub mountain_count: u64,
+    pub highest_id: i32,
+    pub highest_name: String,
+    pub highest_elevation: u32,
+}
+
+impl From<PrefectureStatsDocument> for PrefectureMountainStats {
+    fn from(stats_doc: PrefectureStatsDocument) -> Self {
+        Self {
+            name: stats_doc.name,
+            mountain_count: stats_doc.mountain_count,
+            highest_peak: Some(PrefectureHighestPeak {
+                id: stats_doc.highest_id.into(),
+                name: stats_doc.highest_name,
+                elevation: stats_doc.highest_elevation,
+            }),
+        }
+    }
+}
+
+/// Aggregation pipeline grouping mountains by prefecture
+///
+/// 都道府県ごとに山岳の件数と最高峰を集計するパイプライン
+pub fn prefecture_stats_pipeline() -> Vec<Document> {
+    vec![
+        doc! {"$unwind": "$prefectures"},
+        doc! {"$sort": {"elevation": -1, "_id": 1}},
+        doc! {"$group": {
+            "_id": "$prefectures",
+            "mountain_count": {"$sum": 1},
+            "highest_id": {"$first": "$_id"},
+            "highest_name": {"$first": "$name"},
+            "highest_elevation": {"$first": "$elevation"},
+        }},
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefecture_stats_document_to_stats() {
+        let stats_doc: PrefectureStatsDocument = mongodb::bson::from_document(doc! {
+            "_id": "山梨県",
+            "mountain_count": 31,
+            "highest_id": 396,
+            "highest_name": "富士山<剣ヶ峯>",
+            "highest_elevation": 3776,
+        })
+        .unwrap();
+
+        let stats: PrefectureMountainStats = stats_doc.into();
+        assert_eq!(stats.name, "山梨県");
+        assert_eq!(stats.mountain_count, 31);
+        let highest_peak = stats.highest_peak.unwrap();
+        assert_eq!(highest_peak.id.value, 396);
+        assert_eq!(highest_peak.elevation, 3776);
+    }
+}
@@ -1,7 +1,12 @@
+use crate::model::mountain::{elevation_filter, name_filter, MountainDocument};
 use mongodb::bson::{doc, Document};
+use mongodb::options::FindOptions;
+use mountix_kernel::model::geo::{GeoLineString, GeoPoint, EARTH_RADIUS};
+use mountix_kernel::model::mountain::MountainSortKey;
 use mountix_kernel::model::surrounding_mountain::{
+    CorridorMountainSearchCondition, NearbyMountainSearchCondition, NearestMountainSearchCondition,
     SurroundingMountain, SurroundingMountainData, SurroundingMountainLocation,
-    SurroundingMountainSearchCondition,
+    SurroundingMountainSearchCondition, SurroundingMountainSortKey,
 };
 use mountix_kernel::model::Id;
 use serde::{Deserialize, Serialize};
@@ -26,6 +31,25 @@ pub struct SurroundingMountainLocationDocument {
     pub coordinates: [f64; 2],
 }
 
+impl From<MountainDocument> for SurroundingMountainDocument {
+    fn from(mountain_doc: MountainDocument) -> Self {
+        Self {
+            id: mountain_doc.id,
+            name: mountain_doc.name,
+            name_kana: mountain_doc.name_kana,
+            area: mountain_doc.area,
+            prefectures: mountain_doc.prefectures,
+            elevation: mountain_doc.elevation,
+            tags: mountain_doc.tags,
+            location: SurroundingMountainLocationDocument {
+                r#type: mountain_doc.location.r#type,
+                coordinates: mountain_doc.location.coordinates,
+            },
+            gsi_url: mountain_doc.gsi_url,
+        }
+    }
+}
+
 impl TryFrom<SurroundingMountainDocument> for SurroundingMountain {
     type Error = anyhow::Error;
     fn try_from(mountain_doc: SurroundingMountainDocument) -> Result<Self, Self::Error> {
@@ -52,8 +76,13 @@ impl TryFrom<SurroundingMountainDocument> for SurroundingMountain {
     }
 }
 
+/// Command to find the surroundings
+///
+/// `filter` counts all the surroundings with `$centerSphere`, since `$nearSphere` cannot be counted,
+/// and `pipeline` returns a page of them with `$geoNear`, which can also sort by the distance
 pub struct SurroundingMountainFindCommand {
     pub(crate) filter: Document,
+    pub(crate) pipeline: Vec<Document>,
 }
 
 impl TryFrom<SurroundingMountainSearchCondition> for SurroundingMountainFindCommand {
@@ -65,8 +94,208 @@ impl TryFrom<SurroundingMountainSearchCondition> for SurroundingMountainFindComm
             sc.mountain.location.longitude, // longitude (経度)
             sc.mountain.location.latitude,  // latitude (緯度)
         );
-        let filter = doc! {"$and": [{"location":{"$nearSphere": {"$geometry": { "type": "Point",  "coordinates": [coordinates.0, coordinates.1]},"$minDistance": 0,"$maxDistance": sc.distance.0}}}, {"_id": {"$ne": &sc.mountain.id.value}}]};
+        let mut and_doc = vec![doc! {"_id": {"$ne": &sc.mountain.id.value}}];
+
+        if let Some(pref) = sc.prefecture {
+            and_doc.push(doc! {"prefectures": pref.name});
+        }
+
+        if let Some(tag) = sc.tag {
+            and_doc.push(doc! {"tags": tag.name});
+        }
+
+        if let Some(elevation_doc) = elevation_filter(&sc.elevation) {
+            and_doc.push(elevation_doc);
+        }
+
+        let mut count_and_doc = vec![
+            doc! {"location": {"$geoWithin": {"$centerSphere": [[coordinates.0, coordinates.1], sc.distance.0 as f64 / EARTH_RADIUS]}}},
+        ];
+        count_and_doc.extend(and_doc.iter().cloned());
+        let filter = doc! {"$and": count_and_doc};
+
+        let key = sc.sort.key.to_key();
+        let value = sc.sort.order.to_value();
+        // ページングの結果を安定させるため _id を第 2 キーにする
+        let sort_doc = match sc.sort.key {
+            SurroundingMountainSortKey::Mountain(MountainSortKey::Id) => doc! {key: value},
+            _ => doc! {key: value, "_id": value},
+        };
+
+        let mut pipeline = vec![
+            doc! {"$geoNear": {
+                "near": {"type": "Point", "coordinates": [coordinates.0, coordinates.1]},
+                "distanceField": "distance",
+                "maxDistance": sc.distance.0,
+                "spherical": true,
+                "query": {"$and": and_doc},
+            }},
+            doc! {"$sort": sort_doc},
+            doc! {"$skip": sc.skip as i64},
+        ];
+        // MongoDB の find と同様に limit が 0 の場合は上限なしとして扱う
+        if let Some(limit) = sc.limit.filter(|limit| *limit != 0) {
+            pipeline.push(doc! {"$limit": limit.abs()});
+        }
 
-        Ok(SurroundingMountainFindCommand { filter })
+        Ok(SurroundingMountainFindCommand { filter, pipeline })
     }
 }
+
+pub struct NearbyMountainFindCommand {
+    pub(crate) filter: Document,
+}
+
+impl TryFrom<NearbyMountainSearchCondition> for NearbyMountainFindCommand {
+    type Error = anyhow::Error;
+
+    fn try_from(sc: NearbyMountainSearchCondition) -> Result<Self, Self::Error> {
+        // MongoDBの地理的クエリでは [longitude, latitude] の順序が必要
+        let mut and_doc = vec![
+            doc! {"location":{"$nearSphere": {"$geometry": { "type": "Point",  "coordinates": [sc.point.longitude, sc.point.latitude]},"$minDistance": 0,"$maxDistance": sc.distance.0}}},
+        ];
+
+        if let Some(name) = sc.name {
+            and_doc.push(name_filter(&name));
+        }
+
+        if let Some(tag) = sc.tag {
+            and_doc.push(doc! {"tags": tag.name});
+        }
+
+        let filter = doc! {"$and": and_doc};
+
+        Ok(NearbyMountainFindCommand { filter })
+    }
+}
+
+pub struct NearestMountainFindCommand {
+    pub(crate) filter: Document,
+    pub(crate) options: FindOptions,
+}
+
+impl TryFrom<NearestMountainSearchCondition> for NearestMountainFindCommand {
+    type Error = anyhow::Error;
+
+    fn try_from(sc: NearestMountainSearchCondition) -> Result<Self, Self::Error> {
+        // $maxDistance を指定しない $nearSphere は全件を距離の近い順に返すため、limit で k 件に絞る
+        let mut and_doc = vec![
+            doc! {"location":{"$nearSphere": {"$geometry": { "type": "Point",  "coordinates": [sc.point.longitude, sc.point.latitude]}}}},
+        ];
+
+        if let Some(exclude_id) = sc.exclude_id {
+            and_doc.push(doc! {"_id": {"$ne": exclude_id.value}});
+        }
+
+        if let Some(tag) = sc.tag {
+            and_doc.push(doc! {"tags": tag.name});
+        }
+
+        if let Some(elevation_doc) = elevation_filter(&sc.elevation) {
+            and_doc.push(elevation_doc);
+        }
+
+        let filter = doc! {"$and": and_doc};
+        let options = FindOptions::builder().limit(sc.k as i64).build();
+
+        Ok(NearestMountainFindCommand { filter, options })
+    }
+}
+
+/// Returns boxes ([longitude, latitude] of the bottom left and upper right) covering the buffer
+/// around each segment of the line
+///
+/// ラインの各線分の周囲の指定した距離の範囲を覆うボックス (左下と右上の [経度, 緯度]) を返します
+pub(crate) fn corridor_boxes(line: &GeoLineString, buffer: f64) -> Vec<([f64; 2], [f64; 2])> {
+    let delta_lat = (buffer / EARTH_RADIUS).to_degrees();
+
+    line.points
+        .windows(2)
+        .map(|segment| {
+            let (a, b) = (&segment[0], &segment[1]);
+            let min_lat = (a.latitude.min(b.latitude) - delta_lat).max(-90.0);
+            let max_lat = (a.latitude.max(b.latitude) + delta_lat).min(90.0);
+            // 経度 1 度あたりの距離は高緯度ほど短くなるため、範囲内で最も高い緯度に合わせて広げる
+            let cos_lat = min_lat
+                .abs()
+                .max(max_lat.abs())
+                .to_radians()
+                .cos()
+                .max(1e-6);
+            let delta_lng = delta_lat / cos_lat;
+
+            (
+                [
+                    (a.longitude.min(b.longitude) - delta_lng).max(-180.0),
+                    min_lat,
+                ],
+                [
+                    (a.longitude.max(b.longitude) + delta_lng).min(180.0),
+                    max_lat,
+                ],
+            )
+        })
+        .collect()
+}
+
+pub struct CorridorMountainFindCommand {
+    pub(crate) filter: Document,
+}
+
+impl TryFrom<&CorridorMountainSearchCondition> for CorridorMountainFindCommand {
+    type Error = anyhow::Error;
+
+    fn try_from(sc: &CorridorMountainSearchCondition) -> Result<Self, Self::Error> {
+        // 線分ごとのボックスで候補を絞り込み、ラインからの距離はリポジトリで判定する
+        let boxes: Vec<Document> = corridor_boxes(&sc.line, sc.buffer.0 as f64)
+            .into_iter()
+            .map(|(bottom_left, upper_right)| {
+                doc! {"location": {"$geoWithin": {"$box": [[bottom_left[0], bottom_left[1]], [upper_right[0], upper_right[1]]]}}}
+            })
+            .collect();
+        let mut and_doc = vec![doc! {"$or": boxes}];
+
+        if let Some(name) = &sc.name {
+            and_doc.push(name_filter(name));
+        }
+
+        if let Some(tag) = &sc.tag {
+            and_doc.push(doc! {"tags": &tag.name});
+        }
+
+        if let Some(elevation_doc) = elevation_filter(&sc.elevation) {
+            and_doc.push(elevation_doc);
+        }
+
+        let filter = doc! {"$and": and_doc};
+
+        Ok(CorridorMountainFindCommand { filter })
+    }
+}
+
+/// Keeps the documents within the buffer of the line and orders them by the position along the line
+///
+/// ラインから指定した距離の範囲内にあるドキュメントを残し、ライン上の位置の順に並べます
+pub(crate) fn select_corridor_documents<T>(
+    documents: Vec<T>,
+    coordinates: impl Fn(&T) -> ([f64; 2], i32),
+    sc: &CorridorMountainSearchCondition,
+) -> Vec<T> {
+    let buffer = sc.buffer.0 as f64;
+    let mut selected: Vec<(f64, i32, T)> = documents
+        .into_iter()
+        .filter_map(|document| {
+            let ([longitude, latitude], id) = coordinates(&document);
+            let projection = sc.line.project(&GeoPoint {
+                latitude,
+                longitude,
+            });
+            (projection.cross_track <= buffer).then_some((projection.along_track, id, document))
+        })
+        .collect();
+    selected.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    selected
+        .into_iter()
+        .map(|(_, _, document)| document)
+        .collect()
+}
@@ -0,0 +1,68 @@
+use mongodb::bson::{doc, Document};
+use mountix_kernel::model::tag::Tag;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TagDocument {
+    #[serde(rename = "_id")]
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub source: String,
+}
+
+impl From<TagDocument> for Tag {
+    fn from(tag_doc: TagDocument) -> Self {
+        Tag::new(
+            tag_doc.id.into(),
+            tag_doc.name,
+            tag_doc.description,
+            tag_doc.source,
+        )
+    }
+}
+
+/// Result document of `tag_count_pipeline`
+#[derive(Debug, Deserialize)]
+pub struct TagCountDocument {
+    #[serde(rename = "_id")]
+    pub name: String,
+    pub mountain_count: u64,
+}
+
+/// Aggregation pipeline counting mountains by tag
+///
+/// タグごとに山岳の件数を集計するパイプライン
+pub fn tag_count_pipeline() -> Vec<Document> {
+    vec![
+        doc! {"$unwind": "$tags"},
+        doc! {"$group": {"_id": "$tags", "mountain_count": {"$sum": 1}}},
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_document_deserialize() {
+        let json = r#"{"_id":3,"name":"三百名山","description":"日本山岳会が選定した日本三百名山","source":"日本山岳会"}"#;
+        let tag_doc: TagDocument = serde_json::from_str(json).unwrap();
+
+        let tag: Tag = tag_doc.into();
+        assert_eq!(tag.id.value, 3);
+        assert_eq!(tag.name, "三百名山");
+        assert_eq!(tag.source, "日本山岳会");
+    }
+
+    #[test]
+    fn test_tag_count_document_deserialize() {
+        let count_doc: TagCountDocument = mongodb::bson::from_document(doc! {
+            "_id": "百名山",
+            "mountain_count": 100,
+        })
+        .unwrap();
+        assert_eq!(count_doc.name, "百名山");
+        assert_eq!(count_doc.mountain_count, 100);
+    }
+}
@@ -1,24 +1,45 @@
+use crate::persistence::in_memory::InMemoryDb;
+use crate::repository::in_memory::InMemoryRepositoryImpl;
+use crate::repository::RepositoryImpl;
 use crate::{persistence::mongodb::Db, repository::MongoDBRepositoryImpl};
+use mountix_kernel::model::area::Area;
+use mountix_kernel::model::mountain::MountainPrefecture;
 use mountix_kernel::model::surrounding_mountain::SurroundingMountain;
+use mountix_kernel::model::tag::Tag;
+use mountix_kernel::repository::area::AreaRepository;
+use mountix_kernel::repository::prefecture::PrefectureRepository;
 use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
+use mountix_kernel::repository::tag::TagRepository;
 use mountix_kernel::{model::mountain::Mountain, repository::mountain::MountainRepository};
 
 pub struct RepositoriesModule {
-    mountain_repository: MongoDBRepositoryImpl<Mountain>,
-    surrounding_mountain_repository: MongoDBRepositoryImpl<SurroundingMountain>,
+    mountain_repository: RepositoryImpl<Mountain>,
+    surrounding_mountain_repository: RepositoryImpl<SurroundingMountain>,
+    tag_repository: RepositoryImpl<Tag>,
+    prefecture_repository: RepositoryImpl<MountainPrefecture>,
+    area_repository: RepositoryImpl<Area>,
 }
 
 pub trait RepositoriesModuleExt {
     type MountainRepo: MountainRepository;
     type SurroundingMountainRepo: SurroundingMountainRepository;
+    type TagRepo: TagRepository;
+    type PrefectureRepo: PrefectureRepository;
+    type AreaRepo: AreaRepository;
 
     fn mountain_repository(&self) -> &Self::MountainRepo;
     fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo;
+    fn tag_repository(&self) -> &Self::TagRepo;
+    fn prefecture_repository(&self) -> &Self::PrefectureRepo;
+    fn area_repository(&self) -> &Self::AreaRepo;
 }
 
 impl RepositoriesModuleExt for RepositoriesModule {
-    type MountainRepo = MongoDBRepositoryImpl<Mountain>;
-    type SurroundingMountainRepo = MongoDBRepositoryImpl<SurroundingMountain>;
+    type MountainRepo = RepositoryImpl<Mountain>;
+    type SurroundingMountainRepo = RepositoryImpl<SurroundingMountain>;
+    type TagRepo = RepositoryImpl<Tag>;
+    type PrefectureRepo = RepositoryImpl<MountainPrefecture>;
+    type AreaRepo = RepositoryImpl<Area>;
 
     fn mountain_repository(&self) -> &Self::MountainRepo {
         &self.mountain_repository
@@ -26,15 +47,48 @@ impl RepositoriesModuleExt for RepositoriesModule {
     fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo {
         &self.surrounding_mountain_repository
     }
+    fn tag_repository(&self) -> &Self::TagRepo {
+        &self.tag_repository
+    }
+    fn prefecture_repository(&self) -> &Self::PrefectureRepo {
+        &self.prefecture_repository
+    }
+    fn area_repository(&self) -> &Self::AreaRepo {
+        &self.area_repository
+    }
 }
 
 impl RepositoriesModule {
     pub fn new(db: Db) -> Self {
-        let mountain_repository = MongoDBRepositoryImpl::new(db.clone());
-        let surrounding_mountain_repository = MongoDBRepositoryImpl::new(db.clone());
+        let mountain_repository = RepositoryImpl::MongoDB(MongoDBRepositoryImpl::new(db.clone()));
+        let surrounding_mountain_repository =
+            RepositoryImpl::MongoDB(MongoDBRepositoryImpl::new(db.clone()));
+        let tag_repository = RepositoryImpl::MongoDB(MongoDBRepositoryImpl::new(db.clone()));
+        let prefecture_repository = RepositoryImpl::MongoDB(MongoDBRepositoryImpl::new(db.clone()));
+        let area_repository = RepositoryImpl::MongoDB(MongoDBRepositoryImpl::new(db.clone()));
+        Self {
+            mountain_repository,
+            surrounding_mountain_repository,
+            tag_repository,
+            prefecture_repository,
+            area_repository,
+        }
+    }
+
+    pub fn new_in_memory(db: InMemoryDb) -> Self {
+        let mountain_repository = RepositoryImpl::InMemory(InMemoryRepositoryImpl::new(db.clone()));
+        let surrounding_mountain_repository =
+            RepositoryImpl::InMemory(InMemoryRepositoryImpl::new(db.clone()));
+        let tag_repository = RepositoryImpl::InMemory(InMemoryRepositoryImpl::new(db.clone()));
+        let prefecture_repository =
+            RepositoryImpl::InMemory(InMemoryRepositoryImpl::new(db.clone()));
+        let area_repository = RepositoryImpl::InMemory(InMemoryRepositoryImpl::new(db.clone()));
         Self {
             mountain_repository,
             surrounding_mountain_repository,
+            tag_repository,
+            prefecture_repository,
+            area_repository,
         }
     }
 }
@@ -0,0 +1,434 @@
+use crate::model::mountain::MountainDocument;
+use crate::model::tag::TagDocument;
+use crate::search::MountainSearchIndex;
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, AABB};
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+const DEFAULT_MOUNTAINS_DATA_PATH: &str = "migrations/data/mountix_db-mountains.json";
+const DEFAULT_TAGS_DATA_PATH: &str = "migrations/data/mountix_db-tags.json";
+
+/// Earth radius used by MongoDB for spherical queries on GeoJSON points (meters)
+pub(crate) const EARTH_RADIUS: f64 = 6_378_100.0;
+
+type PlanarPoint = GeomWithData<[f64; 2], i32>;
+type SphericalPoint = GeomWithData<[f64; 3], i32>;
+
+#[derive(Clone)]
+pub struct InMemoryDb {
+    mountains: Arc<RwLock<MountainStore>>,
+    tags: Arc<Vec<TagDocument>>,
+}
+
+impl InMemoryDb {
+    /// Loads the data files specified by `MOUNTAINS_DATA_PATH` and `TAGS_DATA_PATH`
+    ///
+    /// `MOUNTAINS_DATA_PATH` と `TAGS_DATA_PATH` に指定されたデータファイルを読み込みます
+    pub fn load() -> InMemoryDb {
+        let path = env::var("MOUNTAINS_DATA_PATH")
+            .unwrap_or_else(|_| DEFAULT_MOUNTAINS_DATA_PATH.to_string());
+        let db = InMemoryDb::from_reader(open_data_file(&path))
+            .unwrap_or_else(|e| panic!("Could not load mountains data {}: {:?}", path, e));
+
+        let path =
+            env::var("TAGS_DATA_PATH").unwrap_or_else(|_| DEFAULT_TAGS_DATA_PATH.to_string());
+        db.with_tags(open_data_file(&path))
+            .unwrap_or_else(|e| panic!("Could not load tags data {}: {:?}", path, e))
+    }
+
+    /// Builds the store from NDJSON in the `mongoimport` format
+    ///
+    /// `mongoimport` 形式の NDJSON からストアを構築します
+    pub fn from_reader<R: BufRead>(reader: R) -> anyhow::Result<InMemoryDb> {
+        let mut documents: Vec<MountainDocument> = read_documents(reader)?;
+        documents
+            .iter_mut()
+            .for_each(MountainDocument::fill_search_names);
+
+        Ok(InMemoryDb {
+            mountains: Arc::new(RwLock::new(MountainStore::new(documents))),
+            tags: Arc::new(Vec::new()),
+        })
+    }
+
+    /// Returns the store with the tags read from NDJSON in the `mongoimport` format
+    ///
+    /// `mongoimport` 形式の NDJSON からタグを読み込みます
+    pub fn with_tags<R: BufRead>(mut self, reader: R) -> anyhow::Result<InMemoryDb> {
+        let mut tags: Vec<TagDocument> = read_documents(reader)?;
+        tags.sort_by_key(|td| td.id);
+
+        self.tags = Arc::new(tags);
+        Ok(self)
+    }
+
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, MountainStore> {
+        self.mountains.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, MountainStore> {
+        self.mountains.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns all tags in `_id` order
+    pub(crate) fn tags(&self) -> &[TagDocument] {
+        &self.tags
+    }
+}
+
+fn open_data_file(path: &str) -> BufReader<File> {
+    let file =
+        File::open(path).unwrap_or_else(|e| panic!("Could not open data file {}: {:?}", path, e));
+    BufReader::new(file)
+}
+
+fn read_documents<T: DeserializeOwned, R: BufRead>(reader: R) -> anyhow::Result<Vec<T>> {
+    let mut documents: Vec<T> = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        documents.push(serde_json::from_str(&line)?);
+    }
+    Ok(documents)
+}
+
+pub struct MountainStore {
+    mountains: BTreeMap<i32, MountainDocument>,
+    planar_index: RTree<PlanarPoint>,
+    spherical_index: RTree<SphericalPoint>,
+    /// Sorted pairs of the normalized search names and `_id`, which serve as the prefix index
+    name_index: BTreeSet<(String, i32)>,
+    search_index: MountainSearchIndex,
+}
+
+impl MountainStore {
+    fn new(documents: Vec<MountainDocument>) -> Self {
+        let planar_points = documents
+            .iter()
+            .map(|md| PlanarPoint::new(md.location.coordinates, md.id))
+            .collect();
+        let spherical_points = documents
+            .iter()
+            .map(|md| SphericalPoint::new(to_unit_vector(md.location.coordinates), md.id))
+            .collect();
+        let name_index = documents
+            .iter()
+            .flat_map(|md| search_names(md).into_iter().map(|name| (name, md.id)))
+            .collect();
+        let search_index = MountainSearchIndex::new(&documents);
+        let mountains = documents.into_iter().map(|md| (md.id, md)).collect();
+
+        Self {
+            mountains,
+            planar_index: RTree::bulk_load(planar_points),
+            spherical_index: RTree::bulk_load(spherical_points),
+            name_index,
+            search_index,
+        }
+    }
+
+    /// Returns the full-text search index over all the documents
+    pub(crate) fn search_index(&self) -> &MountainSearchIndex {
+        &self.search_index
+    }
+
+    pub(crate) fn get(&self, id: i32) -> Option<&MountainDocument> {
+        self.mountains.get(&id)
+    }
+
+    /// Returns the next `_id` to assign, following the largest one in the store
+    pub(crate) fn next_id(&self) -> i32 {
+        self.mountains
+            .keys()
+            .next_back()
+            .map(|id| id + 1)
+            .unwrap_or(1)
+    }
+
+    /// Inserts or replaces the document and keeps the geo indexes, the name index and the search index in sync
+    pub(crate) fn upsert(&mut self, document: MountainDocument) {
+        self.remove(document.id);
+        for name in search_names(&document) {
+            self.name_index.insert((name, document.id));
+        }
+        self.search_index.upsert(&document);
+        self.planar_index
+            .insert(PlanarPoint::new(document.location.coordinates, document.id));
+        self.spherical_index.insert(SphericalPoint::new(
+            to_unit_vector(document.location.coordinates),
+            document.id,
+        ));
+        self.mountains.insert(document.id, document);
+    }
+
+    /// Removes the document and its geo index, name index and search index entries
+    pub(crate) fn remove(&mut self, id: i32) -> Option<MountainDocument> {
+        let document = self.mountains.remove(&id)?;
+        for name in search_names(&document) {
+            self.name_index.remove(&(name, id));
+        }
+        self.search_index.remove(id);
+        self.planar_index
+            .remove(&PlanarPoint::new(document.location.coordinates, id));
+        self.spherical_index.remove(&SphericalPoint::new(
+            to_unit_vector(document.location.coordinates),
+            id,
+        ));
+        Some(document)
+    }
+
+    /// Returns all documents in `_id` order
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &MountainDocument> {
+        self.mountains.values()
+    }
+
+    /// Returns documents with a search name starting with any of the prefixes in `_id` order,
+    /// like prefix `$regex` on the indexed search names
+    ///
+    /// 検索用の山名のいずれかが前方一致する値で始まるドキュメントを `_id` の順に返します
+    pub(crate) fn with_name_prefix(&self, prefixes: &[&str]) -> Vec<&MountainDocument> {
+        let ids: BTreeSet<i32> = prefixes
+            .iter()
+            .filter(|prefix| !prefix.is_empty())
+            .flat_map(|prefix| {
+                self.name_index
+                    .range((prefix.to_string(), i32::MIN)..)
+                    .take_while(move |(name, _)| name.starts_with(*prefix))
+                    .map(|(_, id)| *id)
+            })
+            .collect();
+        ids.iter().filter_map(|id| self.mountains.get(id)).collect()
+    }
+
+    /// Returns documents inside the box, like `$geoWithin: {$box: ...}`
+    ///
+    /// # Arguments
+    ///
+    /// - `bottom_left`: [longitude, latitude]
+    /// - `upper_right`: [longitude, latitude]
+    pub(crate) fn within_box(
+        &self,
+        bottom_left: [f64; 2],
+        upper_right: [f64; 2],
+    ) -> Vec<&MountainDocument> {
+        let envelope = AABB::from_corners(bottom_left, upper_right);
+        let mut documents: Vec<&MountainDocument> = self
+            .planar_index
+            .locate_in_envelope(&envelope)
+            .filter_map(|point| self.mountains.get(&point.data))
+            .collect();
+        documents.sort_by_key(|md| md.id);
+        documents
+    }
+
+    /// Returns documents within the great-circle distance ordered by distance, like `$nearSphere`
+    ///
+    /// # Arguments
+    ///
+    /// - `center`: [longitude, latitude]
+    /// - `max_distance`: Distance in meters
+    pub(crate) fn within_distance(
+        &self,
+        center: [f64; 2],
+        max_distance: f64,
+    ) -> Vec<(&MountainDocument, f64)> {
+        let center_vector = to_unit_vector(center);
+        let angle = (max_distance / EARTH_RADIUS).min(std::f64::consts::PI);
+        let chord = 2.0 * (angle / 2.0).sin();
+
+        let mut documents: Vec<(&MountainDocument, f64)> = self
+            .spherical_index
+            .locate_within_distance(center_vector, chord * chord)
+            .filter_map(|point| self.mountains.get(&point.data))
+            .map(|md| (md, great_circle_distance(center, md.location.coordinates)))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .collect();
+        documents.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.id.cmp(&b.0.id)));
+        documents
+    }
+
+    /// Returns all documents lazily ordered by great-circle distance, like `$nearSphere` without `$maxDistance`
+    ///
+    /// # Arguments
+    ///
+    /// - `center`: [longitude, latitude]
+    pub(crate) fn nearest(
+        &self,
+        center: [f64; 2],
+    ) -> impl Iterator<Item = (&MountainDocument, f64)> + '_ {
+        // 単位球面上の弦の長さは大圏距離に対して単調増加するため、R-tree の最近傍探索の順序がそのまま距離順になる
+        self.spherical_index
+            .nearest_neighbor_iter(&to_unit_vector(center))
+            .filter_map(|point| self.mountains.get(&point.data))
+            .map(move |md| (md, great_circle_distance(center, md.location.coordinates)))
+    }
+}
+
+/// Returns the normalized names of the document and its aliases registered in the name index
+fn search_names(document: &MountainDocument) -> Vec<String> {
+    let mut names = vec![
+        document.name_folded.clone(),
+        document.name_kana.clone(),
+        document.name_romaji.clone(),
+    ];
+    for alias in &document.aliases {
+        names.push(alias.name_folded.clone());
+        names.push(alias.name_kana.clone());
+        names.push(alias.name_romaji.clone());
+    }
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+/// Converts [longitude, latitude] to a point on the unit sphere
+fn to_unit_vector(coordinates: [f64; 2]) -> [f64; 3] {
+    let longitude = coordinates[0].to_radians();
+    let latitude = coordinates[1].to_radians();
+    [
+        latitude.cos() * longitude.cos(),
+        latitude.cos() * longitude.sin(),
+        latitude.sin(),
+    ]
+}
+
+/// Returns the haversine distance in meters between two [longitude, latitude] points
+pub(crate) fn great_circle_distance(from: [f64; 2], to: [f64; 2]) -> f64 {
+    let (from_lng, from_lat) = (from[0].to_radians(), from[1].to_radians());
+    let (to_lng, to_lat) = (to[0].to_radians(), to[1].to_radians());
+
+    let a = ((to_lat - from_lat) / 2.0).sin().powi(2)
+        + from_lat.cos() * to_lat.cos() * ((to_lng - from_lng) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DATA: &str = r#"{"_id":1,"name":"富士山","name_kana":"ふじさん","area":"富士山","prefectures":["静岡県","山梨県"],"elevation":3776,"tags":["百名山"],"location":{"type":"Point","coordinates":[138.7275,35.36083333333333]},"gsi_url":"https://maps.gsi.go.jp/#15/35.36083333333333/138.7275"}
+
+{"_id":2,"name":"毛無山","name_kana":"けなしやま","area":"天子山地","prefectures":["静岡県","山梨県"],"elevation":1964,"tags":["二百名山"],"location":{"type":"Point","coordinates":[138.5433333333333,35.41777777777778]},"gsi_url":"https://maps.gsi.go.jp/#15/35.41777777777778/138.5433333333333"}
+{"_id":3,"name":"羅臼岳","name_kana":"らうすだけ","area":"知床・阿寒","prefectures":["北海道"],"elevation":1661,"tags":["百名山"],"location":{"type":"Point","coordinates":[145.12222222222223,44.075833333333335]},"gsi_url":"https://maps.gsi.go.jp/#15/44.075833333333335/145.12222222222223"}
+"#;
+
+    #[test]
+    fn test_in_memory_db_from_reader_skips_blank_lines() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        assert_eq!(db.read().iter().count(), 3);
+        assert_eq!(db.read().get(3).unwrap().name, "羅臼岳");
+        assert!(db.read().get(4).is_none());
+    }
+
+    #[test]
+    fn test_in_memory_db_from_reader_generates_search_names() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        assert_eq!(db.read().get(1).unwrap().name_folded, "富士山");
+        assert_eq!(db.read().get(1).unwrap().name_romaji, "fujisan");
+        assert_eq!(db.read().get(3).unwrap().name_romaji, "rausudake");
+    }
+
+    #[test]
+    fn test_in_memory_db_from_reader_invalid_line() {
+        let result = InMemoryDb::from_reader("{\"_id\":1}".as_bytes());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_mountain_store_within_box() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let store = db.read();
+        let documents = store.within_box([138.0, 35.0], [139.0, 36.0]);
+        let ids: Vec<i32> = documents.iter().map(|md| md.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_mountain_store_with_name_prefix() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let ids = |prefixes: &[&str]| -> Vec<i32> {
+            db.read()
+                .with_name_prefix(prefixes)
+                .iter()
+                .map(|md| md.id)
+                .collect()
+        };
+
+        assert_eq!(ids(&["ら"]), vec![3]);
+        assert_eq!(ids(&["fuji", "け"]), vec![1, 2]);
+        assert_eq!(ids(&["富士山", "ふじさん", "fujisan"]), vec![1]);
+        assert!(ids(&["じさん", ""]).is_empty());
+
+        let mut document = db.read().get(3).unwrap().clone();
+        document.name_kana = "しれとこだけ".to_string();
+        db.write().upsert(document);
+        assert!(ids(&["ら"]).is_empty());
+        assert_eq!(ids(&["しれ"]), vec![3]);
+
+        db.write().remove(3);
+        assert!(ids(&["しれ"]).is_empty());
+    }
+
+    #[test]
+    fn test_mountain_store_within_distance_orders_by_distance() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+
+        let store = db.read();
+        let documents = store.within_distance([138.7275, 35.36083333333333], 20_000.0);
+        let ids: Vec<i32> = documents.iter().map(|(md, _)| md.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(documents[0].1, 0.0);
+        assert!(documents[1].1 > 17_000.0 && documents[1].1 < 18_000.0);
+
+        let documents = store.within_distance([138.7275, 35.36083333333333], 10_000.0);
+        assert_eq!(documents.len(), 1);
+    }
+
+    #[test]
+    fn test_mountain_store_nearest_orders_by_distance() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+
+        let store = db.read();
+        let documents: Vec<(i32, f64)> = store
+            .nearest([138.6, 35.4])
+            .map(|(md, distance)| (md.id, distance))
+            .collect();
+        let ids: Vec<i32> = documents.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+        assert!(documents.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_mountain_store_upsert_and_remove() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        assert_eq!(db.read().next_id(), 4);
+
+        let mut document = db.read().get(1).unwrap().clone();
+        document.location.coordinates = [145.0, 44.0];
+        db.write().upsert(document);
+
+        // 移動後の座標でのみ検索される
+        let documents = db.read().within_box([138.0, 35.0], [139.0, 36.0]).len();
+        assert_eq!(documents, 1);
+        let documents = db.read().within_distance([145.0, 44.0], 1_000.0).len();
+        assert_eq!(documents, 1);
+
+        assert!(db.write().remove(1).is_some());
+        assert!(db.write().remove(1).is_none());
+        assert!(db.read().within_distance([145.0, 44.0], 1_000.0).is_empty());
+        assert_eq!(db.read().iter().count(), 2);
+    }
+
+    #[test]
+    fn test_great_circle_distance() {
+        // 富士山 - 羅臼岳 は約 1,100km
+        let distance = great_circle_distance([138.7275, 35.36083333333333], [145.1222, 44.0758]);
+        assert!((1_100_000.0..1_125_000.0).contains(&distance));
+    }
+}
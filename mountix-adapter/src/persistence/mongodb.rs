@@ -1,10 +1,18 @@
 use std::env;
 use std::sync::Arc;
 
+use crate::model::mountain::MountainDocument;
+use crate::search::{MountainSearchIndex, SharedMountainSearchIndex};
+use futures::stream::TryStreamExt;
+use mongodb::bson::doc;
 use mongodb::{Client, Database};
 
 #[derive(Clone)]
-pub struct Db(pub(crate) Arc<Database>);
+pub struct Db(
+    pub(crate) Arc<Database>,
+    /// Full-text search index over the mountains collection
+    pub(crate) SharedMountainSearchIndex,
+);
 
 impl Db {
     pub async fn new() -> Db {
@@ -15,6 +23,21 @@ impl Db {
             .await
             .expect("Could not connect to MongoDB.");
         let db = client.database(&db_name);
-        Db(Arc::new(db))
+        Db(Arc::new(db), SharedMountainSearchIndex::default())
+    }
+
+    /// Builds the search index over the whole mountains collection, replacing the current one
+    ///
+    /// 山岳のコレクション全体から検索用の索引を構築し、現在の索引と置き換えます
+    pub async fn load_search_index(&self) -> anyhow::Result<()> {
+        let collection = self.0.collection::<MountainDocument>("mountains");
+        let documents: Vec<MountainDocument> =
+            collection.find(doc! {}).await?.try_collect().await?;
+        self.1.replace(MountainSearchIndex::new(&documents));
+        Ok(())
+    }
+
+    pub(crate) fn search_index(&self) -> &SharedMountainSearchIndex {
+        &self.1
     }
 }
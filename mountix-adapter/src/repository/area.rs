@@ -0,0 +1,34 @@
+use crate::model::area::{area_stats_pipeline, AreaStatsDocument};
+use crate::repository::{MongoDBRepositoryImpl, RepositoryImpl};
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::bson::from_document;
+use mountix_kernel::model::area::Area;
+use mountix_kernel::repository::area::AreaRepository;
+
+#[async_trait]
+impl AreaRepository for MongoDBRepositoryImpl<Area> {
+    async fn find(&self) -> anyhow::Result<Vec<Area>> {
+        let collection = self.db.0.collection::<mongodb::bson::Document>("mountains");
+
+        let mut stats_doc_list = collection.aggregate(area_stats_pipeline()).await?;
+
+        let mut areas: Vec<Area> = Vec::new();
+        while let Some(doc) = stats_doc_list.try_next().await? {
+            let stats_doc: AreaStatsDocument = from_document(doc)?;
+            areas.push(stats_doc.into());
+        }
+
+        Ok(areas)
+    }
+}
+
+#[async_trait]
+impl AreaRepository for RepositoryImpl<Area> {
+    async fn find(&self) -> anyhow::Result<Vec<Area>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find().await,
+            RepositoryImpl::InMemory(repository) => repository.find().await,
+        }
+    }
+}
@@ -2,16 +2,30 @@ use crate::persistence::mongodb::Db;
 use std::sync::Arc;
 
 pub struct HealthCheckRepository {
-    db: Arc<Db>,
+    db: Option<Arc<Db>>,
 }
 
 impl HealthCheckRepository {
     pub fn new(db: Db) -> Self {
-        Self { db: Arc::new(db) }
+        Self {
+            db: Some(Arc::new(db)),
+        }
+    }
+
+    /// Returns a repository for the in-memory backend, which has no MongoDB connection
+    ///
+    /// MongoDB に接続しないインメモリバックエンド用のリポジトリを生成します
+    pub fn new_in_memory() -> Self {
+        Self { db: None }
     }
 
     pub async fn check_mongo_db(&self) -> anyhow::Result<()> {
-        let db = self.db.0.clone();
+        let db = self
+            .db
+            .as_ref()
+            .ok_or(anyhow::Error::msg("MongoDB is not configured."))?
+            .0
+            .clone();
         let _ = db.list_collections().await?;
         Ok(())
     }
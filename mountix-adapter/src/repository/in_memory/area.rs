@@ -0,0 +1,87 @@
+use crate::repository::in_memory::InMemoryRepositoryImpl;
+use async_trait::async_trait;
+use mountix_kernel::model::area::Area;
+use mountix_kernel::model::mountain::MountainBoxCoordinates;
+use mountix_kernel::repository::area::AreaRepository;
+use std::collections::BTreeMap;
+
+#[async_trait]
+impl AreaRepository for InMemoryRepositoryImpl<Area> {
+    async fn find(&self) -> anyhow::Result<Vec<Area>> {
+        let store = self.db.read();
+
+        let mut areas: BTreeMap<&str, Area> = BTreeMap::new();
+        for md in store.iter() {
+            let [longitude, latitude] = md.location.coordinates;
+            let area = areas.entry(md.area.as_str()).or_insert_with(|| Area {
+                name: md.area.clone(),
+                mountain_count: 0,
+                elevation_min: md.elevation,
+                elevation_max: md.elevation,
+                prefectures: Vec::new(),
+                bounding_box: MountainBoxCoordinates {
+                    bottom_left: (longitude, latitude),
+                    upper_right: (longitude, latitude),
+                },
+            });
+
+            area.mountain_count += 1;
+            area.elevation_min = area.elevation_min.min(md.elevation);
+            area.elevation_max = area.elevation_max.max(md.elevation);
+            area.prefectures.extend(md.prefectures.iter().cloned());
+
+            let bounding_box = &mut area.bounding_box;
+            bounding_box.bottom_left.0 = bounding_box.bottom_left.0.min(longitude);
+            bounding_box.bottom_left.1 = bounding_box.bottom_left.1.min(latitude);
+            bounding_box.upper_right.0 = bounding_box.upper_right.0.max(longitude);
+            bounding_box.upper_right.1 = bounding_box.upper_right.1.max(latitude);
+        }
+
+        // 都道府県の重複除去と並び替えは Area::new に任せる
+        Ok(areas
+            .into_values()
+            .map(|area| {
+                Area::new(
+                    area.name,
+                    area.mountain_count,
+                    (area.elevation_min, area.elevation_max),
+                    area.prefectures,
+                    area.bounding_box,
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::in_memory::InMemoryDb;
+
+    const TEST_DATA: &str = r#"{"_id":1,"name":"富士山","name_kana":"ふじさん","area":"富士山","prefectures":["静岡県","山梨県"],"elevation":3776,"tags":["百名山"],"location":{"type":"Point","coordinates":[138.7275,35.36083333333333]},"gsi_url":"https://maps.gsi.go.jp/#15/35.36083333333333/138.7275"}
+{"_id":2,"name":"毛無山","name_kana":"けなしやま","area":"天子山地","prefectures":["静岡県","山梨県"],"elevation":1964,"tags":["二百名山"],"location":{"type":"Point","coordinates":[138.5433333333333,35.41777777777778]},"gsi_url":"https://maps.gsi.go.jp/#15/35.41777777777778/138.5433333333333"}
+{"_id":3,"name":"愛鷹山","name_kana":"あしたかやま","area":"富士山","prefectures":["静岡県"],"elevation":1504,"tags":[],"location":{"type":"Point","coordinates":[138.80666666666667,35.2325]},"gsi_url":"https://maps.gsi.go.jp/#15/35.2325/138.80666666666667"}
+"#;
+
+    #[tokio::test]
+    async fn test_in_memory_area_find() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<Area>::new(db);
+
+        let areas = repository.find().await.unwrap();
+        assert_eq!(areas.len(), 2);
+        assert_eq!(areas[0].name, "天子山地");
+
+        let fuji = &areas[1];
+        assert_eq!(fuji.name, "富士山");
+        assert_eq!(fuji.mountain_count, 2);
+        assert_eq!(fuji.elevation_min, 1504);
+        assert_eq!(fuji.elevation_max, 3776);
+        assert_eq!(fuji.prefectures, vec!["山梨県", "静岡県"]);
+        assert_eq!(fuji.bounding_box.bottom_left, (138.7275, 35.2325));
+        assert_eq!(
+            fuji.bounding_box.upper_right,
+            (138.80666666666667, 35.36083333333333)
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use crate::persistence::in_memory::InMemoryDb;
+use std::marker::PhantomData;
+
+pub mod area;
+pub mod mountain;
+pub mod prefecture;
+pub mod surrounding_mountain;
+pub mod tag;
+
+pub struct InMemoryRepositoryImpl<T> {
+    db: InMemoryDb,
+    _marker: PhantomData<T>,
+}
+
+impl<T> InMemoryRepositoryImpl<T> {
+    pub fn new(db: InMemoryDb) -> Self {
+        Self {
+            db,
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,1375 @@
+use crate::model::mountain::MountainDocument;
+use crate::persistence::in_memory::MountainStore;
+use crate::repository::in_memory::InMemoryRepositoryImpl;
+use crate::search::MountainRelevanceRanking;
+use async_trait::async_trait;
+use mountix_kernel::model::geo::GeoPoint;
+use mountix_kernel::model::mountain::{
+    Mountain, MountainBoxSearchCondition, MountainCursor, MountainCursorDirection,
+    MountainCursorValue, MountainData, MountainElevationRange, MountainFacet, MountainFacetCount,
+    MountainFacetKey, MountainNameCondition, MountainNameMatcher, MountainOrderType, MountainPage,
+    MountainPolygonSearchCondition, MountainPrefecture, MountainSearchCondition, MountainSort,
+    MountainSortKey, MountainSuggestCondition, MountainSuggestion, MountainTag,
+    MountainValuesCondition, ELEVATION_BAND_WIDTH,
+};
+use mountix_kernel::model::mountain_filter::{
+    MountainFilterComparison, MountainFilterCondition, MountainFilterExpr, MountainFilterText,
+};
+use mountix_kernel::model::Id;
+use mountix_kernel::repository::mountain::MountainRepository;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// In-memory counterpart of the filter built by `MountainFindCommand`
+pub(crate) struct MountainMatcher {
+    name: Option<MountainNameMatcher>,
+    prefecture: MountainValuesCondition<String>,
+    tag: MountainValuesCondition<String>,
+    area: Option<String>,
+    elevation: MountainElevationRange,
+    filter: Option<MountainFilterMatcher>,
+}
+
+/// In-memory counterpart of the filter built by `filter_expression`
+enum MountainFilterMatcher {
+    And(Vec<MountainFilterMatcher>),
+    Or(Vec<MountainFilterMatcher>),
+    Not(Box<MountainFilterMatcher>),
+    Elevation(MountainFilterComparison, u32),
+    Tag(String),
+    Prefecture(String),
+    Area(MountainFilterText),
+    Name(MountainNameMatcher),
+}
+
+impl MountainFilterMatcher {
+    fn new(expr: &MountainFilterExpr) -> anyhow::Result<Self> {
+        let new_all = |exprs: &[MountainFilterExpr]| {
+            exprs
+                .iter()
+                .map(MountainFilterMatcher::new)
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(match expr {
+            MountainFilterExpr::And(exprs) => Self::And(new_all(exprs)?),
+            MountainFilterExpr::Or(exprs) => Self::Or(new_all(exprs)?),
+            MountainFilterExpr::Not(expr) => Self::Not(Box::new(Self::new(expr)?)),
+            MountainFilterExpr::Condition(condition) => match condition {
+                MountainFilterCondition::Elevation(comparison, elevation) => {
+                    Self::Elevation(*comparison, *elevation)
+                }
+                MountainFilterCondition::Tag(tag) => Self::Tag(tag.clone()),
+                MountainFilterCondition::Prefecture(prefecture) => {
+                    Self::Prefecture(prefecture.clone())
+                }
+                MountainFilterCondition::Area(text) => Self::Area(text.clone()),
+                MountainFilterCondition::Name(name) => Self::Name(MountainNameMatcher::new(name)?),
+            },
+        })
+    }
+
+    fn matches(&self, md: &MountainDocument) -> bool {
+        match self {
+            Self::And(matchers) => matchers.iter().all(|matcher| matcher.matches(md)),
+            Self::Or(matchers) => matchers.iter().any(|matcher| matcher.matches(md)),
+            Self::Not(matcher) => !matcher.matches(md),
+            Self::Elevation(comparison, elevation) => comparison.matches(md.elevation, *elevation),
+            Self::Tag(tag) => md.tags.contains(tag),
+            Self::Prefecture(prefecture) => md.prefectures.contains(prefecture),
+            Self::Area(text) => text.matches(&md.area),
+            Self::Name(name) => is_name_match(name, md),
+        }
+    }
+}
+
+/// Returns whether the name or any alias of the document matches like `name_filter`
+///
+/// `name_filter` と同様に山名または別名のいずれかが一致するかを判定します
+fn is_name_match(name: &MountainNameMatcher, md: &MountainDocument) -> bool {
+    name.is_match(&md.name, &md.name_folded, &md.name_kana, &md.name_romaji)
+        || md.aliases.iter().any(|alias| {
+            name.is_match(
+                &alias.name,
+                &alias.name_folded,
+                &alias.name_kana,
+                &alias.name_romaji,
+            )
+        })
+}
+
+impl MountainMatcher {
+    pub(crate) fn new(
+        name: Option<&MountainNameCondition>,
+        prefecture: &MountainValuesCondition<MountainPrefecture>,
+        tag: &MountainValuesCondition<MountainTag>,
+        area: Option<&str>,
+        elevation: MountainElevationRange,
+    ) -> anyhow::Result<Self> {
+        let name = name.map(MountainNameMatcher::new).transpose()?;
+
+        Ok(Self {
+            name,
+            prefecture: prefecture.map(|p| p.name.clone()),
+            tag: tag.map(|t| t.name.clone()),
+            area: area.map(|a| a.to_string()),
+            elevation,
+            filter: None,
+        })
+    }
+
+    /// Adds the filter expression of the `q` parameter
+    ///
+    /// `q` パラメータのフィルタ式を条件に加えます
+    pub(crate) fn with_filter(
+        mut self,
+        filter: Option<&MountainFilterExpr>,
+    ) -> anyhow::Result<Self> {
+        self.filter = filter.map(MountainFilterMatcher::new).transpose()?;
+        Ok(self)
+    }
+
+    pub(crate) fn matches(&self, md: &MountainDocument) -> bool {
+        if let Some(name) = &self.name {
+            if !is_name_match(name, md) {
+                return false;
+            }
+        }
+
+        if !self.prefecture.matches(|p| md.prefectures.contains(p)) {
+            return false;
+        }
+
+        if !self.tag.matches(|t| md.tags.contains(t)) {
+            return false;
+        }
+
+        if let Some(area) = &self.area {
+            if &md.area != area {
+                return false;
+            }
+        }
+
+        if !self.elevation.contains(md.elevation) {
+            return false;
+        }
+
+        if let Some(filter) = &self.filter {
+            if !filter.matches(md) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Sorts documents like the `$sort` stage of `page_stages`, using `_id` as the tiebreaker
+pub(crate) fn sort_documents(documents: &mut [&MountainDocument], sort: &MountainSort) {
+    documents.sort_by(|a, b| {
+        sort.conditions
+            .iter()
+            .map(|condition| {
+                let ordering = compare_documents(a, b, condition.key, sort.point.as_ref());
+                ordered(ordering, condition.order)
+            })
+            .fold(Ordering::Equal, Ordering::then)
+            .then(ordered(a.id.cmp(&b.id), sort.primary().order))
+    });
+}
+
+fn ordered(ordering: Ordering, order: MountainOrderType) -> Ordering {
+    match order {
+        MountainOrderType::Asc => ordering,
+        MountainOrderType::Desc => ordering.reverse(),
+    }
+}
+
+/// Compares documents by the sort key in ascending order
+///
+/// 並び替えのキーでドキュメントを昇順に比較します
+fn compare_documents(
+    a: &MountainDocument,
+    b: &MountainDocument,
+    key: MountainSortKey,
+    point: Option<&GeoPoint>,
+) -> Ordering {
+    match key {
+        MountainSortKey::Id => a.id.cmp(&b.id),
+        MountainSortKey::Elevation => a.elevation.cmp(&b.elevation),
+        MountainSortKey::Name => a.name_kana.cmp(&b.name_kana),
+        // 関連度は保存されないため `MountainRelevanceRanking` で並べ替える
+        MountainSortKey::Relevance => Ordering::Equal,
+        MountainSortKey::Prefecture => prefecture_code(a).cmp(&prefecture_code(b)),
+        MountainSortKey::Area => a.area.cmp(&b.area),
+        MountainSortKey::Latitude => {
+            a.location.coordinates[1].total_cmp(&b.location.coordinates[1])
+        }
+        MountainSortKey::Distance => match point {
+            Some(point) => distance_from(point, a).total_cmp(&distance_from(point, b)),
+            None => Ordering::Equal,
+        },
+    }
+}
+
+/// Returns the smallest prefecture code of the document like `sort_fields`, where unknown names are 0
+///
+/// `sort_fields` と同様にドキュメントの最小の都道府県コードを返します。不明な都道府県名は 0 とします
+fn prefecture_code(md: &MountainDocument) -> u64 {
+    MountainPrefecture::min_code(&md.prefectures)
+}
+
+fn distance_from(point: &GeoPoint, md: &MountainDocument) -> f64 {
+    let [longitude, latitude] = md.location.coordinates;
+    point.distance_to(&GeoPoint {
+        latitude,
+        longitude,
+    })
+}
+
+/// Returns whether the document is on the side of the cursor it pages towards,
+/// comparing the sort keys and the id lexicographically like `cursor_filter`
+///
+/// ドキュメントがカーソルの指す方向 (次または前) にあるかを判定します。
+/// `cursor_filter` と同様にソートキーと ID を辞書式に比較します
+fn is_beyond_cursor(md: &MountainDocument, cursor: &MountainCursor, sort: &MountainSort) -> bool {
+    let ordering = cursor
+        .values
+        .iter()
+        .zip(&sort.conditions)
+        .map(|(value, condition)| {
+            let ordering = match value {
+                MountainCursorValue::Id => md.id.cmp(&cursor.id),
+                MountainCursorValue::Elevation(elevation) => md.elevation.cmp(elevation),
+                MountainCursorValue::Name(name) => md.name_kana.cmp(name),
+                MountainCursorValue::Prefecture(code) => prefecture_code(md).cmp(code),
+                MountainCursorValue::Area(area) => md.area.cmp(area),
+                MountainCursorValue::Latitude(latitude) => {
+                    md.location.coordinates[1].total_cmp(latitude)
+                }
+                MountainCursorValue::Distance(distance) => match &sort.point {
+                    Some(point) => distance_from(point, md).total_cmp(distance),
+                    None => Ordering::Equal,
+                },
+            };
+            ordered(ordering, condition.order)
+        })
+        .fold(Ordering::Equal, Ordering::then)
+        .then(ordered(md.id.cmp(&cursor.id), sort.primary().order));
+
+    match cursor.direction {
+        MountainCursorDirection::Next => ordering == Ordering::Greater,
+        MountainCursorDirection::Prev => ordering == Ordering::Less,
+    }
+}
+
+/// Counts the documents per value of the facets like the `$facet` stage of `facet_pipeline`
+///
+/// `facet_pipeline` の `$facet` ステージと同様にファセットの値ごとにドキュメントの件数を数えます
+pub(crate) fn count_facets(
+    documents: &[&MountainDocument],
+    facets: &[MountainFacetKey],
+) -> Vec<MountainFacet> {
+    let value_counts = |values: Vec<&String>| {
+        let mut counts: BTreeMap<&String, u64> = BTreeMap::new();
+        for value in values {
+            *counts.entry(value).or_default() += 1;
+        }
+        let mut counts: Vec<MountainFacetCount> = counts
+            .into_iter()
+            .map(|(value, count)| MountainFacetCount {
+                value: value.clone(),
+                count,
+            })
+            .collect();
+        // 件数の多い順、同数の場合は値の昇順に並べる
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then(a.value.cmp(&b.value)));
+        counts
+    };
+
+    facets
+        .iter()
+        .map(|facet| {
+            let counts = match facet {
+                MountainFacetKey::Prefecture => {
+                    value_counts(documents.iter().flat_map(|md| &md.prefectures).collect())
+                }
+                MountainFacetKey::Tag => {
+                    value_counts(documents.iter().flat_map(|md| &md.tags).collect())
+                }
+                MountainFacetKey::Area => {
+                    value_counts(documents.iter().map(|md| &md.area).collect())
+                }
+                MountainFacetKey::ElevationBand => {
+                    let mut counts: BTreeMap<u32, u64> = BTreeMap::new();
+                    for md in documents {
+                        let lower_bound = md.elevation - md.elevation % ELEVATION_BAND_WIDTH;
+                        *counts.entry(lower_bound).or_default() += 1;
+                    }
+                    counts
+                        .into_iter()
+                        .map(|(lower_bound, count)| {
+                            MountainFacetCount::elevation_band(lower_bound, count)
+                        })
+                        .collect()
+                }
+            };
+            MountainFacet {
+                key: *facet,
+                counts,
+            }
+        })
+        .collect()
+}
+
+/// Returns all the documents inside the box of the condition in its sort order
+///
+/// 検索条件のボックスの内側にある全てのドキュメントを並び順に返します
+fn box_documents<'a>(
+    store: &'a MountainStore,
+    search_condition: &MountainBoxSearchCondition,
+) -> anyhow::Result<Vec<&'a MountainDocument>> {
+    let matcher = MountainMatcher::new(
+        search_condition.name.as_ref(),
+        &Default::default(),
+        &search_condition.tag,
+        search_condition.area.as_deref(),
+        search_condition.elevation,
+    )?;
+
+    let box_coordinates = &search_condition.box_coordinates;
+    let mut mountain_docs: Vec<&MountainDocument> = store
+        .within_box(
+            [box_coordinates.bottom_left.0, box_coordinates.bottom_left.1],
+            [box_coordinates.upper_right.0, box_coordinates.upper_right.1],
+        )
+        .into_iter()
+        .filter(|md| matcher.matches(md))
+        .collect();
+    sort_documents(&mut mountain_docs, &search_condition.sort);
+
+    Ok(mountain_docs)
+}
+
+/// Converts the sorted documents in the page into mountains
+///
+/// 並び替えたドキュメントのうち取得範囲にあるものを山岳に変換します
+fn page_documents(
+    mountain_docs: Vec<&MountainDocument>,
+    skip: u64,
+    limit: Option<i64>,
+) -> anyhow::Result<Vec<Mountain>> {
+    // MongoDB と同様に limit が 0 の場合は上限なしとして扱う
+    let limit = match limit {
+        Some(limit) if limit != 0 => limit.unsigned_abs() as usize,
+        _ => usize::MAX,
+    };
+
+    let mut mountains: Vec<Mountain> = Vec::new();
+    for md in mountain_docs.into_iter().skip(skip as usize).take(limit) {
+        mountains.push(md.clone().try_into()?);
+    }
+    Ok(mountains)
+}
+
+#[async_trait]
+impl MountainRepository for InMemoryRepositoryImpl<Mountain> {
+    async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>> {
+        match self.db.read().get(id.value) {
+            Some(md) => Ok(Some(md.clone().try_into()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64> {
+        let matcher = MountainMatcher::new(
+            search_condition.name.as_ref(),
+            &search_condition.prefecture,
+            &search_condition.tag,
+            search_condition.area.as_deref(),
+            search_condition.elevation,
+        )?
+        .with_filter(search_condition.filter.as_ref())?;
+
+        let count = self
+            .db
+            .read()
+            .iter()
+            .filter(|md| matcher.matches(md))
+            .count();
+        Ok(count as u64)
+    }
+
+    async fn find(
+        &self,
+        search_condition: MountainSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        let matcher = MountainMatcher::new(
+            search_condition.name.as_ref(),
+            &search_condition.prefecture,
+            &search_condition.tag,
+            search_condition.area.as_deref(),
+            search_condition.elevation,
+        )?
+        .with_filter(search_condition.filter.as_ref())?;
+
+        let store = self.db.read();
+
+        // MongoDB と同様に索引の候補のうち検索条件に一致した山岳を数え、関連度順に並べて取得範囲を適用する
+        if let Some(relevance) = MountainRelevanceRanking::new(&search_condition) {
+            let scores = relevance.scores(store.search_index());
+            let mountain_docs: Vec<&MountainDocument> = scores
+                .keys()
+                .filter_map(|id| store.get(*id))
+                .filter(|md| matcher.matches(md))
+                .collect();
+            let total = mountain_docs.len() as u64;
+            let facets = count_facets(&mountain_docs, &search_condition.facets);
+
+            let ranked = relevance.page(
+                store.search_index(),
+                &scores,
+                mountain_docs.iter().map(|md| md.id),
+            );
+            let mut mountains: Vec<Mountain> = Vec::new();
+            for (id, _) in &ranked {
+                if let Some(md) = store.get(*id) {
+                    mountains.push(md.clone().try_into()?);
+                }
+            }
+            return Ok(MountainPage {
+                mountains,
+                total,
+                facets,
+                relevance: ranked.into_iter().collect(),
+            });
+        }
+
+        let mut mountain_docs: Vec<&MountainDocument> =
+            store.iter().filter(|md| matcher.matches(md)).collect();
+        sort_documents(&mut mountain_docs, &search_condition.sort);
+        // `$facet` と同様に件数はカーソルを適用する前に数える
+        let total = mountain_docs.len() as u64;
+        let facets = count_facets(&mountain_docs, &search_condition.facets);
+
+        // 前のページはカーソルに近い順に取得してから要求された順序に戻す
+        let reversed = match &search_condition.cursor {
+            Some(cursor) => {
+                mountain_docs.retain(|md| is_beyond_cursor(md, cursor, &search_condition.sort));
+                cursor.direction == MountainCursorDirection::Prev
+            }
+            None => false,
+        };
+        if reversed {
+            mountain_docs.reverse();
+        }
+
+        let mut mountains =
+            page_documents(mountain_docs, search_condition.skip, search_condition.limit)?;
+        if reversed {
+            mountains.reverse();
+        }
+
+        Ok(MountainPage {
+            mountains,
+            total,
+            facets,
+            relevance: BTreeMap::new(),
+        })
+    }
+
+    async fn find_box(
+        &self,
+        search_condition: MountainBoxSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        let store = self.db.read();
+        let mountain_docs = box_documents(&store, &search_condition)?;
+        let total = mountain_docs.len() as u64;
+        let facets = count_facets(&mountain_docs, &search_condition.facets);
+        let mountains =
+            page_documents(mountain_docs, search_condition.skip, search_condition.limit)?;
+
+        Ok(MountainPage {
+            mountains,
+            total,
+            facets,
+            relevance: BTreeMap::new(),
+        })
+    }
+
+    async fn find_polygon(
+        &self,
+        search_condition: MountainPolygonSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        let matcher = MountainMatcher::new(
+            search_condition.name.as_ref(),
+            &Default::default(),
+            &search_condition.tag,
+            search_condition.area.as_deref(),
+            search_condition.elevation,
+        )?;
+
+        // MongoDB の `$geoWithin: {$geometry: ...}` は辺を測地線として扱うが、山岳の分布する範囲では差は小さい
+        let polygon = &search_condition.polygon;
+        let store = self.db.read();
+        let mut mountain_docs: Vec<&MountainDocument> = store
+            .iter()
+            .filter(|md| {
+                let [longitude, latitude] = md.location.coordinates;
+                polygon.contains(&GeoPoint {
+                    latitude,
+                    longitude,
+                }) && matcher.matches(md)
+            })
+            .collect();
+        sort_documents(&mut mountain_docs, &search_condition.sort);
+        let total = mountain_docs.len() as u64;
+        let mountains =
+            page_documents(mountain_docs, search_condition.skip, search_condition.limit)?;
+
+        Ok(MountainPage {
+            mountains,
+            total,
+            facets: vec![],
+            relevance: BTreeMap::new(),
+        })
+    }
+
+    async fn suggest(
+        &self,
+        search_condition: MountainSuggestCondition,
+    ) -> anyhow::Result<Vec<MountainSuggestion>> {
+        let prefixes = search_condition.prefixes();
+        let mut keys = vec![prefixes.folded.as_str(), prefixes.kana.as_str()];
+        if let Some(romaji) = &prefixes.romaji {
+            keys.push(romaji);
+        }
+
+        let mut candidates: Vec<Mountain> = Vec::new();
+        for md in self.db.read().with_name_prefix(&keys) {
+            candidates.push(md.clone().try_into()?);
+        }
+
+        Ok(search_condition
+            .rank(candidates)
+            .into_iter()
+            .map(MountainSuggestion::from)
+            .collect())
+    }
+
+    async fn create(&self, data: MountainData) -> anyhow::Result<Mountain> {
+        let mut store = self.db.write();
+
+        let mountain_doc = MountainDocument::new(store.next_id(), data, 0);
+        store.upsert(mountain_doc.clone());
+        mountain_doc.try_into()
+    }
+
+    async fn update(
+        &self,
+        id: Id<Mountain>,
+        data: MountainData,
+        version: u32,
+    ) -> anyhow::Result<Option<Mountain>> {
+        let mut store = self.db.write();
+
+        match store.get(id.value) {
+            Some(md) if md.version == version => {}
+            _ => return Ok(None),
+        }
+
+        let mountain_doc = MountainDocument::new(id.value, data, version + 1);
+        store.upsert(mountain_doc.clone());
+        Ok(Some(mountain_doc.try_into()?))
+    }
+
+    async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool> {
+        let mut store = self.db.write();
+
+        match store.get(id.value) {
+            Some(md) if md.version == version => Ok(store.remove(id.value).is_some()),
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::in_memory::InMemoryDb;
+    use mountix_kernel::model::geo::GeoPolygon;
+    use mountix_kernel::model::kana::{fold_name_variants, hiragana_to_romaji};
+    use mountix_kernel::model::mountain::{
+        MountainBoxCoordinates, MountainLocation, MountainNameMatchType, MountainSearchField,
+        MountainSortCondition, MountainValuesMode,
+    };
+    use mountix_kernel::model::mountain_filter::MountainFilterExpr;
+    use mountix_kernel::model::tag::{Tag, TagRegistry};
+    use std::fs::File;
+    use std::io::BufReader;
+
+    fn create_tag_registry() -> TagRegistry {
+        TagRegistry::new(vec![
+            Tag::new(
+                Id::new(1),
+                "百名山".to_string(),
+                "深田久弥が選定した日本百名山".to_string(),
+                "深田久弥『日本百名山』".to_string(),
+            ),
+            Tag::new(
+                Id::new(2),
+                "二百名山".to_string(),
+                "深田クラブが選定した日本二百名山".to_string(),
+                "深田クラブ『日本二百名山』".to_string(),
+            ),
+        ])
+        .unwrap()
+    }
+    fn create_test_repository() -> InMemoryRepositoryImpl<Mountain> {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../migrations/data/mountix_db-mountains.json"
+        );
+        let db = InMemoryDb::from_reader(BufReader::new(File::open(path).unwrap())).unwrap();
+        InMemoryRepositoryImpl::new(db)
+    }
+
+    fn create_search_condition() -> MountainSearchCondition {
+        MountainSearchCondition {
+            name: None,
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_get() {
+        let repository = create_test_repository();
+
+        let mountain = repository.get(Id::new(5)).await.unwrap().unwrap();
+        assert_eq!(mountain.name, "羅臼岳");
+        assert_eq!(mountain.location.longitude, 145.12222222222223);
+        assert_eq!(mountain.location.latitude, 44.075833333333335);
+
+        assert!(repository.get(Id::new(0)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_get_count() {
+        let repository = create_test_repository();
+
+        let count = repository
+            .get_count(create_search_condition())
+            .await
+            .unwrap();
+        assert_eq!(count, 1059);
+
+        let mut condition = create_search_condition();
+        condition.tag = MountainValuesCondition::single(MountainTag {
+            id: 1,
+            name: "百名山".to_string(),
+        });
+        assert_eq!(repository.get_count(condition).await.unwrap(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_get_count_by_elevation() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.elevation = MountainElevationRange::new(Some(3000), None).unwrap();
+        assert_eq!(repository.get_count(condition).await.unwrap(), 23);
+
+        let mut condition = create_search_condition();
+        condition.prefecture = MountainValuesCondition::single(
+            MountainPrefecture::try_from("東京都".to_string()).unwrap(),
+        );
+        condition.elevation = MountainElevationRange::new(None, Some(999)).unwrap();
+        assert_eq!(repository.get_count(condition).await.unwrap(), 14);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_by_filter_expression() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.filter = Some(
+            MountainFilterExpr::parse(
+                r#"elevation>=3000 AND (tag:百名山 OR tag:二百名山) AND pref:長野県 AND area:"飛騨山脈*""#, &create_tag_registry())
+            .unwrap(),
+        );
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        assert!(mountains.iter().any(|m| m.name == "槍ヶ岳"));
+        assert!(mountains.iter().all(|m| m.elevation >= 3000
+            && m.prefectures.contains(&"長野県".to_string())
+            && m.area.starts_with("飛騨山脈")));
+
+        // 他の条件とは AND で結合する
+        let mut condition = create_search_condition();
+        condition.name = Some(MountainNameCondition::contains("岳".to_string()));
+        condition.filter = Some(
+            MountainFilterExpr::parse("NOT elevation<3100 AND name:槍*", &create_tag_registry())
+                .unwrap(),
+        );
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        let names: Vec<&str> = mountains.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["槍ヶ岳"]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_by_prefectures() {
+        let repository = create_test_repository();
+        let prefectures = |mode| {
+            MountainValuesCondition::new(
+                vec![
+                    MountainPrefecture::try_from("長野県".to_string()).unwrap(),
+                    MountainPrefecture::try_from("富山県".to_string()).unwrap(),
+                ],
+                mode,
+            )
+        };
+
+        let mut condition = create_search_condition();
+        condition.prefecture = prefectures(MountainValuesMode::All);
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        assert!(mountains.iter().any(|m| m.name == "白馬岳"));
+        assert!(mountains
+            .iter()
+            .all(|m| m.prefectures.contains(&"長野県".to_string())
+                && m.prefectures.contains(&"富山県".to_string())));
+
+        let mut condition = create_search_condition();
+        condition.prefecture = prefectures(MountainValuesMode::Any);
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        assert!(mountains
+            .iter()
+            .any(|m| m.prefectures == vec!["長野県".to_string()]));
+        assert!(mountains
+            .iter()
+            .any(|m| m.prefectures == vec!["富山県".to_string()]));
+        assert!(mountains
+            .iter()
+            .all(|m| m.prefectures.contains(&"長野県".to_string())
+                || m.prefectures.contains(&"富山県".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_by_name_and_prefecture() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.name = Some(MountainNameCondition::contains("ふじさん".to_string()));
+        condition.prefecture = MountainValuesCondition::single(
+            MountainPrefecture::try_from("19".to_string()).unwrap(),
+        );
+
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        assert!(mountains.iter().any(|m| m.name == "富士山<剣ヶ峯>"));
+        assert!(mountains
+            .iter()
+            .all(|m| m.prefectures.contains(&"山梨県".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_sorted_by_relevance() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.name = Some(MountainNameCondition::contains("富士".to_string()));
+        condition.sort = MountainSort::try_from("relevance".to_string()).unwrap();
+        condition.limit = Some(5);
+
+        let page = repository.find(condition.clone()).await.unwrap();
+        assert_eq!(page.mountains.len(), 5);
+        assert!(page.total > 5);
+        let scores: Vec<f64> = page
+            .mountains
+            .iter()
+            .map(|m| page.relevance[&m.id.value].score)
+            .collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+        let highlight = &page.relevance[&page.mountains[0].id.value].highlights[0];
+        assert_eq!(highlight.field, MountainSearchField::Name);
+        assert!(highlight.value.contains("富士"));
+
+        // 取得範囲は関連度順に並べた後に適用する
+        condition.skip = 4;
+        condition.limit = Some(1);
+        let next_page = repository.find(condition).await.unwrap();
+        assert_eq!(next_page.mountains[0].id.value, page.mountains[4].id.value);
+        assert_eq!(next_page.relevance.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_sort_and_paging() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.sort = MountainSort::try_from("elevation.desc".to_string()).unwrap();
+        condition.skip = 1;
+        condition.limit = Some(2);
+
+        let count = repository.get_count(condition.clone()).await.unwrap();
+        let page = repository.find(condition).await.unwrap();
+        assert_eq!(page.total, count);
+        let mountains = page.mountains;
+        assert_eq!(mountains.len(), 2);
+        assert!(mountains[0].elevation >= mountains[1].elevation);
+        assert!(mountains[0].elevation < 3776);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_sorted_by_multiple_keys() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.sort =
+            MountainSort::try_from("prefecture.asc,elevation.desc".to_string()).unwrap();
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        let keys: Vec<(u64, u32)> = mountains
+            .iter()
+            .map(|m| {
+                let code = m
+                    .prefectures
+                    .iter()
+                    .filter_map(|p| MountainPrefecture::code_of(p))
+                    .min()
+                    .unwrap();
+                (code, m.elevation)
+            })
+            .collect();
+        assert!(keys
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 >= w[1].1)));
+
+        // 富士山の山頂付近からの距離順
+        let mut condition = create_search_condition();
+        condition.sort = MountainSort::try_from("distance.asc".to_string())
+            .unwrap()
+            .with_point(GeoPoint::new(35.3606, 138.7274).unwrap());
+        condition.limit = Some(3);
+        let mountains = repository.find(condition).await.unwrap().mountains;
+        assert_eq!(mountains[0].name, "富士山<剣ヶ峯>");
+    }
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_with_cursor() {
+        let repository = create_test_repository();
+        let sort: MountainSort = MountainSortCondition::try_from("elevation.desc".to_string())
+            .unwrap()
+            .into();
+
+        let mut search_condition = create_search_condition();
+        search_condition.sort = sort.clone();
+        search_condition.limit = Some(6);
+        let first_page = repository
+            .find(search_condition.clone())
+            .await
+            .unwrap()
+            .mountains;
+        assert_eq!(first_page[0].name, "富士山<剣ヶ峯>");
+
+        // 3 件目を指す次方向のカーソルで 4 件目から 6 件目が取得できる
+        search_condition.limit = Some(3);
+        search_condition.cursor =
+            MountainCursor::new(MountainCursorDirection::Next, &sort, &first_page[2]);
+        let next_page = repository
+            .find(search_condition.clone())
+            .await
+            .unwrap()
+            .mountains;
+        let ids: Vec<i32> = next_page.iter().map(|m| m.id.value).collect();
+        let expected: Vec<i32> = first_page[3..6].iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, expected);
+
+        // 4 件目を指す前方向のカーソルで 1 件目から 3 件目が同じ順序で取得できる
+        search_condition.cursor =
+            MountainCursor::new(MountainCursorDirection::Prev, &sort, &next_page[0]);
+        let prev_page = repository.find(search_condition).await.unwrap().mountains;
+        let ids: Vec<i32> = prev_page.iter().map(|m| m.id.value).collect();
+        let expected: Vec<i32> = first_page[..3].iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_with_multi_key_cursor() {
+        let repository = create_test_repository();
+        let sort = MountainSort::try_from("prefecture.asc,distance.asc,elevation.desc".to_string())
+            .unwrap()
+            .with_point(GeoPoint::new(35.3606, 138.7274).unwrap());
+
+        let mut search_condition = create_search_condition();
+        search_condition.sort = sort.clone();
+        search_condition.limit = Some(0);
+        let all = repository
+            .find(search_condition.clone())
+            .await
+            .unwrap()
+            .mountains;
+        let expected: Vec<i32> = all.iter().map(|m| m.id.value).collect();
+
+        // 次方向のカーソルをたどると、すべての山岳を重複なく同じ順序で取得できる
+        search_condition.limit = Some(7);
+        let mut ids: Vec<i32> = Vec::new();
+        loop {
+            let page = repository
+                .find(search_condition.clone())
+                .await
+                .unwrap()
+                .mountains;
+            let Some(last) = page.last() else {
+                break;
+            };
+            ids.extend(page.iter().map(|m| m.id.value));
+            search_condition.cursor =
+                MountainCursor::new(MountainCursorDirection::Next, &sort, last);
+        }
+        assert_eq!(ids, expected);
+
+        // 前方向のカーソルで直前の山岳を同じ順序で取得できる
+        search_condition.cursor =
+            MountainCursor::new(MountainCursorDirection::Prev, &sort, &all[10]);
+        search_condition.limit = Some(3);
+        let prev_page = repository.find(search_condition).await.unwrap().mountains;
+        let ids: Vec<i32> = prev_page.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, expected[7..10].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_with_facets() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.tag = MountainValuesCondition::single(MountainTag {
+            id: 1,
+            name: "百名山".to_string(),
+        });
+        condition.limit = Some(1);
+        condition.facets = vec![MountainFacetKey::Tag, MountainFacetKey::ElevationBand];
+
+        let page = repository.find(condition).await.unwrap();
+        assert_eq!(page.mountains.len(), 1);
+        assert_eq!(page.facets.len(), 2);
+
+        // 取得範囲に関係なく条件に一致した全ての山岳を数える
+        let tag_counts = &page.facets[0].counts;
+        assert_eq!(tag_counts[0].value, "百名山");
+        assert_eq!(tag_counts[0].count, 100);
+        assert!(tag_counts.windows(2).all(|w| w[0].count >= w[1].count));
+
+        let band_counts = &page.facets[1].counts;
+        assert_eq!(band_counts.iter().map(|c| c.count).sum::<u64>(), 100);
+        assert_eq!(band_counts.last().unwrap().value, "3500-3999");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_invalid_regex() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.name = Some(MountainNameCondition {
+            value: "(".to_string(),
+            match_type: MountainNameMatchType::Regex,
+        });
+
+        assert!(repository.find(condition).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_escapes_name() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.name = Some(MountainNameCondition::contains("(".to_string()));
+
+        let page = repository.find(condition).await.unwrap();
+        assert_eq!(page.total, 1);
+        assert!(page.mountains[0].name_kana.contains('('));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_with_name_prefix() {
+        let repository = create_test_repository();
+
+        let mut condition = create_search_condition();
+        condition.name = Some(
+            MountainNameCondition::new("富士".to_string(), MountainNameMatchType::Prefix).unwrap(),
+        );
+
+        let page = repository.find(condition).await.unwrap();
+        assert!(page.total > 0);
+        assert!(page.mountains.iter().all(|m| m.name.starts_with("富士")));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_by_kana_and_romaji() {
+        let repository = create_test_repository();
+
+        for name in ["ヤリガタケ", "ﾔﾘｶﾞﾀｹ", "yarigatake", "Yari-ga-take"] {
+            let mut condition = create_search_condition();
+            condition.name = Some(MountainNameCondition::contains(name.to_string()));
+
+            let page = repository.find(condition).await.unwrap();
+            assert!(
+                page.mountains.iter().any(|m| m.name == "槍ヶ岳"),
+                "{}",
+                name
+            );
+        }
+
+        // 訓令式と長音を省略したヘボン式
+        for name in ["huzisan", "ryokamisan"] {
+            let mut condition = create_search_condition();
+            condition.name = Some(MountainNameCondition::contains(name.to_string()));
+
+            let page = repository.find(condition).await.unwrap();
+            assert!(page.total > 0, "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_by_name_variant() {
+        let repository = create_test_repository();
+
+        for name in ["槍ケ岳", "槍が岳", "御岳山", "剣ケ峯"] {
+            let mut condition = create_search_condition();
+            condition.name = Some(MountainNameCondition::contains(name.to_string()));
+
+            let page = repository.find(condition).await.unwrap();
+            assert!(page.total > 0, "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_by_alias() {
+        let repository = create_test_repository();
+
+        for (name, expected) in [
+            ("木曽御嶽山", "御嶽山<剣ヶ峰>"),
+            ("マッカリヌプリ", "羊蹄山（蝦夷富士）"),
+            ("makkarinupuri", "羊蹄山（蝦夷富士）"),
+            ("ほうきだいせん", "大山<剣ヶ峰>"),
+        ] {
+            let mut condition = create_search_condition();
+            condition.name = Some(MountainNameCondition::contains(name.to_string()));
+
+            let page = repository.find(condition).await.unwrap();
+            assert_eq!(page.total, 1, "{}", name);
+            assert_eq!(page.mountains[0].name, expected);
+            assert!(!page.mountains[0].aliases.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_suggest() {
+        let repository = create_test_repository();
+        let suggest = |q: &str, limit: usize| {
+            repository.suggest(MountainSuggestCondition::new(q.to_string(), limit))
+        };
+
+        // 百名山を優先し、同じ条件の場合は標高の高い順に並べる
+        for q in ["ふじ", "フジ", "fuji"] {
+            let names: Vec<String> = suggest(q, 10)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|m| m.name)
+                .collect();
+            assert_eq!(names, ["富士山<剣ヶ峯>", "藤無山", "富士写ヶ岳"], "{}", q);
+        }
+
+        let mountains = suggest("やりが", 1).await.unwrap();
+        assert_eq!(mountains.len(), 1);
+        assert_eq!(mountains[0].name, "槍ヶ岳");
+
+        // 山名での一致を別名での一致より優先する
+        let mountains = suggest("しり", 10).await.unwrap();
+        assert_eq!(mountains[0].name, "尻別岳");
+        assert_eq!(mountains[1].name, "羊蹄山（蝦夷富士）");
+
+        let mountains = suggest("伯耆", 10).await.unwrap();
+        assert_eq!(mountains.len(), 1);
+        assert_eq!(mountains[0].name, "大山<剣ヶ峰>");
+
+        assert!(suggest("じさん", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_suggest_after_update() {
+        let repository = create_test_repository();
+
+        let mut mountain = repository.get(Id::new(1)).await.unwrap().unwrap();
+        mountain.name = "テスト岳".to_string();
+        mountain.name_kana = "てすとだけ".to_string();
+        let data = MountainData {
+            name: mountain.name,
+            name_kana: mountain.name_kana,
+            area: mountain.area,
+            prefectures: mountain.prefectures,
+            elevation: mountain.elevation,
+            location: mountain.location,
+            tags: mountain.tags,
+            aliases: mountain.aliases,
+        };
+        repository.update(Id::new(1), data, 0).await.unwrap();
+
+        let suggest =
+            |q: &str| repository.suggest(MountainSuggestCondition::new(q.to_string(), 10));
+        assert_eq!(suggest("tesuto").await.unwrap()[0].id.value, 1);
+        assert!(suggest("しれとこだけ").await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mountains_data_search_names() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../migrations/data/mountix_db-mountains.json"
+        );
+        let data = std::fs::read_to_string(path).unwrap();
+        for line in data.lines().filter(|line| !line.trim().is_empty()) {
+            let md: MountainDocument = serde_json::from_str(line).unwrap();
+            assert_eq!(md.name_folded, fold_name_variants(&md.name));
+            assert_eq!(md.name_romaji, hiragana_to_romaji(&md.name_kana));
+            for alias in md.aliases.iter() {
+                assert_eq!(alias.name_folded, fold_name_variants(&alias.name));
+                assert_eq!(alias.name_romaji, hiragana_to_romaji(&alias.name_kana));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_box() {
+        let repository = create_test_repository();
+
+        let condition = MountainBoxSearchCondition {
+            box_coordinates: MountainBoxCoordinates::try_from(
+                "(138.5,35.2),(138.9,35.5)".to_string(),
+            )
+            .unwrap(),
+            name: None,
+            tag: MountainValuesCondition::single(MountainTag {
+                id: 1,
+                name: "百名山".to_string(),
+            }),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+            facets: vec![],
+        };
+
+        let mountains = repository.find_box(condition).await.unwrap().mountains;
+        assert_eq!(mountains.len(), 1);
+        assert_eq!(mountains[0].name, "富士山<剣ヶ峯>");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_box_with_page() {
+        let repository = create_test_repository();
+
+        let condition = MountainBoxSearchCondition {
+            box_coordinates: MountainBoxCoordinates::try_from(
+                "(138.5,35.2),(138.9,35.5)".to_string(),
+            )
+            .unwrap(),
+            name: None,
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+            facets: vec![],
+        };
+        let all_page = repository.find_box(condition.clone()).await.unwrap();
+        let total = all_page.total;
+        let all_ids: Vec<i32> = all_page.mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(all_ids.len() as u64, total);
+        assert!(total > 3);
+
+        let page_condition = MountainBoxSearchCondition {
+            skip: 1,
+            limit: Some(2),
+            ..condition
+        };
+        let page = repository.find_box(page_condition).await.unwrap();
+        assert_eq!(page.total, total);
+        let page_ids: Vec<i32> = page.mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(page_ids, all_ids[1..3]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_polygon() {
+        let repository = create_test_repository();
+
+        let box_condition = MountainBoxSearchCondition {
+            box_coordinates: MountainBoxCoordinates::try_from(
+                "(138.5,35.2),(138.9,35.5)".to_string(),
+            )
+            .unwrap(),
+            name: None,
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+            facets: vec![],
+        };
+        let box_ids: Vec<i32> = repository
+            .find_box(box_condition)
+            .await
+            .unwrap()
+            .mountains
+            .iter()
+            .map(|m| m.id.value)
+            .collect();
+
+        let polygon_condition = |ring: Vec<[f64; 2]>| MountainPolygonSearchCondition {
+            polygon: GeoPolygon::try_from(vec![vec![ring]]).unwrap(),
+            name: None,
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+        };
+
+        // ボックスと同じ四角形のポリゴンはボックス検索と同じ結果になる
+        let square = vec![
+            [138.5, 35.2],
+            [138.9, 35.2],
+            [138.9, 35.5],
+            [138.5, 35.5],
+            [138.5, 35.2],
+        ];
+        let ids: Vec<i32> = repository
+            .find_polygon(polygon_condition(square))
+            .await
+            .unwrap()
+            .mountains
+            .iter()
+            .map(|m| m.id.value)
+            .collect();
+        assert_eq!(ids, box_ids);
+
+        // 対角線で半分にした三角形
+        let triangle = vec![[138.5, 35.2], [138.9, 35.2], [138.9, 35.5], [138.5, 35.2]];
+        let page = repository
+            .find_polygon(polygon_condition(triangle.clone()))
+            .await
+            .unwrap();
+        let mountains = page.mountains;
+        assert!(!mountains.is_empty() && mountains.len() < box_ids.len());
+        assert_eq!(page.total, mountains.len() as u64);
+        assert!(mountains.iter().all(|m| box_ids.contains(&m.id.value)
+            && m.location.latitude - 35.2 < (m.location.longitude - 138.5) * 0.75));
+
+        // 件数は取得範囲に関係なくポリゴン内のすべての山岳を数える
+        let page = repository
+            .find_polygon(MountainPolygonSearchCondition {
+                skip: 1,
+                limit: Some(1),
+                ..polygon_condition(triangle)
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.total, mountains.len() as u64);
+        assert_eq!(page.mountains.len(), 1);
+        assert_eq!(page.mountains[0].id.value, mountains[1].id.value);
+    }
+
+    fn create_test_data() -> MountainData {
+        MountainData {
+            name: "テスト山".to_string(),
+            name_kana: "てすとやま".to_string(),
+            area: "富士山".to_string(),
+            prefectures: vec!["静岡県".to_string()],
+            elevation: 1000,
+            location: MountainLocation::from_coordinates(35.3, 138.8),
+            tags: vec![],
+            aliases: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_create_update_delete() {
+        let repository = create_test_repository();
+
+        let mountain = repository.create(create_test_data()).await.unwrap();
+        assert_eq!(mountain.id.value, 1060);
+        assert_eq!(mountain.version, 0);
+
+        let mut data = create_test_data();
+        data.elevation = 1200;
+        let updated = repository
+            .update(Id::new(1060), data.clone(), 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.elevation, 1200);
+        assert_eq!(updated.version, 1);
+
+        // 古いバージョンでは更新も削除もできない
+        assert!(repository
+            .update(Id::new(1060), data, 0)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(!repository.delete(Id::new(1060), 0).await.unwrap());
+
+        assert!(repository.delete(Id::new(1060), 1).await.unwrap());
+        assert!(repository.get(Id::new(1060)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_find_sorted_by_relevance_after_update() {
+        let repository = create_test_repository();
+        let find = |name: &str| {
+            let mut condition = create_search_condition();
+            condition.name = Some(MountainNameCondition::contains(name.to_string()));
+            condition.sort = MountainSort::try_from("relevance".to_string()).unwrap();
+            repository.find(condition)
+        };
+
+        // 登録、更新、削除した山岳は索引を作り直さずに関連度順の検索に反映される
+        let mountain = repository.create(create_test_data()).await.unwrap();
+        let page = find("テスト山").await.unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.mountains[0].id.value, mountain.id.value);
+        assert!(page.relevance[&mountain.id.value].score > 0.0);
+
+        let mut data = create_test_data();
+        data.name = "試験岳".to_string();
+        repository
+            .update(mountain.id, data, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(find("テスト山").await.unwrap().total, 0);
+        let page = find("試験岳").await.unwrap();
+        assert_eq!(page.mountains[0].id.value, 1060);
+
+        assert!(repository.delete(Id::new(1060), 1).await.unwrap());
+        assert_eq!(find("試験岳").await.unwrap().total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mountain_update_not_found() {
+        let repository = create_test_repository();
+
+        let result = repository
+            .update(Id::new(0), create_test_data(), 0)
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+}
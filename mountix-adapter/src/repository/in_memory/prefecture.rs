@@ -0,0 +1,70 @@
+use crate::repository::in_memory::InMemoryRepositoryImpl;
+use async_trait::async_trait;
+use mountix_kernel::model::mountain::MountainPrefecture;
+use mountix_kernel::model::prefecture::{PrefectureHighestPeak, PrefectureMountainStats};
+use mountix_kernel::repository::prefecture::PrefectureRepository;
+use std::collections::BTreeMap;
+
+#[async_trait]
+impl PrefectureRepository for InMemoryRepositoryImpl<MountainPrefecture> {
+    async fn find_stats(&self) -> anyhow::Result<Vec<PrefectureMountainStats>> {
+        let store = self.db.read();
+
+        let mut stats: BTreeMap<&str, PrefectureMountainStats> = BTreeMap::new();
+        for md in store.iter() {
+            for prefecture in &md.prefectures {
+                let entry =
+                    stats
+                        .entry(prefecture.as_str())
+                        .or_insert_with(|| PrefectureMountainStats {
+                            name: prefecture.clone(),
+                            mountain_count: 0,
+                            highest_peak: None,
+                        });
+                entry.mountain_count += 1;
+
+                // `_id` 順に走査するため、標高が同じ場合は `_id` の小さい山岳を優先する
+                let is_higher = match &entry.highest_peak {
+                    Some(peak) => md.elevation > peak.elevation,
+                    None => true,
+                };
+                if is_higher {
+                    entry.highest_peak = Some(PrefectureHighestPeak {
+                        id: md.id.into(),
+                        name: md.name.clone(),
+                        elevation: md.elevation,
+                    });
+                }
+            }
+        }
+
+        Ok(stats.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::in_memory::InMemoryDb;
+
+    const TEST_DATA: &str = r#"{"_id":1,"name":"富士山","name_kana":"ふじさん","area":"富士山","prefectures":["静岡県","山梨県"],"elevation":3776,"tags":["百名山"],"location":{"type":"Point","coordinates":[138.7275,35.36083333333333]},"gsi_url":"https://maps.gsi.go.jp/#15/35.36083333333333/138.7275"}
+{"_id":2,"name":"毛無山","name_kana":"けなしやま","area":"天子山地","prefectures":["静岡県","山梨県"],"elevation":1964,"tags":["二百名山"],"location":{"type":"Point","coordinates":[138.5433333333333,35.41777777777778]},"gsi_url":"https://maps.gsi.go.jp/#15/35.41777777777778/138.5433333333333"}
+{"_id":3,"name":"愛鷹山","name_kana":"あしたかやま","area":"富士山","prefectures":["静岡県"],"elevation":1504,"tags":[],"location":{"type":"Point","coordinates":[138.80666666666667,35.2325]},"gsi_url":"https://maps.gsi.go.jp/#15/35.2325/138.80666666666667"}
+"#;
+
+    #[tokio::test]
+    async fn test_in_memory_prefecture_find_stats() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<MountainPrefecture>::new(db);
+
+        let stats = repository.find_stats().await.unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let shizuoka = stats.iter().find(|s| s.name == "静岡県").unwrap();
+        assert_eq!(shizuoka.mountain_count, 3);
+        assert_eq!(shizuoka.highest_peak.as_ref().unwrap().name, "富士山");
+
+        let yamanashi = stats.iter().find(|s| s.name == "山梨県").unwrap();
+        assert_eq!(yamanashi.mountain_count, 2);
+    }
+}
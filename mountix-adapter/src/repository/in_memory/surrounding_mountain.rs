@@ -0,0 +1,413 @@
+use crate::model::mountain::MountainDocument;
+use crate::model::surrounding_mountain::{
+    corridor_boxes, select_corridor_documents, SurroundingMountainDocument,
+};
+use crate::persistence::in_memory::MountainStore;
+use crate::repository::in_memory::mountain::{sort_documents, MountainMatcher};
+use crate::repository::in_memory::InMemoryRepositoryImpl;
+use async_trait::async_trait;
+use mountix_kernel::model::mountain::{
+    MountainElevationRange, MountainOrderType, MountainSortCondition,
+};
+use mountix_kernel::model::surrounding_mountain::{
+    CorridorMountainSearchCondition, NearbyMountainSearchCondition, NearestMountainSearchCondition,
+    SurroundingMountain, SurroundingMountainSearchCondition, SurroundingMountainSortKey,
+};
+use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
+use std::collections::BTreeMap;
+
+/// Returns all the surroundings of the condition in its sort order, like the `$geoNear` pipeline
+///
+/// 検索条件に一致する全ての周辺の山岳を並び順に返します
+fn surrounding_documents<'a>(
+    store: &'a MountainStore,
+    search_condition: &SurroundingMountainSearchCondition,
+) -> anyhow::Result<Vec<&'a MountainDocument>> {
+    let matcher = MountainMatcher::new(
+        None,
+        &search_condition.prefecture.clone().into(),
+        &search_condition.tag.clone().into(),
+        None,
+        search_condition.elevation,
+    )?;
+
+    let center = [
+        search_condition.mountain.location.longitude,
+        search_condition.mountain.location.latitude,
+    ];
+
+    // within_distance は距離と _id の昇順で返す
+    let mut mountain_docs: Vec<&MountainDocument> = store
+        .within_distance(center, search_condition.distance.0 as f64)
+        .into_iter()
+        .map(|(md, _)| md)
+        .filter(|md| md.id != search_condition.mountain.id.value && matcher.matches(md))
+        .collect();
+
+    match search_condition.sort.key {
+        SurroundingMountainSortKey::Distance => {
+            if let MountainOrderType::Desc = search_condition.sort.order {
+                mountain_docs.reverse();
+            }
+        }
+        SurroundingMountainSortKey::Mountain(key) => sort_documents(
+            &mut mountain_docs,
+            &MountainSortCondition {
+                key,
+                order: search_condition.sort.order,
+            }
+            .into(),
+        ),
+    }
+
+    Ok(mountain_docs)
+}
+
+#[async_trait]
+impl SurroundingMountainRepository for InMemoryRepositoryImpl<SurroundingMountain> {
+    async fn get_count(
+        &self,
+        search_condition: SurroundingMountainSearchCondition,
+    ) -> anyhow::Result<u64> {
+        let store = self.db.read();
+        let count = surrounding_documents(&store, &search_condition)?.len();
+        Ok(count as u64)
+    }
+
+    async fn find(
+        &self,
+        search_condition: SurroundingMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let store = self.db.read();
+        let mountain_docs = surrounding_documents(&store, &search_condition)?;
+
+        // MongoDB と同様に limit が 0 の場合は上限なしとして扱う
+        let limit = match search_condition.limit {
+            Some(limit) if limit != 0 => limit.unsigned_abs() as usize,
+            _ => usize::MAX,
+        };
+
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        for md in mountain_docs
+            .into_iter()
+            .skip(search_condition.skip as usize)
+            .take(limit)
+        {
+            let sd: SurroundingMountainDocument = md.clone().into();
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+
+    async fn find_nearby(
+        &self,
+        search_condition: NearbyMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let matcher = MountainMatcher::new(
+            search_condition.name.as_ref(),
+            &Default::default(),
+            &search_condition.tag.clone().into(),
+            None,
+            MountainElevationRange::default(),
+        )?;
+
+        let center = [
+            search_condition.point.longitude,
+            search_condition.point.latitude,
+        ];
+
+        let store = self.db.read();
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        for (md, _) in store.within_distance(center, search_condition.distance.0 as f64) {
+            if !matcher.matches(md) {
+                continue;
+            }
+            let sd: SurroundingMountainDocument = md.clone().into();
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+
+    async fn find_nearest(
+        &self,
+        search_condition: NearestMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let matcher = MountainMatcher::new(
+            None,
+            &Default::default(),
+            &search_condition.tag.clone().into(),
+            None,
+            search_condition.elevation,
+        )?;
+        let exclude_id = search_condition.exclude_id.map(|id| id.value);
+
+        let center = [
+            search_condition.point.longitude,
+            search_condition.point.latitude,
+        ];
+
+        let store = self.db.read();
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        for (md, _) in store
+            .nearest(center)
+            .filter(|(md, _)| Some(md.id) != exclude_id && matcher.matches(md))
+            .take(search_condition.k as usize)
+        {
+            let sd: SurroundingMountainDocument = md.clone().into();
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+
+    async fn find_corridor(
+        &self,
+        search_condition: CorridorMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let matcher = MountainMatcher::new(
+            search_condition.name.as_ref(),
+            &Default::default(),
+            &search_condition.tag.clone().into(),
+            None,
+            search_condition.elevation,
+        )?;
+
+        let store = self.db.read();
+        let mut candidates: BTreeMap<i32, &MountainDocument> = BTreeMap::new();
+        for (bottom_left, upper_right) in
+            corridor_boxes(&search_condition.line, search_condition.buffer.0 as f64)
+        {
+            for md in store.within_box(bottom_left, upper_right) {
+                if matcher.matches(md) {
+                    candidates.insert(md.id, md);
+                }
+            }
+        }
+
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        for md in select_corridor_documents(
+            candidates.into_values().collect(),
+            |md| (md.location.coordinates, md.id),
+            &search_condition,
+        ) {
+            let sd: SurroundingMountainDocument = md.clone().into();
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::in_memory::InMemoryDb;
+    use mountix_kernel::model::geo::{GeoLineString, GeoPoint};
+    use mountix_kernel::model::mountain::{
+        Mountain, MountainData, MountainLocation, MountainNameCondition, MountainPrefecture,
+        MountainTag,
+    };
+    use mountix_kernel::model::surrounding_mountain::{
+        SurroundingMountainSearchDistance, SurroundingMountainSortCondition,
+    };
+    use mountix_kernel::model::Id;
+
+    const TEST_DATA: &str = r#"{"_id":1,"name":"富士山","name_kana":"ふじさん","area":"富士山","prefectures":["静岡県","山梨県"],"elevation":3776,"tags":["百名山"],"location":{"type":"Point","coordinates":[138.7275,35.36083333333333]},"gsi_url":"https://maps.gsi.go.jp/#15/35.36083333333333/138.7275"}
+{"_id":2,"name":"毛無山","name_kana":"けなしやま","area":"天子山地","prefectures":["静岡県","山梨県"],"elevation":1964,"tags":["二百名山"],"location":{"type":"Point","coordinates":[138.5433333333333,35.41777777777778]},"gsi_url":"https://maps.gsi.go.jp/#15/35.41777777777778/138.5433333333333"}
+{"_id":3,"name":"愛鷹山","name_kana":"あしたかやま","area":"富士山","prefectures":["静岡県"],"elevation":1504,"tags":[],"location":{"type":"Point","coordinates":[138.80666666666667,35.2325]},"gsi_url":"https://maps.gsi.go.jp/#15/35.2325/138.80666666666667"}
+"#;
+
+    fn create_test_mountain() -> Mountain {
+        let location = MountainLocation::new(
+            35.36083333333333,
+            138.7275,
+            "https://maps.gsi.go.jp/#15/35.36083333333333/138.7275".to_string(),
+        );
+        let data = MountainData {
+            name: "富士山".to_string(),
+            name_kana: "ふじさん".to_string(),
+            area: "富士山".to_string(),
+            prefectures: vec!["静岡県".to_string(), "山梨県".to_string()],
+            elevation: 3776,
+            location,
+            tags: vec!["百名山".to_string()],
+            aliases: vec![],
+        };
+        Mountain::new(Id::new(1), data)
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        let condition = SurroundingMountainSearchCondition::new(
+            create_test_mountain(),
+            SurroundingMountainSearchDistance::new(20_000),
+        );
+        let mountains = repository.find(condition).await.unwrap();
+
+        // 中心の山岳を除き、距離の近い順に返す
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![3, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find_by_elevation() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        let condition = SurroundingMountainSearchCondition::new(
+            create_test_mountain(),
+            SurroundingMountainSearchDistance::new(20_000),
+        )
+        .with_elevation(MountainElevationRange::new(Some(1900), None).unwrap());
+        let mountains = repository.find(condition).await.unwrap();
+
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find_with_page() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        let condition = SurroundingMountainSearchCondition::new(
+            create_test_mountain(),
+            SurroundingMountainSearchDistance::new(20_000),
+        )
+        .with_page(
+            1,
+            Some(1),
+            SurroundingMountainSortCondition::try_from("distance.desc".to_string()).unwrap(),
+        );
+        assert_eq!(repository.get_count(condition.clone()).await.unwrap(), 2);
+        let mountains = repository.find(condition).await.unwrap();
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![3]);
+
+        let condition = SurroundingMountainSearchCondition::new(
+            create_test_mountain(),
+            SurroundingMountainSearchDistance::new(20_000),
+        )
+        .with_filter(
+            Some(MountainPrefecture::try_from("山梨".to_string()).unwrap()),
+            None,
+        );
+        assert_eq!(repository.get_count(condition.clone()).await.unwrap(), 1);
+        let mountains = repository.find(condition).await.unwrap();
+        assert_eq!(mountains[0].id.value, 2);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find_nearby() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        // 愛鷹山の北の地点
+        let condition = NearbyMountainSearchCondition {
+            point: GeoPoint::new(35.25, 138.8).unwrap(),
+            distance: SurroundingMountainSearchDistance::new(40_000),
+            name: None,
+            tag: None,
+        };
+        let mountains = repository.find_nearby(condition).await.unwrap();
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+
+        let condition = NearbyMountainSearchCondition {
+            point: GeoPoint::new(35.25, 138.8).unwrap(),
+            distance: SurroundingMountainSearchDistance::new(40_000),
+            name: Some(MountainNameCondition::contains("やま".to_string())),
+            tag: Some(MountainTag {
+                id: 2,
+                name: "二百名山".to_string(),
+            }),
+        };
+        let mountains = repository.find_nearby(condition).await.unwrap();
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find_nearest() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        // 距離の上限なしに k 件を返す
+        let condition = NearestMountainSearchCondition {
+            point: GeoPoint::new(35.25, 138.8).unwrap(),
+            k: 2,
+            tag: None,
+            elevation: MountainElevationRange::default(),
+            exclude_id: None,
+        };
+        let mountains = repository.find_nearest(condition).await.unwrap();
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![3, 1]);
+
+        let condition = NearestMountainSearchCondition {
+            point: GeoPoint::new(44.0, 145.0).unwrap(),
+            k: 10,
+            tag: None,
+            elevation: MountainElevationRange::new(None, Some(3000)).unwrap(),
+            exclude_id: Some(Id::new(3)),
+        };
+        let mountains = repository.find_nearest(condition).await.unwrap();
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find_corridor() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        // 愛鷹山から富士山を経て毛無山へ向かうライン
+        let line = GeoLineString::try_from(vec![
+            [138.80666666666667, 35.2325],
+            [138.7275, 35.36083333333333],
+            [138.5433333333333, 35.41777777777778],
+        ])
+        .unwrap();
+        let condition = CorridorMountainSearchCondition {
+            line: line.clone(),
+            buffer: SurroundingMountainSearchDistance::new(1_000),
+            name: None,
+            tag: None,
+            elevation: MountainElevationRange::default(),
+        };
+        let mountains = repository.find_corridor(condition).await.unwrap();
+        let ids: Vec<i32> = mountains.iter().map(|m| m.id.value).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+
+        let condition = CorridorMountainSearchCondition {
+            line: GeoLineString::try_from(vec![[138.7, 35.5], [138.8, 35.5]]).unwrap(),
+            buffer: SurroundingMountainSearchDistance::new(10_000),
+            name: None,
+            tag: None,
+            elevation: MountainElevationRange::new(Some(2000), None).unwrap(),
+        };
+        assert!(repository
+            .find_corridor(condition)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_surrounding_mountain_find_out_of_distance() {
+        let db = InMemoryDb::from_reader(TEST_DATA.as_bytes()).unwrap();
+        let repository = InMemoryRepositoryImpl::<SurroundingMountain>::new(db);
+
+        let condition = SurroundingMountainSearchCondition::new(
+            create_test_mountain(),
+            SurroundingMountainSearchDistance::new(5_000),
+        );
+        let mountains = repository.find(condition).await.unwrap();
+        assert!(mountains.is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::repository::in_memory::InMemoryRepositoryImpl;
+use async_trait::async_trait;
+use mountix_kernel::model::tag::Tag;
+use mountix_kernel::model::Id;
+use mountix_kernel::repository::tag::TagRepository;
+use std::collections::BTreeMap;
+
+#[async_trait]
+impl TagRepository for InMemoryRepositoryImpl<Tag> {
+    async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>> {
+        let tag_doc = self.db.tags().iter().find(|td| td.id == id.value).cloned();
+        Ok(tag_doc.map(|td| td.into()))
+    }
+
+    async fn find(&self) -> anyhow::Result<Vec<Tag>> {
+        Ok(self.db.tags().iter().cloned().map(|td| td.into()).collect())
+    }
+
+    async fn count_mountains(&self) -> anyhow::Result<BTreeMap<String, u64>> {
+        let store = self.db.read();
+
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        for tag in store.iter().flat_map(|md| &md.tags) {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::in_memory::InMemoryDb;
+
+    const TEST_TAGS: &str = r#"{"_id":2,"name":"二百名山","description":"深田クラブが選定した日本二百名山","source":"深田クラブ『日本二百名山』"}
+{"_id":1,"name":"百名山","description":"深田久弥が選定した日本百名山","source":"深田久弥『日本百名山』"}
+"#;
+
+    const TEST_MOUNTAINS: &str = r#"{"_id":1,"name":"富士山","name_kana":"ふじさん","area":"富士山","prefectures":["静岡県","山梨県"],"elevation":3776,"tags":["百名山"],"location":{"type":"Point","coordinates":[138.7275,35.36083333333333]},"gsi_url":"https://maps.gsi.go.jp/#15/35.36083333333333/138.7275"}
+{"_id":2,"name":"毛無山","name_kana":"けなしやま","area":"富士山","prefectures":["静岡県","山梨県"],"elevation":1964,"tags":["二百名山"],"location":{"type":"Point","coordinates":[138.5408,35.4125]},"gsi_url":"https://maps.gsi.go.jp/#15/35.4125/138.5408"}
+{"_id":3,"name":"天子ヶ岳","name_kana":"てんしがたけ","area":"富士山","prefectures":["静岡県"],"elevation":1330,"tags":[],"location":{"type":"Point","coordinates":[138.5311,35.3344]},"gsi_url":"https://maps.gsi.go.jp/#15/35.3344/138.5311"}
+"#;
+
+    fn create_test_repository() -> InMemoryRepositoryImpl<Tag> {
+        let db = InMemoryDb::from_reader(TEST_MOUNTAINS.as_bytes())
+            .unwrap()
+            .with_tags(TEST_TAGS.as_bytes())
+            .unwrap();
+        InMemoryRepositoryImpl::new(db)
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_tag_find_orders_by_id() {
+        let repository = create_test_repository();
+
+        let tags = repository.find().await.unwrap();
+        let ids: Vec<i32> = tags.iter().map(|t| t.id.value).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_tag_get() {
+        let repository = create_test_repository();
+
+        let tag = repository.get(Id::new(2)).await.unwrap().unwrap();
+        assert_eq!(tag.name, "二百名山");
+        assert!(repository.get(Id::new(3)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_tag_count_mountains() {
+        let repository = create_test_repository();
+
+        let counts = repository.count_mountains().await.unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["百名山"], 1);
+        assert_eq!(counts["二百名山"], 1);
+    }
+}
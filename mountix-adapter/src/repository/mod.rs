@@ -1,9 +1,14 @@
 use crate::persistence::mongodb::Db;
+use crate::repository::in_memory::InMemoryRepositoryImpl;
 use std::marker::PhantomData;
 
+pub mod area;
 pub mod health_check;
+pub mod in_memory;
 pub mod mountain;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
 
 pub struct MongoDBRepositoryImpl<T> {
     db: Db,
@@ -18,3 +23,11 @@ impl<T> MongoDBRepositoryImpl<T> {
         }
     }
 }
+
+/// Repository implementation selected at startup
+///
+/// 起動時に選択されたリポジトリの実装
+pub enum RepositoryImpl<T> {
+    MongoDB(MongoDBRepositoryImpl<T>),
+    InMemory(InMemoryRepositoryImpl<T>),
+}
@@ -1,13 +1,26 @@
-use crate::model::mountain::{MountainDocument, MountainFindBoxCommand, MountainFindCommand};
-use crate::repository::MongoDBRepositoryImpl;
+use crate::model::mountain::{
+    version_filter, MountainDocument, MountainFacetDocument, MountainFindBoxCommand,
+    MountainFindCommand, MountainFindPolygonCommand, MountainIdDocument, MountainSuggestCommand,
+    MountainSuggestionDocument,
+};
+use crate::repository::{MongoDBRepositoryImpl, RepositoryImpl};
+use crate::search::MountainRelevanceRanking;
 use async_trait::async_trait;
 use futures::stream::TryStreamExt;
-use mongodb::bson::doc;
+use mongodb::bson::{doc, from_document};
+use mongodb::error::{ErrorKind, WriteFailure};
+use mongodb::options::ReturnDocument;
 use mountix_kernel::model::mountain::{
-    Mountain, MountainBoxSearchCondition, MountainSearchCondition,
+    Mountain, MountainBoxSearchCondition, MountainData, MountainPage,
+    MountainPolygonSearchCondition, MountainSearchCondition, MountainSuggestCondition,
+    MountainSuggestion,
 };
 use mountix_kernel::model::Id;
 use mountix_kernel::repository::mountain::MountainRepository;
+use std::collections::BTreeMap;
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+const MAX_CREATE_ATTEMPTS: usize = 3;
 
 #[async_trait]
 impl MountainRepository for MongoDBRepositoryImpl<Mountain> {
@@ -32,57 +45,344 @@ impl MountainRepository for MongoDBRepositoryImpl<Mountain> {
     async fn find(
         &self,
         search_condition: MountainSearchCondition,
-    ) -> anyhow::Result<Vec<Mountain>> {
+    ) -> anyhow::Result<MountainPage> {
         let collection = self.db.0.collection::<MountainDocument>("mountains");
 
         let find_command: MountainFindCommand = search_condition.try_into()?;
-        let mut mountain_doc_list = collection
-            .find(find_command.filter)
-            .with_options(find_command.options)
-            .await?;
-
-        let mut mountains: Vec<Mountain> = Vec::new();
-        while let Some(md) = mountain_doc_list.try_next().await? {
-            mountains.push(md.try_into()?);
+        if let Some(relevance) = &find_command.relevance {
+            return self.find_by_relevance(&find_command, relevance).await;
         }
 
-        Ok(mountains)
+        let facet_doc = collection
+            .aggregate(find_command.pipeline)
+            .await?
+            .try_next()
+            .await?
+            .ok_or(anyhow::anyhow!("The $facet stage returned no document."))?;
+        let facet_doc: MountainFacetDocument = from_document(facet_doc)?;
+
+        facet_doc.into_page(&find_command.facets, find_command.reversed)
     }
 
     async fn find_box(
         &self,
         search_condition: MountainBoxSearchCondition,
-    ) -> anyhow::Result<Vec<Mountain>> {
+    ) -> anyhow::Result<MountainPage> {
         let collection = self.db.0.collection::<MountainDocument>("mountains");
 
         let find_command: MountainFindBoxCommand = search_condition.try_into()?;
-        let mut mountain_doc_list = collection
-            .find(find_command.filter)
-            .with_options(find_command.options)
+        let facet_doc = collection
+            .aggregate(find_command.pipeline)
+            .await?
+            .try_next()
+            .await?
+            .ok_or(anyhow::anyhow!("The $facet stage returned no document."))?;
+        let facet_doc: MountainFacetDocument = from_document(facet_doc)?;
+
+        facet_doc.into_page(&find_command.facets, false)
+    }
+
+    async fn find_polygon(
+        &self,
+        search_condition: MountainPolygonSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        let collection = self.db.0.collection::<MountainDocument>("mountains");
+
+        let find_command: MountainFindPolygonCommand = search_condition.try_into()?;
+        let facet_doc = collection
+            .aggregate(find_command.pipeline)
+            .await?
+            .try_next()
+            .await?
+            .ok_or(anyhow::anyhow!("The $facet stage returned no document."))?;
+        let facet_doc: MountainFacetDocument = from_document(facet_doc)?;
+
+        facet_doc.into_page(&[], false)
+    }
+
+    async fn suggest(
+        &self,
+        search_condition: MountainSuggestCondition,
+    ) -> anyhow::Result<Vec<MountainSuggestion>> {
+        let collection = self.db.0.collection::<MountainDocument>("mountains");
+
+        let suggest_command = MountainSuggestCommand::from(&search_condition);
+        let mut suggestion_doc_list = collection.aggregate(suggest_command.pipeline).await?;
+
+        let mut suggestions: Vec<MountainSuggestion> = Vec::new();
+        while let Some(sd) = suggestion_doc_list.try_next().await? {
+            let sd: MountainSuggestionDocument = from_document(sd)?;
+            suggestions.push(sd.into());
+        }
+
+        Ok(suggestions)
+    }
+
+    async fn create(&self, data: MountainData) -> anyhow::Result<Mountain> {
+        let collection = self.db.0.collection::<MountainDocument>("mountains");
+
+        // 採番した ID が同時に登録された場合は再採番する
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+
+            let last_doc = collection.find_one(doc! {}).sort(doc! {"_id": -1}).await?;
+            let id = last_doc.map(|md| md.id + 1).unwrap_or(1);
+
+            let mountain_doc = MountainDocument::new(id, data.clone(), 0);
+            match collection.insert_one(&mountain_doc).await {
+                Ok(_) => {
+                    self.db.search_index().upsert(&mountain_doc);
+                    return mountain_doc.try_into();
+                }
+                Err(e) => match *e.kind {
+                    ErrorKind::Write(WriteFailure::WriteError(ref write_error))
+                        if write_error.code == DUPLICATE_KEY_ERROR_CODE
+                            && attempts < MAX_CREATE_ATTEMPTS =>
+                    {
+                        continue
+                    }
+                    _ => return Err(e.into()),
+                },
+            }
+        }
+    }
+
+    async fn update(
+        &self,
+        id: Id<Mountain>,
+        data: MountainData,
+        version: u32,
+    ) -> anyhow::Result<Option<Mountain>> {
+        let collection = self.db.0.collection::<MountainDocument>("mountains");
+
+        let mountain_doc = MountainDocument::new(id.value, data, version + 1);
+        let updated_doc = collection
+            .find_one_and_replace(version_filter(id.value, version), mountain_doc)
+            .return_document(ReturnDocument::After)
+            .await?;
+        match updated_doc {
+            Some(md) => {
+                self.db.search_index().upsert(&md);
+                Ok(Some(md.try_into()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool> {
+        let collection = self.db.0.collection::<MountainDocument>("mountains");
+
+        let result = collection
+            .delete_one(version_filter(id.value, version))
+            .await?;
+        let deleted = result.deleted_count == 1;
+        if deleted {
+            self.db.search_index().remove(id.value);
+        }
+        Ok(deleted)
+    }
+}
+
+impl MongoDBRepositoryImpl<Mountain> {
+    /// Searches the mountains sorted by relevance, taking the candidates and their scores from the search index,
+    /// counting those matching the search condition in the collection and fetching only the ranked page
+    ///
+    /// 関連度順に山岳を検索します。索引から候補とスコアを取得し、検索条件に一致する候補をコレクションで数え、
+    /// 関連度順に切り出したページの山岳のみを取得します
+    async fn find_by_relevance(
+        &self,
+        find_command: &MountainFindCommand,
+        relevance: &MountainRelevanceRanking,
+    ) -> anyhow::Result<MountainPage> {
+        let collection = self.db.0.collection::<MountainDocument>("mountains");
+
+        // 起動時に索引を構築できなかった場合は再度構築し、構築できない場合はエラーとする
+        if !self.db.search_index().is_loaded() {
+            self.db.load_search_index().await?;
+        }
+        let scores = self
+            .db
+            .search_index()
+            .with_index(|index| relevance.scores(index))?;
+        let pipeline = find_command.relevance_pipeline(scores.keys().copied().collect());
+        let facet_doc = collection
+            .aggregate(pipeline)
+            .await?
+            .try_next()
+            .await?
+            .ok_or(anyhow::anyhow!("The $facet stage returned no document."))?;
+        let facet_doc: MountainFacetDocument<MountainIdDocument> = from_document(facet_doc)?;
+
+        let ranked = self.db.search_index().with_index(|index| {
+            relevance.page(index, &scores, facet_doc.mountains.iter().map(|md| md.id))
+        })?;
+        let ids: Vec<i32> = ranked.iter().map(|(id, _)| *id).collect();
+        let mut mountain_docs: BTreeMap<i32, MountainDocument> = collection
+            .find(doc! {"_id": {"$in": &ids}})
+            .await?
+            .map_ok(|md| (md.id, md))
+            .try_collect()
             .await?;
 
+        // 取得中に削除された山岳は除き、関連度の順に並べる
         let mut mountains: Vec<Mountain> = Vec::new();
-        while let Some(md) = mountain_doc_list.try_next().await? {
-            mountains.push(md.try_into()?);
+        let mut relevance_map = BTreeMap::new();
+        for (id, r) in ranked {
+            if let Some(md) = mountain_docs.remove(&id) {
+                mountains.push(md.try_into()?);
+                relevance_map.insert(id, r);
+            }
         }
 
-        Ok(mountains)
+        Ok(MountainPage {
+            mountains,
+            total: facet_doc.total(),
+            facets: facet_doc.facets(&find_command.facets),
+            relevance: relevance_map,
+        })
+    }
+}
+
+#[async_trait]
+impl MountainRepository for RepositoryImpl<Mountain> {
+    async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.get(id).await,
+            RepositoryImpl::InMemory(repository) => repository.get(id).await,
+        }
+    }
+
+    async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.get_count(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.get_count(search_condition).await,
+        }
+    }
+
+    async fn find(
+        &self,
+        search_condition: MountainSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.find(search_condition).await,
+        }
+    }
+
+    async fn find_box(
+        &self,
+        search_condition: MountainBoxSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find_box(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.find_box(search_condition).await,
+        }
+    }
+
+    async fn find_polygon(
+        &self,
+        search_condition: MountainPolygonSearchCondition,
+    ) -> anyhow::Result<MountainPage> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find_polygon(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.find_polygon(search_condition).await,
+        }
+    }
+
+    async fn suggest(
+        &self,
+        search_condition: MountainSuggestCondition,
+    ) -> anyhow::Result<Vec<MountainSuggestion>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.suggest(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.suggest(search_condition).await,
+        }
+    }
+
+    async fn create(&self, data: MountainData) -> anyhow::Result<Mountain> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.create(data).await,
+            RepositoryImpl::InMemory(repository) => repository.create(data).await,
+        }
+    }
+
+    async fn update(
+        &self,
+        id: Id<Mountain>,
+        data: MountainData,
+        version: u32,
+    ) -> anyhow::Result<Option<Mountain>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.update(id, data, version).await,
+            RepositoryImpl::InMemory(repository) => repository.update(id, data, version).await,
+        }
+    }
+
+    async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.delete(id, version).await,
+            RepositoryImpl::InMemory(repository) => repository.delete(id, version).await,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::mountain::MountainLocationDocument;
+    use crate::model::mountain::{
+        cursor_filter, filter_expression, name_filter, page_stages, polygon_geometry,
+        values_filter, MountainAliasDocument, MountainLocationDocument,
+    };
+    use mongodb::bson::{Bson, Document};
+    use mountix_kernel::model::geo::{GeoPoint, GeoPolygon};
     use mountix_kernel::model::mountain::{
-        MountainPrefecture, MountainSearchCondition, MountainSortCondition, MountainTag,
+        MountainAliasType, MountainBoxCoordinates, MountainBoxSearchCondition, MountainCursor,
+        MountainCursorDirection, MountainCursorValue, MountainElevationRange, MountainFacetCount,
+        MountainFacetKey, MountainNameCondition, MountainNameMatchType, MountainPrefecture,
+        MountainSearchCondition, MountainSort, MountainSortCondition, MountainTag,
+        MountainValuesCondition, MountainValuesMode,
     };
+    use mountix_kernel::model::mountain_filter::MountainFilterExpr;
+    use mountix_kernel::model::tag::{Tag, TagRegistry};
+
+    fn create_tag_registry() -> TagRegistry {
+        TagRegistry::new(vec![
+            Tag::new(
+                Id::new(1),
+                "百名山".to_string(),
+                "深田久弥が選定した日本百名山".to_string(),
+                "深田久弥『日本百名山』".to_string(),
+            ),
+            Tag::new(
+                Id::new(2),
+                "二百名山".to_string(),
+                "深田クラブが選定した日本二百名山".to_string(),
+                "深田クラブ『日本二百名山』".to_string(),
+            ),
+        ])
+        .unwrap()
+    }
+
+    /// Returns the stages of the page in the `$facet` stage
+    fn facet_page_stages(pipeline: &[Document]) -> Vec<Document> {
+        pipeline[1]
+            .get_document("$facet")
+            .unwrap()
+            .get_array("mountains")
+            .unwrap()
+            .iter()
+            .map(|stage| stage.as_document().unwrap().clone())
+            .collect()
+    }
 
     fn create_test_mountain_document() -> MountainDocument {
         MountainDocument {
             id: 1,
             name: "富士山".to_string(),
+            name_folded: "富士山".to_string(),
             name_kana: "ふじさん".to_string(),
+            name_romaji: "fujisan".to_string(),
             area: "関東地方".to_string(),
             prefectures: vec!["静岡県".to_string(), "山梨県".to_string()],
             elevation: 3776,
@@ -92,6 +392,14 @@ mod tests {
                 coordinates: [138.727778, 35.360556], // [longitude, latitude] - MongoDB形式
             },
             gsi_url: "https://maps.gsi.go.jp/fuji".to_string(),
+            aliases: vec![MountainAliasDocument {
+                name: "不二山".to_string(),
+                name_folded: "不二山".to_string(),
+                name_kana: "ふじさん".to_string(),
+                name_romaji: "fujisan".to_string(),
+                r#type: "historic".to_string(),
+            }],
+            version: 0,
         }
     }
 
@@ -112,17 +420,73 @@ mod tests {
         assert_eq!(mountain.location.longitude, 138.727778);
         assert_eq!(mountain.location.gsi_url, "https://maps.gsi.go.jp/fuji");
         assert_eq!(mountain.tags, vec!["百名山"]);
+        assert_eq!(mountain.aliases[0].name, "不二山");
+        assert_eq!(mountain.aliases[0].alias_type, MountainAliasType::Historic);
+        assert_eq!(mountain.version, 0);
+    }
+
+    #[test]
+    fn test_mountain_document_with_invalid_alias_type() {
+        let mut mountain_doc = create_test_mountain_document();
+        mountain_doc.aliases[0].r#type = "nickname".to_string();
+        assert!(Mountain::try_from(mountain_doc).is_err());
+    }
+
+    #[test]
+    fn test_mountain_document_new() {
+        let data = MountainData {
+            name: "富士山".to_string(),
+            name_kana: "ふじさん".to_string(),
+            area: "関東地方".to_string(),
+            prefectures: vec!["静岡県".to_string(), "山梨県".to_string()],
+            elevation: 3776,
+            location: mountix_kernel::model::mountain::MountainLocation::from_coordinates(
+                35.360556, 138.727778,
+            ),
+            tags: vec!["百名山".to_string()],
+            aliases: vec![],
+        };
+
+        let mountain_doc = MountainDocument::new(1060, data, 2);
+        assert_eq!(mountain_doc.id, 1060);
+        assert_eq!(mountain_doc.name_romaji, "fujisan");
+        assert_eq!(mountain_doc.location.r#type, "Point");
+        assert_eq!(mountain_doc.location.coordinates, [138.727778, 35.360556]);
+        assert_eq!(
+            mountain_doc.gsi_url,
+            "https://maps.gsi.go.jp/#15/35.360556/138.727778"
+        );
+        assert_eq!(mountain_doc.version, 2);
+    }
+
+    #[test]
+    fn test_version_filter() {
+        let filter = version_filter(1, 0);
+        assert!(filter.get_document("version").unwrap().contains_key("$in"));
+
+        let filter = version_filter(1, 3);
+        assert_eq!(filter, doc! {"_id": 1, "version": 3u32});
     }
 
     #[test]
     fn test_mountain_search_condition_to_find_command() {
         let search_condition = MountainSearchCondition {
-            name: Some("富士".to_string()),
-            prefecture: Some(MountainPrefecture::try_from("19".to_string()).unwrap()),
-            tag: Some(MountainTag::try_from("1".to_string()).unwrap()),
+            name: Some(MountainNameCondition::contains("富士".to_string())),
+            prefecture: MountainValuesCondition::single(
+                MountainPrefecture::try_from("19".to_string()).unwrap(),
+            ),
+            tag: MountainValuesCondition::single(MountainTag {
+                id: 1,
+                name: "百名山".to_string(),
+            }),
+            area: None,
+            elevation: MountainElevationRange::default(),
             skip: 10,
             limit: Some(5),
-            sort: MountainSortCondition::default(),
+            sort: MountainSort::default(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
         };
 
         let result = MountainFindCommand::try_from(search_condition);
@@ -130,8 +494,180 @@ mod tests {
 
         let command = result.unwrap();
         assert!(command.filter.contains_key("$and"));
-        assert_eq!(command.options.skip, Some(10));
-        assert_eq!(command.options.limit, Some(5));
+        assert_eq!(command.pipeline[0], doc! {"$match": command.filter.clone()});
+        let stages = facet_page_stages(&command.pipeline);
+        assert_eq!(stages[1], doc! {"$skip": 10_i64});
+        assert_eq!(stages[2], doc! {"$limit": 5_i64});
+        assert!(command.relevance.is_none());
+    }
+
+    #[test]
+    fn test_mountain_search_condition_to_find_command_sorted_by_relevance() {
+        let search_condition = MountainSearchCondition {
+            name: Some(MountainNameCondition::contains("富士".to_string())),
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 10,
+            limit: Some(5),
+            sort: MountainSort::try_from("relevance".to_string()).unwrap(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
+        };
+
+        let command = MountainFindCommand::try_from(search_condition).unwrap();
+        assert!(command.relevance.is_some());
+        assert!(command.pipeline.is_empty());
+        // 関連度は索引で計算して取得範囲を切り出すため、索引の候補に一致した山岳の _id のみを取得する
+        let pipeline = command.relevance_pipeline(vec![3, 5]);
+        assert_eq!(
+            pipeline[0],
+            doc! {"$match": {"$and": [command.filter.clone(), {"_id": {"$in": [3, 5]}}]}}
+        );
+        assert_eq!(
+            facet_page_stages(&pipeline),
+            vec![doc! {"$project": {"_id": 1}}]
+        );
+    }
+
+    #[test]
+    fn test_values_filter() {
+        let prefectures = |mode| {
+            MountainValuesCondition::new(vec!["長野県".to_string(), "富山県".to_string()], mode)
+        };
+        assert_eq!(
+            values_filter("prefectures", &prefectures(MountainValuesMode::Any)),
+            Some(doc! {"prefectures": {"$in": ["長野県", "富山県"]}})
+        );
+        assert_eq!(
+            values_filter("prefectures", &prefectures(MountainValuesMode::All)),
+            Some(doc! {"prefectures": {"$all": ["長野県", "富山県"]}})
+        );
+
+        // 値が 1 つの場合は結合方法によらず等価比較にする
+        let tags =
+            MountainValuesCondition::new(vec!["百名山".to_string()], MountainValuesMode::All);
+        assert_eq!(values_filter("tags", &tags), Some(doc! {"tags": "百名山"}));
+        assert_eq!(values_filter("tags", &Default::default()), None);
+    }
+
+    #[test]
+    fn test_name_filter() {
+        let filter = name_filter(&MountainNameCondition::contains("ヤリガタケ".to_string()));
+        assert_eq!(
+            filter,
+            doc! {"$or": [
+                {"name": {"$regex": "ヤリガタケ", "$options": "i"}},
+                {"name_kana": {"$regex": "やりがたけ", "$options": "i"}},
+                {"name_folded": {"$regex": "ヤリヶタヶ", "$options": "i"}},
+                {"aliases.name": {"$regex": "ヤリガタケ", "$options": "i"}},
+                {"aliases.name_kana": {"$regex": "やりがたけ", "$options": "i"}},
+                {"aliases.name_folded": {"$regex": "ヤリヶタヶ", "$options": "i"}},
+            ]}
+        );
+
+        let filter = name_filter(&MountainNameCondition::contains("Yarigatake".to_string()));
+        let or_doc = filter.get_array("$or").unwrap();
+        assert_eq!(
+            or_doc[3],
+            Bson::Document(doc! {"name_romaji": {"$regex": "yarigatake", "$options": "i"}})
+        );
+        assert_eq!(
+            or_doc[7],
+            Bson::Document(doc! {"aliases.name_romaji": {"$regex": "yarigatake", "$options": "i"}})
+        );
+    }
+
+    #[test]
+    fn test_filter_expression() {
+        let expr = MountainFilterExpr::parse(
+            r#"elevation>=3000 AND (tag:百名山 OR NOT pref:19) AND area:"飛騨山脈*""#,
+            &create_tag_registry(),
+        )
+        .unwrap();
+        assert_eq!(
+            filter_expression(&expr),
+            doc! {"$and": [
+                {"elevation": {"$gte": 3000}},
+                {"$or": [
+                    {"tags": "百名山"},
+                    {"$nor": [{"prefectures": "山梨県"}]},
+                ]},
+                {"area": {"$regex": "^飛騨山脈"}},
+            ]}
+        );
+
+        let expr = MountainFilterExpr::parse("name:槍*", &create_tag_registry()).unwrap();
+        assert_eq!(
+            filter_expression(&expr),
+            name_filter(
+                &MountainNameCondition::new("槍".to_string(), MountainNameMatchType::Prefix)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_mountain_suggest_condition_to_suggest_command() {
+        let condition = MountainSuggestCondition::new("Yari".to_string(), 10);
+        let command = MountainSuggestCommand::from(&condition);
+        assert_eq!(
+            *command.pipeline[0].get_document("$match").unwrap(),
+            doc! {"$or": [
+                {"name_folded": {"$regex": "^yari"}},
+                {"name_kana": {"$regex": "^やり"}},
+                {"name_romaji": {"$regex": "^yari"}},
+                {"aliases.name_folded": {"$regex": "^yari"}},
+                {"aliases.name_kana": {"$regex": "^やり"}},
+                {"aliases.name_romaji": {"$regex": "^yari"}},
+            ]}
+        );
+
+        let condition = MountainSuggestCondition::new("槍ケ(".to_string(), 10);
+        let command = MountainSuggestCommand::from(&condition);
+        let or_doc = command.pipeline[0]
+            .get_document("$match")
+            .unwrap()
+            .get_array("$or")
+            .unwrap();
+        assert_eq!(or_doc.len(), 4);
+        assert_eq!(
+            or_doc[0],
+            Bson::Document(doc! {"name_folded": {"$regex": "^槍ヶ\\("}})
+        );
+
+        // 候補は DB で並べ替えて上限の件数まで取得し、表示する項目のみを返す
+        let branches = command.pipeline[1]
+            .get_document("$addFields")
+            .unwrap()
+            .get_document("suggest_match")
+            .unwrap()
+            .get_document("$switch")
+            .unwrap()
+            .get_array("branches")
+            .unwrap();
+        assert_eq!(
+            branches[0]
+                .as_document()
+                .unwrap()
+                .get_document("case")
+                .unwrap(),
+            &doc! {"$or": [
+                {"$regexMatch": {"input": "$$ROOT.name_folded", "regex": "^槍ヶ\\((?:$|[<（(])"}},
+                {"$regexMatch": {"input": "$$ROOT.name_kana", "regex": "^槍け\\((?:$|[<（(])"}},
+            ]}
+        );
+        assert_eq!(
+            command.pipeline[2],
+            doc! {"$sort": {"suggest_match": 1, "suggest_preferred": -1, "elevation": -1, "_id": 1}}
+        );
+        assert_eq!(command.pipeline[3], doc! {"$limit": 10_i64});
+        assert_eq!(
+            command.pipeline[4],
+            doc! {"$project": {"name": 1, "name_kana": 1, "prefectures": 1}}
+        );
     }
 
     #[test]
@@ -142,25 +678,161 @@ mod tests {
         .unwrap();
         let search_condition = mountix_kernel::model::mountain::MountainBoxSearchCondition {
             box_coordinates: box_coords,
-            name: Some("富士".to_string()),
-            tag: Some(MountainTag::try_from("1".to_string()).unwrap()),
-            sort: MountainSortCondition::default(),
+            name: Some(MountainNameCondition::contains("富士".to_string())),
+            tag: MountainValuesCondition::new(
+                vec![
+                    MountainTag {
+                        id: 1,
+                        name: "百名山".to_string(),
+                    },
+                    MountainTag {
+                        id: 2,
+                        name: "二百名山".to_string(),
+                    },
+                ],
+                MountainValuesMode::Any,
+            ),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 20,
+            limit: Some(10),
+            sort: MountainSort::default(),
+            facets: vec![],
         };
 
         let result = MountainFindBoxCommand::try_from(search_condition);
         assert!(result.is_ok());
 
         let command = result.unwrap();
-        assert!(command.filter.contains_key("$and"));
-        assert!(
-            command
-                .filter
-                .get("$and")
-                .unwrap()
-                .as_array()
-                .unwrap()
-                .len()
-                >= 2
+        let filter = command.pipeline[0].get_document("$match").unwrap();
+        let and_doc = filter.get_array("$and").unwrap();
+        assert_eq!(and_doc.len(), 3);
+        assert_eq!(
+            and_doc[2],
+            Bson::Document(doc! {"tags": {"$in": ["百名山", "二百名山"]}})
+        );
+        let stages = facet_page_stages(&command.pipeline);
+        assert_eq!(stages[1], doc! {"$skip": 20_i64});
+        assert_eq!(stages[2], doc! {"$limit": 10_i64});
+    }
+
+    #[test]
+    fn test_multi_key_sort_page_stages() {
+        let sort = MountainSort::try_from("prefecture.asc,distance.asc,elevation.desc".to_string())
+            .unwrap()
+            .with_point(GeoPoint::new(35.3606, 138.7274).unwrap());
+
+        let stages = page_stages(&sort, None, 0, Some(10)).unwrap();
+        let fields = stages[0].get_document("$addFields").unwrap();
+        assert_eq!(
+            fields.keys().collect::<Vec<_>>(),
+            vec!["prefecture_code", "distance"]
+        );
+        assert_eq!(
+            stages[1],
+            doc! {"$sort": {"prefecture_code": 1_i64, "distance": 1_i64, "elevation": -1_i64, "_id": 1_i64}}
+        );
+
+        // 検索と範囲検索で同じステージを使用する
+        let search_condition = MountainBoxSearchCondition {
+            box_coordinates: MountainBoxCoordinates::try_from(
+                "(138.0,35.0),(139.0,36.0)".to_string(),
+            )
+            .unwrap(),
+            name: None,
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: Some(10),
+            sort: sort.clone(),
+            facets: vec![],
+        };
+        let command = MountainFindBoxCommand::try_from(search_condition).unwrap();
+        assert_eq!(facet_page_stages(&command.pipeline), stages);
+
+        // 距離の基準となる地点がない場合はエラーにする
+        let sort = MountainSort::try_from("distance.asc".to_string()).unwrap();
+        assert!(page_stages(&sort, None, 0, None).is_err());
+    }
+
+    #[test]
+    fn test_mountain_polygon_search_condition_to_find_command() {
+        let square = vec![
+            [138.0, 35.0],
+            [139.0, 35.0],
+            [139.0, 36.0],
+            [138.0, 36.0],
+            [138.0, 35.0],
+        ];
+        let search_condition = MountainPolygonSearchCondition {
+            polygon: GeoPolygon::try_from(vec![vec![square.clone()]]).unwrap(),
+            name: None,
+            tag: MountainValuesCondition::single(MountainTag {
+                id: 1,
+                name: "百名山".to_string(),
+            }),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 20,
+            limit: Some(10),
+            sort: MountainSort::try_from("prefecture.asc,elevation.desc".to_string()).unwrap(),
+        };
+
+        let command = MountainFindPolygonCommand::try_from(search_condition).unwrap();
+        let stages = facet_page_stages(&command.pipeline);
+        assert_eq!(
+            stages[1],
+            doc! {"$sort": {"prefecture_code": 1_i64, "elevation": -1_i64, "_id": 1_i64}}
+        );
+        assert_eq!(stages[2], doc! {"$skip": 20_i64});
+        assert_eq!(stages[3], doc! {"$limit": 10_i64});
+
+        let filter = command.pipeline[0].get_document("$match").unwrap();
+        let and_doc = filter.get_array("$and").unwrap();
+        assert_eq!(and_doc.len(), 2);
+        let geometry = and_doc[0]
+            .as_document()
+            .unwrap()
+            .get_document("location")
+            .unwrap()
+            .get_document("$geoWithin")
+            .unwrap()
+            .get_document("$geometry")
+            .unwrap();
+        assert_eq!(geometry.get_str("type").unwrap(), "Polygon");
+        assert_eq!(
+            geometry.get_array("coordinates").unwrap()[0],
+            mongodb::bson::to_bson(&square).unwrap()
+        );
+
+        let polygon = GeoPolygon::try_from(vec![vec![square.clone()], vec![square]]).unwrap();
+        assert_eq!(
+            polygon_geometry(&polygon).get_str("type").unwrap(),
+            "MultiPolygon"
+        );
+    }
+
+    #[test]
+    fn test_mountain_search_condition_elevation_range() {
+        let search_condition = MountainSearchCondition {
+            name: None,
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::new(Some(1500), Some(2500)).unwrap(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
+        };
+
+        let command = MountainFindCommand::try_from(search_condition).unwrap();
+        assert_eq!(
+            command.filter,
+            doc! {"$and": [{"elevation": {"$gte": 1500u32, "$lte": 2500u32}}]}
         );
     }
 
@@ -168,11 +840,16 @@ mod tests {
     fn test_mountain_search_condition_empty_filters() {
         let search_condition = MountainSearchCondition {
             name: None,
-            prefecture: None,
-            tag: None,
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
             skip: 0,
             limit: None,
-            sort: MountainSortCondition::default(),
+            sort: MountainSort::default(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
         };
 
         let result = MountainFindCommand::try_from(search_condition);
@@ -180,8 +857,9 @@ mod tests {
 
         let command = result.unwrap();
         assert!(!command.filter.contains_key("$and"));
-        assert_eq!(command.options.skip, Some(0));
-        assert_eq!(command.options.limit, None);
+        let stages = facet_page_stages(&command.pipeline);
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[1], doc! {"$skip": 0_i64});
     }
 
     #[test]
@@ -189,18 +867,206 @@ mod tests {
         let sort_condition = MountainSortCondition::try_from("elevation.desc".to_string()).unwrap();
         let search_condition = MountainSearchCondition {
             name: None,
-            prefecture: None,
-            tag: None,
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
             skip: 0,
             limit: None,
-            sort: sort_condition,
+            sort: sort_condition.into(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
         };
 
         let result = MountainFindCommand::try_from(search_condition);
         assert!(result.is_ok());
 
         let command = result.unwrap();
-        let sort_doc = command.options.sort.unwrap();
+        let stages = facet_page_stages(&command.pipeline);
+        let sort_doc = stages[0].get_document("$sort").unwrap();
         assert_eq!(sort_doc.get("elevation").unwrap().as_i64().unwrap(), -1);
     }
+
+    #[test]
+    fn test_mountain_search_condition_with_cursor_to_find_command() {
+        let sort_condition = MountainSortCondition::try_from("elevation.desc".to_string()).unwrap();
+        let search_condition = MountainSearchCondition {
+            name: None,
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: Some(10),
+            sort: sort_condition.into(),
+            cursor: Some(MountainCursor {
+                direction: MountainCursorDirection::Next,
+                values: vec![MountainCursorValue::Elevation(3776)],
+                id: 396,
+            }),
+            facets: vec![],
+            filter: None,
+        };
+
+        let command = MountainFindCommand::try_from(search_condition.clone()).unwrap();
+        assert!(!command.reversed);
+        // カーソルは件数の数え方に影響しない
+        assert!(!command.filter.contains_key("$and"));
+        let stages = facet_page_stages(&command.pipeline);
+        assert_eq!(
+            stages[0],
+            doc! {"$match": {"$or": [{"elevation": {"$lt": 3776u32}}, {"elevation": 3776u32, "_id": {"$lt": 396}}]}}
+        );
+        assert_eq!(
+            stages[1],
+            doc! {"$sort": {"elevation": -1_i64, "_id": -1_i64}}
+        );
+
+        let mut search_condition = search_condition;
+        search_condition.cursor = Some(MountainCursor {
+            direction: MountainCursorDirection::Prev,
+            values: vec![MountainCursorValue::Elevation(3776)],
+            id: 396,
+        });
+        let command = MountainFindCommand::try_from(search_condition).unwrap();
+        assert!(command.reversed);
+        let stages = facet_page_stages(&command.pipeline);
+        assert_eq!(
+            stages[0],
+            doc! {"$match": {"$or": [{"elevation": {"$gt": 3776u32}}, {"elevation": 3776u32, "_id": {"$gt": 396}}]}}
+        );
+        assert_eq!(
+            stages[1],
+            doc! {"$sort": {"elevation": 1_i64, "_id": 1_i64}}
+        );
+    }
+
+    #[test]
+    fn test_multi_key_cursor_filter() {
+        let sort = MountainSort::try_from("prefecture.asc,elevation.desc".to_string()).unwrap();
+        let cursor = MountainCursor {
+            direction: MountainCursorDirection::Next,
+            values: vec![
+                MountainCursorValue::Prefecture(19),
+                MountainCursorValue::Elevation(3000),
+            ],
+            id: 100,
+        };
+        assert_eq!(
+            cursor_filter(&cursor, &sort),
+            doc! {"$or": [
+                {"prefecture_code": {"$gt": 19_i64}},
+                {"prefecture_code": 19_i64, "elevation": {"$lt": 3000u32}},
+                {"prefecture_code": 19_i64, "elevation": 3000u32, "_id": {"$gt": 100}},
+            ]}
+        );
+
+        // 計算したキーで絞り込めるよう、カーソルは項目を追加した後、並び替える前に適用する
+        let stages = page_stages(&sort, Some(&cursor), 0, Some(10)).unwrap();
+        assert!(stages[0].contains_key("$addFields"));
+        assert_eq!(stages[1], doc! {"$match": cursor_filter(&cursor, &sort)});
+        assert!(stages[2].contains_key("$sort"));
+
+        // _id をキーに含む場合は最後のキーとして追加せず、距離の誤差に備えてカーソルの山岳を除外する
+        let sort = MountainSort::try_from("distance.asc,id.desc".to_string())
+            .unwrap()
+            .with_point(GeoPoint::new(35.3606, 138.7274).unwrap());
+        let cursor = MountainCursor {
+            direction: MountainCursorDirection::Next,
+            values: vec![MountainCursorValue::Distance(1.5), MountainCursorValue::Id],
+            id: 100,
+        };
+        assert_eq!(
+            cursor_filter(&cursor, &sort),
+            doc! {"$and": [
+                {"$or": [
+                    {"distance": {"$gt": 1.5}},
+                    {"distance": 1.5, "_id": {"$lt": 100}},
+                ]},
+                {"_id": {"$ne": 100}},
+            ]}
+        );
+    }
+
+    #[test]
+    fn test_mountain_facet_document_total() {
+        let facet_doc: MountainFacetDocument = from_document(doc! {
+            "mountains": [],
+            "total": [],
+        })
+        .unwrap();
+        assert_eq!(facet_doc.total(), 0);
+
+        let facet_doc: MountainFacetDocument = from_document(doc! {
+            "mountains": [mongodb::bson::to_document(&create_test_mountain_document()).unwrap()],
+            "total": [{"count": 12}],
+        })
+        .unwrap();
+        assert_eq!(facet_doc.mountains.len(), 1);
+        assert_eq!(facet_doc.total(), 12);
+    }
+
+    #[test]
+    fn test_mountain_search_condition_with_facets_to_find_command() {
+        let search_condition = MountainSearchCondition {
+            name: None,
+            prefecture: Default::default(),
+            tag: MountainValuesCondition::single(MountainTag {
+                id: 1,
+                name: "百名山".to_string(),
+            }),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: Some(10),
+            sort: MountainSort::default(),
+            cursor: None,
+            facets: vec![
+                MountainFacetKey::ElevationBand,
+                MountainFacetKey::Prefecture,
+            ],
+            filter: None,
+        };
+
+        let command = MountainFindCommand::try_from(search_condition).unwrap();
+        let facet_doc = command.pipeline[1].get_document("$facet").unwrap();
+        let keys: Vec<&String> = facet_doc.keys().collect();
+        assert_eq!(keys, ["mountains", "total", "elevation_band", "prefecture"]);
+        assert_eq!(
+            facet_doc.get_array("prefecture").unwrap()[0],
+            Bson::Document(doc! {"$unwind": "$prefectures"})
+        );
+    }
+
+    #[test]
+    fn test_mountain_facet_document_into_page() {
+        let facet_doc: MountainFacetDocument = from_document(doc! {
+            "mountains": [mongodb::bson::to_document(&create_test_mountain_document()).unwrap()],
+            "total": [{"count": 3}],
+            "prefecture": [{"_id": "山梨県", "count": 2}, {"_id": "静岡県", "count": 1}],
+            "elevation_band": [{"_id": 1500_i64, "count": 1}, {"_id": 3500_i64, "count": 2}],
+        })
+        .unwrap();
+
+        let page = facet_doc
+            .into_page(
+                &[
+                    MountainFacetKey::Prefecture,
+                    MountainFacetKey::ElevationBand,
+                ],
+                false,
+            )
+            .unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.facets[0].key, MountainFacetKey::Prefecture);
+        assert_eq!(
+            page.facets[0].counts[0],
+            MountainFacetCount {
+                value: "山梨県".to_string(),
+                count: 2
+            }
+        );
+        assert_eq!(page.facets[1].counts[1].value, "3500-3999");
+    }
 }
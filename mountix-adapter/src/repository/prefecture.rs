@@ -0,0 +1,35 @@
+use crate::model::prefecture::{prefecture_stats_pipeline, PrefectureStatsDocument};
+use crate::repository::{MongoDBRepositoryImpl, RepositoryImpl};
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::bson::from_document;
+use mountix_kernel::model::mountain::MountainPrefecture;
+use mountix_kernel::model::prefecture::PrefectureMountainStats;
+use mountix_kernel::repository::prefecture::PrefectureRepository;
+
+#[async_trait]
+impl PrefectureRepository for MongoDBRepositoryImpl<MountainPrefecture> {
+    async fn find_stats(&self) -> anyhow::Result<Vec<PrefectureMountainStats>> {
+        let collection = self.db.0.collection::<mongodb::bson::Document>("mountains");
+
+        let mut stats_doc_list = collection.aggregate(prefecture_stats_pipeline()).await?;
+
+        let mut stats: Vec<PrefectureMountainStats> = Vec::new();
+        while let Some(doc) = stats_doc_list.try_next().await? {
+            let stats_doc: PrefectureStatsDocument = from_document(doc)?;
+            stats.push(stats_doc.into());
+        }
+
+        Ok(stats)
+    }
+}
+
+#[async_trait]
+impl PrefectureRepository for RepositoryImpl<MountainPrefecture> {
+    async fn find_stats(&self) -> anyhow::Result<Vec<PrefectureMountainStats>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find_stats().await,
+            RepositoryImpl::InMemory(repository) => repository.find_stats().await,
+        }
+    }
+}
@@ -1,16 +1,33 @@
 use crate::model::surrounding_mountain::{
-    SurroundingMountainDocument, SurroundingMountainFindCommand,
+    select_corridor_documents, CorridorMountainFindCommand, NearbyMountainFindCommand,
+    NearestMountainFindCommand, SurroundingMountainDocument, SurroundingMountainFindCommand,
 };
-use crate::repository::MongoDBRepositoryImpl;
+use crate::repository::{MongoDBRepositoryImpl, RepositoryImpl};
 use async_trait::async_trait;
 use futures::TryStreamExt;
+use mongodb::bson::from_document;
 use mountix_kernel::model::surrounding_mountain::{
+    CorridorMountainSearchCondition, NearbyMountainSearchCondition, NearestMountainSearchCondition,
     SurroundingMountain, SurroundingMountainSearchCondition,
 };
 use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
 
 #[async_trait]
 impl SurroundingMountainRepository for MongoDBRepositoryImpl<SurroundingMountain> {
+    async fn get_count(
+        &self,
+        search_condition: SurroundingMountainSearchCondition,
+    ) -> anyhow::Result<u64> {
+        let collection = self
+            .db
+            .0
+            .collection::<SurroundingMountainDocument>("mountains");
+
+        let find_command: SurroundingMountainFindCommand = search_condition.try_into()?;
+        let count = collection.count_documents(find_command.filter).await?;
+        Ok(count)
+    }
+
     async fn find(
         &self,
         search_condition: SurroundingMountainSearchCondition,
@@ -21,6 +38,27 @@ impl SurroundingMountainRepository for MongoDBRepositoryImpl<SurroundingMountain
             .collection::<SurroundingMountainDocument>("mountains");
 
         let find_command: SurroundingMountainFindCommand = search_condition.try_into()?;
+        let mut mountain_doc_list = collection.aggregate(find_command.pipeline).await?;
+
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        while let Some(doc) = mountain_doc_list.try_next().await? {
+            let sd: SurroundingMountainDocument = from_document(doc)?;
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+
+    async fn find_nearby(
+        &self,
+        search_condition: NearbyMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let collection = self
+            .db
+            .0
+            .collection::<SurroundingMountainDocument>("mountains");
+
+        let find_command: NearbyMountainFindCommand = search_condition.try_into()?;
         let mut mountain_doc_list = collection.find(find_command.filter).await?;
 
         let mut mountains: Vec<SurroundingMountain> = Vec::new();
@@ -30,17 +68,129 @@ impl SurroundingMountainRepository for MongoDBRepositoryImpl<SurroundingMountain
 
         Ok(mountains)
     }
+
+    async fn find_nearest(
+        &self,
+        search_condition: NearestMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let collection = self
+            .db
+            .0
+            .collection::<SurroundingMountainDocument>("mountains");
+
+        let find_command: NearestMountainFindCommand = search_condition.try_into()?;
+        let mut mountain_doc_list = collection
+            .find(find_command.filter)
+            .with_options(find_command.options)
+            .await?;
+
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        while let Some(sd) = mountain_doc_list.try_next().await? {
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+
+    async fn find_corridor(
+        &self,
+        search_condition: CorridorMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        let collection = self
+            .db
+            .0
+            .collection::<SurroundingMountainDocument>("mountains");
+
+        let find_command = CorridorMountainFindCommand::try_from(&search_condition)?;
+        let mountain_docs: Vec<SurroundingMountainDocument> = collection
+            .find(find_command.filter)
+            .await?
+            .try_collect()
+            .await?;
+
+        let mut mountains: Vec<SurroundingMountain> = Vec::new();
+        for sd in select_corridor_documents(
+            mountain_docs,
+            |sd| (sd.location.coordinates, sd.id),
+            &search_condition,
+        ) {
+            mountains.push(sd.try_into()?);
+        }
+
+        Ok(mountains)
+    }
+}
+
+#[async_trait]
+impl SurroundingMountainRepository for RepositoryImpl<SurroundingMountain> {
+    async fn get_count(
+        &self,
+        search_condition: SurroundingMountainSearchCondition,
+    ) -> anyhow::Result<u64> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.get_count(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.get_count(search_condition).await,
+        }
+    }
+
+    async fn find(
+        &self,
+        search_condition: SurroundingMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.find(search_condition).await,
+        }
+    }
+
+    async fn find_nearby(
+        &self,
+        search_condition: NearbyMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find_nearby(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.find_nearby(search_condition).await,
+        }
+    }
+
+    async fn find_nearest(
+        &self,
+        search_condition: NearestMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find_nearest(search_condition).await,
+            RepositoryImpl::InMemory(repository) => repository.find_nearest(search_condition).await,
+        }
+    }
+
+    async fn find_corridor(
+        &self,
+        search_condition: CorridorMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find_corridor(search_condition).await,
+            RepositoryImpl::InMemory(repository) => {
+                repository.find_corridor(search_condition).await
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::model::surrounding_mountain::{
-        SurroundingMountainDocument, SurroundingMountainLocationDocument,
+        corridor_boxes, SurroundingMountainDocument, SurroundingMountainLocationDocument,
+    };
+    use mongodb::bson::doc;
+    use mountix_kernel::model::geo::{GeoLineString, GeoPoint};
+    use mountix_kernel::model::mountain::{
+        Mountain, MountainData, MountainElevationRange, MountainLocation, MountainNameCondition,
+        MountainPrefecture, MountainTag,
     };
-    use mountix_kernel::model::mountain::{Mountain, MountainData, MountainLocation};
     use mountix_kernel::model::surrounding_mountain::{
         SurroundingMountainSearchCondition, SurroundingMountainSearchDistance,
+        SurroundingMountainSortCondition,
     };
     use mountix_kernel::model::Id;
 
@@ -73,6 +223,7 @@ mod tests {
             elevation: 3776,
             location,
             tags: vec!["百名山".to_string()],
+            aliases: vec![],
         };
         Mountain::new(id, data)
     }
@@ -141,6 +292,152 @@ mod tests {
         assert!(command.filter.contains_key("$and"));
     }
 
+    #[test]
+    fn test_surrounding_mountain_search_condition_with_page_to_find_command() {
+        let search_condition = SurroundingMountainSearchCondition::new(
+            create_test_mountain(),
+            SurroundingMountainSearchDistance::new(10000),
+        )
+        .with_filter(
+            Some(MountainPrefecture::try_from("22".to_string()).unwrap()),
+            Some(MountainTag {
+                id: 2,
+                name: "二百名山".to_string(),
+            }),
+        )
+        .with_page(
+            20,
+            Some(10),
+            SurroundingMountainSortCondition::try_from("distance.desc".to_string()).unwrap(),
+        );
+
+        let command = SurroundingMountainFindCommand::try_from(search_condition).unwrap();
+        let and_doc = command.filter.get_array("$and").unwrap();
+        assert_eq!(and_doc.len(), 4);
+        assert_eq!(
+            command.pipeline,
+            vec![
+                doc! {"$geoNear": {
+                    "near": {"type": "Point", "coordinates": [138.727778, 35.360556]},
+                    "distanceField": "distance",
+                    "maxDistance": 10000,
+                    "spherical": true,
+                    "query": {"$and": [
+                        {"_id": {"$ne": 1}},
+                        {"prefectures": "静岡県"},
+                        {"tags": "二百名山"},
+                    ]},
+                }},
+                doc! {"$sort": {"distance": -1_i64, "_id": -1_i64}},
+                doc! {"$skip": 20_i64},
+                doc! {"$limit": 10_i64},
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nearby_mountain_search_condition_to_find_command() {
+        let search_condition = NearbyMountainSearchCondition {
+            point: GeoPoint::new(35.36, 138.72).unwrap(),
+            distance: SurroundingMountainSearchDistance::new(10000),
+            name: Some(MountainNameCondition::contains("岳".to_string())),
+            tag: Some(MountainTag {
+                id: 2,
+                name: "二百名山".to_string(),
+            }),
+        };
+
+        let command = NearbyMountainFindCommand::try_from(search_condition).unwrap();
+        let and_doc = command.filter.get_array("$and").unwrap();
+        assert_eq!(and_doc.len(), 3);
+
+        let near_doc = and_doc[0]
+            .as_document()
+            .unwrap()
+            .get_document("location")
+            .unwrap()
+            .get_document("$nearSphere")
+            .unwrap();
+        assert_eq!(near_doc.get_i32("$maxDistance").unwrap(), 10000);
+        assert_eq!(and_doc[2], doc! {"tags": "二百名山"}.into());
+    }
+
+    #[test]
+    fn test_nearest_mountain_search_condition_to_find_command() {
+        let search_condition = NearestMountainSearchCondition {
+            point: GeoPoint::new(35.36, 138.72).unwrap(),
+            k: 5,
+            tag: None,
+            elevation: MountainElevationRange::new(Some(1500), None).unwrap(),
+            exclude_id: Some(Id::new(1)),
+        };
+
+        let command = NearestMountainFindCommand::try_from(search_condition).unwrap();
+        let and_doc = command.filter.get_array("$and").unwrap();
+        assert_eq!(and_doc.len(), 3);
+
+        // 距離の上限は指定しない
+        let near_doc = and_doc[0]
+            .as_document()
+            .unwrap()
+            .get_document("location")
+            .unwrap()
+            .get_document("$nearSphere")
+            .unwrap();
+        assert!(!near_doc.contains_key("$maxDistance"));
+        assert_eq!(and_doc[1], doc! {"_id": {"$ne": 1}}.into());
+        assert_eq!(command.options.limit, Some(5));
+    }
+
+    #[test]
+    fn test_corridor_mountain_search_condition_to_find_command() {
+        let search_condition = CorridorMountainSearchCondition {
+            line: GeoLineString::try_from(vec![[138.7, 35.3], [138.8, 35.3], [138.8, 35.4]])
+                .unwrap(),
+            buffer: SurroundingMountainSearchDistance::new(5000),
+            name: None,
+            tag: Some(MountainTag {
+                id: 1,
+                name: "百名山".to_string(),
+            }),
+            elevation: MountainElevationRange::default(),
+        };
+
+        let command = CorridorMountainFindCommand::try_from(&search_condition).unwrap();
+        let and_doc = command.filter.get_array("$and").unwrap();
+        assert_eq!(and_doc.len(), 2);
+        // 線分ごとのボックス
+        let boxes = and_doc[0].as_document().unwrap().get_array("$or").unwrap();
+        assert_eq!(boxes.len(), 2);
+
+        let corridor = corridor_boxes(&search_condition.line, 5000.0);
+        let (bottom_left, upper_right) = corridor[0];
+        assert!((35.3 - bottom_left[1] - 0.0449).abs() < 0.001);
+        assert!(bottom_left[0] < 138.7 - 0.0449 && upper_right[0] > 138.8 + 0.0449);
+    }
+
+    #[test]
+    fn test_select_corridor_documents() {
+        let search_condition = CorridorMountainSearchCondition {
+            line: GeoLineString::try_from(vec![[138.7, 35.3], [138.9, 35.3]]).unwrap(),
+            buffer: SurroundingMountainSearchDistance::new(2000),
+            name: None,
+            tag: None,
+            elevation: MountainElevationRange::default(),
+        };
+        let documents = vec![
+            ([138.85, 35.31], 1),
+            ([138.75, 35.29], 2),
+            ([138.80, 35.35], 3),
+            ([138.75, 35.29], 0),
+        ];
+
+        let selected = select_corridor_documents(documents, |d| *d, &search_condition);
+        let ids: Vec<i32> = selected.iter().map(|(_, id)| *id).collect();
+        // ラインから約 5.5 km 離れた 3 を除き、ライン上の位置の順に並べる
+        assert_eq!(ids, vec![0, 2, 1]);
+    }
+
     #[test]
     fn test_surrounding_mountain_location_document_structure() {
         let location_doc = SurroundingMountainLocationDocument {
@@ -0,0 +1,71 @@
+use crate::model::tag::{tag_count_pipeline, TagCountDocument, TagDocument};
+use crate::repository::{MongoDBRepositoryImpl, RepositoryImpl};
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::bson::{doc, from_document};
+use mountix_kernel::model::tag::Tag;
+use mountix_kernel::model::Id;
+use mountix_kernel::repository::tag::TagRepository;
+use std::collections::BTreeMap;
+
+#[async_trait]
+impl TagRepository for MongoDBRepositoryImpl<Tag> {
+    async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>> {
+        let collection = self.db.0.collection::<TagDocument>("tags");
+
+        let filter = doc! {"_id": id.value};
+        let tag_doc = collection.find_one(filter).await?;
+        Ok(tag_doc.map(|td| td.into()))
+    }
+
+    async fn find(&self) -> anyhow::Result<Vec<Tag>> {
+        let collection = self.db.0.collection::<TagDocument>("tags");
+
+        let mut tag_doc_list = collection.find(doc! {}).sort(doc! {"_id": 1}).await?;
+
+        let mut tags: Vec<Tag> = Vec::new();
+        while let Some(td) = tag_doc_list.try_next().await? {
+            tags.push(td.into());
+        }
+
+        Ok(tags)
+    }
+
+    async fn count_mountains(&self) -> anyhow::Result<BTreeMap<String, u64>> {
+        let collection = self.db.0.collection::<mongodb::bson::Document>("mountains");
+
+        let mut count_doc_list = collection.aggregate(tag_count_pipeline()).await?;
+
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        while let Some(doc) = count_doc_list.try_next().await? {
+            let count_doc: TagCountDocument = from_document(doc)?;
+            counts.insert(count_doc.name, count_doc.mountain_count);
+        }
+
+        Ok(counts)
+    }
+}
+
+#[async_trait]
+impl TagRepository for RepositoryImpl<Tag> {
+    async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.get(id).await,
+            RepositoryImpl::InMemory(repository) => repository.get(id).await,
+        }
+    }
+
+    async fn find(&self) -> anyhow::Result<Vec<Tag>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.find().await,
+            RepositoryImpl::InMemory(repository) => repository.find().await,
+        }
+    }
+
+    async fn count_mountains(&self) -> anyhow::Result<BTreeMap<String, u64>> {
+        match self {
+            RepositoryImpl::MongoDB(repository) => repository.count_mountains().await,
+            RepositoryImpl::InMemory(repository) => repository.count_mountains().await,
+        }
+    }
+}
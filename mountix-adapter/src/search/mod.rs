@@ -0,0 +1,572 @@
+use crate::model::mountain::MountainDocument;
+use crate::search::tokenizer::{
+    index_tokens, normalize_kana_text, normalize_text, query_tokens, NormalizedChar,
+};
+use mountix_kernel::model::kana::romaji_to_hiragana;
+use mountix_kernel::model::mountain::{
+    MountainHighlight, MountainHighlightSpan, MountainRelevance, MountainSearchCondition,
+    MountainSearchField, MountainSortKey,
+};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+mod tokenizer;
+
+/// Parameters of the BM25 ranking function
+///
+/// 関連度の計算に用いる BM25 のパラメータ
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
+/// Weight of the score of each field, which ranks a match in the name above one in the prefectures
+///
+/// 項目ごとのスコアの重み。山名での一致を都道府県での一致より上位に並べます
+fn field_weight(field: MountainSearchField) -> f64 {
+    match field {
+        MountainSearchField::Name => 3.0,
+        MountainSearchField::NameKana => 2.0,
+        MountainSearchField::Area => 1.0,
+        MountainSearchField::Prefecture => 0.5,
+    }
+}
+
+/// Field value of an indexed mountain, normalized for the tokens and the highlights
+///
+/// 索引に登録した山岳の項目の値で、語の生成とハイライトのために正規化したもの
+struct IndexedValue {
+    field: MountainSearchField,
+    value: String,
+    chars: Vec<NormalizedChar>,
+}
+
+impl IndexedValue {
+    fn new(field: MountainSearchField, value: &str) -> Self {
+        let chars = match field {
+            MountainSearchField::NameKana => normalize_kana_text(value),
+            _ => normalize_text(value),
+        };
+        Self {
+            field,
+            value: value.to_string(),
+            chars,
+        }
+    }
+
+    /// Returns the ranges of the original value which contain any of the tokens
+    ///
+    /// 元の値のうち、いずれかの語を含む範囲を返します
+    fn spans(&self, tokens: &[Vec<char>]) -> Vec<MountainHighlightSpan> {
+        let values: Vec<char> = self.chars.iter().map(|c| c.value).collect();
+        let mut spans: Vec<MountainHighlightSpan> = Vec::new();
+        for token in tokens {
+            for (start, window) in values.windows(token.len()).enumerate() {
+                if window == token.as_slice() {
+                    spans.push(MountainHighlightSpan {
+                        start: self.chars[start].position,
+                        end: self.chars[start + token.len() - 1].position + 1,
+                    });
+                }
+            }
+        }
+
+        // 重なる範囲や隣接する範囲は 1 つにまとめる
+        spans.sort_by_key(|span| (span.start, span.end));
+        let mut merged: Vec<MountainHighlightSpan> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+        merged
+    }
+}
+
+/// Inverted index over the name, the kana name, the area and the prefectures of mountains,
+/// tokenized into single characters and n-grams so that Japanese text without spaces can be searched
+///
+/// 山名、山名かな、山域、都道府県の転置索引。空白で区切られない日本語を検索できるよう、
+/// 1 文字ずつと n-gram に分割して登録します
+#[derive(Default)]
+pub struct MountainSearchIndex {
+    values: BTreeMap<i32, Vec<IndexedValue>>,
+    /// Term frequency per mountain for each field and token
+    postings: HashMap<(MountainSearchField, String), BTreeMap<i32, u32>>,
+    /// Number of tokens per mountain and field
+    lengths: HashMap<(i32, MountainSearchField), u32>,
+    /// Number of tokens of all the mountains per field
+    total_lengths: HashMap<MountainSearchField, u64>,
+}
+
+impl MountainSearchIndex {
+    /// Builds the index over the documents
+    ///
+    /// ドキュメントの索引を構築します
+    pub fn new<'a>(documents: impl IntoIterator<Item = &'a MountainDocument>) -> Self {
+        let mut index = Self::default();
+        for document in documents {
+            index.upsert(document);
+        }
+        index
+    }
+
+    /// Adds or replaces the entries of the document
+    ///
+    /// ドキュメントを索引に登録します。登録済みの場合は置き換えます
+    pub fn upsert(&mut self, document: &MountainDocument) {
+        self.remove(document.id);
+
+        let id = document.id;
+        let mut values = vec![
+            IndexedValue::new(MountainSearchField::Name, &document.name),
+            IndexedValue::new(MountainSearchField::NameKana, &document.name_kana),
+            IndexedValue::new(MountainSearchField::Area, &document.area),
+        ];
+        values.extend(
+            document
+                .prefectures
+                .iter()
+                .map(|prefecture| IndexedValue::new(MountainSearchField::Prefecture, prefecture)),
+        );
+
+        for value in &values {
+            let tokens = index_tokens(&value.chars);
+            *self.lengths.entry((id, value.field)).or_default() += tokens.len() as u32;
+            *self.total_lengths.entry(value.field).or_default() += tokens.len() as u64;
+            for token in tokens {
+                *self
+                    .postings
+                    .entry((value.field, token))
+                    .or_default()
+                    .entry(id)
+                    .or_default() += 1;
+            }
+        }
+        self.values.insert(id, values);
+    }
+
+    /// Removes the entries of the mountain
+    ///
+    /// 山岳を索引から削除します
+    pub fn remove(&mut self, id: i32) {
+        let Some(values) = self.values.remove(&id) else {
+            return;
+        };
+        for value in &values {
+            if let Some(length) = self.lengths.remove(&(id, value.field)) {
+                if let Some(total) = self.total_lengths.get_mut(&value.field) {
+                    *total -= length as u64;
+                }
+            }
+            for token in index_tokens(&value.chars) {
+                let key = (value.field, token);
+                if let Some(postings) = self.postings.get_mut(&key) {
+                    postings.remove(&id);
+                    if postings.is_empty() {
+                        self.postings.remove(&key);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the average number of tokens of the field per mountain
+    ///
+    /// 山岳 1 件あたりの項目の語の数の平均を返します
+    fn average_length(&self, field: MountainSearchField) -> f64 {
+        let total = self.total_lengths.get(&field).copied().unwrap_or(0);
+        (total as f64 / self.values.len().max(1) as f64).max(1.0)
+    }
+
+    /// Scores all the indexed mountains with BM25 per field and returns the scores of those matching the query
+    ///
+    /// 索引に登録したすべての山岳を項目ごとの BM25 で採点し、入力値に一致した山岳のスコアを返します
+    pub fn scores(&self, query: &str) -> BTreeMap<i32, f64> {
+        let tokens = QueryTokens::new(query);
+
+        let count = self.values.len() as f64;
+        let mut scores: BTreeMap<i32, f64> = BTreeMap::new();
+        for field in [
+            MountainSearchField::Name,
+            MountainSearchField::NameKana,
+            MountainSearchField::Area,
+            MountainSearchField::Prefecture,
+        ] {
+            let average_length = self.average_length(field);
+            for token in tokens.of(field) {
+                let Some(postings) = self.postings.get(&(field, token.clone())) else {
+                    continue;
+                };
+                let frequency = postings.len() as f64;
+                let idf = (1.0 + (count - frequency + 0.5) / (frequency + 0.5)).ln();
+                for (id, term_frequency) in postings {
+                    let term_frequency = *term_frequency as f64;
+                    let length = self.lengths.get(&(*id, field)).copied().unwrap_or(0) as f64;
+                    let normalization = BM25_K1 * (1.0 - BM25_B + BM25_B * length / average_length);
+                    let score =
+                        idf * term_frequency * (BM25_K1 + 1.0) / (term_frequency + normalization);
+                    *scores.entry(*id).or_default() += field_weight(field) * score;
+                }
+            }
+        }
+        scores
+    }
+
+    /// Returns the field values of the mountain containing the query with the ranges of the matches
+    ///
+    /// 山岳の項目のうち入力値を含む値と、一致した文字の範囲を返します
+    pub fn highlights(&self, query: &str, id: i32) -> Vec<MountainHighlight> {
+        let tokens = QueryTokens::new(query);
+        let to_chars = |tokens: &Vec<String>| -> Vec<Vec<char>> {
+            tokens.iter().map(|token| token.chars().collect()).collect()
+        };
+        let text_chars = to_chars(&tokens.text);
+        let kana_chars = to_chars(&tokens.kana);
+
+        self.values
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter_map(|value| {
+                let tokens = match value.field {
+                    MountainSearchField::NameKana => &kana_chars,
+                    _ => &text_chars,
+                };
+                let spans = value.spans(tokens);
+                (!spans.is_empty()).then(|| MountainHighlight {
+                    field: value.field,
+                    value: value.value.clone(),
+                    spans,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Tokens of the query for the kana name and for the other fields
+///
+/// 山名かなとその他の項目それぞれで検索する入力値の語
+struct QueryTokens {
+    text: Vec<String>,
+    kana: Vec<String>,
+}
+
+impl QueryTokens {
+    fn new(query: &str) -> Self {
+        // ローマ字の入力値は山名かなと照合できるようひらがなに変換する
+        let kana_query = romaji_to_hiragana(query).unwrap_or_else(|| query.to_string());
+        Self {
+            text: query_tokens(&normalize_text(query)),
+            kana: query_tokens(&normalize_kana_text(&kana_query)),
+        }
+    }
+
+    fn of(&self, field: MountainSearchField) -> &Vec<String> {
+        match field {
+            MountainSearchField::NameKana => &self.kana,
+            _ => &self.text,
+        }
+    }
+}
+
+/// Index shared by the requests, which is built over the whole collection at startup
+/// and kept in sync with the mountains created, updated and deleted by this process
+///
+/// リクエスト間で共有する索引。起動時にコレクション全体から構築し、
+/// このプロセスでの山岳の登録・更新・削除にあわせて更新します。構築できなかった場合は未構築のままとします
+#[derive(Clone, Default)]
+pub struct SharedMountainSearchIndex(Arc<RwLock<Option<MountainSearchIndex>>>);
+
+impl SharedMountainSearchIndex {
+    pub(crate) fn is_loaded(&self) -> bool {
+        self.read().is_some()
+    }
+
+    /// Replaces the index with the one built over the whole collection
+    ///
+    /// コレクション全体から構築した索引に置き換えます
+    pub(crate) fn replace(&self, index: MountainSearchIndex) {
+        *self.write() = Some(index);
+    }
+
+    /// Calls the function with the index, or returns an error if the index is not built
+    ///
+    /// 索引を引数に関数を呼び出します。索引が未構築の場合はエラーを返します
+    pub(crate) fn with_index<T>(
+        &self,
+        f: impl FnOnce(&MountainSearchIndex) -> T,
+    ) -> anyhow::Result<T> {
+        self.read()
+            .as_ref()
+            .map(f)
+            .ok_or(anyhow::anyhow!("The search index is not loaded."))
+    }
+
+    /// Adds or replaces the entries of the document, which is skipped until the index is built
+    ///
+    /// ドキュメントを索引に登録します。未構築の場合は構築時にコレクションから読み込むため何もしません
+    pub(crate) fn upsert(&self, document: &MountainDocument) {
+        if let Some(index) = self.write().as_mut() {
+            index.upsert(document);
+        }
+    }
+
+    pub(crate) fn remove(&self, id: i32) {
+        if let Some(index) = self.write().as_mut() {
+            index.remove(id);
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Option<MountainSearchIndex>> {
+        self.0.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Option<MountainSearchIndex>> {
+        self.0.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Ranking of the mountains by relevance to the name condition, which scores the candidates
+/// with the index and pages them in the ranked order
+///
+/// 山名の条件との関連度による山岳の並び替え。索引で候補を採点し、関連度順に並べて取得範囲を適用します
+#[derive(Debug, Clone)]
+pub struct MountainRelevanceRanking {
+    query: String,
+    skip: u64,
+    limit: Option<i64>,
+}
+
+impl MountainRelevanceRanking {
+    /// Returns the ranking if the search condition is sorted by relevance with a name condition
+    ///
+    /// 検索条件が山名の条件を持ち関連度順の場合に並び替えを返します
+    pub fn new(search_condition: &MountainSearchCondition) -> Option<Self> {
+        if !search_condition.sort.contains(MountainSortKey::Relevance) {
+            return None;
+        }
+        search_condition.name.as_ref().map(|name| Self {
+            query: name.value.clone(),
+            skip: search_condition.skip,
+            limit: search_condition.limit,
+        })
+    }
+
+    /// Returns the scores of the mountains matching the query, which are the candidates of the ranking
+    ///
+    /// 並び替えの候補となる、入力値に一致した山岳のスコアを返します
+    pub fn scores(&self, index: &MountainSearchIndex) -> BTreeMap<i32, f64> {
+        index.scores(&self.query)
+    }
+
+    /// Orders the candidates by the score with `_id` as the tiebreaker, applies `skip` and `limit`
+    /// and returns the ids of the page with the relevance of each of them
+    ///
+    /// 候補を関連度の高い順 (同じ場合は `_id` の順) に並べて `skip` と `limit` を適用し、
+    /// ページの山岳の ID をそれぞれの関連度とともに返します
+    pub fn page(
+        &self,
+        index: &MountainSearchIndex,
+        scores: &BTreeMap<i32, f64>,
+        candidates: impl IntoIterator<Item = i32>,
+    ) -> Vec<(i32, MountainRelevance)> {
+        let mut ranked: Vec<(i32, f64)> = candidates
+            .into_iter()
+            .filter_map(|id| scores.get(&id).map(|score| (id, *score)))
+            .collect();
+        ranked.sort_by(|(a_id, a_score), (b_id, b_score)| {
+            b_score
+                .partial_cmp(a_score)
+                .unwrap_or(Ordering::Equal)
+                .then(a_id.cmp(b_id))
+        });
+
+        // MongoDB と同様に limit が 0 の場合は上限なしとして扱う
+        let limit = match self.limit {
+            Some(limit) if limit != 0 => limit.unsigned_abs() as usize,
+            _ => usize::MAX,
+        };
+        ranked
+            .into_iter()
+            .skip(self.skip as usize)
+            .take(limit)
+            .map(|(id, score)| {
+                let relevance = MountainRelevance {
+                    score: (score * 1000.0).round() / 1000.0,
+                    highlights: index.highlights(&self.query, id),
+                };
+                (id, relevance)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mountix_kernel::model::mountain::{
+        MountainData, MountainLocation, MountainNameCondition, MountainSort,
+    };
+
+    fn create_document(id: i32, name: &str, name_kana: &str, area: &str) -> MountainDocument {
+        let data = MountainData {
+            name: name.to_string(),
+            name_kana: name_kana.to_string(),
+            area: area.to_string(),
+            prefectures: vec!["静岡県".to_string()],
+            elevation: 1000,
+            location: MountainLocation {
+                latitude: 35.0,
+                longitude: 138.0,
+                gsi_url: String::new(),
+            },
+            tags: vec![],
+            aliases: vec![],
+        };
+        MountainDocument::new(id, data, 1)
+    }
+
+    #[test]
+    fn test_mountain_search_index_search() {
+        let documents = vec![
+            create_document(1, "富士山", "ふじさん", "富士山"),
+            create_document(2, "富士見山", "ふじみやま", "身延山地"),
+            create_document(3, "愛鷹山", "あしたかやま", "富士山周辺"),
+            create_document(4, "天城山", "あまぎさん", "伊豆"),
+        ];
+        let index = MountainSearchIndex::new(&documents);
+
+        let scores = index.scores("富士");
+        assert!(!scores.contains_key(&4));
+        // 山名での一致は山域のみでの一致より関連度が高い
+        assert!(scores[&1] > scores[&3]);
+        assert!(scores[&2] > scores[&3]);
+
+        let highlights = index.highlights("富士", 1);
+        assert_eq!(highlights[0].field, MountainSearchField::Name);
+        assert_eq!(highlights[0].value, "富士山");
+        assert_eq!(
+            highlights[0].spans,
+            vec![MountainHighlightSpan { start: 0, end: 2 }]
+        );
+        assert_eq!(highlights[1].field, MountainSearchField::Area);
+        let highlights = index.highlights("富士", 3);
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].field, MountainSearchField::Area);
+    }
+
+    #[test]
+    fn test_mountain_search_index_search_kana_and_romaji() {
+        let documents = vec![
+            create_document(1, "槍ヶ岳", "やりがたけ", "飛騨山脈南部"),
+            create_document(2, "北岳", "きただけ", "赤石山脈"),
+        ];
+        let index = MountainSearchIndex::new(&documents);
+
+        // 表記ゆれをまとめた上でハイライトは元の文字の位置を返す
+        let scores = index.scores("槍ケ岳");
+        assert_eq!(scores.keys().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            index.highlights("槍ケ岳", 1)[0].spans,
+            vec![MountainHighlightSpan { start: 0, end: 3 }]
+        );
+
+        let highlights = index.highlights("yariga", 1);
+        assert_eq!(highlights[0].field, MountainSearchField::NameKana);
+        assert_eq!(
+            highlights[0].spans,
+            vec![MountainHighlightSpan { start: 0, end: 3 }]
+        );
+    }
+
+    #[test]
+    fn test_mountain_search_index_upsert_and_remove() {
+        let documents = vec![
+            create_document(1, "富士山", "ふじさん", "富士山"),
+            create_document(2, "愛鷹山", "あしたかやま", "富士山周辺"),
+            create_document(3, "天城山", "あまぎさん", "伊豆"),
+        ];
+        let mut index = MountainSearchIndex::new(&documents);
+
+        // 更新後の索引は更新後のドキュメントから構築した索引と同じスコアを返す
+        let updated = create_document(3, "富士見山", "ふじみやま", "身延山地");
+        index.upsert(&updated);
+        let rebuilt = MountainSearchIndex::new([&documents[0], &documents[1], &updated]);
+        assert_eq!(index.scores("富士"), rebuilt.scores("富士"));
+        assert!(index.scores("天城").is_empty());
+
+        index.remove(1);
+        let rebuilt = MountainSearchIndex::new([&documents[1], &updated]);
+        assert_eq!(index.scores("富士"), rebuilt.scores("富士"));
+        assert!(index.highlights("富士", 1).is_empty());
+    }
+
+    #[test]
+    fn test_shared_mountain_search_index() {
+        let shared = SharedMountainSearchIndex::default();
+        assert!(!shared.is_loaded());
+        // 未構築の間は検索できず、登録も構築時にコレクションから読み込むため反映しない
+        assert!(shared.with_index(|index| index.scores("富士")).is_err());
+        shared.upsert(&create_document(1, "富士山", "ふじさん", "富士山"));
+        assert!(!shared.is_loaded());
+
+        shared.replace(MountainSearchIndex::new(&[create_document(
+            2,
+            "富士見山",
+            "ふじみやま",
+            "身延山地",
+        )]));
+        shared.upsert(&create_document(1, "富士山", "ふじさん", "富士山"));
+        let scores = shared.with_index(|index| index.scores("富士")).unwrap();
+        assert_eq!(scores.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        shared.remove(2);
+        let scores = shared.with_index(|index| index.scores("富士")).unwrap();
+        assert_eq!(scores.keys().copied().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_mountain_relevance_ranking_page() {
+        let documents = vec![
+            create_document(1, "愛鷹山", "あしたかやま", "富士山周辺"),
+            create_document(2, "赤石岳", "あかいしだけ", "赤石山脈"),
+            create_document(3, "富士山", "ふじさん", "富士山"),
+            create_document(4, "富士見山", "ふじみやま", "身延山地"),
+        ];
+        let index = MountainSearchIndex::new(&documents);
+        let mut condition = MountainSearchCondition {
+            name: Some(MountainNameCondition::contains("富士山".to_string())),
+            prefecture: Default::default(),
+            tag: Default::default(),
+            area: None,
+            elevation: Default::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::try_from("relevance".to_string()).unwrap(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
+        };
+
+        // 索引に一致しない候補は除外し、スコアは全件から計算する
+        let ranking = MountainRelevanceRanking::new(&condition).unwrap();
+        let scores = ranking.scores(&index);
+        let ranked = ranking.page(&index, &scores, [1, 2, 3]);
+        assert_eq!(
+            ranked.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+        assert_eq!(ranked[0].1.highlights[0].value, "富士山");
+
+        condition.skip = 1;
+        condition.limit = Some(1);
+        let ranking = MountainRelevanceRanking::new(&condition).unwrap();
+        let ranked = ranking.page(&index, &scores, [1, 2, 3, 4]);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0, 4);
+
+        condition.sort = MountainSort::default();
+        assert!(MountainRelevanceRanking::new(&condition).is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use mountix_kernel::model::kana::{fold_name_variants, normalize_kana};
+
+/// Length of the n-grams indexed together with the single characters
+///
+/// 1 文字の語とあわせて索引に登録する n-gram の長さ
+pub(crate) const NGRAM_SIZE: usize = 2;
+
+/// Character of a normalized value with the position of the character in the original value
+///
+/// 正規化した値の 1 文字と、元の値における文字の位置
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct NormalizedChar {
+    pub(crate) value: char,
+    pub(crate) position: usize,
+}
+
+/// Normalizes the value character by character, keeping the positions of the original characters
+///
+/// 元の文字の位置を保持したまま、値を 1 文字ずつ正規化します
+fn normalize_chars(value: &str, normalize: fn(&str) -> String) -> Vec<NormalizedChar> {
+    value
+        .chars()
+        .enumerate()
+        .flat_map(|(position, c)| {
+            normalize(c.encode_utf8(&mut [0; 4]))
+                .chars()
+                .map(|value| NormalizedChar { value, position })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Normalizes the name, the area or the prefecture like `fold_name_variants`
+///
+/// 山名、山域、都道府県を `fold_name_variants` と同様に正規化します
+pub(crate) fn normalize_text(value: &str) -> Vec<NormalizedChar> {
+    normalize_chars(value, fold_name_variants)
+}
+
+/// Normalizes the kana name like `normalize_kana`
+///
+/// 山名かなを `normalize_kana` と同様に正規化します
+pub(crate) fn normalize_kana_text(value: &str) -> Vec<NormalizedChar> {
+    normalize_chars(value, normalize_kana)
+}
+
+/// Splits the normalized value into runs of characters separated by whitespace
+///
+/// 正規化した値を空白で区切った文字の並びに分割します
+fn segments(chars: &[NormalizedChar]) -> impl Iterator<Item = Vec<char>> + '_ {
+    chars
+        .split(|c| c.value.is_whitespace())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.iter().map(|c| c.value).collect())
+}
+
+fn ngrams(segment: &[char], size: usize) -> impl Iterator<Item = String> + '_ {
+    segment.windows(size).map(|window| window.iter().collect())
+}
+
+/// Returns the tokens to index, which are the single characters and the n-grams of the value
+///
+/// 索引に登録する語として、値の 1 文字ずつと n-gram を返します
+pub(crate) fn index_tokens(chars: &[NormalizedChar]) -> Vec<String> {
+    segments(chars)
+        .flat_map(|segment| {
+            ngrams(&segment, 1)
+                .chain(ngrams(&segment, NGRAM_SIZE))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Returns the tokens to search with, which are the n-grams of the query,
+/// or its single characters if the query is shorter than an n-gram
+///
+/// 検索する語として入力値の n-gram を返します。n-gram より短い場合は 1 文字ずつ返します
+pub(crate) fn query_tokens(chars: &[NormalizedChar]) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    for segment in segments(chars) {
+        let size = NGRAM_SIZE.min(segment.len());
+        for token in ngrams(&segment, size) {
+            if !tokens.contains(&token) {
+                tokens.push(token);
+            }
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_text_keeps_positions() {
+        let chars = normalize_text("槍ケ岳");
+        let values: String = chars.iter().map(|c| c.value).collect();
+        assert_eq!(values, "槍ヶ岳");
+        assert_eq!(
+            chars.iter().map(|c| c.position).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        let chars = normalize_kana_text("ヤリガタケ");
+        let values: String = chars.iter().map(|c| c.value).collect();
+        assert_eq!(values, "やりがたけ");
+    }
+
+    #[test]
+    fn test_index_tokens() {
+        let tokens = index_tokens(&normalize_text("富士山"));
+        assert_eq!(tokens, vec!["富", "士", "山", "富士", "士山"]);
+
+        // 空白をまたぐ n-gram は生成しない
+        let tokens = index_tokens(&normalize_text("北 岳"));
+        assert_eq!(tokens, vec!["北", "岳"]);
+    }
+
+    #[test]
+    fn test_query_tokens() {
+        assert_eq!(
+            query_tokens(&normalize_text("富士富士")),
+            vec!["富士", "士富"]
+        );
+        assert_eq!(query_tokens(&normalize_text("岳")), vec!["岳"]);
+        assert_eq!(query_tokens(&normalize_text("槍 岳")), vec!["槍", "岳"]);
+        assert!(query_tokens(&normalize_text(" ")).is_empty());
+    }
+}
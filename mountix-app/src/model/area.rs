@@ -0,0 +1,51 @@
+use mountix_kernel::model::area::Area;
+
+#[derive(Debug)]
+pub struct SearchedArea {
+    pub name: String,
+    pub mountain_count: u64,
+    pub elevation_min: u32,
+    pub elevation_max: u32,
+    pub prefectures: Vec<String>,
+    pub bounding_box: SearchedAreaBoundingBox,
+}
+
+impl From<Area> for SearchedArea {
+    fn from(area: Area) -> Self {
+        Self {
+            name: area.name,
+            mountain_count: area.mountain_count,
+            elevation_min: area.elevation_min,
+            elevation_max: area.elevation_max,
+            prefectures: area.prefectures,
+            bounding_box: SearchedAreaBoundingBox {
+                bottom_left: SearchedAreaCoordinates {
+                    longitude: area.bounding_box.bottom_left.0,
+                    latitude: area.bounding_box.bottom_left.1,
+                },
+                upper_right: SearchedAreaCoordinates {
+                    longitude: area.bounding_box.upper_right.0,
+                    latitude: area.bounding_box.upper_right.1,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedAreaBoundingBox {
+    pub bottom_left: SearchedAreaCoordinates,
+    pub upper_right: SearchedAreaCoordinates,
+}
+
+#[derive(Debug)]
+pub struct SearchedAreaCoordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Debug)]
+pub struct SearchedAreaResult {
+    pub areas: Vec<SearchedArea>,
+    pub total: u64,
+}
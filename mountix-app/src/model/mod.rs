@@ -1,6 +1,334 @@
+use mountix_kernel::model::geo::GeoPoint;
+use mountix_kernel::model::mountain::{
+    MountainElevationRange, MountainFacetKey, MountainNameCondition, MountainNameMatchType,
+    MountainSort, MountainSortKey, MountainTag, MountainValuesCondition, MountainValuesMode,
+};
+use mountix_kernel::model::tag::TagRegistry;
+
+pub mod area;
 pub mod mountain;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
 
 pub fn invalid_param_error(query_name: &str) -> String {
     format!("クエリパラメータ {} の値が不正です。", query_name)
 }
+
+pub fn required_field_error(field_name: &str) -> String {
+    format!("{} は必須です。", field_name)
+}
+
+/// Parses `elevation_min` and `elevation_max` query parameters into an elevation range
+///
+/// クエリパラメータ elevation_min、elevation_max から標高の範囲を生成します
+pub fn parse_elevation_range(
+    elevation_min: Option<String>,
+    elevation_max: Option<String>,
+) -> Result<MountainElevationRange, Vec<String>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut min: Option<u32> = None;
+    if let Some(min_param) = elevation_min {
+        match min_param.parse::<u32>() {
+            Ok(min_value) => min = Some(min_value),
+            Err(_) => errors.push(invalid_param_error("elevation_min")),
+        }
+    }
+
+    let mut max: Option<u32> = None;
+    if let Some(max_param) = elevation_max {
+        match max_param.parse::<u32>() {
+            Ok(max_value) => max = Some(max_value),
+            Err(_) => errors.push(invalid_param_error("elevation_max")),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    MountainElevationRange::new(min, max).map_err(|_| {
+        vec![
+            "クエリパラメータ elevation_min は elevation_max 以下の値を指定してください。"
+                .to_string(),
+        ]
+    })
+}
+
+/// Parses `offset` and `limit` query parameters into the number of skipped documents and the limit
+///
+/// クエリパラメータ offset、limit からスキップする件数と取得件数の上限を生成します
+pub fn parse_page(
+    offset: Option<String>,
+    limit: Option<String>,
+) -> Result<(u64, Option<i64>), Vec<String>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut skip = 0u64;
+    if let Some(offset_param) = offset {
+        match offset_param.parse::<u64>() {
+            Ok(skip_value) => skip = skip_value,
+            Err(_) => errors.push(invalid_param_error("offset")),
+        }
+    }
+
+    let mut parsed_limit: Option<i64> = None;
+    if let Some(limit_param) = limit {
+        match limit_param.parse::<i64>() {
+            Ok(limit_value) if limit_value >= 0 => parsed_limit = Some(limit_value),
+            _ => errors.push(invalid_param_error("limit")),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok((skip, parsed_limit))
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses the comma-separated `facets` query parameter, ignoring duplicated facets
+///
+/// カンマ区切りのクエリパラメータ facets からファセットの一覧を生成します。重複したファセットは無視します
+pub fn parse_facets(facets: Option<String>) -> Result<Vec<MountainFacetKey>, Vec<String>> {
+    let mut facet_keys: Vec<MountainFacetKey> = Vec::new();
+    let Some(facets_param) = facets else {
+        return Ok(facet_keys);
+    };
+
+    for facet_param in facets_param.split(',') {
+        match MountainFacetKey::try_from(facet_param.to_string()) {
+            Ok(key) if facet_keys.contains(&key) => {}
+            Ok(key) => facet_keys.push(key),
+            Err(_) => return Err(vec![invalid_param_error("facets")]),
+        }
+    }
+    Ok(facet_keys)
+}
+
+/// Parses a comma-separated query parameter and its `_mode` parameter into a values condition
+///
+/// カンマ区切りのクエリパラメータと対応する `_mode` パラメータから複数の値の検索条件を生成します
+pub fn parse_values<T, E>(
+    values: Option<String>,
+    mode: Option<String>,
+    values_name: &str,
+    mode_name: &str,
+    parse_value: impl Fn(String) -> Result<T, E>,
+) -> Result<MountainValuesCondition<T>, Vec<String>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut parsed_values: Vec<T> = Vec::new();
+    if let Some(values_param) = values {
+        for value_param in values_param.split(',') {
+            match parse_value(value_param.trim().to_string()) {
+                Ok(value) => parsed_values.push(value),
+                Err(_) => {
+                    errors.push(invalid_param_error(values_name));
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut parsed_mode: MountainValuesMode = Default::default();
+    if let Some(mode_param) = mode {
+        match MountainValuesMode::try_from(mode_param) {
+            Ok(m) => parsed_mode = m,
+            Err(_) => errors.push(invalid_param_error(mode_name)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(MountainValuesCondition::new(parsed_values, parsed_mode))
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses the `tag` query parameter into a tag resolved against the tag registry
+///
+/// クエリパラメータ tag をタグレジストリから解決します
+pub fn parse_tag(
+    tag: Option<String>,
+    tags: &TagRegistry,
+) -> Result<Option<MountainTag>, Vec<String>> {
+    let Some(tag_param) = tag else {
+        return Ok(None);
+    };
+    MountainTag::try_from((tag_param, tags))
+        .map(Some)
+        .map_err(|_| vec![invalid_param_error("tag (タグID)")])
+}
+
+/// Parses the required `lat` and `lng` query parameters
+///
+/// 必須のクエリパラメータ lat と lng を解析します
+pub fn parse_point(
+    latitude: Option<String>,
+    longitude: Option<String>,
+) -> Result<GeoPoint, Vec<String>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut point = GeoPoint::default();
+    match latitude {
+        Some(latitude_param) => match latitude_param.parse::<f64>() {
+            Ok(lat) if (-90.0..=90.0).contains(&lat) => point.latitude = lat,
+            _ => errors.push(invalid_param_error("lat")),
+        },
+        None => errors.push(required_field_error("クエリパラメータ lat")),
+    }
+
+    match longitude {
+        Some(longitude_param) => match longitude_param.parse::<f64>() {
+            Ok(lng) if (-180.0..=180.0).contains(&lng) => point.longitude = lng,
+            _ => errors.push(invalid_param_error("lng")),
+        },
+        None => errors.push(required_field_error("クエリパラメータ lng")),
+    }
+
+    if errors.is_empty() {
+        Ok(point)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses the comma-separated `sort` query parameter, which needs `lat` and `lng` to sort by the distance
+///
+/// カンマ区切りのクエリパラメータ sort を解析します。距離で並び替える場合は lat と lng が必要です
+pub fn parse_sort(
+    sort: Option<String>,
+    latitude: Option<String>,
+    longitude: Option<String>,
+) -> Result<MountainSort, Vec<String>> {
+    let Some(sort_param) = sort else {
+        return Ok(Default::default());
+    };
+    let sort = MountainSort::try_from(sort_param).map_err(|_| vec![invalid_param_error("sort")])?;
+    if !sort.contains(MountainSortKey::Distance) {
+        return Ok(sort);
+    }
+    parse_point(latitude, longitude).map(|point| sort.with_point(point))
+}
+
+/// Parses `name` and `match` query parameters into a name condition
+///
+/// クエリパラメータ name、match から山名の検索条件を生成します
+pub fn parse_name(
+    name: Option<String>,
+    match_type: Option<String>,
+) -> Result<Option<MountainNameCondition>, Vec<String>> {
+    let match_type = match match_type {
+        Some(match_param) => MountainNameMatchType::try_from(match_param)
+            .map_err(|_| vec![invalid_param_error("match")])?,
+        None => Default::default(),
+    };
+
+    let Some(name_param) = name else {
+        return Ok(None);
+    };
+    MountainNameCondition::new(name_param, match_type)
+        .map(Some)
+        .map_err(|_| vec!["クエリパラメータ name の正規表現が不正か、複雑すぎます。".to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_elevation_range() {
+        let range = parse_elevation_range(Some("1500".to_string()), Some("2500".to_string()));
+        assert_eq!(
+            range.unwrap(),
+            MountainElevationRange {
+                min: Some(1500),
+                max: Some(2500)
+            }
+        );
+
+        let range = parse_elevation_range(None, None).unwrap();
+        assert!(range.is_unbounded());
+
+        let errors =
+            parse_elevation_range(Some("-1".to_string()), Some("high".to_string())).unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        let errors =
+            parse_elevation_range(Some("3000".to_string()), Some("1000".to_string())).unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_page() {
+        let page = parse_page(Some("20".to_string()), Some("10".to_string())).unwrap();
+        assert_eq!(page, (20, Some(10)));
+
+        let page = parse_page(None, None).unwrap();
+        assert_eq!(page, (0, None));
+
+        let errors = parse_page(Some("-1".to_string()), Some("-1".to_string())).unwrap_err();
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_name() {
+        let name = parse_name(Some("富士".to_string()), None).unwrap().unwrap();
+        assert_eq!(name.match_type, MountainNameMatchType::Contains);
+
+        let name = parse_name(Some("富士".to_string()), Some("prefix".to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name.match_type, MountainNameMatchType::Prefix);
+
+        assert!(parse_name(None, Some("exact".to_string()))
+            .unwrap()
+            .is_none());
+        assert!(parse_name(Some("富士".to_string()), Some("fuzzy".to_string())).is_err());
+        assert!(parse_name(Some("(a+)+".to_string()), Some("regex".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        let sort = parse_sort(
+            Some("prefecture.asc,elevation.desc".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(sort.conditions.len(), 2);
+        assert!(sort.point.is_none());
+
+        let sort = parse_sort(
+            Some("distance.asc".to_string()),
+            Some("35.3606".to_string()),
+            Some("138.7274".to_string()),
+        )
+        .unwrap();
+        assert_eq!(sort.point.unwrap().latitude, 35.3606);
+
+        let errors = parse_sort(Some("distance.asc".to_string()), None, None).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "クエリパラメータ lat は必須です。".to_string(),
+                "クエリパラメータ lng は必須です。".to_string()
+            ]
+        );
+        assert!(parse_sort(Some("elevation.asc,elevation.desc".to_string()), None, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_facets() {
+        let facets = parse_facets(Some("tag,elevation_band,tag".to_string())).unwrap();
+        assert_eq!(
+            facets,
+            vec![MountainFacetKey::Tag, MountainFacetKey::ElevationBand]
+        );
+
+        assert!(parse_facets(None).unwrap().is_empty());
+        assert!(parse_facets(Some("tag,name".to_string())).is_err());
+    }
+}
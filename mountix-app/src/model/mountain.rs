@@ -1,8 +1,26 @@
-use crate::model::invalid_param_error;
+use crate::model::{
+    invalid_param_error, parse_elevation_range, parse_facets, parse_name, parse_page, parse_sort,
+    parse_values, required_field_error,
+};
+use mountix_kernel::model::geo::GeoPolygon;
 use mountix_kernel::model::mountain::{
-    Mountain, MountainBoxCoordinates, MountainBoxSearchCondition, MountainLocation,
-    MountainPrefecture, MountainSearchCondition, MountainSortCondition, MountainTag,
+    Mountain, MountainAlias, MountainAliasType, MountainBoxCoordinates, MountainBoxSearchCondition,
+    MountainCursor, MountainData, MountainFacet, MountainHighlight, MountainLocation,
+    MountainNameCondition, MountainNameMatchType, MountainNameMatcher,
+    MountainPolygonSearchCondition, MountainPrefecture, MountainRelevance, MountainSearchCondition,
+    MountainSort, MountainSortKey, MountainSuggestCondition, MountainSuggestion, MountainTag,
 };
+use mountix_kernel::model::mountain_filter::MountainFilterExpr;
+use mountix_kernel::model::tag::TagRegistry;
+use std::env;
+use std::ffi::OsString;
+
+/// Upper limit of `limit` for the box and polygon searches when `MAX_GEOSEARCH_LIMIT` is not set
+const DEFAULT_MAX_GEOSEARCH_LIMIT: i64 = 100;
+
+/// Default and upper limit of `limit` for the suggestions
+const DEFAULT_SUGGEST_LIMIT: usize = 10;
+const MAX_SUGGEST_LIMIT: usize = 20;
 
 #[derive(Debug)]
 pub struct SearchedMountain {
@@ -14,6 +32,92 @@ pub struct SearchedMountain {
     pub elevation: u32,
     pub location: SearchedMountainLocation,
     pub tags: Vec<String>,
+    pub aliases: Vec<SearchedMountainAlias>,
+    pub version: u32,
+    /// Part of the name which the name condition matched, including orthographic variants
+    pub matched_variant: Option<String>,
+    /// Alias which the name condition matched
+    pub matched_alias: Option<SearchedMountainAlias>,
+    /// Relevance score to the name condition, which is set only when sorted by relevance
+    pub score: Option<f64>,
+    pub highlights: Vec<SearchedMountainHighlight>,
+}
+
+impl SearchedMountain {
+    /// Sets the part of the name and the alias which the name condition matched
+    ///
+    /// 山名のうち山名の条件に一致した部分と、一致した別名を設定します
+    pub fn with_name_match(mut self, name: Option<&MountainNameCondition>) -> Self {
+        let Some(name) = name else {
+            return self;
+        };
+
+        self.matched_variant = name.matched_variant(&self.name);
+        self.matched_alias = MountainNameMatcher::new(name).ok().and_then(|matcher| {
+            self.aliases
+                .iter()
+                .find(|alias| matcher.is_name_match(&alias.name, &alias.name_kana))
+                .cloned()
+        });
+        self
+    }
+
+    /// Sets the relevance score and the highlighted field values
+    ///
+    /// 関連度のスコアと一致した項目の値を設定します
+    pub fn with_relevance(mut self, relevance: Option<MountainRelevance>) -> Self {
+        if let Some(relevance) = relevance {
+            self.score = Some(relevance.score);
+            self.highlights = relevance
+                .highlights
+                .into_iter()
+                .map(SearchedMountainHighlight::from)
+                .collect();
+        }
+        self
+    }
+}
+
+/// Field value of a mountain with the ranges of characters which matched the name condition
+///
+/// 山名の条件に一致した文字の範囲を持つ山岳の項目の値
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchedMountainHighlight {
+    pub field: String,
+    pub value: String,
+    /// Ranges of characters, where the end is exclusive
+    pub spans: Vec<(usize, usize)>,
+}
+
+impl From<MountainHighlight> for SearchedMountainHighlight {
+    fn from(highlight: MountainHighlight) -> Self {
+        Self {
+            field: highlight.field.to_key(),
+            value: highlight.value,
+            spans: highlight
+                .spans
+                .into_iter()
+                .map(|span| (span.start, span.end))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchedMountainAlias {
+    pub name: String,
+    pub name_kana: String,
+    pub alias_type: String,
+}
+
+impl From<MountainAlias> for SearchedMountainAlias {
+    fn from(alias: MountainAlias) -> Self {
+        Self {
+            name: alias.name,
+            name_kana: alias.name_kana,
+            alias_type: alias.alias_type.to_key(),
+        }
+    }
 }
 
 impl From<Mountain> for SearchedMountain {
@@ -27,6 +131,16 @@ impl From<Mountain> for SearchedMountain {
             elevation: mountain.elevation,
             location: mountain.location.into(),
             tags: mountain.tags,
+            aliases: mountain
+                .aliases
+                .into_iter()
+                .map(SearchedMountainAlias::from)
+                .collect(),
+            version: mountain.version,
+            matched_variant: None,
+            matched_alias: None,
+            score: None,
+            highlights: vec![],
         }
     }
 }
@@ -48,73 +162,168 @@ impl From<MountainLocation> for SearchedMountainLocation {
     }
 }
 
+/// Counts of the matched mountains per value of the facet
+///
+/// ファセットの値ごとの検索条件に一致した山岳の件数
+#[derive(Debug)]
+pub struct SearchedMountainFacet {
+    pub key: String,
+    pub counts: Vec<SearchedMountainFacetCount>,
+}
+
+#[derive(Debug)]
+pub struct SearchedMountainFacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+impl From<MountainFacet> for SearchedMountainFacet {
+    fn from(facet: MountainFacet) -> Self {
+        Self {
+            key: facet.key.to_key(),
+            counts: facet
+                .counts
+                .into_iter()
+                .map(|c| SearchedMountainFacetCount {
+                    value: c.value,
+                    count: c.count,
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SearchedMountainResult {
     pub mountains: Vec<SearchedMountain>,
     pub total: u64,
     pub offset: u64,
     pub limit: Option<u64>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+    pub facets: Vec<SearchedMountainFacet>,
 }
 
 pub struct MountainSearchQuery {
     pub name: Option<String>,
+    pub match_type: Option<String>,
+    /// Comma-separated prefectures
     pub prefecture: Option<String>,
+    pub prefecture_mode: Option<String>,
+    /// Comma-separated tag ids
     pub tag: Option<String>,
+    pub tag_mode: Option<String>,
+    pub area: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
     pub offset: Option<String>,
     pub limit: Option<String>,
+    /// Comma-separated sort keys
     pub sort: Option<String>,
+    /// Latitude of the point to sort by the distance from
+    pub latitude: Option<String>,
+    /// Longitude of the point to sort by the distance from
+    pub longitude: Option<String>,
+    pub cursor: Option<String>,
+    pub facets: Option<String>,
+    pub q: Option<String>,
 }
 
-impl TryFrom<MountainSearchQuery> for MountainSearchCondition {
-    type Error = Vec<String>;
-
-    fn try_from(ms: MountainSearchQuery) -> Result<Self, Self::Error> {
+impl MountainSearchQuery {
+    /// Converts the query into a search condition, resolving the tags against the registry
+    ///
+    /// 検索条件に変換します。タグはタグレジストリから解決します
+    pub fn into_condition(
+        self,
+        tags: &TagRegistry,
+    ) -> Result<MountainSearchCondition, Vec<String>> {
+        let ms = self;
         let mut errors: Vec<String> = Vec::new();
 
-        let name = ms.name;
+        let name = parse_name(ms.name, ms.match_type).unwrap_or_else(|name_errors| {
+            errors.extend(name_errors);
+            None
+        });
 
-        let mut prefecture: Option<MountainPrefecture> = None;
-        if let Some(prefecture_param) = ms.prefecture {
-            match MountainPrefecture::try_from(prefecture_param) {
-                Ok(p) => prefecture = Some(p),
-                Err(_) => errors.push(invalid_param_error("prefecture (都道府県ID)")),
-            }
-        };
+        let prefecture = parse_values(
+            ms.prefecture,
+            ms.prefecture_mode,
+            "prefecture (都道府県)",
+            "prefecture_mode",
+            MountainPrefecture::try_from,
+        )
+        .unwrap_or_else(|prefecture_errors| {
+            errors.extend(prefecture_errors);
+            Default::default()
+        });
 
-        let mut tag: Option<MountainTag> = None;
-        if let Some(tag_param) = ms.tag {
-            match MountainTag::try_from(tag_param) {
-                Ok(t) => tag = Some(t),
-                Err(_) => errors.push(invalid_param_error("tag (タグID)")),
-            }
-        }
+        let tag = parse_values(
+            ms.tag,
+            ms.tag_mode,
+            "tag (タグID)",
+            "tag_mode",
+            |tag_param| MountainTag::try_from((tag_param, tags)),
+        )
+        .unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            Default::default()
+        });
 
-        let mut sort: MountainSortCondition = Default::default();
-        if let Some(sort_param) = ms.sort {
-            match MountainSortCondition::try_from(sort_param) {
-                Ok(s) => sort = s,
-                Err(_) => errors.push(invalid_param_error("sort")),
-            }
+        let elevation = parse_elevation_range(ms.elevation_min, ms.elevation_max).unwrap_or_else(
+            |elevation_errors| {
+                errors.extend(elevation_errors);
+                Default::default()
+            },
+        );
+
+        let sort = parse_sort(ms.sort, ms.latitude, ms.longitude).unwrap_or_else(|sort_errors| {
+            errors.extend(sort_errors);
+            Default::default()
+        });
+        if sort.contains(MountainSortKey::Relevance)
+            && !name
+                .as_ref()
+                .is_some_and(|name| name.match_type != MountainNameMatchType::Regex)
+        {
+            errors.push(
+                "クエリパラメータ sort に relevance を指定する場合は、name を指定し match に regex 以外を指定してください。"
+                    .to_string(),
+            );
         }
 
-        let mut skip = 0u64;
-        if let Some(offset_param) = ms.offset {
-            match offset_param.parse::<u64>() {
-                Ok(skip_value) => skip = skip_value,
-                Err(_) => errors.push(invalid_param_error("offset")),
+        let mut cursor: Option<MountainCursor> = None;
+        if let Some(cursor_param) = ms.cursor {
+            match MountainCursor::try_from(cursor_param) {
+                Ok(c) if sort.matches_cursor(&c) => cursor = Some(c),
+                Ok(_) => errors.push(
+                    "クエリパラメータ cursor は同じ sort で取得した値を指定してください。"
+                        .to_string(),
+                ),
+                Err(_) => errors.push(invalid_param_error("cursor")),
+            }
+            if ms.offset.is_some() {
+                errors
+                    .push("クエリパラメータ cursor と offset は同時に指定できません。".to_string());
             }
         }
 
-        let mut limit: Option<i64> = None;
-        if let Some(limit_param) = ms.limit {
-            match limit_param.parse::<i64>() {
-                Ok(limit_value) => {
-                    if limit_value < 0 {
-                        errors.push(invalid_param_error("limit"));
-                    }
-                    limit = Some(limit_value)
-                }
-                Err(_) => errors.push(invalid_param_error("limit")),
+        let (skip, limit) = parse_page(ms.offset, ms.limit).unwrap_or_else(|page_errors| {
+            errors.extend(page_errors);
+            Default::default()
+        });
+
+        let facets = parse_facets(ms.facets).unwrap_or_else(|facet_errors| {
+            errors.extend(facet_errors);
+            Default::default()
+        });
+
+        let mut filter: Option<MountainFilterExpr> = None;
+        if let Some(q_param) = ms.q {
+            match MountainFilterExpr::parse(&q_param, tags) {
+                Ok(f) => filter = Some(f),
+                Err(filter_errors) => errors.extend(filter_errors.into_iter().map(|e| {
+                    format!("クエリパラメータ q の {} 文字目: {}", e.position, e.message)
+                })),
             }
         }
 
@@ -126,9 +335,14 @@ impl TryFrom<MountainSearchQuery> for MountainSearchCondition {
             name,
             prefecture,
             tag,
+            area: ms.area,
+            elevation,
             skip,
             limit,
             sort,
+            cursor,
+            facets,
+            filter,
         })
     }
 }
@@ -137,19 +351,90 @@ impl TryFrom<MountainSearchQuery> for MountainSearchCondition {
 pub struct SearchedBoxMountainResult {
     pub mountains: Vec<SearchedMountain>,
     pub total: u64,
+    pub offset: u64,
+    pub limit: Option<u64>,
+    pub facets: Vec<SearchedMountainFacet>,
 }
 
 pub struct MountainBoxSearchQuery {
     pub box_coordinates: String,
     pub name: Option<String>,
+    pub match_type: Option<String>,
+    /// Comma-separated tag ids
     pub tag: Option<String>,
+    pub tag_mode: Option<String>,
+    pub area: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+    pub offset: Option<String>,
+    pub limit: Option<String>,
+    /// Comma-separated sort keys
     pub sort: Option<String>,
+    /// Latitude of the point to sort by the distance from
+    pub latitude: Option<String>,
+    /// Longitude of the point to sort by the distance from
+    pub longitude: Option<String>,
+    pub facets: Option<String>,
 }
 
-impl TryFrom<MountainBoxSearchQuery> for MountainBoxSearchCondition {
-    type Error = Vec<String>;
+/// Returns the upper limit of `limit` for the box and polygon searches, configured by `MAX_GEOSEARCH_LIMIT`
+///
+/// 範囲検索とポリゴン検索の limit の上限を返します。上限は `MAX_GEOSEARCH_LIMIT` で設定します
+fn max_geosearch_limit() -> i64 {
+    let env_max_limit = env::var_os("MAX_GEOSEARCH_LIMIT").unwrap_or(OsString::from(""));
+    env_max_limit
+        .into_string()
+        .unwrap_or_default()
+        .parse::<i64>()
+        .ok()
+        .filter(|max_limit| *max_limit > 0)
+        .unwrap_or(DEFAULT_MAX_GEOSEARCH_LIMIT)
+}
 
-    fn try_from(query: MountainBoxSearchQuery) -> Result<Self, Self::Error> {
+/// Parses the sort of the box and polygon searches, which cannot sort by the relevance
+///
+/// 範囲検索とポリゴン検索の並び順を変換します。関連度順は指定できません
+fn parse_geosearch_sort(
+    sort: Option<String>,
+    latitude: Option<String>,
+    longitude: Option<String>,
+) -> Result<MountainSort, Vec<String>> {
+    let sort = parse_sort(sort, latitude, longitude)?;
+    // 関連度順は山名で検索する /mountains でのみ指定できる
+    if sort.contains(MountainSortKey::Relevance) {
+        return Err(vec![invalid_param_error("sort")]);
+    }
+    Ok(sort)
+}
+
+/// Parses the offset and the limit of the box and polygon searches, where the limit defaults to
+/// and is capped at `max_geosearch_limit`
+///
+/// 範囲検索とポリゴン検索の取得範囲を変換します。limit の既定値と上限は `max_geosearch_limit` です
+fn parse_geosearch_page(
+    offset: Option<String>,
+    limit: Option<String>,
+) -> Result<(u64, i64), Vec<String>> {
+    // 広い範囲で全件を返さないように limit の既定値と上限を設ける
+    let max_limit = max_geosearch_limit();
+    match parse_page(offset, limit)? {
+        (_, Some(limit)) if limit == 0 || limit > max_limit => Err(vec![format!(
+            "クエリパラメータ limit は 1 以上 {} 以下の値を指定してください。",
+            max_limit
+        )]),
+        (skip, limit) => Ok((skip, limit.unwrap_or(max_limit))),
+    }
+}
+
+impl MountainBoxSearchQuery {
+    /// Converts the query into a box search condition, resolving the tag against the registry
+    ///
+    /// 範囲検索の条件に変換します。タグはタグレジストリから解決します
+    pub fn into_condition(
+        self,
+        tags: &TagRegistry,
+    ) -> Result<MountainBoxSearchCondition, Vec<String>> {
+        let query = self;
         let mut errors: Vec<String> = Vec::new();
 
         let mut box_coordinates = MountainBoxCoordinates {
@@ -161,23 +446,45 @@ impl TryFrom<MountainBoxSearchQuery> for MountainBoxSearchCondition {
             Err(_) => errors.push(invalid_param_error("box")),
         }
 
-        let name = query.name;
+        let name = parse_name(query.name, query.match_type).unwrap_or_else(|name_errors| {
+            errors.extend(name_errors);
+            None
+        });
 
-        let mut tag: Option<MountainTag> = None;
-        if let Some(tag_param) = query.tag {
-            match MountainTag::try_from(tag_param) {
-                Ok(t) => tag = Some(t),
-                Err(_) => errors.push(invalid_param_error("tag (タグID)")),
-            }
-        }
+        let tag = parse_values(
+            query.tag,
+            query.tag_mode,
+            "tag (タグID)",
+            "tag_mode",
+            |tag_param| MountainTag::try_from((tag_param, tags)),
+        )
+        .unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            Default::default()
+        });
 
-        let mut sort: MountainSortCondition = Default::default();
-        if let Some(sort_param) = query.sort {
-            match MountainSortCondition::try_from(sort_param) {
-                Ok(s) => sort = s,
-                Err(_) => errors.push(invalid_param_error("sort")),
-            }
-        }
+        let elevation = parse_elevation_range(query.elevation_min, query.elevation_max)
+            .unwrap_or_else(|elevation_errors| {
+                errors.extend(elevation_errors);
+                Default::default()
+            });
+
+        let sort = parse_geosearch_sort(query.sort, query.latitude, query.longitude)
+            .unwrap_or_else(|sort_errors| {
+                errors.extend(sort_errors);
+                Default::default()
+            });
+
+        let (skip, limit) =
+            parse_geosearch_page(query.offset, query.limit).unwrap_or_else(|page_errors| {
+                errors.extend(page_errors);
+                (0, max_geosearch_limit())
+            });
+
+        let facets = parse_facets(query.facets).unwrap_or_else(|facet_errors| {
+            errors.extend(facet_errors);
+            Default::default()
+        });
 
         if !errors.is_empty() {
             return Err(errors);
@@ -187,7 +494,342 @@ impl TryFrom<MountainBoxSearchQuery> for MountainBoxSearchCondition {
             box_coordinates,
             name,
             tag,
+            area: query.area,
+            elevation,
+            skip,
+            limit: Some(limit),
             sort,
+            facets,
+        })
+    }
+}
+
+/// Polygon search query, where the GeoJSON geometry has already been parsed into positions
+///
+/// ポリゴン検索クエリ (GeoJSON のジオメトリは座標に変換済み)
+pub struct MountainPolygonSearchQuery {
+    /// Polygons of rings of [longitude, latitude] positions
+    pub polygons: Vec<Vec<Vec<[f64; 2]>>>,
+    pub name: Option<String>,
+    pub match_type: Option<String>,
+    /// Comma-separated tag ids
+    pub tag: Option<String>,
+    pub tag_mode: Option<String>,
+    pub area: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+    pub offset: Option<String>,
+    pub limit: Option<String>,
+    pub sort: Option<String>,
+    /// Latitude of the point to sort by the distance from
+    pub latitude: Option<String>,
+    /// Longitude of the point to sort by the distance from
+    pub longitude: Option<String>,
+}
+
+impl MountainPolygonSearchQuery {
+    /// Converts the query into a polygon search condition, resolving the tag against the registry
+    ///
+    /// ポリゴン検索の条件に変換します。タグはタグレジストリから解決します
+    pub fn into_condition(
+        self,
+        tags: &TagRegistry,
+    ) -> Result<MountainPolygonSearchCondition, Vec<String>> {
+        let query = self;
+        let mut errors: Vec<String> = Vec::new();
+
+        let polygon = GeoPolygon::try_from(query.polygons)
+            .map_err(|polygon_errors| errors.extend(polygon_errors))
+            .ok();
+
+        let name = parse_name(query.name, query.match_type).unwrap_or_else(|name_errors| {
+            errors.extend(name_errors);
+            None
+        });
+
+        let tag = parse_values(
+            query.tag,
+            query.tag_mode,
+            "tag (タグID)",
+            "tag_mode",
+            |tag_param| MountainTag::try_from((tag_param, tags)),
+        )
+        .unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            Default::default()
+        });
+
+        let elevation = parse_elevation_range(query.elevation_min, query.elevation_max)
+            .unwrap_or_else(|elevation_errors| {
+                errors.extend(elevation_errors);
+                Default::default()
+            });
+
+        let sort = parse_geosearch_sort(query.sort, query.latitude, query.longitude)
+            .unwrap_or_else(|sort_errors| {
+                errors.extend(sort_errors);
+                Default::default()
+            });
+
+        let (skip, limit) =
+            parse_geosearch_page(query.offset, query.limit).unwrap_or_else(|page_errors| {
+                errors.extend(page_errors);
+                (0, max_geosearch_limit())
+            });
+
+        match polygon {
+            Some(polygon) if errors.is_empty() => Ok(MountainPolygonSearchCondition {
+                polygon,
+                name,
+                tag,
+                area: query.area,
+                elevation,
+                skip,
+                limit: Some(limit),
+                sort,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+/// Mountain suggested for the query, which has only the fields shown in the search box
+///
+/// 入力値に対する候補の山岳。検索ボックスに表示する項目のみを持ちます
+#[derive(Debug)]
+pub struct SuggestedMountain {
+    pub id: i32,
+    pub name: String,
+    pub name_kana: String,
+    pub prefectures: Vec<String>,
+}
+
+impl From<MountainSuggestion> for SuggestedMountain {
+    fn from(suggestion: MountainSuggestion) -> Self {
+        Self {
+            id: suggestion.id.value,
+            name: suggestion.name,
+            name_kana: suggestion.name_kana,
+            prefectures: suggestion.prefectures,
+        }
+    }
+}
+
+pub struct MountainSuggestQuery {
+    pub q: Option<String>,
+    pub limit: Option<String>,
+}
+
+impl TryFrom<MountainSuggestQuery> for MountainSuggestCondition {
+    type Error = Vec<String>;
+
+    fn try_from(query: MountainSuggestQuery) -> Result<Self, Self::Error> {
+        let mut errors: Vec<String> = Vec::new();
+
+        let q = query.q.unwrap_or_default().trim().to_string();
+        if q.is_empty() {
+            errors.push(required_field_error("クエリパラメータ q"));
+        }
+
+        let mut limit = DEFAULT_SUGGEST_LIMIT;
+        if let Some(limit_param) = query.limit {
+            match limit_param.parse::<usize>() {
+                Ok(limit_value) if (1..=MAX_SUGGEST_LIMIT).contains(&limit_value) => {
+                    limit = limit_value
+                }
+                _ => errors.push(format!(
+                    "クエリパラメータ limit は 1 以上 {} 以下の値を指定してください。",
+                    MAX_SUGGEST_LIMIT
+                )),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(MountainSuggestCondition::new(q, limit))
+    }
+}
+
+/// Mountain write request
+///
+/// 山岳情報の登録・更新リクエスト
+pub struct MountainWriteRequest {
+    pub name: Option<String>,
+    pub name_kana: Option<String>,
+    pub area: Option<String>,
+    pub prefectures: Option<Vec<String>>,
+    pub elevation: Option<u32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub tags: Option<Vec<String>>,
+    pub aliases: Option<Vec<MountainAliasWriteRequest>>,
+}
+
+/// Alias of the mountain write request
+///
+/// 山岳情報の登録・更新リクエストの別名
+pub struct MountainAliasWriteRequest {
+    pub name: String,
+    pub name_kana: String,
+    pub alias_type: String,
+}
+
+impl TryFrom<MountainAliasWriteRequest> for MountainAlias {
+    type Error = String;
+
+    fn try_from(request: MountainAliasWriteRequest) -> Result<Self, Self::Error> {
+        let alias_type = MountainAliasType::try_from(request.alias_type.clone()).map_err(|_| {
+            format!(
+                "aliases (別名) の type の値 {} が不正です。official、local、historic、ainu のいずれかを指定してください。",
+                request.alias_type
+            )
+        })?;
+
+        Ok(MountainAlias {
+            name: request.name,
+            name_kana: request.name_kana,
+            alias_type,
         })
     }
 }
+
+/// Converts the aliases of the write request, collecting every invalid type
+///
+/// 登録・更新リクエストの別名を変換し、不正な type をすべて集めます
+fn parse_aliases(
+    aliases: Vec<MountainAliasWriteRequest>,
+) -> Result<Vec<MountainAlias>, Vec<String>> {
+    let (aliases, errors): (Vec<_>, Vec<_>) = aliases
+        .into_iter()
+        .map(MountainAlias::try_from)
+        .partition(Result::is_ok);
+
+    if !errors.is_empty() {
+        return Err(errors.into_iter().filter_map(Result::err).collect());
+    }
+    Ok(aliases.into_iter().filter_map(Result::ok).collect())
+}
+
+impl MountainWriteRequest {
+    /// Applies the specified fields to the existing mountain
+    ///
+    /// 指定された項目のみを既存の山岳情報に反映します
+    pub fn merge(self, mountain: Mountain) -> Result<MountainData, Vec<String>> {
+        let location = if self.latitude.is_some() || self.longitude.is_some() {
+            MountainLocation::from_coordinates(
+                self.latitude.unwrap_or(mountain.location.latitude),
+                self.longitude.unwrap_or(mountain.location.longitude),
+            )
+        } else {
+            mountain.location
+        };
+
+        let aliases = match self.aliases {
+            Some(aliases) => parse_aliases(aliases)?,
+            None => mountain.aliases,
+        };
+
+        Ok(MountainData {
+            name: self.name.unwrap_or(mountain.name),
+            name_kana: self.name_kana.unwrap_or(mountain.name_kana),
+            area: self.area.unwrap_or(mountain.area),
+            prefectures: self.prefectures.unwrap_or(mountain.prefectures),
+            elevation: self.elevation.unwrap_or(mountain.elevation),
+            location,
+            tags: self.tags.unwrap_or(mountain.tags),
+            aliases,
+        })
+    }
+}
+
+impl TryFrom<MountainWriteRequest> for MountainData {
+    type Error = Vec<String>;
+
+    fn try_from(request: MountainWriteRequest) -> Result<Self, Self::Error> {
+        let mut errors: Vec<String> = Vec::new();
+
+        if request.name.is_none() {
+            errors.push(required_field_error("name (山名)"));
+        }
+        if request.name_kana.is_none() {
+            errors.push(required_field_error("nameKana (山名かな)"));
+        }
+        if request.area.is_none() {
+            errors.push(required_field_error("area (山域)"));
+        }
+        if request.prefectures.is_none() {
+            errors.push(required_field_error("prefectures (都道府県)"));
+        }
+        if request.elevation.is_none() {
+            errors.push(required_field_error("elevation (標高)"));
+        }
+        if request.latitude.is_none() || request.longitude.is_none() {
+            errors.push(required_field_error("location (位置情報)"));
+        }
+        let aliases = match parse_aliases(request.aliases.unwrap_or_default()) {
+            Ok(aliases) => aliases,
+            Err(messages) => {
+                errors.extend(messages);
+                vec![]
+            }
+        };
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(MountainData {
+            name: request.name.unwrap_or_default(),
+            name_kana: request.name_kana.unwrap_or_default(),
+            area: request.area.unwrap_or_default(),
+            prefectures: request.prefectures.unwrap_or_default(),
+            elevation: request.elevation.unwrap_or_default(),
+            location: MountainLocation::from_coordinates(
+                request.latitude.unwrap_or_default(),
+                request.longitude.unwrap_or_default(),
+            ),
+            tags: request.tags.unwrap_or_default(),
+            aliases,
+        })
+    }
+}
+
+/// Version condition specified by `If-Match`
+///
+/// `If-Match` で指定された更新対象のバージョン
+#[derive(Debug, PartialEq)]
+pub enum MountainVersionCondition {
+    Any,
+    Version(u32),
+}
+
+impl MountainVersionCondition {
+    pub fn matches(&self, version: u32) -> bool {
+        match self {
+            MountainVersionCondition::Any => true,
+            MountainVersionCondition::Version(v) => *v == version,
+        }
+    }
+}
+
+impl TryFrom<String> for MountainVersionCondition {
+    type Error = ();
+
+    /// Parses `*`, `"N"` or `W/"N"`
+    ///
+    /// `*`、`"N"` または `W/"N"` 形式の値を解析します
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if value == "*" {
+            return Ok(MountainVersionCondition::Any);
+        }
+
+        let tag = value.strip_prefix("W/").unwrap_or(value);
+        tag.strip_prefix('"')
+            .and_then(|t| t.strip_suffix('"'))
+            .and_then(|t| t.parse::<u32>().ok())
+            .map(MountainVersionCondition::Version)
+            .ok_or(())
+    }
+}
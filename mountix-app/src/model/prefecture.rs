@@ -0,0 +1,55 @@
+use mountix_kernel::model::mountain::MountainPrefecture;
+use mountix_kernel::model::prefecture::{PrefectureHighestPeak, PrefectureMountainStats};
+
+#[derive(Debug)]
+pub struct SearchedPrefecture {
+    pub id: u64,
+    pub name: String,
+    pub iso_code: String,
+    pub romaji: String,
+    pub region: String,
+    pub mountain_count: u64,
+    pub highest_peak: Option<SearchedPrefectureHighestPeak>,
+}
+
+impl SearchedPrefecture {
+    pub fn new(prefecture: MountainPrefecture, stats: Option<PrefectureMountainStats>) -> Self {
+        let (mountain_count, highest_peak) = match stats {
+            Some(stats) => (stats.mountain_count, stats.highest_peak.map(|p| p.into())),
+            None => (0, None),
+        };
+
+        Self {
+            id: prefecture.id,
+            iso_code: prefecture.iso_code(),
+            name: prefecture.name,
+            romaji: prefecture.romaji,
+            region: prefecture.region,
+            mountain_count,
+            highest_peak,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedPrefectureHighestPeak {
+    pub id: i32,
+    pub name: String,
+    pub elevation: u32,
+}
+
+impl From<PrefectureHighestPeak> for SearchedPrefectureHighestPeak {
+    fn from(peak: PrefectureHighestPeak) -> Self {
+        Self {
+            id: peak.id.value,
+            name: peak.name,
+            elevation: peak.elevation,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedPrefectureResult {
+    pub prefectures: Vec<SearchedPrefecture>,
+    pub total: u64,
+}
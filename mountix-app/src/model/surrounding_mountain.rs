@@ -1,10 +1,22 @@
-use crate::model::invalid_param_error;
+use crate::model::{
+    invalid_param_error, parse_elevation_range, parse_page, parse_point, parse_tag,
+};
+use mountix_kernel::model::geo::{compass_point, GeoLineString, GeoPoint};
+use mountix_kernel::model::mountain::{Mountain, MountainNameCondition, MountainPrefecture};
 use mountix_kernel::model::surrounding_mountain::{
-    SurroundingMountain, SurroundingMountainLocation, SurroundingMountainSearchDistance,
+    CorridorMountainSearchCondition, NearbyMountainSearchCondition, NearestMountainSearchCondition,
+    SurroundingMountain, SurroundingMountainLocation, SurroundingMountainSearchCondition,
+    SurroundingMountainSearchDistance, SurroundingMountainSortCondition,
 };
+use mountix_kernel::model::tag::TagRegistry;
 use std::env;
 use std::ffi::OsString;
 
+/// Number of mountains returned by the nearest search when `k` is omitted
+const DEFAULT_NEAREST_COUNT: u32 = 5;
+/// Upper limit of `k` for the nearest search
+const MAX_NEAREST_COUNT: u32 = 100;
+
 #[derive(Debug)]
 pub struct SearchedSurroundingMountain {
     pub id: i32,
@@ -49,39 +61,568 @@ impl From<SurroundingMountainLocation> for SearchedSurroundingMountainLocation {
     }
 }
 
+/// Mountain around a center mountain with its position relative to the center summit
+///
+/// 中心の山頂に対する位置を含む周辺の山岳
+#[derive(Debug)]
+pub struct SearchedRelativeMountain {
+    pub mountain: SearchedSurroundingMountain,
+    /// Distance from the center summit in meters
+    pub distance: u32,
+    /// Initial bearing from the center summit in degrees clockwise from north
+    pub bearing: f64,
+    /// 16-point compass direction from the center summit, such as 北東
+    pub compass_point: String,
+    /// Elevation minus the elevation of the center mountain in meters
+    pub elevation_diff: i64,
+}
+
+impl SearchedRelativeMountain {
+    pub fn new(center: &GeoPoint, center_elevation: u32, mountain: SurroundingMountain) -> Self {
+        let nearby = SearchedNearbyMountain::new(center, mountain);
+        let elevation_diff = nearby.mountain.elevation as i64 - center_elevation as i64;
+
+        Self {
+            compass_point: compass_point(nearby.bearing).to_string(),
+            mountain: nearby.mountain,
+            distance: nearby.distance,
+            bearing: nearby.bearing,
+            elevation_diff,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SearchedSurroundingMountainResult {
-    pub mountains: Vec<SearchedSurroundingMountain>,
+    pub mountains: Vec<SearchedRelativeMountain>,
     pub distance: u32,
+    pub total: u64,
+    pub offset: u64,
+    pub limit: Option<u64>,
 }
 
 pub struct SurroundingMountainSearchQuery {
     pub distance: Option<String>,
+    pub prefecture: Option<String>,
+    pub tag: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+    pub offset: Option<String>,
+    pub limit: Option<String>,
+    pub sort: Option<String>,
+}
+
+impl SurroundingMountainSearchQuery {
+    /// Converts the query into a condition to search the surroundings of the mountain
+    ///
+    /// 山岳の周辺の山岳を検索する条件に変換します
+    pub fn into_condition(
+        self,
+        mountain: Mountain,
+        tags: &TagRegistry,
+    ) -> Result<SurroundingMountainSearchCondition, Vec<String>> {
+        let mut errors: Vec<String> = Vec::new();
+
+        let distance =
+            parse_distance("distance", self.distance).unwrap_or_else(|distance_errors| {
+                errors.extend(distance_errors);
+                Default::default()
+            });
+
+        let mut prefecture: Option<MountainPrefecture> = None;
+        if let Some(prefecture_param) = self.prefecture {
+            match MountainPrefecture::try_from(prefecture_param) {
+                Ok(p) => prefecture = Some(p),
+                Err(_) => errors.push(invalid_param_error("prefecture (都道府県)")),
+            }
+        }
+
+        let tag = parse_tag(self.tag, tags).unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            None
+        });
+
+        let elevation = parse_elevation_range(self.elevation_min, self.elevation_max)
+            .unwrap_or_else(|elevation_errors| {
+                errors.extend(elevation_errors);
+                Default::default()
+            });
+
+        let mut sort = SurroundingMountainSortCondition::default();
+        if let Some(sort_param) = self.sort {
+            match SurroundingMountainSortCondition::try_from(sort_param) {
+                Ok(s) => sort = s,
+                Err(_) => errors.push(invalid_param_error("sort")),
+            }
+        }
+
+        let (skip, limit) = parse_page(self.offset, self.limit).unwrap_or_else(|page_errors| {
+            errors.extend(page_errors);
+            Default::default()
+        });
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(SurroundingMountainSearchCondition::new(mountain, distance)
+            .with_filter(prefecture, tag)
+            .with_elevation(elevation)
+            .with_page(skip, limit, sort))
+    }
 }
 
 impl TryFrom<SurroundingMountainSearchQuery> for SurroundingMountainSearchDistance {
     type Error = Vec<String>;
 
     fn try_from(query: SurroundingMountainSearchQuery) -> Result<Self, Self::Error> {
-        let env_max_distance = env::var_os("MAX_DISTANCE").unwrap_or(OsString::from("100000"));
-        let max_distance = env_max_distance
-            .into_string()
-            .unwrap_or("100000".to_string())
-            .parse::<u32>()
-            .unwrap_or(100_000);
-
-        match query.distance {
-            Some(query_distance) => match query_distance.parse::<u32>() {
-                Ok(distance) => {
-                    if distance <= max_distance {
-                        Ok(SurroundingMountainSearchDistance::new(distance))
-                    } else {
-                        Err(vec![invalid_param_error("distance")])
-                    }
+        parse_distance("distance", query.distance)
+    }
+}
+
+/// Parses a distance query parameter such as `distance`, limited by `MAX_DISTANCE`
+///
+/// クエリパラメータ distance などの距離を `MAX_DISTANCE` を上限として解析します
+fn parse_distance(
+    param_name: &str,
+    distance: Option<String>,
+) -> Result<SurroundingMountainSearchDistance, Vec<String>> {
+    let env_max_distance = env::var_os("MAX_DISTANCE").unwrap_or(OsString::from("100000"));
+    let max_distance = env_max_distance
+        .into_string()
+        .unwrap_or("100000".to_string())
+        .parse::<u32>()
+        .unwrap_or(100_000);
+
+    match distance {
+        Some(query_distance) => match query_distance.parse::<u32>() {
+            Ok(distance) => {
+                if distance <= max_distance {
+                    Ok(SurroundingMountainSearchDistance::new(distance))
+                } else {
+                    Err(vec![invalid_param_error(param_name)])
                 }
-                Err(_) => Err(vec![invalid_param_error("distance")]),
-            },
-            None => Ok(Default::default()),
+            }
+            Err(_) => Err(vec![invalid_param_error(param_name)]),
+        },
+        None => Ok(Default::default()),
+    }
+}
+
+/// Mountain around a point with the distance and bearing from the point
+///
+/// 地点からの距離と方位角を含む周辺の山岳
+#[derive(Debug)]
+pub struct SearchedNearbyMountain {
+    pub mountain: SearchedSurroundingMountain,
+    /// Distance from the point in meters
+    pub distance: u32,
+    /// Bearing from the point in degrees clockwise from north
+    pub bearing: f64,
+}
+
+impl SearchedNearbyMountain {
+    pub fn new(point: &GeoPoint, mountain: SurroundingMountain) -> Self {
+        let mountain_point = mountain.location.point();
+        let distance = point.distance_to(&mountain_point).round() as u32;
+        // 方位角は小数点以下 1 桁に丸める
+        let bearing = (point.bearing_to(&mountain_point) * 10.0).round() / 10.0 % 360.0;
+
+        Self {
+            mountain: mountain.into(),
+            distance,
+            bearing,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedNearbyMountainResult {
+    pub mountains: Vec<SearchedNearbyMountain>,
+    pub distance: u32,
+}
+
+pub struct NearbyMountainSearchQuery {
+    pub latitude: Option<String>,
+    pub longitude: Option<String>,
+    pub distance: Option<String>,
+    pub name: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl NearbyMountainSearchQuery {
+    /// Converts the query into a condition, resolving the tag against the registry
+    ///
+    /// 検索条件に変換します。タグはタグレジストリから解決します
+    pub fn into_condition(
+        self,
+        tags: &TagRegistry,
+    ) -> Result<NearbyMountainSearchCondition, Vec<String>> {
+        let query = self;
+        let mut errors: Vec<String> = Vec::new();
+
+        let point = parse_point(query.latitude, query.longitude).unwrap_or_else(|point_errors| {
+            errors.extend(point_errors);
+            GeoPoint::default()
+        });
+
+        let distance =
+            parse_distance("distance", query.distance).unwrap_or_else(|distance_errors| {
+                errors.extend(distance_errors);
+                Default::default()
+            });
+
+        let tag = parse_tag(query.tag, tags).unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            None
+        });
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(NearbyMountainSearchCondition {
+            point,
+            distance,
+            name: query.name.map(MountainNameCondition::contains),
+            tag,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedNearestMountainResult {
+    pub mountains: Vec<SearchedNearbyMountain>,
+    pub k: u32,
+}
+
+pub struct NearestMountainSearchQuery {
+    pub latitude: Option<String>,
+    pub longitude: Option<String>,
+    pub k: Option<String>,
+    pub tag: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+}
+
+impl NearestMountainSearchQuery {
+    /// Converts the query into a condition, using `point` instead of `lat` and `lng` when given
+    ///
+    /// 検索条件に変換します。`point` を指定した場合は lat と lng の代わりに使用します
+    pub fn into_condition(
+        self,
+        point: Option<GeoPoint>,
+        tags: &TagRegistry,
+    ) -> Result<NearestMountainSearchCondition, Vec<String>> {
+        let mut errors: Vec<String> = Vec::new();
+
+        let point = match point {
+            Some(point) => point,
+            None => parse_point(self.latitude, self.longitude).unwrap_or_else(|point_errors| {
+                errors.extend(point_errors);
+                GeoPoint::default()
+            }),
+        };
+
+        let mut k = DEFAULT_NEAREST_COUNT;
+        if let Some(k_param) = self.k {
+            match k_param.parse::<u32>() {
+                Ok(count) if (1..=MAX_NEAREST_COUNT).contains(&count) => k = count,
+                _ => errors.push(invalid_param_error("k")),
+            }
+        }
+
+        let tag = parse_tag(self.tag, tags).unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            None
+        });
+
+        let elevation = parse_elevation_range(self.elevation_min, self.elevation_max)
+            .unwrap_or_else(|elevation_errors| {
+                errors.extend(elevation_errors);
+                Default::default()
+            });
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(NearestMountainSearchCondition {
+            point,
+            k,
+            tag,
+            elevation,
+            exclude_id: None,
+        })
+    }
+}
+
+/// Mountain along a line with its position relative to the line
+///
+/// ラインに対する位置を含むライン沿いの山岳
+#[derive(Debug)]
+pub struct SearchedCorridorMountain {
+    pub mountain: SearchedSurroundingMountain,
+    /// Distance along the line from its start in meters
+    pub along_track: u32,
+    /// Distance from the line in meters
+    pub cross_track: u32,
+}
+
+impl SearchedCorridorMountain {
+    pub fn new(line: &GeoLineString, mountain: SurroundingMountain) -> Self {
+        let projection = line.project(&mountain.location.point());
+
+        Self {
+            mountain: mountain.into(),
+            along_track: projection.along_track.round() as u32,
+            cross_track: projection.cross_track.round() as u32,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedCorridorMountainResult {
+    pub mountains: Vec<SearchedCorridorMountain>,
+    pub buffer: u32,
+    /// Length of the line in meters
+    pub length: u32,
+}
+
+/// Corridor search query, where the GeoJSON line has already been parsed into positions
+///
+/// ライン沿いの山岳の検索クエリ (GeoJSON のラインは座標に変換済み)
+pub struct CorridorMountainSearchQuery {
+    /// [longitude, latitude] positions of the line
+    pub line: Vec<[f64; 2]>,
+    pub buffer: Option<String>,
+    pub name: Option<String>,
+    pub tag: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+}
+
+impl CorridorMountainSearchQuery {
+    /// Converts the query into a condition, resolving the tag against the registry
+    ///
+    /// 検索条件に変換します。タグはタグレジストリから解決します
+    pub fn into_condition(
+        self,
+        tags: &TagRegistry,
+    ) -> Result<CorridorMountainSearchCondition, Vec<String>> {
+        let query = self;
+        let mut errors: Vec<String> = Vec::new();
+
+        let line = GeoLineString::try_from(query.line)
+            .map_err(|line_errors| errors.extend(line_errors))
+            .ok();
+
+        let buffer = parse_distance("buffer", query.buffer).unwrap_or_else(|buffer_errors| {
+            errors.extend(buffer_errors);
+            Default::default()
+        });
+
+        let tag = parse_tag(query.tag, tags).unwrap_or_else(|tag_errors| {
+            errors.extend(tag_errors);
+            None
+        });
+
+        let elevation = parse_elevation_range(query.elevation_min, query.elevation_max)
+            .unwrap_or_else(|elevation_errors| {
+                errors.extend(elevation_errors);
+                Default::default()
+            });
+
+        match line {
+            Some(line) if errors.is_empty() => Ok(CorridorMountainSearchCondition {
+                line,
+                buffer,
+                name: query.name.map(MountainNameCondition::contains),
+                tag,
+                elevation,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mountix_kernel::model::surrounding_mountain::SurroundingMountainData;
+    use mountix_kernel::model::tag::Tag;
+    use mountix_kernel::model::Id;
+
+    fn create_tag_registry() -> TagRegistry {
+        TagRegistry::new(vec![
+            Tag::new(
+                Id::new(1),
+                "百名山".to_string(),
+                "深田久弥が選定した日本百名山".to_string(),
+                "深田久弥『日本百名山』".to_string(),
+            ),
+            Tag::new(
+                Id::new(2),
+                "二百名山".to_string(),
+                "深田クラブが選定した日本二百名山".to_string(),
+                "深田クラブ『日本二百名山』".to_string(),
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn create_query(latitude: Option<&str>, longitude: Option<&str>) -> NearbyMountainSearchQuery {
+        NearbyMountainSearchQuery {
+            latitude: latitude.map(|v| v.to_string()),
+            longitude: longitude.map(|v| v.to_string()),
+            distance: Some("20000".to_string()),
+            name: None,
+            tag: Some("1".to_string()),
         }
     }
+
+    #[test]
+    fn test_nearby_mountain_search_query_to_condition() {
+        let condition = create_query(Some("35.36"), Some("138.73"))
+            .into_condition(&create_tag_registry())
+            .unwrap();
+        assert_eq!(condition.point.latitude, 35.36);
+        assert_eq!(condition.point.longitude, 138.73);
+        assert_eq!(condition.distance.0, 20000);
+        assert_eq!(condition.tag.unwrap().name, "百名山");
+    }
+
+    #[test]
+    fn test_nearby_mountain_search_query_invalid_point() {
+        let errors = create_query(None, Some("181"))
+            .into_condition(&create_tag_registry())
+            .err()
+            .unwrap();
+        assert_eq!(errors.len(), 2);
+
+        let mut query = create_query(Some("35.36"), Some("138.73"));
+        query.distance = Some("100001".to_string());
+        assert!(query.into_condition(&create_tag_registry()).is_err());
+    }
+
+    #[test]
+    fn test_nearest_mountain_search_query_to_condition() {
+        let query = NearestMountainSearchQuery {
+            latitude: Some("35.36".to_string()),
+            longitude: Some("138.73".to_string()),
+            k: None,
+            tag: Some("1".to_string()),
+            elevation_min: Some("2000".to_string()),
+            elevation_max: None,
+        };
+        let condition = query.into_condition(None, &create_tag_registry()).unwrap();
+        assert_eq!(condition.point.latitude, 35.36);
+        assert_eq!(condition.k, DEFAULT_NEAREST_COUNT);
+        assert_eq!(condition.elevation.min, Some(2000));
+        assert!(condition.exclude_id.is_none());
+    }
+
+    #[test]
+    fn test_nearest_mountain_search_query_with_point() {
+        let point = GeoPoint::new(35.36, 138.73).unwrap();
+        let query = NearestMountainSearchQuery {
+            latitude: None,
+            longitude: None,
+            k: Some("100".to_string()),
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+        };
+        let condition = query
+            .into_condition(Some(point), &create_tag_registry())
+            .unwrap();
+        assert_eq!(condition.point.longitude, 138.73);
+        assert_eq!(condition.k, 100);
+    }
+
+    #[test]
+    fn test_nearest_mountain_search_query_invalid_k() {
+        for k in ["0", "101", "-1", "abc"] {
+            let query = NearestMountainSearchQuery {
+                latitude: Some("35.36".to_string()),
+                longitude: Some("138.73".to_string()),
+                k: Some(k.to_string()),
+                tag: None,
+                elevation_min: None,
+                elevation_max: None,
+            };
+            let errors = query
+                .into_condition(None, &create_tag_registry())
+                .err()
+                .unwrap();
+            assert_eq!(errors, vec![invalid_param_error("k")]);
+        }
+    }
+
+    #[test]
+    fn test_corridor_mountain_search_query_to_condition() {
+        let query = CorridorMountainSearchQuery {
+            line: vec![[138.7, 35.3], [138.8, 35.3]],
+            buffer: Some("3000".to_string()),
+            name: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+        };
+        let condition = query.into_condition(&create_tag_registry()).unwrap();
+        assert_eq!(condition.line.points.len(), 2);
+        assert_eq!(condition.buffer.0, 3000);
+
+        let query = CorridorMountainSearchQuery {
+            line: vec![[138.7, 35.3]],
+            buffer: Some("100001".to_string()),
+            name: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+        };
+        let errors = query.into_condition(&create_tag_registry()).err().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[1], invalid_param_error("buffer"));
+    }
+
+    fn create_kenashi_mountain() -> SurroundingMountain {
+        let data = SurroundingMountainData {
+            name: "毛無山".to_string(),
+            name_kana: "けなしやま".to_string(),
+            area: "天子山地".to_string(),
+            prefectures: vec!["静岡県".to_string(), "山梨県".to_string()],
+            elevation: 1964,
+            location: SurroundingMountainLocation::new(
+                35.41777777777778,
+                138.5433333333333,
+                "https://maps.gsi.go.jp".to_string(),
+            ),
+            tags: vec!["二百名山".to_string()],
+        };
+        SurroundingMountain::new(Id::new(2), data)
+    }
+
+    #[test]
+    fn test_searched_nearby_mountain_distance_and_bearing() {
+        let point = GeoPoint::new(35.36083333333333, 138.7275).unwrap();
+
+        let nearby = SearchedNearbyMountain::new(&point, create_kenashi_mountain());
+        assert_eq!(nearby.distance, 17875);
+        assert!((290.0..300.0).contains(&nearby.bearing));
+        assert_eq!(nearby.mountain.name, "毛無山");
+    }
+
+    #[test]
+    fn test_searched_relative_mountain() {
+        // 富士山<剣ヶ峯> から見た毛無山
+        let center = GeoPoint::new(35.36083333333333, 138.7275).unwrap();
+
+        let relative = SearchedRelativeMountain::new(&center, 3776, create_kenashi_mountain());
+        assert_eq!(relative.distance, 17875);
+        assert!((290.0..300.0).contains(&relative.bearing));
+        assert_eq!(relative.compass_point, "西北西");
+        assert_eq!(relative.elevation_diff, -1812);
+        assert_eq!(relative.mountain.name, "毛無山");
+    }
 }
@@ -0,0 +1,28 @@
+use mountix_kernel::model::tag::Tag;
+
+#[derive(Debug)]
+pub struct SearchedTag {
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub source: String,
+    pub mountain_count: u64,
+}
+
+impl SearchedTag {
+    pub fn new(tag: Tag, mountain_count: u64) -> Self {
+        Self {
+            id: tag.id.value,
+            name: tag.name,
+            description: tag.description,
+            source: tag.source,
+            mountain_count,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchedTagResult {
+    pub tags: Vec<SearchedTag>,
+    pub total: u64,
+}
@@ -0,0 +1,197 @@
+use crate::model::area::{SearchedArea, SearchedAreaResult};
+use mountix_adapter::modules::RepositoriesModuleExt;
+use mountix_kernel::model::area::AreaFindException;
+use mountix_kernel::model::ErrorCode;
+use mountix_kernel::repository::area::AreaRepository;
+use std::sync::Arc;
+
+pub struct AreaUseCase<R: RepositoriesModuleExt> {
+    repositories: Arc<R>,
+}
+
+impl<R: RepositoriesModuleExt> AreaUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+
+    pub async fn find(&self) -> Result<SearchedAreaResult, AreaFindException> {
+        let areas = self
+            .repositories
+            .area_repository()
+            .find()
+            .await
+            .map_err(|_| AreaFindException::new_with_error_code(ErrorCode::ServerError))?;
+
+        let areas: Vec<SearchedArea> = areas.into_iter().map(|area| area.into()).collect();
+
+        let total = areas.len() as u64;
+        Ok(SearchedAreaResult { areas, total })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use mountix_kernel::model::area::Area;
+    use mountix_kernel::model::mountain::MountainBoxCoordinates;
+    use mountix_kernel::model::mountain::{
+        Mountain, MountainBoxSearchCondition, MountainData, MountainSearchCondition,
+    };
+    use mountix_kernel::model::prefecture::PrefectureMountainStats;
+    use mountix_kernel::model::surrounding_mountain::{
+        SurroundingMountain, SurroundingMountainSearchCondition,
+    };
+    use mountix_kernel::model::tag::Tag;
+    use mountix_kernel::model::Id;
+    use mountix_kernel::repository::mountain::MountainRepository;
+    use mountix_kernel::repository::prefecture::PrefectureRepository;
+    use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
+    use mountix_kernel::repository::tag::TagRepository;
+
+    mock! {
+        TestMountainRepository {}
+
+        #[async_trait::async_trait]
+        impl MountainRepository for TestMountainRepository {
+            async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>>;
+            async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64>;
+            async fn find(&self, search_condition: MountainSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_box(&self, search_condition: MountainBoxSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_polygon(&self, search_condition: mountix_kernel::model::mountain::MountainPolygonSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn suggest(&self, search_condition: mountix_kernel::model::mountain::MountainSuggestCondition) -> anyhow::Result<Vec<mountix_kernel::model::mountain::MountainSuggestion>>;
+            async fn create(&self, data: MountainData) -> anyhow::Result<Mountain>;
+            async fn update(&self, id: Id<Mountain>, data: MountainData, version: u32) -> anyhow::Result<Option<Mountain>>;
+            async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool>;
+        }
+    }
+
+    mock! {
+        TestSurroundingMountainRepository {}
+
+        #[async_trait::async_trait]
+        impl SurroundingMountainRepository for TestSurroundingMountainRepository {
+            async fn get_count(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<u64>;
+            async fn find(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearby(&self, condition: mountix_kernel::model::surrounding_mountain::NearbyMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearest(&self, condition: mountix_kernel::model::surrounding_mountain::NearestMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_corridor(&self, condition: mountix_kernel::model::surrounding_mountain::CorridorMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+        }
+    }
+
+    mock! {
+        TestTagRepository {}
+
+        #[async_trait::async_trait]
+        impl TagRepository for TestTagRepository {
+            async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>>;
+            async fn find(&self) -> anyhow::Result<Vec<Tag>>;
+            async fn count_mountains(&self) -> anyhow::Result<std::collections::BTreeMap<String, u64>>;
+        }
+    }
+
+    mock! {
+        TestPrefectureRepository {}
+
+        #[async_trait::async_trait]
+        impl PrefectureRepository for TestPrefectureRepository {
+            async fn find_stats(&self) -> anyhow::Result<Vec<PrefectureMountainStats>>;
+        }
+    }
+
+    mock! {
+        TestAreaRepository {}
+
+        #[async_trait::async_trait]
+        impl AreaRepository for TestAreaRepository {
+            async fn find(&self) -> anyhow::Result<Vec<Area>>;
+        }
+    }
+
+    struct MockRepositoriesModule {
+        mountain_repository: MockTestMountainRepository,
+        surrounding_mountain_repository: MockTestSurroundingMountainRepository,
+        tag_repository: MockTestTagRepository,
+        prefecture_repository: MockTestPrefectureRepository,
+        area_repository: MockTestAreaRepository,
+    }
+
+    impl RepositoriesModuleExt for MockRepositoriesModule {
+        type MountainRepo = MockTestMountainRepository;
+        type SurroundingMountainRepo = MockTestSurroundingMountainRepository;
+        type TagRepo = MockTestTagRepository;
+        type PrefectureRepo = MockTestPrefectureRepository;
+        type AreaRepo = MockTestAreaRepository;
+
+        fn mountain_repository(&self) -> &Self::MountainRepo {
+            &self.mountain_repository
+        }
+
+        fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo {
+            &self.surrounding_mountain_repository
+        }
+
+        fn tag_repository(&self) -> &Self::TagRepo {
+            &self.tag_repository
+        }
+
+        fn prefecture_repository(&self) -> &Self::PrefectureRepo {
+            &self.prefecture_repository
+        }
+
+        fn area_repository(&self) -> &Self::AreaRepo {
+            &self.area_repository
+        }
+    }
+
+    fn create_use_case(
+        area_repository: MockTestAreaRepository,
+    ) -> AreaUseCase<MockRepositoriesModule> {
+        AreaUseCase::new(Arc::new(MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: MockTestTagRepository::new(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_area_use_case_find() {
+        let mut mock_repo = MockTestAreaRepository::new();
+        mock_repo.expect_find().times(1).returning(|| {
+            Ok(vec![Area::new(
+                "富士山".to_string(),
+                2,
+                (1504, 3776),
+                vec!["静岡県".to_string(), "山梨県".to_string()],
+                MountainBoxCoordinates {
+                    bottom_left: (138.7275, 35.2325),
+                    upper_right: (138.80666666666667, 35.36083333333333),
+                },
+            )])
+        });
+
+        let result = create_use_case(mock_repo).find().await.unwrap();
+        assert_eq!(result.total, 1);
+
+        let fuji = &result.areas[0];
+        assert_eq!(fuji.name, "富士山");
+        assert_eq!(fuji.mountain_count, 2);
+        assert_eq!(fuji.prefectures, vec!["山梨県", "静岡県"]);
+        assert_eq!(fuji.bounding_box.bottom_left.latitude, 35.2325);
+        assert_eq!(fuji.bounding_box.upper_right.longitude, 138.80666666666667);
+    }
+
+    #[tokio::test]
+    async fn test_area_use_case_find_repository_error() {
+        let mut mock_repo = MockTestAreaRepository::new();
+        mock_repo
+            .expect_find()
+            .times(1)
+            .returning(|| Err(anyhow::anyhow!("Database error")));
+
+        let result = create_use_case(mock_repo).find().await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::ServerError);
+    }
+}
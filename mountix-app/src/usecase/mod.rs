@@ -1,3 +1,6 @@
+pub mod area;
 pub mod health_check;
 pub mod mountain;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
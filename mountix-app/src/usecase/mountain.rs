@@ -1,24 +1,38 @@
 use crate::model::mountain::{
-    MountainBoxSearchQuery, MountainSearchQuery, SearchedBoxMountainResult, SearchedMountain,
-    SearchedMountainResult,
+    MountainBoxSearchQuery, MountainPolygonSearchQuery, MountainSearchQuery, MountainSuggestQuery,
+    MountainVersionCondition, MountainWriteRequest, SearchedBoxMountainResult, SearchedMountain,
+    SearchedMountainResult, SuggestedMountain,
 };
+use crate::usecase::tag::TagCache;
 use mountix_adapter::modules::RepositoriesModuleExt;
 use mountix_kernel::model::mountain::{
-    MountainBoxSearchCondition, MountainFindException, MountainGetException,
-    MountainSearchCondition,
+    Mountain, MountainCursor, MountainCursorDirection, MountainData, MountainFindException,
+    MountainGetException, MountainPrefecture, MountainSuggestCondition, MountainWriteException,
 };
-use mountix_kernel::model::ErrorCode;
+use mountix_kernel::model::tag::TagRegistry;
+use mountix_kernel::model::{ErrorCode, Id};
 use mountix_kernel::repository::mountain::MountainRepository;
 use num::FromPrimitive;
 use std::sync::Arc;
 
+const ERR_MESSAGE_NOT_FOUND: &str = "山岳情報が見つかりませんでした。";
+const ERR_MESSAGE_PREFECTURE_NOT_FOUND: &str = "都道府県が見つかりませんでした。";
+const ERR_MESSAGE_PRECONDITION_REQUIRED: &str = "If-Match ヘッダーにバージョンを指定してください。";
+const ERR_MESSAGE_VERSION_CONFLICT: &str =
+    "山岳情報が他の更新と競合しました。最新の山岳情報を取得してから再度実行してください。";
+
 pub struct MountainUseCase<R: RepositoriesModuleExt> {
     repositories: Arc<R>,
+    tags: Arc<TagCache>,
 }
 
 impl<R: RepositoriesModuleExt> MountainUseCase<R> {
-    pub fn new(repositories: Arc<R>) -> Self {
-        Self { repositories }
+    pub fn new(repositories: Arc<R>, tags: Arc<TagCache>) -> Self {
+        Self { repositories, tags }
+    }
+
+    async fn tag_registry(&self) -> anyhow::Result<Arc<TagRegistry>> {
+        self.tags.get(self.repositories.tag_repository()).await
     }
 
     pub async fn get(&self, id: String) -> Result<Option<SearchedMountain>, MountainGetException> {
@@ -38,30 +52,77 @@ impl<R: RepositoriesModuleExt> MountainUseCase<R> {
         &self,
         search_query: MountainSearchQuery,
     ) -> Result<SearchedMountainResult, MountainFindException> {
-        match MountainSearchCondition::try_from(search_query) {
+        let tags = self
+            .tag_registry()
+            .await
+            .map_err(|_| MountainFindException::new_with_error_code(ErrorCode::ServerError))?;
+        match search_query.into_condition(&tags) {
             Ok(condition) => {
                 let offset = condition.skip;
                 let condition_limit = condition.limit;
+                let cursor = condition.cursor.clone();
+                let sort = condition.sort.clone();
+                let name = condition.name.clone();
 
-                let mut total = 0u64;
-                if let Ok(count) = self
-                    .repositories
-                    .mountain_repository()
-                    .get_count(condition.clone())
-                    .await
-                {
-                    total = count;
-                }
+                // 1 件多く取得し、カーソルの先に続きがあるかを判定する
+                let page_size = condition_limit.filter(|limit| *limit > 0);
+                let mut find_condition = condition;
+                find_condition.limit = page_size.map(|limit| limit + 1).or(find_condition.limit);
 
                 match self
                     .repositories
                     .mountain_repository()
-                    .find(condition)
+                    .find(find_condition)
                     .await
                 {
-                    Ok(mountains) => {
-                        let searched_mountains: Vec<SearchedMountain> =
-                            mountains.into_iter().map(|m| m.into()).collect();
+                    Ok(page) => {
+                        let total = page.total;
+                        let facets = page.facets.into_iter().map(|f| f.into()).collect();
+                        let mut mountains = page.mountains;
+                        let is_prev = cursor
+                            .as_ref()
+                            .is_some_and(|c| c.direction == MountainCursorDirection::Prev);
+                        let has_more =
+                            page_size.is_some_and(|page_size| mountains.len() as i64 > page_size);
+                        if has_more {
+                            if is_prev {
+                                mountains.remove(0);
+                            } else {
+                                mountains.pop();
+                            }
+                        }
+
+                        let (has_prev, has_next) = if is_prev {
+                            (has_more, true)
+                        } else {
+                            (cursor.is_some() || offset > 0, has_more)
+                        };
+                        // 関連度順で並び替える場合はカーソルを返さず offset で取得範囲を指定する
+                        let next_cursor = mountains
+                            .last()
+                            .filter(|_| has_next)
+                            .and_then(|m| {
+                                MountainCursor::new(MountainCursorDirection::Next, &sort, m)
+                            })
+                            .map(|cursor| cursor.encode());
+                        let prev_cursor = mountains
+                            .first()
+                            .filter(|_| has_prev)
+                            .and_then(|m| {
+                                MountainCursor::new(MountainCursorDirection::Prev, &sort, m)
+                            })
+                            .map(|cursor| cursor.encode());
+
+                        let mut relevance = page.relevance;
+                        let searched_mountains: Vec<SearchedMountain> = mountains
+                            .into_iter()
+                            .map(|m| {
+                                let r = relevance.remove(&m.id.value);
+                                SearchedMountain::from(m)
+                                    .with_name_match(name.as_ref())
+                                    .with_relevance(r)
+                            })
+                            .collect();
 
                         let mut limit: Option<u64> = None;
                         if let Some(limit_value) = condition_limit {
@@ -75,6 +136,9 @@ impl<R: RepositoriesModuleExt> MountainUseCase<R> {
                             total,
                             offset,
                             limit,
+                            next_cursor,
+                            prev_cursor,
+                            facets,
                         })
                     }
                     Err(_) => Err(MountainFindException::new_with_error_code(
@@ -89,49 +153,307 @@ impl<R: RepositoriesModuleExt> MountainUseCase<R> {
         }
     }
 
+    /// Finds mountains in the prefecture specified by the path parameter
+    ///
+    /// パスパラメータで指定された都道府県の山岳情報を検索します
+    pub async fn find_by_prefecture(
+        &self,
+        prefecture: String,
+        mut search_query: MountainSearchQuery,
+    ) -> Result<SearchedMountainResult, MountainFindException> {
+        if MountainPrefecture::try_from(prefecture.clone()).is_err() {
+            return Err(MountainFindException::new(
+                ErrorCode::NotFound,
+                vec![ERR_MESSAGE_PREFECTURE_NOT_FOUND.to_string()],
+            ));
+        }
+
+        search_query.prefecture = Some(prefecture);
+        self.find(search_query).await
+    }
+
     pub async fn find_box(
         &self,
         search_query: MountainBoxSearchQuery,
     ) -> Result<SearchedBoxMountainResult, MountainFindException> {
-        match MountainBoxSearchCondition::try_from(search_query) {
-            Ok(condition) => {
-                match self
-                    .repositories
-                    .mountain_repository()
-                    .find_box(condition)
-                    .await
-                {
-                    Ok(mountains) => {
-                        let searched_mountains: Vec<SearchedMountain> =
-                            mountains.into_iter().map(|m| m.into()).collect();
-                        let total = searched_mountains.len() as u64;
+        let tags = self
+            .tag_registry()
+            .await
+            .map_err(|_| MountainFindException::new_with_error_code(ErrorCode::ServerError))?;
+        let condition = search_query
+            .into_condition(&tags)
+            .map_err(|error_messages| {
+                MountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
 
-                        Ok(SearchedBoxMountainResult {
-                            mountains: searched_mountains,
-                            total,
-                        })
-                    }
-                    Err(_) => Err(MountainFindException::new_with_error_code(
-                        ErrorCode::ServerError,
-                    )),
-                }
+        let offset = condition.skip;
+        let limit = condition.limit.and_then(|limit| u64::try_from(limit).ok());
+        let name = condition.name.clone();
+
+        let page = self
+            .repositories
+            .mountain_repository()
+            .find_box(condition)
+            .await
+            .map_err(|_| MountainFindException::new_with_error_code(ErrorCode::ServerError))?;
+
+        let searched_mountains: Vec<SearchedMountain> = page
+            .mountains
+            .into_iter()
+            .map(|m| SearchedMountain::from(m).with_name_match(name.as_ref()))
+            .collect();
+
+        Ok(SearchedBoxMountainResult {
+            mountains: searched_mountains,
+            total: page.total,
+            offset,
+            limit,
+            facets: page.facets.into_iter().map(|f| f.into()).collect(),
+        })
+    }
+
+    /// Finds mountains inside the GeoJSON polygon
+    ///
+    /// GeoJSON のポリゴンの内側にある山岳情報を検索します
+    pub async fn find_polygon(
+        &self,
+        search_query: MountainPolygonSearchQuery,
+    ) -> Result<SearchedBoxMountainResult, MountainFindException> {
+        let tags = self
+            .tag_registry()
+            .await
+            .map_err(|_| MountainFindException::new_with_error_code(ErrorCode::ServerError))?;
+        let condition = search_query
+            .into_condition(&tags)
+            .map_err(|error_messages| {
+                MountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
+
+        let offset = condition.skip;
+        let limit = condition.limit.and_then(|limit| u64::try_from(limit).ok());
+        let name = condition.name.clone();
+
+        let page = self
+            .repositories
+            .mountain_repository()
+            .find_polygon(condition)
+            .await
+            .map_err(|_| MountainFindException::new_with_error_code(ErrorCode::ServerError))?;
+
+        let searched_mountains: Vec<SearchedMountain> = page
+            .mountains
+            .into_iter()
+            .map(|m| SearchedMountain::from(m).with_name_match(name.as_ref()))
+            .collect();
+
+        Ok(SearchedBoxMountainResult {
+            mountains: searched_mountains,
+            total: page.total,
+            offset,
+            limit,
+            facets: vec![],
+        })
+    }
+
+    /// Suggests mountains whose names or aliases start with the query
+    ///
+    /// 山名または別名が入力値で始まる山岳を候補として返します
+    pub async fn suggest(
+        &self,
+        search_query: MountainSuggestQuery,
+    ) -> Result<Vec<SuggestedMountain>, MountainFindException> {
+        let condition =
+            MountainSuggestCondition::try_from(search_query).map_err(|error_messages| {
+                MountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
+
+        let mountains = self
+            .repositories
+            .mountain_repository()
+            .suggest(condition)
+            .await
+            .map_err(|_| MountainFindException::new_with_error_code(ErrorCode::ServerError))?;
+
+        Ok(mountains.into_iter().map(SuggestedMountain::from).collect())
+    }
+
+    pub async fn create(
+        &self,
+        request: MountainWriteRequest,
+    ) -> Result<SearchedMountain, MountainWriteException> {
+        let tags = self
+            .tag_registry()
+            .await
+            .map_err(|_| MountainWriteException::new_with_error_code(ErrorCode::ServerError))?;
+        let data = MountainData::try_from(request)
+            .and_then(|data| data.validate(&tags).map(|_| data))
+            .map_err(|messages| {
+                MountainWriteException::new(ErrorCode::InvalidRequestBody, messages)
+            })?;
+
+        match self.repositories.mountain_repository().create(data).await {
+            Ok(mountain) => Ok(mountain.into()),
+            Err(_) => Err(MountainWriteException::new_with_error_code(
+                ErrorCode::ServerError,
+            )),
+        }
+    }
+
+    /// Replaces all fields of the mountain
+    ///
+    /// 山岳情報を置き換えます
+    pub async fn update(
+        &self,
+        id: String,
+        request: MountainWriteRequest,
+        if_match: Option<String>,
+    ) -> Result<SearchedMountain, MountainWriteException> {
+        let (id, version_condition) = Self::parse_write_target(id, if_match)?;
+        let data = MountainData::try_from(request).map_err(|messages| {
+            MountainWriteException::new(ErrorCode::InvalidRequestBody, messages)
+        })?;
+
+        let mountain = self.get_for_write(id).await?;
+        self.replace(mountain.id, mountain.version, data, version_condition)
+            .await
+    }
+
+    /// Updates only the specified fields of the mountain
+    ///
+    /// 指定された項目のみ山岳情報を更新します
+    pub async fn patch(
+        &self,
+        id: String,
+        request: MountainWriteRequest,
+        if_match: Option<String>,
+    ) -> Result<SearchedMountain, MountainWriteException> {
+        let (id, version_condition) = Self::parse_write_target(id, if_match)?;
+
+        let mountain = self.get_for_write(id).await?;
+        let (id, version) = (Id::new(mountain.id.value), mountain.version);
+        let data = request.merge(mountain).map_err(|messages| {
+            MountainWriteException::new(ErrorCode::InvalidRequestBody, messages)
+        })?;
+        self.replace(id, version, data, version_condition).await
+    }
+
+    pub async fn delete(
+        &self,
+        id: String,
+        if_match: Option<String>,
+    ) -> Result<(), MountainWriteException> {
+        let (id, version_condition) = Self::parse_write_target(id, if_match)?;
+
+        let mountain = self.get_for_write(id).await?;
+        if !version_condition.matches(mountain.version) {
+            return Err(version_conflict());
+        }
+
+        match self
+            .repositories
+            .mountain_repository()
+            .delete(mountain.id, mountain.version)
+            .await
+        {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(version_conflict()),
+            Err(_) => Err(MountainWriteException::new_with_error_code(
+                ErrorCode::ServerError,
+            )),
+        }
+    }
+
+    fn parse_write_target(
+        id: String,
+        if_match: Option<String>,
+    ) -> Result<(Id<Mountain>, MountainVersionCondition), MountainWriteException> {
+        let id: Id<Mountain> = id.try_into().map_err(|error_code| {
+            MountainWriteException::new(error_code, vec![ERR_MESSAGE_NOT_FOUND.to_string()])
+        })?;
+
+        let version_condition = match if_match {
+            Some(value) => {
+                MountainVersionCondition::try_from(value).map_err(|_| version_conflict())?
             }
-            Err(error_messages) => Err(MountainFindException::new(
-                ErrorCode::InvalidQueryParam,
-                error_messages,
+            None => {
+                return Err(MountainWriteException::new(
+                    ErrorCode::PreconditionRequired,
+                    vec![ERR_MESSAGE_PRECONDITION_REQUIRED.to_string()],
+                ))
+            }
+        };
+
+        Ok((id, version_condition))
+    }
+
+    async fn get_for_write(&self, id: Id<Mountain>) -> Result<Mountain, MountainWriteException> {
+        match self.repositories.mountain_repository().get(id).await {
+            Ok(Some(mountain)) => Ok(mountain),
+            Ok(None) => Err(MountainWriteException::new(
+                ErrorCode::NotFound,
+                vec![ERR_MESSAGE_NOT_FOUND.to_string()],
+            )),
+            Err(_) => Err(MountainWriteException::new_with_error_code(
+                ErrorCode::ServerError,
+            )),
+        }
+    }
+
+    async fn replace(
+        &self,
+        id: Id<Mountain>,
+        version: u32,
+        data: MountainData,
+        version_condition: MountainVersionCondition,
+    ) -> Result<SearchedMountain, MountainWriteException> {
+        if !version_condition.matches(version) {
+            return Err(version_conflict());
+        }
+        let tags = self
+            .tag_registry()
+            .await
+            .map_err(|_| MountainWriteException::new_with_error_code(ErrorCode::ServerError))?;
+        data.validate(&tags).map_err(|messages| {
+            MountainWriteException::new(ErrorCode::InvalidRequestBody, messages)
+        })?;
+
+        match self
+            .repositories
+            .mountain_repository()
+            .update(id, data, version)
+            .await
+        {
+            Ok(Some(mountain)) => Ok(mountain.into()),
+            Ok(None) => Err(version_conflict()),
+            Err(_) => Err(MountainWriteException::new_with_error_code(
+                ErrorCode::ServerError,
             )),
         }
     }
 }
 
+fn version_conflict() -> MountainWriteException {
+    MountainWriteException::new(
+        ErrorCode::VersionConflict,
+        vec![ERR_MESSAGE_VERSION_CONFLICT.to_string()],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::mountain::{MountainBoxSearchQuery, MountainSearchQuery};
+    use crate::model::mountain::{
+        MountainAliasWriteRequest, MountainBoxSearchQuery, MountainPolygonSearchQuery,
+        MountainSearchQuery, MountainSuggestQuery, MountainWriteRequest,
+    };
     use mockall::mock;
     use mountix_kernel::model::mountain::{
-        Mountain, MountainBoxSearchCondition, MountainLocation, MountainSearchCondition,
+        Mountain, MountainAlias, MountainAliasType, MountainBoxSearchCondition, MountainFacet,
+        MountainFacetCount, MountainFacetKey, MountainHighlight, MountainHighlightSpan,
+        MountainLocation, MountainPage, MountainRelevance, MountainSearchCondition,
+        MountainSearchField, MountainSort, MountainSortKey, MountainValuesMode,
     };
+    use mountix_kernel::model::mountain_filter::MountainFilterExpr;
     use mountix_kernel::model::{ErrorCode, Id};
     use mountix_kernel::repository::mountain::MountainRepository;
     use std::sync::Arc;
@@ -143,8 +465,13 @@ mod tests {
         impl MountainRepository for TestMountainRepository {
             async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>>;
             async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64>;
-            async fn find(&self, search_condition: MountainSearchCondition) -> anyhow::Result<Vec<Mountain>>;
-            async fn find_box(&self, search_condition: MountainBoxSearchCondition) -> anyhow::Result<Vec<Mountain>>;
+            async fn find(&self, search_condition: MountainSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_box(&self, search_condition: MountainBoxSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_polygon(&self, search_condition: mountix_kernel::model::mountain::MountainPolygonSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn suggest(&self, search_condition: mountix_kernel::model::mountain::MountainSuggestCondition) -> anyhow::Result<Vec<mountix_kernel::model::mountain::MountainSuggestion>>;
+            async fn create(&self, data: mountix_kernel::model::mountain::MountainData) -> anyhow::Result<Mountain>;
+            async fn update(&self, id: Id<Mountain>, data: mountix_kernel::model::mountain::MountainData, version: u32) -> anyhow::Result<Option<Mountain>>;
+            async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool>;
         }
     }
 
@@ -153,18 +480,57 @@ mod tests {
 
         #[async_trait::async_trait]
         impl mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository for TestSurroundingMountainRepository {
+            async fn get_count(&self, condition: mountix_kernel::model::surrounding_mountain::SurroundingMountainSearchCondition) -> anyhow::Result<u64>;
             async fn find(&self, condition: mountix_kernel::model::surrounding_mountain::SurroundingMountainSearchCondition) -> anyhow::Result<Vec<mountix_kernel::model::surrounding_mountain::SurroundingMountain>>;
+            async fn find_nearby(&self, condition: mountix_kernel::model::surrounding_mountain::NearbyMountainSearchCondition) -> anyhow::Result<Vec<mountix_kernel::model::surrounding_mountain::SurroundingMountain>>;
+            async fn find_nearest(&self, condition: mountix_kernel::model::surrounding_mountain::NearestMountainSearchCondition) -> anyhow::Result<Vec<mountix_kernel::model::surrounding_mountain::SurroundingMountain>>;
+            async fn find_corridor(&self, condition: mountix_kernel::model::surrounding_mountain::CorridorMountainSearchCondition) -> anyhow::Result<Vec<mountix_kernel::model::surrounding_mountain::SurroundingMountain>>;
+        }
+    }
+
+    mock! {
+        TestTagRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::tag::TagRepository for TestTagRepository {
+            async fn get(&self, id: Id<mountix_kernel::model::tag::Tag>) -> anyhow::Result<Option<mountix_kernel::model::tag::Tag>>;
+            async fn find(&self) -> anyhow::Result<Vec<mountix_kernel::model::tag::Tag>>;
+            async fn count_mountains(&self) -> anyhow::Result<std::collections::BTreeMap<String, u64>>;
+        }
+    }
+
+    mock! {
+        TestPrefectureRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::prefecture::PrefectureRepository for TestPrefectureRepository {
+            async fn find_stats(&self) -> anyhow::Result<Vec<mountix_kernel::model::prefecture::PrefectureMountainStats>>;
+        }
+    }
+
+    mock! {
+        TestAreaRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::area::AreaRepository for TestAreaRepository {
+            async fn find(&self) -> anyhow::Result<Vec<mountix_kernel::model::area::Area>>;
         }
     }
 
     struct MockRepositoriesModule {
         mountain_repository: MockTestMountainRepository,
         surrounding_mountain_repository: MockTestSurroundingMountainRepository,
+        tag_repository: MockTestTagRepository,
+        prefecture_repository: MockTestPrefectureRepository,
+        area_repository: MockTestAreaRepository,
     }
 
     impl RepositoriesModuleExt for MockRepositoriesModule {
         type MountainRepo = MockTestMountainRepository;
         type SurroundingMountainRepo = MockTestSurroundingMountainRepository;
+        type TagRepo = MockTestTagRepository;
+        type PrefectureRepo = MockTestPrefectureRepository;
+        type AreaRepo = MockTestAreaRepository;
 
         fn mountain_repository(&self) -> &Self::MountainRepo {
             &self.mountain_repository
@@ -173,8 +539,41 @@ mod tests {
         fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo {
             &self.surrounding_mountain_repository
         }
+
+        fn tag_repository(&self) -> &Self::TagRepo {
+            &self.tag_repository
+        }
+
+        fn prefecture_repository(&self) -> &Self::PrefectureRepo {
+            &self.prefecture_repository
+        }
+
+        fn area_repository(&self) -> &Self::AreaRepo {
+            &self.area_repository
+        }
     }
 
+    /// Returns the tag repository mock that returns the tags in `migrations/data/mountix_db-tags.json`
+    fn create_tag_repository() -> MockTestTagRepository {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        mock_tag_repo.expect_find().returning(|| {
+            Ok(vec![
+                mountix_kernel::model::tag::Tag::new(
+                    Id::new(1),
+                    "百名山".to_string(),
+                    "深田久弥が選定した日本百名山".to_string(),
+                    "深田久弥『日本百名山』".to_string(),
+                ),
+                mountix_kernel::model::tag::Tag::new(
+                    Id::new(2),
+                    "二百名山".to_string(),
+                    "深田クラブが選定した日本二百名山".to_string(),
+                    "深田クラブ『日本二百名山』".to_string(),
+                ),
+            ])
+        });
+        mock_tag_repo
+    }
     fn create_test_mountain() -> Mountain {
         let id = Id::new(1);
         let location =
@@ -187,10 +586,20 @@ mod tests {
             elevation: 3776,
             location,
             tags: vec!["百名山".to_string()],
+            aliases: vec![],
         };
         Mountain::new(id, data)
     }
 
+    fn create_test_page(total: u64) -> MountainPage {
+        MountainPage {
+            mountains: vec![create_test_mountain()],
+            total,
+            facets: vec![],
+            relevance: Default::default(),
+        }
+    }
+
     #[tokio::test]
     async fn test_mountain_use_case_get_success() {
         let mut mock_repo = MockTestMountainRepository::new();
@@ -205,9 +614,12 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let result = use_case.get("1".to_string()).await;
 
         assert!(result.is_ok());
@@ -232,9 +644,12 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let result = use_case.get("999".to_string()).await;
 
         assert!(result.is_ok());
@@ -248,9 +663,12 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let result = use_case.get("invalid".to_string()).await;
 
         assert!(result.is_err());
@@ -272,9 +690,12 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let result = use_case.get("1".to_string()).await;
 
         assert!(result.is_err());
@@ -285,25 +706,38 @@ mod tests {
     #[tokio::test]
     async fn test_mountain_use_case_find_success() {
         let mut mock_repo = MockTestMountainRepository::new();
-        mock_repo.expect_get_count().times(1).returning(|_| Ok(1));
         mock_repo
             .expect_find()
             .times(1)
-            .returning(|_| Ok(vec![create_test_mountain()]));
+            .returning(|_| Ok(create_test_page(1)));
 
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = MountainSearchQuery {
             name: None,
+            match_type: None,
             prefecture: None,
+            prefecture_mode: None,
             tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
             offset: None,
             limit: None,
             sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
         };
         let result = use_case.find(search_query).await;
 
@@ -320,16 +754,30 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = MountainSearchQuery {
             name: None,
+            match_type: None,
             prefecture: Some("invalid".to_string()),
+            prefecture_mode: None,
             tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
             offset: None,
             limit: None,
             sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
         };
         let result = use_case.find(search_query).await;
 
@@ -338,10 +786,228 @@ mod tests {
         assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
     }
 
+    #[tokio::test]
+    async fn test_mountain_use_case_find_by_prefectures_and_tags() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find()
+            .withf(|condition| {
+                let prefectures: Vec<&str> = condition
+                    .prefecture
+                    .values
+                    .iter()
+                    .map(|p| p.name.as_str())
+                    .collect();
+                let tags: Vec<&str> = condition
+                    .tag
+                    .values
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect();
+                prefectures == vec!["長野県", "富山県"]
+                    && condition.prefecture.mode == MountainValuesMode::All
+                    && tags == vec!["百名山", "二百名山"]
+                    && condition.tag.mode == MountainValuesMode::Any
+            })
+            .times(1)
+            .returning(|_| Ok(create_test_page(1)));
+
+        let use_case = create_use_case(mock_repo);
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: Some("長野県, 16".to_string()),
+            prefecture_mode: Some("all".to_string()),
+            tag: Some("1,2".to_string()),
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        assert!(use_case.find(search_query).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_with_invalid_values_mode() {
+        let use_case = create_use_case(MockTestMountainRepository::new());
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: Some("長野県,invalid".to_string()),
+            prefecture_mode: Some("both".to_string()),
+            tag: Some("1".to_string()),
+            tag_mode: Some("none".to_string()),
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let error = use_case.find(search_query).await.err().unwrap();
+
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(
+            error.messages,
+            vec![
+                "クエリパラメータ prefecture (都道府県) の値が不正です。",
+                "クエリパラメータ prefecture_mode の値が不正です。",
+                "クエリパラメータ tag_mode の値が不正です。",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_by_filter_expression() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find()
+            .withf(|condition| {
+                matches!(
+                    condition.filter,
+                    Some(MountainFilterExpr::Or(ref exprs)) if exprs.len() == 2
+                )
+            })
+            .times(1)
+            .returning(|_| Ok(create_test_page(1)));
+
+        let use_case = create_use_case(mock_repo);
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: Some("tag:百名山 OR elevation>3000".to_string()),
+        };
+        assert!(use_case.find(search_query).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_with_invalid_filter_expression() {
+        let use_case = create_use_case(MockTestMountainRepository::new());
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: Some("elevation>=high AND tag:三百名山".to_string()),
+        };
+        let error = use_case.find(search_query).await.err().unwrap();
+
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(
+            error.messages,
+            vec![
+                "クエリパラメータ q の 12 文字目: elevation には 0 以上の整数を指定してください。",
+                "クエリパラメータ q の 25 文字目: タグ 三百名山 が見つかりません。",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_by_elevation() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find()
+            .withf(|condition| {
+                condition.elevation.min == Some(3000) && condition.elevation.max.is_none()
+            })
+            .times(1)
+            .returning(|_| Ok(create_test_page(1)));
+
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: Some("3000".to_string()),
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = create_use_case(mock_repo).find(search_query).await.unwrap();
+        assert_eq!(result.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_with_inverted_elevation_range() {
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: Some("2500".to_string()),
+            elevation_max: Some("1500".to_string()),
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = create_use_case(MockTestMountainRepository::new())
+            .find(search_query)
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_mountain_use_case_find_repository_error() {
         let mut mock_repo = MockTestMountainRepository::new();
-        mock_repo.expect_get_count().times(1).returning(|_| Ok(1));
         mock_repo
             .expect_find()
             .times(1)
@@ -350,16 +1016,30 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = MountainSearchQuery {
             name: None,
+            match_type: None,
             prefecture: None,
+            prefecture_mode: None,
             tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
             offset: None,
             limit: None,
             sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
         };
         let result = use_case.find(search_query).await;
 
@@ -369,80 +1049,912 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_mountain_use_case_find_box_success() {
+    async fn test_mountain_use_case_find_with_cursor() {
         let mut mock_repo = MockTestMountainRepository::new();
         mock_repo
-            .expect_find_box()
+            .expect_find()
+            .withf(|condition| {
+                condition.limit == Some(3)
+                    && condition
+                        .cursor
+                        .as_ref()
+                        .is_some_and(|cursor| cursor.id == 1)
+            })
             .times(1)
-            .returning(|_| Ok(vec![create_test_mountain()]));
+            .returning(|_| {
+                let mountains = (2..=4)
+                    .map(|id| {
+                        let mut mountain = create_test_mountain();
+                        mountain.id = Id::new(id);
+                        mountain
+                    })
+                    .collect();
+                Ok(MountainPage {
+                    mountains,
+                    total: 10,
+                    facets: vec![],
+                    relevance: Default::default(),
+                })
+            });
 
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_repo,
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
-        let search_query = MountainBoxSearchQuery {
-            box_coordinates: "(139.0,35.0),(140.0,36.0)".to_string(),
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let cursor = MountainCursor::new(
+            MountainCursorDirection::Next,
+            &MountainSort::default(),
+            &create_test_mountain(),
+        )
+        .unwrap();
+        let search_query = MountainSearchQuery {
             name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
             tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: Some("2".to_string()),
             sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: Some(cursor.encode()),
+            facets: None,
+            q: None,
         };
-        let result = use_case.find_box(search_query).await;
+        let result = use_case.find(search_query).await.unwrap();
 
-        assert!(result.is_ok());
-        let search_result = result.unwrap();
-        assert_eq!(search_result.mountains.len(), 1);
-        assert_eq!(search_result.total, 1);
-        assert_eq!(search_result.mountains[0].name, "富士山");
+        assert_eq!(result.total, 10);
+        let ids: Vec<i32> = result.mountains.iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        let next_cursor = MountainCursor::try_from(result.next_cursor.unwrap()).unwrap();
+        assert_eq!(next_cursor.direction, MountainCursorDirection::Next);
+        assert_eq!(next_cursor.id, 3);
+        let prev_cursor = MountainCursor::try_from(result.prev_cursor.unwrap()).unwrap();
+        assert_eq!(prev_cursor.direction, MountainCursorDirection::Prev);
+        assert_eq!(prev_cursor.id, 2);
     }
 
     #[tokio::test]
-    async fn test_mountain_use_case_find_box_with_invalid_coordinates() {
-        let mock_repo = MockTestMountainRepository::new();
+    async fn test_mountain_use_case_find_with_cursor_and_offset() {
         let mock_module = MockRepositoriesModule {
-            mountain_repository: mock_repo,
+            mountain_repository: MockTestMountainRepository::new(),
             surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
-        let search_query = MountainBoxSearchQuery {
-            box_coordinates: "invalid_format".to_string(),
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let cursor = MountainCursor::new(
+            MountainCursorDirection::Next,
+            &MountainSort::default(),
+            &create_test_mountain(),
+        )
+        .unwrap();
+        let search_query = MountainSearchQuery {
             name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
             tag: None,
-            sort: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: Some("10".to_string()),
+            limit: None,
+            sort: Some("elevation.desc".to_string()),
+            latitude: None,
+            longitude: None,
+            cursor: Some(cursor.encode()),
+            facets: None,
+            q: None,
         };
-        let result = use_case.find_box(search_query).await;
+        let result = use_case.find(search_query).await;
 
-        assert!(result.is_err());
-        let error = result.unwrap_err();
+        let error = result.err().unwrap();
         assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 2);
     }
 
     #[tokio::test]
-    async fn test_mountain_use_case_find_box_repository_error() {
+    async fn test_mountain_use_case_find_with_facets() {
         let mut mock_repo = MockTestMountainRepository::new();
         mock_repo
-            .expect_find_box()
+            .expect_find()
+            .withf(|condition| {
+                condition.facets == vec![MountainFacetKey::Prefecture, MountainFacetKey::Tag]
+            })
             .times(1)
-            .returning(|_| Err(anyhow::anyhow!("Database error")));
-
-        let mock_module = MockRepositoriesModule {
-            mountain_repository: mock_repo,
-            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
-        };
+            .returning(|_| {
+                let mut page = create_test_page(1);
+                page.facets = vec![MountainFacet {
+                    key: MountainFacetKey::Prefecture,
+                    counts: vec![MountainFacetCount {
+                        value: "静岡県".to_string(),
+                        count: 1,
+                    }],
+                }];
+                Ok(page)
+            });
 
-        let use_case = MountainUseCase::new(Arc::new(mock_module));
-        let search_query = MountainBoxSearchQuery {
-            box_coordinates: "(139.0,35.0),(140.0,36.0)".to_string(),
+        let use_case = create_use_case(mock_repo);
+        let search_query = MountainSearchQuery {
             name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
             tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
             sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: Some("prefecture,tag".to_string()),
+            q: None,
         };
-        let result = use_case.find_box(search_query).await;
+        let result = use_case.find(search_query).await.unwrap();
 
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.error_code, ErrorCode::ServerError);
+        assert_eq!(result.facets.len(), 1);
+        assert_eq!(result.facets[0].key, "prefecture");
+        assert_eq!(result.facets[0].counts[0].value, "静岡県");
+        assert_eq!(result.facets[0].counts[0].count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_with_name_match() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find()
+            .times(1)
+            .returning(|_| Ok(create_test_page(1)));
+
+        let use_case = create_use_case(mock_repo);
+        let search_query = MountainSearchQuery {
+            name: Some("冨士".to_string()),
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = use_case.find(search_query).await.unwrap();
+
+        assert_eq!(result.mountains[0].matched_variant.as_deref(), Some("富士"));
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_with_matched_alias() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo.expect_find().times(1).returning(|_| {
+            let mut page = create_test_page(1);
+            page.mountains[0].aliases = vec![MountainAlias {
+                name: "不二山".to_string(),
+                name_kana: "ふじさん".to_string(),
+                alias_type: MountainAliasType::Historic,
+            }];
+            Ok(page)
+        });
+
+        let use_case = create_use_case(mock_repo);
+        let search_query = MountainSearchQuery {
+            name: Some("不二".to_string()),
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = use_case.find(search_query).await.unwrap();
+
+        assert_eq!(result.mountains[0].matched_variant, None);
+        let alias = result.mountains[0].matched_alias.as_ref().unwrap();
+        assert_eq!(alias.name, "不二山");
+        assert_eq!(alias.alias_type, "historic");
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_sorted_by_relevance() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find()
+            .withf(|condition| condition.sort.contains(MountainSortKey::Relevance))
+            .times(1)
+            .returning(|_| {
+                let mut page = create_test_page(2);
+                page.relevance.insert(
+                    1,
+                    MountainRelevance {
+                        score: 6.5,
+                        highlights: vec![MountainHighlight {
+                            field: MountainSearchField::Name,
+                            value: "富士山".to_string(),
+                            spans: vec![MountainHighlightSpan { start: 0, end: 2 }],
+                        }],
+                    },
+                );
+                Ok(page)
+            });
+
+        let use_case = create_use_case(mock_repo);
+        let search_query = MountainSearchQuery {
+            name: Some("富士".to_string()),
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: Some("1".to_string()),
+            sort: Some("relevance".to_string()),
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = use_case.find(search_query).await.unwrap();
+
+        assert_eq!(result.mountains[0].score, Some(6.5));
+        let highlight = &result.mountains[0].highlights[0];
+        assert_eq!(highlight.field, "name");
+        assert_eq!(highlight.spans, vec![(0, 2)]);
+        // 関連度順はカーソルを返さない
+        assert!(result.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_sorted_by_relevance_without_name() {
+        let use_case = create_use_case(MockTestMountainRepository::new());
+        let search_query = |name: Option<&str>, match_type: Option<&str>| MountainSearchQuery {
+            name: name.map(|name| name.to_string()),
+            match_type: match_type.map(|match_type| match_type.to_string()),
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: Some("relevance".to_string()),
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+
+        for query in [
+            search_query(None, None),
+            search_query(Some("^富士"), Some("regex")),
+        ] {
+            let error = use_case.find(query).await.err().unwrap();
+            assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+            assert_eq!(
+                error.messages,
+                vec!["クエリパラメータ sort に relevance を指定する場合は、name を指定し match に regex 以外を指定してください。"]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_box_success() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find_box()
+            .times(1)
+            .returning(|_| Ok(create_test_page(1)));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = MountainBoxSearchQuery {
+            box_coordinates: "(139.0,35.0),(140.0,36.0)".to_string(),
+            name: None,
+            match_type: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            facets: None,
+        };
+        let result = use_case.find_box(search_query).await;
+
+        assert!(result.is_ok());
+        let search_result = result.unwrap();
+        assert_eq!(search_result.mountains.len(), 1);
+        assert_eq!(search_result.total, 1);
+        assert_eq!(search_result.limit, Some(100));
+        assert_eq!(search_result.mountains[0].name, "富士山");
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_box_with_page() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find_box()
+            .withf(|condition| condition.skip == 200 && condition.limit == Some(50))
+            .times(1)
+            .returning(|_| Ok(create_test_page(250)));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let box_query = |offset: &str, limit: &str| MountainBoxSearchQuery {
+            box_coordinates: "(122.0,20.0),(154.0,46.0)".to_string(),
+            name: None,
+            match_type: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: Some(offset.to_string()),
+            limit: Some(limit.to_string()),
+            sort: None,
+            latitude: None,
+            longitude: None,
+            facets: None,
+        };
+
+        let search_result = use_case.find_box(box_query("200", "50")).await.unwrap();
+        assert_eq!(search_result.total, 250);
+        assert_eq!(search_result.offset, 200);
+        assert_eq!(search_result.limit, Some(50));
+
+        for (offset, limit) in [("0", "101"), ("0", "0"), ("-1", "10")] {
+            let error = use_case
+                .find_box(box_query(offset, limit))
+                .await
+                .unwrap_err();
+            assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_box_with_invalid_coordinates() {
+        let mock_repo = MockTestMountainRepository::new();
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = MountainBoxSearchQuery {
+            box_coordinates: "invalid_format".to_string(),
+            name: None,
+            match_type: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            facets: None,
+        };
+        let result = use_case.find_box(search_query).await;
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_box_repository_error() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find_box()
+            .times(1)
+            .returning(|_| Err(anyhow::anyhow!("Database error")));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = MountainBoxSearchQuery {
+            box_coordinates: "(139.0,35.0),(140.0,36.0)".to_string(),
+            name: None,
+            match_type: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            facets: None,
+        };
+        let result = use_case.find_box(search_query).await;
+
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::ServerError);
+    }
+
+    fn create_polygon_query(ring: Vec<[f64; 2]>) -> MountainPolygonSearchQuery {
+        MountainPolygonSearchQuery {
+            polygons: vec![vec![ring]],
+            name: None,
+            match_type: None,
+            tag: Some("1".to_string()),
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: Some("20".to_string()),
+            limit: Some("10".to_string()),
+            sort: Some("prefecture.asc,elevation.desc".to_string()),
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_polygon() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find_polygon()
+            .withf(|condition| {
+                condition.polygon.polygons[0][0].len() == 4
+                    && condition.tag.values[0].name == "百名山"
+                    && condition.skip == 20
+                    && condition.limit == Some(10)
+                    && condition.sort.conditions.len() == 2
+            })
+            .times(1)
+            .returning(|_| Ok(create_test_page(25)));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let ring = vec![[138.0, 35.0], [139.0, 35.0], [139.0, 36.0], [138.0, 35.0]];
+        let result = use_case
+            .find_polygon(create_polygon_query(ring))
+            .await
+            .unwrap();
+        assert_eq!(result.total, 25);
+        assert_eq!(result.offset, 20);
+        assert_eq!(result.limit, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_polygon_invalid_geometry() {
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        // 閉じていないリングと不正なソートのエラーをまとめて返す
+        let ring = vec![[138.0, 35.0], [139.0, 35.0], [139.0, 36.0], [138.0, 36.0]];
+        let mut search_query = create_polygon_query(ring);
+        search_query.sort = Some("invalid".to_string());
+        let error = use_case.find_polygon(search_query).await.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_polygon_invalid_params() {
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case = MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        // 範囲検索と同様に一致方法、limit の上限、関連度順を検証する
+        let ring = vec![[138.0, 35.0], [139.0, 35.0], [139.0, 36.0], [138.0, 35.0]];
+        let mut search_query = create_polygon_query(ring);
+        search_query.name = Some("富士".to_string());
+        search_query.match_type = Some("invalid".to_string());
+        search_query.limit = Some("0".to_string());
+        search_query.sort = Some("relevance".to_string());
+        let error = use_case.find_polygon(search_query).await.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 3);
+    }
+
+    fn create_write_request() -> MountainWriteRequest {
+        MountainWriteRequest {
+            name: Some("富士山".to_string()),
+            name_kana: Some("ふじさん".to_string()),
+            area: Some("関東地方".to_string()),
+            prefectures: Some(vec!["静岡県".to_string(), "山梨県".to_string()]),
+            elevation: Some(3776),
+            latitude: Some(35.360556),
+            longitude: Some(138.727778),
+            tags: Some(vec!["百名山".to_string()]),
+            aliases: None,
+        }
+    }
+
+    fn create_use_case(
+        mock_repo: MockTestMountainRepository,
+    ) -> MountainUseCase<MockRepositoriesModule> {
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+        MountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()))
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_create_success() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_create()
+            .withf(|data| {
+                data.location.gsi_url == "https://maps.gsi.go.jp/#15/35.360556/138.727778"
+            })
+            .times(1)
+            .returning(|data| Ok(Mountain::new(Id::new(1060), data)));
+
+        let result = create_use_case(mock_repo)
+            .create(create_write_request())
+            .await;
+
+        let mountain = result.unwrap();
+        assert_eq!(mountain.id, 1060);
+        assert_eq!(mountain.version, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_create_invalid_request() {
+        let mock_repo = MockTestMountainRepository::new();
+
+        let mut request = create_write_request();
+        request.elevation = None;
+        request.prefectures = Some(vec!["東京".to_string()]);
+        let result = create_use_case(mock_repo).create(request).await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidRequestBody);
+        assert_eq!(error.messages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_update_success() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain().with_version(2))));
+        mock_repo
+            .expect_update()
+            .withf(|id, data, version| id.value == 1 && data.elevation == 3775 && *version == 2)
+            .times(1)
+            .returning(|id, data, _| Ok(Some(Mountain::new(id, data).with_version(3))));
+
+        let mut request = create_write_request();
+        request.elevation = Some(3775);
+        let result = create_use_case(mock_repo)
+            .update("1".to_string(), request, Some("\"2\"".to_string()))
+            .await;
+
+        let mountain = result.unwrap();
+        assert_eq!(mountain.elevation, 3775);
+        assert_eq!(mountain.version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_update_without_if_match() {
+        let mock_repo = MockTestMountainRepository::new();
+
+        let result = create_use_case(mock_repo)
+            .update("1".to_string(), create_write_request(), None)
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::PreconditionRequired);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_update_version_conflict() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain().with_version(2))));
+        mock_repo.expect_update().times(0);
+
+        let result = create_use_case(mock_repo)
+            .update(
+                "1".to_string(),
+                create_write_request(),
+                Some("W/\"1\"".to_string()),
+            )
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::VersionConflict);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_patch_merges_fields() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain())));
+        mock_repo
+            .expect_update()
+            .withf(|_, data, _| {
+                data.name == "富士山"
+                    && data.location.latitude == 35.36
+                    && data.location.longitude == 138.727778
+                    && data.location.gsi_url == "https://maps.gsi.go.jp/#15/35.36/138.727778"
+            })
+            .times(1)
+            .returning(|id, data, _| Ok(Some(Mountain::new(id, data).with_version(1))));
+
+        let request = MountainWriteRequest {
+            name: None,
+            name_kana: None,
+            area: None,
+            prefectures: None,
+            elevation: None,
+            latitude: Some(35.36),
+            longitude: None,
+            tags: None,
+            aliases: None,
+        };
+        let result = create_use_case(mock_repo)
+            .patch("1".to_string(), request, Some("*".to_string()))
+            .await;
+
+        assert_eq!(result.unwrap().version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_suggest() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_suggest()
+            .withf(|condition| condition.query == "ふじ" && condition.limit == 10)
+            .times(1)
+            .returning(|_| Ok(vec![create_test_mountain().into()]));
+
+        let query = MountainSuggestQuery {
+            q: Some(" ふじ ".to_string()),
+            limit: None,
+        };
+        let result = create_use_case(mock_repo).suggest(query).await.unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "富士山");
+        assert_eq!(result[0].prefectures, vec!["静岡県", "山梨県"]);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_suggest_invalid_query() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo.expect_suggest().never();
+        let use_case = create_use_case(mock_repo);
+
+        let query = MountainSuggestQuery {
+            q: Some("  ".to_string()),
+            limit: Some("21".to_string()),
+        };
+        let error = use_case.suggest(query).await.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 2);
+
+        let query = MountainSuggestQuery {
+            q: None,
+            limit: Some("0".to_string()),
+        };
+        let error = use_case.suggest(query).await.unwrap_err();
+        assert_eq!(error.messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_patch_invalid_alias_type() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain())));
+        mock_repo.expect_update().never();
+
+        let mut request = create_write_request();
+        request.aliases = Some(vec![MountainAliasWriteRequest {
+            name: "不二山".to_string(),
+            name_kana: "ふじさん".to_string(),
+            alias_type: "nickname".to_string(),
+        }]);
+        let result = create_use_case(mock_repo)
+            .patch("1".to_string(), request, Some("*".to_string()))
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidRequestBody);
+        assert_eq!(error.messages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_delete_not_found() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo.expect_get().times(1).returning(|_| Ok(None));
+
+        let result = create_use_case(mock_repo)
+            .delete("999".to_string(), Some("\"0\"".to_string()))
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_delete_concurrent_update() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain())));
+        mock_repo
+            .expect_delete()
+            .times(1)
+            .returning(|_, _| Ok(false));
+
+        let result = create_use_case(mock_repo)
+            .delete("1".to_string(), Some("\"0\"".to_string()))
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::VersionConflict);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_by_prefecture() {
+        let mut mock_repo = MockTestMountainRepository::new();
+        mock_repo
+            .expect_find()
+            .withf(|condition| condition.prefecture.values[0].name == "長野県")
+            .times(1)
+            .returning(|_| Ok(create_test_page(1)));
+
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: Some("1".to_string()),
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = create_use_case(mock_repo)
+            .find_by_prefecture("JP-20".to_string(), search_query)
+            .await;
+
+        assert_eq!(result.unwrap().total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mountain_use_case_find_by_unknown_prefecture() {
+        let search_query = MountainSearchQuery {
+            name: None,
+            match_type: None,
+            prefecture: None,
+            prefecture_mode: None,
+            tag: None,
+            tag_mode: None,
+            area: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
+            latitude: None,
+            longitude: None,
+            cursor: None,
+            facets: None,
+            q: None,
+        };
+        let result = create_use_case(MockTestMountainRepository::new())
+            .find_by_prefecture("Atlantis".to_string(), search_query)
+            .await;
+
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::NotFound);
     }
 }
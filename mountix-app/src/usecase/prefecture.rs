@@ -0,0 +1,208 @@
+use crate::model::prefecture::{SearchedPrefecture, SearchedPrefectureResult};
+use mountix_adapter::modules::RepositoriesModuleExt;
+use mountix_kernel::model::mountain::MountainPrefecture;
+use mountix_kernel::model::prefecture::{PrefectureFindException, PrefectureMountainStats};
+use mountix_kernel::model::ErrorCode;
+use mountix_kernel::repository::prefecture::PrefectureRepository;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct PrefectureUseCase<R: RepositoriesModuleExt> {
+    repositories: Arc<R>,
+}
+
+impl<R: RepositoriesModuleExt> PrefectureUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+
+    pub async fn find(&self) -> Result<SearchedPrefectureResult, PrefectureFindException> {
+        let stats = self
+            .repositories
+            .prefecture_repository()
+            .find_stats()
+            .await
+            .map_err(|_| PrefectureFindException::new_with_error_code(ErrorCode::ServerError))?;
+
+        let mut stats: HashMap<String, PrefectureMountainStats> =
+            stats.into_iter().map(|s| (s.name.clone(), s)).collect();
+
+        // 山岳が存在しない都道府県も件数 0 として返す
+        let prefectures: Vec<SearchedPrefecture> = MountainPrefecture::all()
+            .into_iter()
+            .map(|prefecture| {
+                let prefecture_stats = stats.remove(&prefecture.name);
+                SearchedPrefecture::new(prefecture, prefecture_stats)
+            })
+            .collect();
+
+        let total = prefectures.len() as u64;
+        Ok(SearchedPrefectureResult { prefectures, total })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use mountix_kernel::model::mountain::{
+        Mountain, MountainBoxSearchCondition, MountainData, MountainSearchCondition,
+    };
+    use mountix_kernel::model::prefecture::PrefectureHighestPeak;
+    use mountix_kernel::model::surrounding_mountain::{
+        SurroundingMountain, SurroundingMountainSearchCondition,
+    };
+    use mountix_kernel::model::tag::Tag;
+    use mountix_kernel::model::Id;
+    use mountix_kernel::repository::mountain::MountainRepository;
+    use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
+    use mountix_kernel::repository::tag::TagRepository;
+
+    mock! {
+        TestMountainRepository {}
+
+        #[async_trait::async_trait]
+        impl MountainRepository for TestMountainRepository {
+            async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>>;
+            async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64>;
+            async fn find(&self, search_condition: MountainSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_box(&self, search_condition: MountainBoxSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_polygon(&self, search_condition: mountix_kernel::model::mountain::MountainPolygonSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn suggest(&self, search_condition: mountix_kernel::model::mountain::MountainSuggestCondition) -> anyhow::Result<Vec<mountix_kernel::model::mountain::MountainSuggestion>>;
+            async fn create(&self, data: MountainData) -> anyhow::Result<Mountain>;
+            async fn update(&self, id: Id<Mountain>, data: MountainData, version: u32) -> anyhow::Result<Option<Mountain>>;
+            async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool>;
+        }
+    }
+
+    mock! {
+        TestSurroundingMountainRepository {}
+
+        #[async_trait::async_trait]
+        impl SurroundingMountainRepository for TestSurroundingMountainRepository {
+            async fn get_count(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<u64>;
+            async fn find(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearby(&self, condition: mountix_kernel::model::surrounding_mountain::NearbyMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearest(&self, condition: mountix_kernel::model::surrounding_mountain::NearestMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_corridor(&self, condition: mountix_kernel::model::surrounding_mountain::CorridorMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+        }
+    }
+
+    mock! {
+        TestTagRepository {}
+
+        #[async_trait::async_trait]
+        impl TagRepository for TestTagRepository {
+            async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>>;
+            async fn find(&self) -> anyhow::Result<Vec<Tag>>;
+            async fn count_mountains(&self) -> anyhow::Result<std::collections::BTreeMap<String, u64>>;
+        }
+    }
+
+    mock! {
+        TestPrefectureRepository {}
+
+        #[async_trait::async_trait]
+        impl PrefectureRepository for TestPrefectureRepository {
+            async fn find_stats(&self) -> anyhow::Result<Vec<PrefectureMountainStats>>;
+        }
+    }
+
+    mock! {
+        TestAreaRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::area::AreaRepository for TestAreaRepository {
+            async fn find(&self) -> anyhow::Result<Vec<mountix_kernel::model::area::Area>>;
+        }
+    }
+
+    struct MockRepositoriesModule {
+        mountain_repository: MockTestMountainRepository,
+        surrounding_mountain_repository: MockTestSurroundingMountainRepository,
+        tag_repository: MockTestTagRepository,
+        prefecture_repository: MockTestPrefectureRepository,
+        area_repository: MockTestAreaRepository,
+    }
+
+    impl RepositoriesModuleExt for MockRepositoriesModule {
+        type MountainRepo = MockTestMountainRepository;
+        type SurroundingMountainRepo = MockTestSurroundingMountainRepository;
+        type TagRepo = MockTestTagRepository;
+        type PrefectureRepo = MockTestPrefectureRepository;
+        type AreaRepo = MockTestAreaRepository;
+
+        fn mountain_repository(&self) -> &Self::MountainRepo {
+            &self.mountain_repository
+        }
+
+        fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo {
+            &self.surrounding_mountain_repository
+        }
+
+        fn tag_repository(&self) -> &Self::TagRepo {
+            &self.tag_repository
+        }
+
+        fn prefecture_repository(&self) -> &Self::PrefectureRepo {
+            &self.prefecture_repository
+        }
+
+        fn area_repository(&self) -> &Self::AreaRepo {
+            &self.area_repository
+        }
+    }
+
+    fn create_use_case(
+        prefecture_repository: MockTestPrefectureRepository,
+    ) -> PrefectureUseCase<MockRepositoriesModule> {
+        PrefectureUseCase::new(Arc::new(MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: MockTestTagRepository::new(),
+            prefecture_repository,
+            area_repository: MockTestAreaRepository::new(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_prefecture_use_case_find() {
+        let mut mock_repo = MockTestPrefectureRepository::new();
+        mock_repo.expect_find_stats().times(1).returning(|| {
+            Ok(vec![PrefectureMountainStats {
+                name: "山梨県".to_string(),
+                mountain_count: 31,
+                highest_peak: Some(PrefectureHighestPeak {
+                    id: Id::new(396),
+                    name: "富士山<剣ヶ峯>".to_string(),
+                    elevation: 3776,
+                }),
+            }])
+        });
+
+        let result = create_use_case(mock_repo).find().await.unwrap();
+        assert_eq!(result.total, 47);
+
+        let yamanashi = &result.prefectures[18];
+        assert_eq!(yamanashi.iso_code, "JP-19");
+        assert_eq!(yamanashi.romaji, "Yamanashi");
+        assert_eq!(yamanashi.mountain_count, 31);
+        assert_eq!(yamanashi.highest_peak.as_ref().unwrap().id, 396);
+
+        let hokkaido = &result.prefectures[0];
+        assert_eq!(hokkaido.mountain_count, 0);
+        assert!(hokkaido.highest_peak.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prefecture_use_case_find_repository_error() {
+        let mut mock_repo = MockTestPrefectureRepository::new();
+        mock_repo
+            .expect_find_stats()
+            .times(1)
+            .returning(|| Err(anyhow::anyhow!("Database error")));
+
+        let result = create_use_case(mock_repo).find().await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::ServerError);
+    }
+}
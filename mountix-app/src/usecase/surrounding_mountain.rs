@@ -1,23 +1,41 @@
 use crate::model::surrounding_mountain::{
-    SearchedSurroundingMountain, SearchedSurroundingMountainResult, SurroundingMountainSearchQuery,
+    CorridorMountainSearchQuery, NearbyMountainSearchQuery, NearestMountainSearchQuery,
+    SearchedCorridorMountain, SearchedCorridorMountainResult, SearchedNearbyMountain,
+    SearchedNearbyMountainResult, SearchedNearestMountainResult, SearchedRelativeMountain,
+    SearchedSurroundingMountainResult, SurroundingMountainSearchQuery,
 };
+use crate::usecase::tag::TagCache;
 use mountix_adapter::modules::RepositoriesModuleExt;
+use mountix_kernel::model::geo::GeoPoint;
+use mountix_kernel::model::mountain::Mountain;
 use mountix_kernel::model::surrounding_mountain::{
-    SurroundingMountainFindException, SurroundingMountainSearchCondition,
-    SurroundingMountainSearchDistance,
+    NearestMountainSearchCondition, SurroundingMountainFindException,
 };
+use mountix_kernel::model::tag::TagRegistry;
 use mountix_kernel::model::ErrorCode;
 use mountix_kernel::repository::mountain::MountainRepository;
 use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
 use std::sync::Arc;
 
+const ERR_MESSAGE_NOT_FOUND: &str = "山岳情報が見つかりませんでした。";
+
 pub struct SurroundingMountainUseCase<R: RepositoriesModuleExt> {
     repositories: Arc<R>,
+    tags: Arc<TagCache>,
 }
 
 impl<R: RepositoriesModuleExt> SurroundingMountainUseCase<R> {
-    pub fn new(repositories: Arc<R>) -> Self {
-        Self { repositories }
+    pub fn new(repositories: Arc<R>, tags: Arc<TagCache>) -> Self {
+        Self { repositories, tags }
+    }
+
+    async fn tag_registry(&self) -> Result<Arc<TagRegistry>, SurroundingMountainFindException> {
+        self.tags
+            .get(self.repositories.tag_repository())
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })
     }
 
     pub async fn find(
@@ -28,44 +46,7 @@ impl<R: RepositoriesModuleExt> SurroundingMountainUseCase<R> {
         match id.try_into() {
             Ok(id) => match self.repositories.mountain_repository().get(id).await {
                 Ok(mountain) => match mountain {
-                    Some(mountain) => {
-                        match SurroundingMountainSearchDistance::try_from(search_query) {
-                            Ok(search_distance) => {
-                                let distance = search_distance.0;
-
-                                let condition = SurroundingMountainSearchCondition::new(
-                                    mountain,
-                                    search_distance,
-                                );
-
-                                match self
-                                    .repositories
-                                    .surrounding_mountain_repository()
-                                    .find(condition)
-                                    .await
-                                {
-                                    Ok(mountains) => {
-                                        let searched_mountains: Vec<SearchedSurroundingMountain> =
-                                            mountains.into_iter().map(|m| m.into()).collect();
-
-                                        Ok(SearchedSurroundingMountainResult {
-                                            mountains: searched_mountains,
-                                            distance,
-                                        })
-                                    }
-                                    Err(_) => {
-                                        Err(SurroundingMountainFindException::new_with_error_code(
-                                            ErrorCode::ServerError,
-                                        ))
-                                    }
-                                }
-                            }
-                            Err(error_messages) => Err(SurroundingMountainFindException::new(
-                                ErrorCode::InvalidQueryParam,
-                                error_messages,
-                            )),
-                        }
-                    }
+                    Some(mountain) => self.search_surroundings(mountain, search_query).await,
                     None => Err(SurroundingMountainFindException::new_with_error_code(
                         ErrorCode::ServerError,
                     )),
@@ -79,6 +60,221 @@ impl<R: RepositoriesModuleExt> SurroundingMountainUseCase<R> {
             )),
         }
     }
+
+    async fn search_surroundings(
+        &self,
+        mountain: Mountain,
+        search_query: SurroundingMountainSearchQuery,
+    ) -> Result<SearchedSurroundingMountainResult, SurroundingMountainFindException> {
+        let center = GeoPoint {
+            latitude: mountain.location.latitude,
+            longitude: mountain.location.longitude,
+        };
+        let center_elevation = mountain.elevation;
+
+        let tags = self.tag_registry().await?;
+        let condition = search_query
+            .into_condition(mountain, &tags)
+            .map_err(|error_messages| {
+                SurroundingMountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
+
+        let distance = condition.distance.0;
+        let offset = condition.skip;
+        let limit = condition.limit.and_then(|limit| u64::try_from(limit).ok());
+
+        let total = self
+            .repositories
+            .surrounding_mountain_repository()
+            .get_count(condition.clone())
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })?;
+
+        let mountains = self
+            .repositories
+            .surrounding_mountain_repository()
+            .find(condition)
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })?;
+
+        let searched_mountains: Vec<SearchedRelativeMountain> = mountains
+            .into_iter()
+            .map(|m| SearchedRelativeMountain::new(&center, center_elevation, m))
+            .collect();
+
+        Ok(SearchedSurroundingMountainResult {
+            mountains: searched_mountains,
+            distance,
+            total,
+            offset,
+            limit,
+        })
+    }
+
+    pub async fn find_nearby(
+        &self,
+        search_query: NearbyMountainSearchQuery,
+    ) -> Result<SearchedNearbyMountainResult, SurroundingMountainFindException> {
+        let tags = self.tag_registry().await?;
+        let condition = search_query
+            .into_condition(&tags)
+            .map_err(|error_messages| {
+                SurroundingMountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
+
+        let point = condition.point;
+        let distance = condition.distance.0;
+
+        let mountains = self
+            .repositories
+            .surrounding_mountain_repository()
+            .find_nearby(condition)
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })?;
+
+        let searched_mountains: Vec<SearchedNearbyMountain> = mountains
+            .into_iter()
+            .map(|m| SearchedNearbyMountain::new(&point, m))
+            .collect();
+
+        Ok(SearchedNearbyMountainResult {
+            mountains: searched_mountains,
+            distance,
+        })
+    }
+
+    /// Finds the k nearest mountains to the point specified by the query
+    ///
+    /// クエリで指定された地点に最も近い k 件の山岳を検索します
+    pub async fn find_nearest(
+        &self,
+        search_query: NearestMountainSearchQuery,
+    ) -> Result<SearchedNearestMountainResult, SurroundingMountainFindException> {
+        let tags = self.tag_registry().await?;
+        let condition = search_query
+            .into_condition(None, &tags)
+            .map_err(|error_messages| {
+                SurroundingMountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
+
+        self.search_nearest(condition).await
+    }
+
+    /// Finds the k nearest mountains to the mountain, excluding the mountain itself
+    ///
+    /// 指定された山岳に最も近い k 件の山岳を、その山岳自身を除いて検索します
+    pub async fn find_nearest_to_mountain(
+        &self,
+        id: String,
+        search_query: NearestMountainSearchQuery,
+    ) -> Result<SearchedNearestMountainResult, SurroundingMountainFindException> {
+        let not_found = || {
+            SurroundingMountainFindException::new(
+                ErrorCode::NotFound,
+                vec![ERR_MESSAGE_NOT_FOUND.to_string()],
+            )
+        };
+
+        let id = id.try_into().map_err(|_| not_found())?;
+        let mountain = self
+            .repositories
+            .mountain_repository()
+            .get(id)
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })?
+            .ok_or_else(not_found)?;
+
+        let point = GeoPoint {
+            latitude: mountain.location.latitude,
+            longitude: mountain.location.longitude,
+        };
+        let tags = self.tag_registry().await?;
+        let mut condition =
+            search_query
+                .into_condition(Some(point), &tags)
+                .map_err(|error_messages| {
+                    SurroundingMountainFindException::new(
+                        ErrorCode::InvalidQueryParam,
+                        error_messages,
+                    )
+                })?;
+        condition.exclude_id = Some(mountain.id);
+
+        self.search_nearest(condition).await
+    }
+
+    /// Finds mountains within the buffer distance of the line, ordered by the position along the line
+    ///
+    /// ラインから指定した距離の範囲内にある山岳をライン上の位置の順に検索します
+    pub async fn find_corridor(
+        &self,
+        search_query: CorridorMountainSearchQuery,
+    ) -> Result<SearchedCorridorMountainResult, SurroundingMountainFindException> {
+        let tags = self.tag_registry().await?;
+        let condition = search_query
+            .into_condition(&tags)
+            .map_err(|error_messages| {
+                SurroundingMountainFindException::new(ErrorCode::InvalidQueryParam, error_messages)
+            })?;
+
+        let line = condition.line.clone();
+        let buffer = condition.buffer.0;
+
+        let mountains = self
+            .repositories
+            .surrounding_mountain_repository()
+            .find_corridor(condition)
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })?;
+
+        let searched_mountains: Vec<SearchedCorridorMountain> = mountains
+            .into_iter()
+            .map(|m| SearchedCorridorMountain::new(&line, m))
+            .collect();
+
+        Ok(SearchedCorridorMountainResult {
+            mountains: searched_mountains,
+            buffer,
+            length: line.length().round() as u32,
+        })
+    }
+
+    async fn search_nearest(
+        &self,
+        condition: NearestMountainSearchCondition,
+    ) -> Result<SearchedNearestMountainResult, SurroundingMountainFindException> {
+        let point = condition.point;
+        let k = condition.k;
+
+        let mountains = self
+            .repositories
+            .surrounding_mountain_repository()
+            .find_nearest(condition)
+            .await
+            .map_err(|_| {
+                SurroundingMountainFindException::new_with_error_code(ErrorCode::ServerError)
+            })?;
+
+        let searched_mountains: Vec<SearchedNearbyMountain> = mountains
+            .into_iter()
+            .map(|m| SearchedNearbyMountain::new(&point, m))
+            .collect();
+
+        Ok(SearchedNearestMountainResult {
+            mountains: searched_mountains,
+            k,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -89,6 +285,7 @@ mod tests {
     use mountix_kernel::model::mountain::{Mountain, MountainLocation};
     use mountix_kernel::model::surrounding_mountain::{
         SurroundingMountain, SurroundingMountainLocation, SurroundingMountainSearchCondition,
+        SurroundingMountainSortKey,
     };
     use mountix_kernel::model::{ErrorCode, Id};
     use mountix_kernel::repository::mountain::MountainRepository;
@@ -100,7 +297,11 @@ mod tests {
 
         #[async_trait::async_trait]
         impl SurroundingMountainRepository for TestSurroundingMountainRepository {
+            async fn get_count(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<u64>;
             async fn find(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearby(&self, condition: mountix_kernel::model::surrounding_mountain::NearbyMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearest(&self, condition: mountix_kernel::model::surrounding_mountain::NearestMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_corridor(&self, condition: mountix_kernel::model::surrounding_mountain::CorridorMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
         }
     }
 
@@ -111,19 +312,59 @@ mod tests {
         impl MountainRepository for TestMountainRepository {
             async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>>;
             async fn get_count(&self, search_condition: mountix_kernel::model::mountain::MountainSearchCondition) -> anyhow::Result<u64>;
-            async fn find(&self, search_condition: mountix_kernel::model::mountain::MountainSearchCondition) -> anyhow::Result<Vec<Mountain>>;
-            async fn find_box(&self, search_condition: mountix_kernel::model::mountain::MountainBoxSearchCondition) -> anyhow::Result<Vec<Mountain>>;
+            async fn find(&self, search_condition: mountix_kernel::model::mountain::MountainSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_box(&self, search_condition: mountix_kernel::model::mountain::MountainBoxSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_polygon(&self, search_condition: mountix_kernel::model::mountain::MountainPolygonSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn suggest(&self, search_condition: mountix_kernel::model::mountain::MountainSuggestCondition) -> anyhow::Result<Vec<mountix_kernel::model::mountain::MountainSuggestion>>;
+            async fn create(&self, data: mountix_kernel::model::mountain::MountainData) -> anyhow::Result<Mountain>;
+            async fn update(&self, id: Id<Mountain>, data: mountix_kernel::model::mountain::MountainData, version: u32) -> anyhow::Result<Option<Mountain>>;
+            async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool>;
+        }
+    }
+
+    mock! {
+        TestTagRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::tag::TagRepository for TestTagRepository {
+            async fn get(&self, id: Id<mountix_kernel::model::tag::Tag>) -> anyhow::Result<Option<mountix_kernel::model::tag::Tag>>;
+            async fn find(&self) -> anyhow::Result<Vec<mountix_kernel::model::tag::Tag>>;
+            async fn count_mountains(&self) -> anyhow::Result<std::collections::BTreeMap<String, u64>>;
+        }
+    }
+
+    mock! {
+        TestPrefectureRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::prefecture::PrefectureRepository for TestPrefectureRepository {
+            async fn find_stats(&self) -> anyhow::Result<Vec<mountix_kernel::model::prefecture::PrefectureMountainStats>>;
+        }
+    }
+
+    mock! {
+        TestAreaRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::area::AreaRepository for TestAreaRepository {
+            async fn find(&self) -> anyhow::Result<Vec<mountix_kernel::model::area::Area>>;
         }
     }
 
     struct MockRepositoriesModule {
         mountain_repository: MockTestMountainRepository,
         surrounding_mountain_repository: MockTestSurroundingMountainRepository,
+        tag_repository: MockTestTagRepository,
+        prefecture_repository: MockTestPrefectureRepository,
+        area_repository: MockTestAreaRepository,
     }
 
     impl RepositoriesModuleExt for MockRepositoriesModule {
         type MountainRepo = MockTestMountainRepository;
         type SurroundingMountainRepo = MockTestSurroundingMountainRepository;
+        type TagRepo = MockTestTagRepository;
+        type PrefectureRepo = MockTestPrefectureRepository;
+        type AreaRepo = MockTestAreaRepository;
 
         fn mountain_repository(&self) -> &Self::MountainRepo {
             &self.mountain_repository
@@ -132,8 +373,41 @@ mod tests {
         fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo {
             &self.surrounding_mountain_repository
         }
+
+        fn tag_repository(&self) -> &Self::TagRepo {
+            &self.tag_repository
+        }
+
+        fn prefecture_repository(&self) -> &Self::PrefectureRepo {
+            &self.prefecture_repository
+        }
+
+        fn area_repository(&self) -> &Self::AreaRepo {
+            &self.area_repository
+        }
     }
 
+    /// Returns the tag repository mock that returns the tags in `migrations/data/mountix_db-tags.json`
+    fn create_tag_repository() -> MockTestTagRepository {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        mock_tag_repo.expect_find().returning(|| {
+            Ok(vec![
+                mountix_kernel::model::tag::Tag::new(
+                    Id::new(1),
+                    "百名山".to_string(),
+                    "深田久弥が選定した日本百名山".to_string(),
+                    "深田久弥『日本百名山』".to_string(),
+                ),
+                mountix_kernel::model::tag::Tag::new(
+                    Id::new(2),
+                    "二百名山".to_string(),
+                    "深田クラブが選定した日本二百名山".to_string(),
+                    "深田クラブ『日本二百名山』".to_string(),
+                ),
+            ])
+        });
+        mock_tag_repo
+    }
     fn create_test_mountain() -> Mountain {
         let id = Id::new(1);
         let location =
@@ -146,6 +420,7 @@ mod tests {
             elevation: 3776,
             location,
             tags: vec!["百名山".to_string()],
+            aliases: vec![],
         };
         Mountain::new(id, data)
     }
@@ -181,6 +456,10 @@ mod tests {
             .returning(|_| Ok(Some(create_test_mountain())));
 
         let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_get_count()
+            .times(1)
+            .returning(|_| Ok(1));
         mock_surrounding_repo
             .expect_find()
             .times(1)
@@ -189,11 +468,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: Some("10000".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("1".to_string(), search_query).await;
 
@@ -201,7 +491,164 @@ mod tests {
         let search_result = result.unwrap();
         assert_eq!(search_result.mountains.len(), 1);
         assert_eq!(search_result.distance, 10000);
-        assert_eq!(search_result.mountains[0].name, "周辺の山");
+        assert_eq!(search_result.total, 1);
+        assert_eq!(search_result.offset, 0);
+        assert_eq!(search_result.mountains[0].mountain.name, "周辺の山");
+        assert!(search_result.mountains[0].distance > 0);
+        assert_eq!(search_result.mountains[0].compass_point, "南東");
+        assert_eq!(search_result.mountains[0].elevation_diff, -1276);
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_with_page() {
+        let mut mock_mountain_repo = MockTestMountainRepository::new();
+        mock_mountain_repo
+            .expect_get()
+            .times(2)
+            .returning(|_| Ok(Some(create_test_mountain())));
+
+        let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_get_count()
+            .times(1)
+            .returning(|_| Ok(25));
+        mock_surrounding_repo
+            .expect_find()
+            .withf(|condition| {
+                condition.skip == 20
+                    && condition.limit == Some(10)
+                    && condition.sort.key == SurroundingMountainSortKey::Distance
+                    && condition.tag.as_ref().is_some_and(|tag| tag.id == 2)
+                    && condition
+                        .prefecture
+                        .as_ref()
+                        .is_some_and(|pref| pref.id == 22)
+            })
+            .times(1)
+            .returning(|_| Ok(vec![create_test_surrounding_mountain()]));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_mountain_repo,
+            surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = SurroundingMountainSearchQuery {
+            distance: Some("10000".to_string()),
+            prefecture: Some("静岡".to_string()),
+            tag: Some("2".to_string()),
+            elevation_min: None,
+            elevation_max: None,
+            offset: Some("20".to_string()),
+            limit: Some("10".to_string()),
+            sort: Some("distance.desc".to_string()),
+        };
+        let search_result = use_case.find("1".to_string(), search_query).await.unwrap();
+        assert_eq!(search_result.total, 25);
+        assert_eq!(search_result.offset, 20);
+        assert_eq!(search_result.limit, Some(10));
+
+        let search_query = SurroundingMountainSearchQuery {
+            distance: None,
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: Some("-1".to_string()),
+            limit: None,
+            sort: Some("distance".to_string()),
+        };
+        let error = use_case
+            .find("1".to_string(), search_query)
+            .await
+            .unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_by_elevation() {
+        let mut mock_mountain_repo = MockTestMountainRepository::new();
+        mock_mountain_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain())));
+
+        let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_get_count()
+            .times(1)
+            .returning(|_| Ok(1));
+        mock_surrounding_repo
+            .expect_find()
+            .withf(|condition| {
+                condition.elevation.min == Some(2000) && condition.elevation.max == Some(3000)
+            })
+            .times(1)
+            .returning(|_| Ok(vec![create_test_surrounding_mountain()]));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_mountain_repo,
+            surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = SurroundingMountainSearchQuery {
+            distance: None,
+            prefecture: None,
+            tag: None,
+            elevation_min: Some("2000".to_string()),
+            elevation_max: Some("3000".to_string()),
+            offset: None,
+            limit: None,
+            sort: None,
+        };
+        let result = use_case.find("1".to_string(), search_query).await;
+        assert_eq!(result.unwrap().mountains.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_with_invalid_distance_and_elevation() {
+        let mut mock_mountain_repo = MockTestMountainRepository::new();
+        mock_mountain_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain())));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_mountain_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = SurroundingMountainSearchQuery {
+            distance: Some("invalid".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: Some("3000".to_string()),
+            elevation_max: Some("1000".to_string()),
+            offset: None,
+            limit: None,
+            sort: None,
+        };
+        let error = use_case
+            .find("1".to_string(), search_query)
+            .await
+            .unwrap_err();
+        assert_eq!(error.error_code, ErrorCode::InvalidQueryParam);
+        assert_eq!(error.messages.len(), 2);
     }
 
     #[tokio::test]
@@ -220,11 +667,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: Some("5000".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("999".to_string(), search_query).await;
 
@@ -241,11 +699,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: Some("5000".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("invalid".to_string(), search_query).await;
 
@@ -254,6 +723,223 @@ mod tests {
         assert_eq!(error.error_code, ErrorCode::ServerError);
     }
 
+    fn create_nearby_query(distance: &str) -> NearbyMountainSearchQuery {
+        NearbyMountainSearchQuery {
+            latitude: Some("35.360556".to_string()),
+            longitude: Some("138.727778".to_string()),
+            distance: Some(distance.to_string()),
+            name: None,
+            tag: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_nearby() {
+        let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_find_nearby()
+            .withf(|condition| {
+                condition.distance.0 == 20000 && condition.point.latitude == 35.360556
+            })
+            .times(1)
+            .returning(|_| Ok(vec![create_test_surrounding_mountain()]));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let result = use_case
+            .find_nearby(create_nearby_query("20000"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.distance, 20000);
+        assert_eq!(result.mountains.len(), 1);
+        let nearby = &result.mountains[0];
+        assert_eq!(nearby.mountain.id, 2);
+        assert!(nearby.distance > 0);
+        // 周辺の山は富士山の南東にある
+        assert!((90.0..180.0).contains(&nearby.bearing));
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_nearby_invalid_params() {
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let result = use_case.find_nearby(create_nearby_query("far")).await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::InvalidQueryParam);
+    }
+
+    fn create_nearest_query(k: &str) -> NearestMountainSearchQuery {
+        NearestMountainSearchQuery {
+            latitude: Some("35.360556".to_string()),
+            longitude: Some("138.727778".to_string()),
+            k: Some(k.to_string()),
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_nearest() {
+        let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_find_nearest()
+            .withf(|condition| condition.k == 3 && condition.exclude_id.is_none())
+            .times(1)
+            .returning(|_| Ok(vec![create_test_surrounding_mountain()]));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let result = use_case
+            .find_nearest(create_nearest_query("3"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.k, 3);
+        assert_eq!(result.mountains.len(), 1);
+        assert!(result.mountains[0].distance > 0);
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let result = use_case.find_nearest(create_nearest_query("0")).await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::InvalidQueryParam);
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_nearest_to_mountain() {
+        let mut mock_mountain_repo = MockTestMountainRepository::new();
+        mock_mountain_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(Some(create_test_mountain())));
+
+        let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_find_nearest()
+            .withf(|condition| {
+                condition.exclude_id.as_ref().map(|id| id.value) == Some(1)
+                    && condition.point.latitude == 35.360556
+            })
+            .times(1)
+            .returning(|_| Ok(vec![create_test_surrounding_mountain()]));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_mountain_repo,
+            surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let mut search_query = create_nearest_query("5");
+        search_query.latitude = None;
+        search_query.longitude = None;
+        let result = use_case
+            .find_nearest_to_mountain("1".to_string(), search_query)
+            .await
+            .unwrap();
+        assert_eq!(result.mountains[0].mountain.id, 2);
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_nearest_to_mountain_not_found() {
+        let mut mock_mountain_repo = MockTestMountainRepository::new();
+        mock_mountain_repo
+            .expect_get()
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: mock_mountain_repo,
+            surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let result = use_case
+            .find_nearest_to_mountain("9999".to_string(), create_nearest_query("5"))
+            .await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::NotFound);
+
+        let result = use_case
+            .find_nearest_to_mountain("invalid".to_string(), create_nearest_query("5"))
+            .await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_surrounding_mountain_use_case_find_corridor() {
+        let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_find_corridor()
+            .withf(|condition| condition.buffer.0 == 3000 && condition.line.points.len() == 2)
+            .times(1)
+            .returning(|_| Ok(vec![create_test_surrounding_mountain()]));
+
+        let mock_module = MockRepositoriesModule {
+            mountain_repository: MockTestMountainRepository::new(),
+            surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
+        };
+
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
+        let search_query = CorridorMountainSearchQuery {
+            line: vec![[138.7, 35.3], [138.9, 35.3]],
+            buffer: Some("3000".to_string()),
+            name: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+        };
+        let result = use_case.find_corridor(search_query).await.unwrap();
+
+        assert_eq!(result.buffer, 3000);
+        assert!((18_100..18_300).contains(&result.length));
+        // 周辺の山はラインの中間点にある
+        let corridor = &result.mountains[0];
+        assert!((corridor.along_track as i64 - result.length as i64 / 2).abs() < 10);
+        assert!(corridor.cross_track < 10);
+    }
+
     #[tokio::test]
     async fn test_surrounding_mountain_use_case_find_repository_error() {
         let mut mock_mountain_repo = MockTestMountainRepository::new();
@@ -270,11 +956,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: Some("5000".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("1".to_string(), search_query).await;
 
@@ -295,6 +992,10 @@ mod tests {
             .returning(|_| Ok(Some(create_test_mountain())));
 
         let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_get_count()
+            .times(1)
+            .returning(|_| Ok(1));
         mock_surrounding_repo
             .expect_find()
             .times(1)
@@ -303,11 +1004,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: Some("5000".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("1".to_string(), search_query).await;
 
@@ -328,6 +1040,10 @@ mod tests {
             .returning(|_| Ok(Some(create_test_mountain())));
 
         let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_get_count()
+            .times(1)
+            .returning(|_| Ok(1));
         mock_surrounding_repo
             .expect_find()
             .times(1)
@@ -336,11 +1052,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: None, // Use default distance
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("1".to_string(), search_query).await;
 
@@ -362,6 +1089,10 @@ mod tests {
             .returning(|_| Ok(Some(create_test_mountain())));
 
         let mut mock_surrounding_repo = MockTestSurroundingMountainRepository::new();
+        mock_surrounding_repo
+            .expect_get_count()
+            .times(1)
+            .returning(|_| Ok(0));
         mock_surrounding_repo
             .expect_find()
             .times(1)
@@ -370,11 +1101,22 @@ mod tests {
         let mock_module = MockRepositoriesModule {
             mountain_repository: mock_mountain_repo,
             surrounding_mountain_repository: mock_surrounding_repo,
+            tag_repository: create_tag_repository(),
+            prefecture_repository: MockTestPrefectureRepository::new(),
+            area_repository: MockTestAreaRepository::new(),
         };
 
-        let use_case = SurroundingMountainUseCase::new(Arc::new(mock_module));
+        let use_case =
+            SurroundingMountainUseCase::new(Arc::new(mock_module), Arc::new(TagCache::default()));
         let search_query = SurroundingMountainSearchQuery {
             distance: Some("1000".to_string()),
+            prefecture: None,
+            tag: None,
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: None,
+            sort: None,
         };
         let result = use_case.find("1".to_string(), search_query).await;
 
@@ -0,0 +1,401 @@
+use crate::model::tag::{SearchedTag, SearchedTagResult};
+use mountix_adapter::modules::RepositoriesModuleExt;
+use mountix_kernel::model::mountain::{
+    MountainElevationRange, MountainSearchCondition, MountainSort, MountainValuesCondition,
+};
+use mountix_kernel::model::tag::{Tag, TagFindException, TagGetException, TagRegistry};
+use mountix_kernel::model::ErrorCode;
+use mountix_kernel::repository::mountain::MountainRepository;
+use mountix_kernel::repository::tag::TagRepository;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Period after which the cached tags are reloaded from the repository
+const TAG_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Snapshot of the tag registry shared by the use cases, reloaded from the repository after it expires
+///
+/// ユースケース間で共有するタグレジストリのスナップショットで、有効期限が切れるとリポジトリから再読み込みする
+pub struct TagCache {
+    snapshot: RwLock<Option<(Arc<TagRegistry>, Instant)>>,
+    ttl: Duration,
+}
+
+impl Default for TagCache {
+    fn default() -> Self {
+        Self::new(TAG_CACHE_TTL)
+    }
+}
+
+impl TagCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            snapshot: RwLock::new(None),
+            ttl,
+        }
+    }
+
+    /// Returns the cached registry, reloading it when it has expired
+    ///
+    /// キャッシュしたタグレジストリを返します。有効期限が切れている場合は再読み込みし、
+    /// 再読み込みに失敗した場合は直前のスナップショットを返します
+    pub async fn get<T: TagRepository>(&self, repository: &T) -> anyhow::Result<Arc<TagRegistry>> {
+        let cached = self
+            .snapshot
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        match cached {
+            Some((registry, loaded_at)) if loaded_at.elapsed() < self.ttl => Ok(registry),
+            Some((registry, _)) => Ok(self.reload(repository).await.unwrap_or(registry)),
+            None => self.reload(repository).await,
+        }
+    }
+
+    /// Reloads the registry from the repository, keeping the current snapshot if there are no tags
+    ///
+    /// リポジトリからタグレジストリを再読み込みします。タグが 1 件もない場合は現在のスナップショットを維持します
+    pub async fn reload<T: TagRepository>(
+        &self,
+        repository: &T,
+    ) -> anyhow::Result<Arc<TagRegistry>> {
+        let tags = repository.find().await?;
+        self.install(tags)
+    }
+
+    fn install(&self, tags: Vec<Tag>) -> anyhow::Result<Arc<TagRegistry>> {
+        let registry = Arc::new(TagRegistry::new(tags)?);
+        let mut snapshot = self.snapshot.write().unwrap_or_else(|e| e.into_inner());
+        *snapshot = Some((registry.clone(), Instant::now()));
+        Ok(registry)
+    }
+}
+
+pub struct TagUseCase<R: RepositoriesModuleExt> {
+    repositories: Arc<R>,
+    tags: Arc<TagCache>,
+}
+
+impl<R: RepositoriesModuleExt> TagUseCase<R> {
+    pub fn new(repositories: Arc<R>, tags: Arc<TagCache>) -> Self {
+        Self { repositories, tags }
+    }
+
+    /// Loads the tag collection into the tag cache
+    ///
+    /// タグコレクションをタグのキャッシュに読み込みます
+    pub async fn load_tags(&self) -> anyhow::Result<()> {
+        self.tags
+            .reload(self.repositories.tag_repository())
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn get(&self, id: String) -> Result<Option<SearchedTag>, TagGetException> {
+        let id = id.try_into().map_err(TagGetException::new)?;
+
+        match self.repositories.tag_repository().get(id).await {
+            Ok(Some(tag)) => match self.count_mountains(&tag).await {
+                Ok(count) => Ok(Some(SearchedTag::new(tag, count))),
+                Err(_) => Err(TagGetException::new(ErrorCode::ServerError)),
+            },
+            Ok(None) => Ok(None),
+            Err(_) => Err(TagGetException::new(ErrorCode::ServerError)),
+        }
+    }
+
+    pub async fn find(&self) -> Result<SearchedTagResult, TagFindException> {
+        let tag_repository = self.repositories.tag_repository();
+        let tags = tag_repository
+            .find()
+            .await
+            .map_err(|_| TagFindException::new_with_error_code(ErrorCode::ServerError))?;
+        let counts = tag_repository
+            .count_mountains()
+            .await
+            .map_err(|_| TagFindException::new_with_error_code(ErrorCode::ServerError))?;
+
+        // 一覧を取得したタグでキャッシュを更新する (タグが 1 件もない場合は更新しない)
+        let _ = self.tags.install(tags.clone());
+
+        let searched_tags: Vec<SearchedTag> = tags
+            .into_iter()
+            .map(|tag| {
+                let count = counts.get(&tag.name).copied().unwrap_or(0);
+                SearchedTag::new(tag, count)
+            })
+            .collect();
+
+        let total = searched_tags.len() as u64;
+        Ok(SearchedTagResult {
+            tags: searched_tags,
+            total,
+        })
+    }
+
+    async fn count_mountains(&self, tag: &Tag) -> anyhow::Result<u64> {
+        let condition = MountainSearchCondition {
+            name: None,
+            prefecture: Default::default(),
+            tag: MountainValuesCondition::single(tag.clone().into()),
+            area: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: MountainSort::default(),
+            cursor: None,
+            facets: vec![],
+            filter: None,
+        };
+        self.repositories
+            .mountain_repository()
+            .get_count(condition)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use mountix_kernel::model::mountain::{Mountain, MountainBoxSearchCondition, MountainData};
+    use mountix_kernel::model::surrounding_mountain::{
+        SurroundingMountain, SurroundingMountainSearchCondition,
+    };
+    use mountix_kernel::model::Id;
+    use mountix_kernel::repository::surrounding_mountain::SurroundingMountainRepository;
+    use std::collections::BTreeMap;
+
+    mock! {
+        TestMountainRepository {}
+
+        #[async_trait::async_trait]
+        impl MountainRepository for TestMountainRepository {
+            async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>>;
+            async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64>;
+            async fn find(&self, search_condition: MountainSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_box(&self, search_condition: MountainBoxSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn find_polygon(&self, search_condition: mountix_kernel::model::mountain::MountainPolygonSearchCondition) -> anyhow::Result<mountix_kernel::model::mountain::MountainPage>;
+            async fn suggest(&self, search_condition: mountix_kernel::model::mountain::MountainSuggestCondition) -> anyhow::Result<Vec<mountix_kernel::model::mountain::MountainSuggestion>>;
+            async fn create(&self, data: MountainData) -> anyhow::Result<Mountain>;
+            async fn update(&self, id: Id<Mountain>, data: MountainData, version: u32) -> anyhow::Result<Option<Mountain>>;
+            async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool>;
+        }
+    }
+
+    mock! {
+        TestSurroundingMountainRepository {}
+
+        #[async_trait::async_trait]
+        impl SurroundingMountainRepository for TestSurroundingMountainRepository {
+            async fn get_count(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<u64>;
+            async fn find(&self, condition: SurroundingMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearby(&self, condition: mountix_kernel::model::surrounding_mountain::NearbyMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_nearest(&self, condition: mountix_kernel::model::surrounding_mountain::NearestMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+            async fn find_corridor(&self, condition: mountix_kernel::model::surrounding_mountain::CorridorMountainSearchCondition) -> anyhow::Result<Vec<SurroundingMountain>>;
+        }
+    }
+
+    mock! {
+        TestTagRepository {}
+
+        #[async_trait::async_trait]
+        impl TagRepository for TestTagRepository {
+            async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>>;
+            async fn find(&self) -> anyhow::Result<Vec<Tag>>;
+            async fn count_mountains(&self) -> anyhow::Result<BTreeMap<String, u64>>;
+        }
+    }
+
+    mock! {
+        TestPrefectureRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::prefecture::PrefectureRepository for TestPrefectureRepository {
+            async fn find_stats(&self) -> anyhow::Result<Vec<mountix_kernel::model::prefecture::PrefectureMountainStats>>;
+        }
+    }
+
+    mock! {
+        TestAreaRepository {}
+
+        #[async_trait::async_trait]
+        impl mountix_kernel::repository::area::AreaRepository for TestAreaRepository {
+            async fn find(&self) -> anyhow::Result<Vec<mountix_kernel::model::area::Area>>;
+        }
+    }
+
+    struct MockRepositoriesModule {
+        mountain_repository: MockTestMountainRepository,
+        surrounding_mountain_repository: MockTestSurroundingMountainRepository,
+        tag_repository: MockTestTagRepository,
+        prefecture_repository: MockTestPrefectureRepository,
+        area_repository: MockTestAreaRepository,
+    }
+
+    impl RepositoriesModuleExt for MockRepositoriesModule {
+        type MountainRepo = MockTestMountainRepository;
+        type SurroundingMountainRepo = MockTestSurroundingMountainRepository;
+        type TagRepo = MockTestTagRepository;
+        type PrefectureRepo = MockTestPrefectureRepository;
+        type AreaRepo = MockTestAreaRepository;
+
+        fn mountain_repository(&self) -> &Self::MountainRepo {
+            &self.mountain_repository
+        }
+
+        fn surrounding_mountain_repository(&self) -> &Self::SurroundingMountainRepo {
+            &self.surrounding_mountain_repository
+        }
+
+        fn tag_repository(&self) -> &Self::TagRepo {
+            &self.tag_repository
+        }
+
+        fn prefecture_repository(&self) -> &Self::PrefectureRepo {
+            &self.prefecture_repository
+        }
+
+        fn area_repository(&self) -> &Self::AreaRepo {
+            &self.area_repository
+        }
+    }
+
+    fn create_test_tag(id: i32, name: &str) -> Tag {
+        Tag::new(
+            Id::new(id),
+            name.to_string(),
+            format!("{}の説明", name),
+            "テスト".to_string(),
+        )
+    }
+
+    fn create_use_case(
+        mountain_repository: MockTestMountainRepository,
+        tag_repository: MockTestTagRepository,
+    ) -> TagUseCase<MockRepositoriesModule> {
+        TagUseCase::new(
+            Arc::new(MockRepositoriesModule {
+                mountain_repository,
+                surrounding_mountain_repository: MockTestSurroundingMountainRepository::new(),
+                tag_repository,
+                prefecture_repository: MockTestPrefectureRepository::new(),
+                area_repository: MockTestAreaRepository::new(),
+            }),
+            Arc::new(TagCache::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tag_use_case_find_with_counts() {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        mock_tag_repo.expect_find().times(1).returning(|| {
+            Ok(vec![
+                create_test_tag(1, "百名山"),
+                create_test_tag(2, "二百名山"),
+            ])
+        });
+        // タグごとに件数を数えず、1 回の集計で全タグの件数を取得する
+        mock_tag_repo
+            .expect_count_mountains()
+            .times(1)
+            .returning(|| Ok(BTreeMap::from([("百名山".to_string(), 100)])));
+        let mut mock_mountain_repo = MockTestMountainRepository::new();
+        mock_mountain_repo.expect_get_count().never();
+
+        let result = create_use_case(mock_mountain_repo, mock_tag_repo)
+            .find()
+            .await
+            .unwrap();
+        assert_eq!(result.total, 2);
+        assert_eq!(result.tags[0].mountain_count, 100);
+        assert_eq!(result.tags[1].name, "二百名山");
+        assert_eq!(result.tags[1].mountain_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_tag_use_case_get_not_found() {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        mock_tag_repo.expect_get().times(1).returning(|_| Ok(None));
+
+        let result = create_use_case(MockTestMountainRepository::new(), mock_tag_repo)
+            .get("9".to_string())
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tag_use_case_get_invalid_id() {
+        let result = create_use_case(
+            MockTestMountainRepository::new(),
+            MockTestTagRepository::new(),
+        )
+        .get("invalid".to_string())
+        .await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::InvalidId);
+    }
+
+    #[tokio::test]
+    async fn test_tag_use_case_find_repository_error() {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        mock_tag_repo
+            .expect_find()
+            .times(1)
+            .returning(|| Err(anyhow::anyhow!("Database error")));
+
+        let result = create_use_case(MockTestMountainRepository::new(), mock_tag_repo)
+            .find()
+            .await;
+        assert_eq!(result.unwrap_err().error_code, ErrorCode::ServerError);
+    }
+
+    #[tokio::test]
+    async fn test_tag_cache_reload() {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        let mut sequence = mockall::Sequence::new();
+        mock_tag_repo
+            .expect_find()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|| Ok(vec![create_test_tag(1, "百名山")]));
+        mock_tag_repo
+            .expect_find()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|| {
+                Ok(vec![
+                    create_test_tag(1, "百名山"),
+                    create_test_tag(3, "三百名山"),
+                ])
+            });
+        mock_tag_repo
+            .expect_find()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|| Ok(vec![]));
+
+        // 有効期限が切れるたびにリポジトリから再読み込みする
+        let cache = TagCache::new(Duration::ZERO);
+        let registry = cache.get(&mock_tag_repo).await.unwrap();
+        assert!(registry.find_by_id(3).is_none());
+        let registry = cache.get(&mock_tag_repo).await.unwrap();
+        assert_eq!(registry.find_by_id(3).unwrap().name, "三百名山");
+
+        // タグが 1 件もない場合は直前のスナップショットを使い続ける
+        let registry = cache.get(&mock_tag_repo).await.unwrap();
+        assert!(registry.contains_name("三百名山"));
+    }
+
+    #[tokio::test]
+    async fn test_tag_cache_empty_tags() {
+        let mut mock_tag_repo = MockTestTagRepository::new();
+        mock_tag_repo
+            .expect_find()
+            .times(1)
+            .returning(|| Ok(vec![]));
+
+        let cache = TagCache::default();
+        assert!(cache.get(&mock_tag_repo).await.is_err());
+    }
+}
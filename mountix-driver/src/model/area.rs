@@ -0,0 +1,120 @@
+use crate::model::JsonErrorResponse;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use mountix_app::model::area::{
+    SearchedArea, SearchedAreaBoundingBox, SearchedAreaCoordinates, SearchedAreaResult,
+};
+use serde::Serialize;
+
+/// Area json object
+///
+/// 山域情報
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonArea {
+    pub name: String,
+    pub mountain_count: u64,
+    pub elevation_min: u32,
+    pub elevation_max: u32,
+    pub prefectures: Vec<String>,
+    pub bounding_box: JsonAreaBoundingBox,
+}
+
+/// Bounding box of area json object
+///
+/// 山域の範囲
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonAreaBoundingBox {
+    pub bottom_left: JsonAreaCoordinates,
+    pub upper_right: JsonAreaCoordinates,
+}
+
+/// Coordinates json object
+///
+/// 緯度経度
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonAreaCoordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl From<SearchedArea> for JsonArea {
+    fn from(searched_area: SearchedArea) -> Self {
+        Self {
+            name: searched_area.name,
+            mountain_count: searched_area.mountain_count,
+            elevation_min: searched_area.elevation_min,
+            elevation_max: searched_area.elevation_max,
+            prefectures: searched_area.prefectures,
+            bounding_box: searched_area.bounding_box.into(),
+        }
+    }
+}
+
+impl From<SearchedAreaBoundingBox> for JsonAreaBoundingBox {
+    fn from(bounding_box: SearchedAreaBoundingBox) -> Self {
+        Self {
+            bottom_left: bounding_box.bottom_left.into(),
+            upper_right: bounding_box.upper_right.into(),
+        }
+    }
+}
+
+impl From<SearchedAreaCoordinates> for JsonAreaCoordinates {
+    fn from(coordinates: SearchedAreaCoordinates) -> Self {
+        Self {
+            latitude: coordinates.latitude,
+            longitude: coordinates.longitude,
+        }
+    }
+}
+
+/// Areas response
+///
+/// 山域情報レスポンス
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonAreasResponse {
+    areas: Vec<JsonArea>,
+    total: u64,
+}
+
+impl From<SearchedAreaResult> for JsonAreasResponse {
+    /// Converts to `JsonAreasResponse` from `SearchedAreaResult`
+    ///
+    /// 山域情報検索結果から山域情報レスポンスに変換します
+    fn from(result: SearchedAreaResult) -> Self {
+        let areas = result.areas.into_iter().map(|area| area.into()).collect();
+
+        Self {
+            areas,
+            total: result.total,
+        }
+    }
+}
+
+/// Area search error
+///
+/// 山域情報検索エラー
+pub enum AreaError {
+    ServerError,
+}
+
+impl IntoResponse for AreaError {
+    /// Create error response
+    ///
+    /// 山域情報検索エラー時のレスポンスを生成します
+    fn into_response(self) -> Response {
+        match self {
+            AreaError::ServerError => {
+                let json = JsonErrorResponse::new(vec![
+                    "山域情報を取得中に予期せぬエラーが発生しました。".to_string(),
+                ]);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(json)).into_response()
+            }
+        }
+    }
+}
@@ -103,7 +103,7 @@ mod tests {
 
     #[test]
     fn test_json_endpoint_multiple_resources() {
-        let endpoints = vec![
+        let endpoints = [
             JsonEndpoint::new(
                 "mountains".to_string(),
                 "https://api.com/mountains".to_string(),
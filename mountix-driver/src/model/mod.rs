@@ -1,8 +1,11 @@
 use serde::Serialize;
 
+pub mod area;
 pub mod information;
 pub mod mountain;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
 
 /// Error response struct
 ///
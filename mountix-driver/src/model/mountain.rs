@@ -3,10 +3,15 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use mountix_app::model::mountain::{
-    MountainBoxSearchQuery, MountainSearchQuery, SearchedBoxMountainResult, SearchedMountain,
-    SearchedMountainLocation, SearchedMountainResult,
+    MountainAliasWriteRequest, MountainBoxSearchQuery, MountainPolygonSearchQuery,
+    MountainSearchQuery, MountainSuggestQuery, MountainWriteRequest, SearchedBoxMountainResult,
+    SearchedMountain, SearchedMountainAlias, SearchedMountainFacet, SearchedMountainHighlight,
+    SearchedMountainLocation, SearchedMountainResult, SuggestedMountain,
 };
+use mountix_kernel::model::mountain::MountainWriteException;
+use mountix_kernel::model::ErrorCode;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Mountain json object
 ///
@@ -22,6 +27,71 @@ pub struct JsonMountain {
     pub elevation: u32,
     pub location: JsonMountainLocation,
     pub tags: Vec<String>,
+    pub aliases: Vec<JsonMountainAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_variant: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_alias: Option<JsonMountainAlias>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<JsonMountainHighlight>,
+}
+
+/// Mountain alias json object
+///
+/// 山岳の別名
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainAlias {
+    pub name: String,
+    pub name_kana: String,
+    pub r#type: String,
+}
+
+impl From<SearchedMountainAlias> for JsonMountainAlias {
+    fn from(alias: SearchedMountainAlias) -> Self {
+        Self {
+            name: alias.name,
+            name_kana: alias.name_kana,
+            r#type: alias.alias_type,
+        }
+    }
+}
+
+/// Highlighted field value json object
+///
+/// 山名の条件に一致した項目の値と文字の範囲
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainHighlight {
+    pub field: String,
+    pub value: String,
+    pub spans: Vec<JsonMountainHighlightSpan>,
+}
+
+/// Range of characters json object, where `end` is exclusive
+///
+/// 一致した文字の範囲 (`end` の位置の文字は含まない)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainHighlightSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<SearchedMountainHighlight> for JsonMountainHighlight {
+    fn from(highlight: SearchedMountainHighlight) -> Self {
+        Self {
+            field: highlight.field,
+            value: highlight.value,
+            spans: highlight
+                .spans
+                .into_iter()
+                .map(|(start, end)| JsonMountainHighlightSpan { start, end })
+                .collect(),
+        }
+    }
 }
 
 /// Mountain location json object
@@ -46,6 +116,19 @@ impl From<SearchedMountain> for JsonMountain {
             elevation: searched_mountain.elevation,
             location: searched_mountain.location.into(),
             tags: searched_mountain.tags,
+            aliases: searched_mountain
+                .aliases
+                .into_iter()
+                .map(JsonMountainAlias::from)
+                .collect(),
+            matched_variant: searched_mountain.matched_variant,
+            matched_alias: searched_mountain.matched_alias.map(JsonMountainAlias::from),
+            score: searched_mountain.score,
+            highlights: searched_mountain
+                .highlights
+                .into_iter()
+                .map(JsonMountainHighlight::from)
+                .collect(),
         }
     }
 }
@@ -60,6 +143,43 @@ impl From<SearchedMountainLocation> for JsonMountainLocation {
     }
 }
 
+/// Facet count json object
+///
+/// ファセットの値ごとの件数
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainFacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Facet counts keyed by the facet, which is omitted when no facet is requested
+///
+/// ファセットごとの件数。ファセットが指定されていない場合は省略します
+type JsonMountainFacets = Option<BTreeMap<String, Vec<JsonMountainFacetCount>>>;
+
+fn into_json_facets(facets: Vec<SearchedMountainFacet>) -> JsonMountainFacets {
+    if facets.is_empty() {
+        return None;
+    }
+
+    let facets = facets
+        .into_iter()
+        .map(|facet| {
+            let counts = facet
+                .counts
+                .into_iter()
+                .map(|c| JsonMountainFacetCount {
+                    value: c.value,
+                    count: c.count,
+                })
+                .collect();
+            (facet.key, counts)
+        })
+        .collect();
+    Some(facets)
+}
+
 /// Mountain response
 ///
 /// 山岳情報レスポンス
@@ -70,6 +190,10 @@ pub struct JsonMountainsResponse {
     total: u64,
     offset: u64,
     limit: Option<u64>,
+    next_cursor: Option<String>,
+    prev_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    facets: JsonMountainFacets,
 }
 
 impl From<SearchedMountainResult> for JsonMountainsResponse {
@@ -88,6 +212,9 @@ impl From<SearchedMountainResult> for JsonMountainsResponse {
             total: result.total,
             offset: result.offset,
             limit: result.limit,
+            next_cursor: result.next_cursor,
+            prev_cursor: result.prev_cursor,
+            facets: into_json_facets(result.facets),
         }
     }
 }
@@ -95,14 +222,71 @@ impl From<SearchedMountainResult> for JsonMountainsResponse {
 /// Mountain search query object
 ///
 /// 山岳情報検索クエリパラメータ
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default)]
 pub struct MountainSearchQueryParam {
     name: Option<String>,
+    r#match: Option<String>,
     prefecture: Option<String>,
+    prefecture_mode: Option<String>,
     tag: Option<String>,
+    tag_mode: Option<String>,
+    area: Option<String>,
+    elevation_min: Option<String>,
+    elevation_max: Option<String>,
     offset: Option<String>,
     limit: Option<String>,
     sort: Option<String>,
+    lat: Option<String>,
+    lng: Option<String>,
+    cursor: Option<String>,
+    facets: Option<String>,
+    q: Option<String>,
+}
+
+impl From<Vec<(String, String)>> for MountainSearchQueryParam {
+    /// Collects the query pairs, joining the repeated `prefecture` and `tag` with commas
+    ///
+    /// クエリパラメータの組から生成します。繰り返し指定された prefecture、tag はカンマ区切りで連結します
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        let mut param = MountainSearchQueryParam::default();
+        for (key, value) in pairs {
+            let field = match key.as_str() {
+                "name" => &mut param.name,
+                "match" => &mut param.r#match,
+                "prefecture" => &mut param.prefecture,
+                "prefecture_mode" => &mut param.prefecture_mode,
+                "tag" => &mut param.tag,
+                "tag_mode" => &mut param.tag_mode,
+                "area" => &mut param.area,
+                "elevation_min" => &mut param.elevation_min,
+                "elevation_max" => &mut param.elevation_max,
+                "offset" => &mut param.offset,
+                "limit" => &mut param.limit,
+                "sort" => &mut param.sort,
+                "lat" => &mut param.lat,
+                "lng" => &mut param.lng,
+                "cursor" => &mut param.cursor,
+                "facets" => &mut param.facets,
+                "q" => &mut param.q,
+                _ => continue,
+            };
+            set_query_value(field, value, key == "prefecture" || key == "tag");
+        }
+        param
+    }
+}
+
+/// Sets the query value, joining it with commas to the previous one if the parameter can be repeated
+///
+/// クエリパラメータの値を設定します。繰り返し指定できるパラメータは前の値にカンマ区切りで連結します
+fn set_query_value(field: &mut Option<String>, value: String, repeatable: bool) {
+    match field {
+        Some(values) if repeatable => {
+            values.push(',');
+            values.push_str(&value);
+        }
+        _ => *field = Some(value),
+    }
 }
 
 impl From<MountainSearchQueryParam> for MountainSearchQuery {
@@ -112,11 +296,22 @@ impl From<MountainSearchQueryParam> for MountainSearchQuery {
     fn from(mq: MountainSearchQueryParam) -> Self {
         MountainSearchQuery {
             name: mq.name,
+            match_type: mq.r#match,
             prefecture: mq.prefecture,
+            prefecture_mode: mq.prefecture_mode,
             tag: mq.tag,
+            tag_mode: mq.tag_mode,
+            area: mq.area,
+            elevation_min: mq.elevation_min,
+            elevation_max: mq.elevation_max,
             offset: mq.offset,
             limit: mq.limit,
             sort: mq.sort,
+            latitude: mq.lat,
+            longitude: mq.lng,
+            cursor: mq.cursor,
+            facets: mq.facets,
+            q: mq.q,
         }
     }
 }
@@ -129,6 +324,10 @@ impl From<MountainSearchQueryParam> for MountainSearchQuery {
 pub struct JsonBoxMountainsResponse {
     mountains: Vec<JsonMountain>,
     total: u64,
+    offset: u64,
+    limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    facets: JsonMountainFacets,
 }
 
 impl From<SearchedBoxMountainResult> for JsonBoxMountainsResponse {
@@ -145,6 +344,9 @@ impl From<SearchedBoxMountainResult> for JsonBoxMountainsResponse {
         Self {
             mountains,
             total: result.total,
+            offset: result.offset,
+            limit: result.limit,
+            facets: into_json_facets(result.facets),
         }
     }
 }
@@ -152,12 +354,52 @@ impl From<SearchedBoxMountainResult> for JsonBoxMountainsResponse {
 /// Box mountains search query object
 ///
 /// 山岳情報の範囲検索クエリパラメータ
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default)]
 pub struct MountainBoxSearchQueryParam {
     r#box: Option<String>,
     name: Option<String>,
+    r#match: Option<String>,
     tag: Option<String>,
+    tag_mode: Option<String>,
+    area: Option<String>,
+    elevation_min: Option<String>,
+    elevation_max: Option<String>,
+    offset: Option<String>,
+    limit: Option<String>,
     sort: Option<String>,
+    lat: Option<String>,
+    lng: Option<String>,
+    facets: Option<String>,
+}
+
+impl From<Vec<(String, String)>> for MountainBoxSearchQueryParam {
+    /// Collects the query pairs, joining the repeated `tag` with commas like `MountainSearchQueryParam`
+    ///
+    /// クエリパラメータの組から生成します。`MountainSearchQueryParam` と同様に繰り返し指定された tag はカンマ区切りで連結します
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        let mut param = MountainBoxSearchQueryParam::default();
+        for (key, value) in pairs {
+            let field = match key.as_str() {
+                "box" => &mut param.r#box,
+                "name" => &mut param.name,
+                "match" => &mut param.r#match,
+                "tag" => &mut param.tag,
+                "tag_mode" => &mut param.tag_mode,
+                "area" => &mut param.area,
+                "elevation_min" => &mut param.elevation_min,
+                "elevation_max" => &mut param.elevation_max,
+                "offset" => &mut param.offset,
+                "limit" => &mut param.limit,
+                "sort" => &mut param.sort,
+                "lat" => &mut param.lat,
+                "lng" => &mut param.lng,
+                "facets" => &mut param.facets,
+                _ => continue,
+            };
+            set_query_value(field, value, key == "tag");
+        }
+        param
+    }
 }
 
 impl TryFrom<MountainBoxSearchQueryParam> for MountainBoxSearchQuery {
@@ -171,14 +413,290 @@ impl TryFrom<MountainBoxSearchQueryParam> for MountainBoxSearchQuery {
             Some(box_param) => Ok(MountainBoxSearchQuery {
                 box_coordinates: box_param,
                 name: bq.name,
+                match_type: bq.r#match,
                 tag: bq.tag,
+                tag_mode: bq.tag_mode,
+                area: bq.area,
+                elevation_min: bq.elevation_min,
+                elevation_max: bq.elevation_max,
+                offset: bq.offset,
+                limit: bq.limit,
                 sort: bq.sort,
+                latitude: bq.lat,
+                longitude: bq.lng,
+                facets: bq.facets,
             }),
             None => Err(vec!["クエリパラメータ box=(bottom left longitude,bottom left latitude),(upper right longitude,upper right latitude) は必須です。".to_string()]),
         }
     }
 }
 
+/// GeoJSON geometry for the polygon search
+///
+/// ポリゴン検索の GeoJSON ジオメトリ
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum JsonPolygonGeometry {
+    Polygon {
+        coordinates: Vec<Vec<[f64; 2]>>,
+    },
+    MultiPolygon {
+        coordinates: Vec<Vec<Vec<[f64; 2]>>>,
+    },
+}
+
+impl JsonPolygonGeometry {
+    /// Returns the coordinates as polygons of rings
+    ///
+    /// 座標をリングからなるポリゴンの一覧として返します
+    fn into_polygons(self) -> Vec<Vec<Vec<[f64; 2]>>> {
+        match self {
+            JsonPolygonGeometry::Polygon { coordinates } => vec![coordinates],
+            JsonPolygonGeometry::MultiPolygon { coordinates } => coordinates,
+        }
+    }
+}
+
+/// Polygon mountains search query object
+///
+/// 山岳情報のポリゴン検索クエリパラメータ
+#[derive(Debug, Default)]
+pub struct MountainPolygonSearchQueryParam {
+    geometry: Option<String>,
+    name: Option<String>,
+    r#match: Option<String>,
+    tag: Option<String>,
+    tag_mode: Option<String>,
+    area: Option<String>,
+    elevation_min: Option<String>,
+    elevation_max: Option<String>,
+    offset: Option<String>,
+    limit: Option<String>,
+    sort: Option<String>,
+    lat: Option<String>,
+    lng: Option<String>,
+}
+
+impl From<Vec<(String, String)>> for MountainPolygonSearchQueryParam {
+    /// Collects the query pairs, joining the repeated `tag` with commas like `MountainSearchQueryParam`
+    ///
+    /// クエリパラメータの組から生成します。`MountainSearchQueryParam` と同様に繰り返し指定された tag はカンマ区切りで連結します
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        let mut param = MountainPolygonSearchQueryParam::default();
+        for (key, value) in pairs {
+            let field = match key.as_str() {
+                "geometry" => &mut param.geometry,
+                "name" => &mut param.name,
+                "match" => &mut param.r#match,
+                "tag" => &mut param.tag,
+                "tag_mode" => &mut param.tag_mode,
+                "area" => &mut param.area,
+                "elevation_min" => &mut param.elevation_min,
+                "elevation_max" => &mut param.elevation_max,
+                "offset" => &mut param.offset,
+                "limit" => &mut param.limit,
+                "sort" => &mut param.sort,
+                "lat" => &mut param.lat,
+                "lng" => &mut param.lng,
+                _ => continue,
+            };
+            set_query_value(field, value, key == "tag");
+        }
+        param
+    }
+}
+
+impl MountainPolygonSearchQueryParam {
+    /// Converts to `MountainPolygonSearchQuery`, using the geometry of the request body if given,
+    /// or the URL-encoded GeoJSON of the `geometry` query parameter otherwise
+    ///
+    /// 山岳情報のポリゴン検索クエリオブジェクトに変換します。
+    /// リクエストボディのジオメトリがない場合はクエリパラメータ geometry の GeoJSON を使用します
+    pub fn into_search_query(
+        self,
+        geometry: Option<JsonPolygonGeometry>,
+    ) -> Result<MountainPolygonSearchQuery, Vec<String>> {
+        let geometry = match (geometry, self.geometry) {
+            (Some(geometry), _) => geometry,
+            (None, Some(geometry_param)) => serde_json::from_str(&geometry_param).map_err(|e| {
+                vec![format!(
+                    "クエリパラメータ geometry の GeoJSON が不正です。({})",
+                    e
+                )]
+            })?,
+            (None, None) => {
+                return Err(vec![
+                "クエリパラメータ geometry (GeoJSON の Polygon または MultiPolygon) は必須です。"
+                    .to_string(),
+            ])
+            }
+        };
+
+        Ok(MountainPolygonSearchQuery {
+            polygons: geometry.into_polygons(),
+            name: self.name,
+            match_type: self.r#match,
+            tag: self.tag,
+            tag_mode: self.tag_mode,
+            area: self.area,
+            elevation_min: self.elevation_min,
+            elevation_max: self.elevation_max,
+            offset: self.offset,
+            limit: self.limit,
+            sort: self.sort,
+            latitude: self.lat,
+            longitude: self.lng,
+        })
+    }
+}
+
+/// Mountain suggestion json object, which has only the fields shown in the search box
+///
+/// 山岳の候補。検索ボックスに表示する項目のみを持ちます
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainSuggestion {
+    pub id: i32,
+    pub name: String,
+    pub name_kana: String,
+    pub prefectures: Vec<String>,
+}
+
+impl From<SuggestedMountain> for JsonMountainSuggestion {
+    fn from(suggested_mountain: SuggestedMountain) -> Self {
+        Self {
+            id: suggested_mountain.id,
+            name: suggested_mountain.name,
+            name_kana: suggested_mountain.name_kana,
+            prefectures: suggested_mountain.prefectures,
+        }
+    }
+}
+
+/// Mountain suggestions response
+///
+/// 山岳の候補レスポンス
+#[derive(Serialize)]
+pub struct JsonMountainSuggestionsResponse {
+    suggestions: Vec<JsonMountainSuggestion>,
+}
+
+impl From<Vec<SuggestedMountain>> for JsonMountainSuggestionsResponse {
+    fn from(suggested_mountains: Vec<SuggestedMountain>) -> Self {
+        Self {
+            suggestions: suggested_mountains.into_iter().map(|m| m.into()).collect(),
+        }
+    }
+}
+
+/// Mountain suggestion query object
+///
+/// 山岳の候補検索クエリパラメータ
+#[derive(Debug, Deserialize)]
+pub struct MountainSuggestQueryParam {
+    q: Option<String>,
+    limit: Option<String>,
+}
+
+impl From<MountainSuggestQueryParam> for MountainSuggestQuery {
+    fn from(query: MountainSuggestQueryParam) -> Self {
+        MountainSuggestQuery {
+            q: query.q,
+            limit: query.limit,
+        }
+    }
+}
+
+/// Mountain write request body
+///
+/// 山岳情報の登録・更新リクエストボディ
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainWriteRequest {
+    name: Option<String>,
+    name_kana: Option<String>,
+    area: Option<String>,
+    prefectures: Option<Vec<String>>,
+    elevation: Option<u32>,
+    location: Option<JsonMountainLocationWriteRequest>,
+    tags: Option<Vec<String>>,
+    aliases: Option<Vec<JsonMountainAliasWriteRequest>>,
+}
+
+/// Mountain alias in write request body
+///
+/// 山岳情報の登録・更新リクエストボディの別名
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonMountainAliasWriteRequest {
+    name: String,
+    name_kana: String,
+    r#type: String,
+}
+
+/// Mountain location in write request body
+///
+/// 山岳情報の登録・更新リクエストボディの位置情報
+#[derive(Debug, Deserialize)]
+pub struct JsonMountainLocationWriteRequest {
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+impl From<JsonMountainWriteRequest> for MountainWriteRequest {
+    /// Converts to `MountainWriteRequest` from `JsonMountainWriteRequest`
+    ///
+    /// 山岳情報の登録・更新リクエストボディから登録・更新リクエストオブジェクトに変換します
+    fn from(req: JsonMountainWriteRequest) -> Self {
+        let (latitude, longitude) = match req.location {
+            Some(location) => (location.latitude, location.longitude),
+            None => (None, None),
+        };
+
+        MountainWriteRequest {
+            name: req.name,
+            name_kana: req.name_kana,
+            area: req.area,
+            prefectures: req.prefectures,
+            elevation: req.elevation,
+            latitude,
+            longitude,
+            tags: req.tags,
+            aliases: req.aliases.map(|aliases| {
+                aliases
+                    .into_iter()
+                    .map(|alias| MountainAliasWriteRequest {
+                        name: alias.name,
+                        name_kana: alias.name_kana,
+                        alias_type: alias.r#type,
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Mountain write error
+///
+/// 山岳情報の登録・更新・削除エラー
+pub struct MountainWriteError(pub MountainWriteException);
+
+impl IntoResponse for MountainWriteError {
+    /// Create error response
+    ///
+    /// 山岳情報の登録・更新・削除エラー時のレスポンスを生成します
+    fn into_response(self) -> Response {
+        let status_code = match self.0.error_code {
+            ErrorCode::InvalidRequestBody => StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidId | ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::VersionConflict => StatusCode::PRECONDITION_FAILED,
+            ErrorCode::PreconditionRequired => StatusCode::PRECONDITION_REQUIRED,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status_code, Json(JsonErrorResponse::new(self.0.messages))).into_response()
+    }
+}
+
 /// Mountain search error
 ///
 /// 山岳情報検索エラー
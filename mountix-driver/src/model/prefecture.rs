@@ -0,0 +1,109 @@
+use crate::model::JsonErrorResponse;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use mountix_app::model::prefecture::{
+    SearchedPrefecture, SearchedPrefectureHighestPeak, SearchedPrefectureResult,
+};
+use serde::Serialize;
+
+/// Prefecture json object
+///
+/// 都道府県情報
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPrefecture {
+    pub id: u64,
+    pub name: String,
+    pub iso_code: String,
+    pub romaji: String,
+    pub region: String,
+    pub mountain_count: u64,
+    pub highest_peak: Option<JsonPrefectureHighestPeak>,
+}
+
+/// Highest peak of prefecture json object
+///
+/// 都道府県の最高峰
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPrefectureHighestPeak {
+    pub id: i32,
+    pub name: String,
+    pub elevation: u32,
+}
+
+impl From<SearchedPrefecture> for JsonPrefecture {
+    fn from(searched_prefecture: SearchedPrefecture) -> Self {
+        Self {
+            id: searched_prefecture.id,
+            name: searched_prefecture.name,
+            iso_code: searched_prefecture.iso_code,
+            romaji: searched_prefecture.romaji,
+            region: searched_prefecture.region,
+            mountain_count: searched_prefecture.mountain_count,
+            highest_peak: searched_prefecture.highest_peak.map(|p| p.into()),
+        }
+    }
+}
+
+impl From<SearchedPrefectureHighestPeak> for JsonPrefectureHighestPeak {
+    fn from(peak: SearchedPrefectureHighestPeak) -> Self {
+        Self {
+            id: peak.id,
+            name: peak.name,
+            elevation: peak.elevation,
+        }
+    }
+}
+
+/// Prefectures response
+///
+/// 都道府県情報レスポンス
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPrefecturesResponse {
+    prefectures: Vec<JsonPrefecture>,
+    total: u64,
+}
+
+impl From<SearchedPrefectureResult> for JsonPrefecturesResponse {
+    /// Converts to `JsonPrefecturesResponse` from `SearchedPrefectureResult`
+    ///
+    /// 都道府県情報検索結果から都道府県情報レスポンスに変換します
+    fn from(result: SearchedPrefectureResult) -> Self {
+        let prefectures = result
+            .prefectures
+            .into_iter()
+            .map(|prefecture| prefecture.into())
+            .collect();
+
+        Self {
+            prefectures,
+            total: result.total,
+        }
+    }
+}
+
+/// Prefecture search error
+///
+/// 都道府県情報検索エラー
+pub enum PrefectureError {
+    ServerError,
+}
+
+impl IntoResponse for PrefectureError {
+    /// Create error response
+    ///
+    /// 都道府県情報検索エラー時のレスポンスを生成します
+    fn into_response(self) -> Response {
+        match self {
+            PrefectureError::ServerError => {
+                let json = JsonErrorResponse::new(vec![
+                    "都道府県情報を取得中に予期せぬエラーが発生しました。".to_string(),
+                ]);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(json)).into_response()
+            }
+        }
+    }
+}
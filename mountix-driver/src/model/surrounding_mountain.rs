@@ -1,4 +1,7 @@
 use mountix_app::model::surrounding_mountain::{
+    CorridorMountainSearchQuery, NearbyMountainSearchQuery, NearestMountainSearchQuery,
+    SearchedCorridorMountain, SearchedCorridorMountainResult, SearchedNearbyMountain,
+    SearchedNearbyMountainResult, SearchedNearestMountainResult, SearchedRelativeMountain,
     SearchedSurroundingMountain, SearchedSurroundingMountainLocation,
     SearchedSurroundingMountainResult, SurroundingMountainSearchQuery,
 };
@@ -50,11 +53,40 @@ impl From<SearchedSurroundingMountainLocation> for JsonSurroundingMountainLocati
     }
 }
 
+/// Mountain around a center mountain json object
+///
+/// 中心の山岳の周辺の山岳情報
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonRelativeMountain {
+    #[serde(flatten)]
+    pub mountain: JsonSurroundingMountain,
+    pub distance: u32,
+    pub bearing: f64,
+    pub compass_point: String,
+    pub elevation_diff: i64,
+}
+
+impl From<SearchedRelativeMountain> for JsonRelativeMountain {
+    fn from(searched_mountain: SearchedRelativeMountain) -> Self {
+        Self {
+            mountain: searched_mountain.mountain.into(),
+            distance: searched_mountain.distance,
+            bearing: searched_mountain.bearing,
+            compass_point: searched_mountain.compass_point,
+            elevation_diff: searched_mountain.elevation_diff,
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JsonSurroundingMountainResponse {
-    mountains: Vec<JsonSurroundingMountain>,
+    mountains: Vec<JsonRelativeMountain>,
     distance: u32,
+    total: u64,
+    offset: u64,
+    limit: Option<u64>,
 }
 
 impl From<SearchedSurroundingMountainResult> for JsonSurroundingMountainResponse {
@@ -68,6 +100,9 @@ impl From<SearchedSurroundingMountainResult> for JsonSurroundingMountainResponse
         Self {
             mountains,
             distance: result.distance,
+            total: result.total,
+            offset: result.offset,
+            limit: result.limit,
         }
     }
 }
@@ -75,12 +110,238 @@ impl From<SearchedSurroundingMountainResult> for JsonSurroundingMountainResponse
 #[derive(Debug, Deserialize)]
 pub struct SurroundingMountainSearchQueryParam {
     pub distance: Option<String>,
+    pub prefecture: Option<String>,
+    pub tag: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+    pub offset: Option<String>,
+    pub limit: Option<String>,
+    pub sort: Option<String>,
 }
 
 impl From<SurroundingMountainSearchQueryParam> for SurroundingMountainSearchQuery {
     fn from(query: SurroundingMountainSearchQueryParam) -> Self {
         SurroundingMountainSearchQuery {
             distance: query.distance,
+            prefecture: query.prefecture,
+            tag: query.tag,
+            elevation_min: query.elevation_min,
+            elevation_max: query.elevation_max,
+            offset: query.offset,
+            limit: query.limit,
+            sort: query.sort,
+        }
+    }
+}
+
+/// Mountain around a point json object
+///
+/// 地点の周辺の山岳情報
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonNearbyMountain {
+    #[serde(flatten)]
+    pub mountain: JsonSurroundingMountain,
+    pub distance: u32,
+    pub bearing: f64,
+}
+
+impl From<SearchedNearbyMountain> for JsonNearbyMountain {
+    fn from(searched_mountain: SearchedNearbyMountain) -> Self {
+        Self {
+            mountain: searched_mountain.mountain.into(),
+            distance: searched_mountain.distance,
+            bearing: searched_mountain.bearing,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonNearbyMountainResponse {
+    mountains: Vec<JsonNearbyMountain>,
+    distance: u32,
+}
+
+impl From<SearchedNearbyMountainResult> for JsonNearbyMountainResponse {
+    fn from(result: SearchedNearbyMountainResult) -> Self {
+        let mountains = result
+            .mountains
+            .into_iter()
+            .map(|mountain| mountain.into())
+            .collect();
+
+        Self {
+            mountains,
+            distance: result.distance,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearbyMountainSearchQueryParam {
+    pub lat: Option<String>,
+    pub lng: Option<String>,
+    pub distance: Option<String>,
+    pub name: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl From<NearbyMountainSearchQueryParam> for NearbyMountainSearchQuery {
+    fn from(query: NearbyMountainSearchQueryParam) -> Self {
+        NearbyMountainSearchQuery {
+            latitude: query.lat,
+            longitude: query.lng,
+            distance: query.distance,
+            name: query.name,
+            tag: query.tag,
         }
     }
 }
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonNearestMountainResponse {
+    mountains: Vec<JsonNearbyMountain>,
+    k: u32,
+}
+
+impl From<SearchedNearestMountainResult> for JsonNearestMountainResponse {
+    fn from(result: SearchedNearestMountainResult) -> Self {
+        let mountains = result
+            .mountains
+            .into_iter()
+            .map(|mountain| mountain.into())
+            .collect();
+
+        Self {
+            mountains,
+            k: result.k,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearestMountainSearchQueryParam {
+    pub lat: Option<String>,
+    pub lng: Option<String>,
+    pub k: Option<String>,
+    pub tag: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+}
+
+impl From<NearestMountainSearchQueryParam> for NearestMountainSearchQuery {
+    fn from(query: NearestMountainSearchQueryParam) -> Self {
+        NearestMountainSearchQuery {
+            latitude: query.lat,
+            longitude: query.lng,
+            k: query.k,
+            tag: query.tag,
+            elevation_min: query.elevation_min,
+            elevation_max: query.elevation_max,
+        }
+    }
+}
+
+/// Mountain along a line
+///
+/// ライン沿いの山岳情報
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonCorridorMountain {
+    #[serde(flatten)]
+    pub mountain: JsonSurroundingMountain,
+    pub along_track: u32,
+    pub cross_track: u32,
+}
+
+impl From<SearchedCorridorMountain> for JsonCorridorMountain {
+    fn from(searched_mountain: SearchedCorridorMountain) -> Self {
+        Self {
+            mountain: searched_mountain.mountain.into(),
+            along_track: searched_mountain.along_track,
+            cross_track: searched_mountain.cross_track,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonCorridorMountainResponse {
+    mountains: Vec<JsonCorridorMountain>,
+    buffer: u32,
+    length: u32,
+}
+
+impl From<SearchedCorridorMountainResult> for JsonCorridorMountainResponse {
+    fn from(result: SearchedCorridorMountainResult) -> Self {
+        let mountains = result
+            .mountains
+            .into_iter()
+            .map(|mountain| mountain.into())
+            .collect();
+
+        Self {
+            mountains,
+            buffer: result.buffer,
+            length: result.length,
+        }
+    }
+}
+
+/// GeoJSON geometry for the corridor search
+///
+/// ライン沿いの山岳の検索の GeoJSON ジオメトリ
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum JsonLineStringGeometry {
+    LineString { coordinates: Vec<[f64; 2]> },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CorridorMountainSearchQueryParam {
+    pub line: Option<String>,
+    pub buffer: Option<String>,
+    pub name: Option<String>,
+    pub tag: Option<String>,
+    pub elevation_min: Option<String>,
+    pub elevation_max: Option<String>,
+}
+
+impl CorridorMountainSearchQueryParam {
+    /// Converts to `CorridorMountainSearchQuery`, using the geometry of the request body if given,
+    /// or the URL-encoded GeoJSON of the `line` query parameter otherwise
+    ///
+    /// ライン沿いの山岳の検索クエリに変換します。
+    /// リクエストボディのジオメトリがない場合はクエリパラメータ line の GeoJSON を使用します
+    pub fn into_search_query(
+        self,
+        geometry: Option<JsonLineStringGeometry>,
+    ) -> Result<CorridorMountainSearchQuery, Vec<String>> {
+        let geometry = match (geometry, self.line) {
+            (Some(geometry), _) => geometry,
+            (None, Some(line_param)) => serde_json::from_str(&line_param).map_err(|e| {
+                vec![format!(
+                    "クエリパラメータ line の GeoJSON が不正です。({})",
+                    e
+                )]
+            })?,
+            (None, None) => {
+                return Err(vec![
+                    "クエリパラメータ line (GeoJSON の LineString) は必須です。".to_string(),
+                ])
+            }
+        };
+        let JsonLineStringGeometry::LineString { coordinates } = geometry;
+
+        Ok(CorridorMountainSearchQuery {
+            line: coordinates,
+            buffer: self.buffer,
+            name: self.name,
+            tag: self.tag,
+            elevation_min: self.elevation_min,
+            elevation_max: self.elevation_max,
+        })
+    }
+}
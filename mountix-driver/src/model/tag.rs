@@ -0,0 +1,84 @@
+use crate::model::JsonErrorResponse;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use mountix_app::model::tag::{SearchedTag, SearchedTagResult};
+use serde::Serialize;
+
+/// Tag json object
+///
+/// タグ情報
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonTag {
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub source: String,
+    pub mountain_count: u64,
+}
+
+impl From<SearchedTag> for JsonTag {
+    fn from(searched_tag: SearchedTag) -> Self {
+        Self {
+            id: searched_tag.id,
+            name: searched_tag.name,
+            description: searched_tag.description,
+            source: searched_tag.source,
+            mountain_count: searched_tag.mountain_count,
+        }
+    }
+}
+
+/// Tags response
+///
+/// タグ情報レスポンス
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonTagsResponse {
+    tags: Vec<JsonTag>,
+    total: u64,
+}
+
+impl From<SearchedTagResult> for JsonTagsResponse {
+    /// Converts to `JsonTagsResponse` from `SearchedTagResult`
+    ///
+    /// タグ情報検索結果からタグ情報レスポンスに変換します
+    fn from(result: SearchedTagResult) -> Self {
+        let tags = result.tags.into_iter().map(|tag| tag.into()).collect();
+
+        Self {
+            tags,
+            total: result.total,
+        }
+    }
+}
+
+/// Tag search error
+///
+/// タグ情報検索エラー
+pub enum TagError {
+    NotFound,
+    ServerError,
+}
+
+impl IntoResponse for TagError {
+    /// Create error response
+    ///
+    /// タグ情報検索エラー時のレスポンスを生成します
+    fn into_response(self) -> Response {
+        match self {
+            TagError::NotFound => {
+                let json =
+                    JsonErrorResponse::new(vec!["タグ情報が見つかりませんでした。".to_string()]);
+                (StatusCode::NOT_FOUND, Json(json)).into_response()
+            }
+            TagError::ServerError => {
+                let json = JsonErrorResponse::new(vec![
+                    "タグ情報を取得中に予期せぬエラーが発生しました。".to_string(),
+                ]);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(json)).into_response()
+            }
+        }
+    }
+}
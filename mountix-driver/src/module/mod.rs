@@ -1,15 +1,23 @@
 use mountix_adapter::modules::{RepositoriesModule, RepositoriesModuleExt};
+use mountix_adapter::persistence::in_memory::InMemoryDb;
 use mountix_adapter::persistence::mongodb::Db;
 use mountix_adapter::repository::health_check::HealthCheckRepository;
+use mountix_app::usecase::area::AreaUseCase;
 use mountix_app::usecase::health_check::HealthCheckUseCase;
 use mountix_app::usecase::mountain::MountainUseCase;
+use mountix_app::usecase::prefecture::PrefectureUseCase;
 use mountix_app::usecase::surrounding_mountain::SurroundingMountainUseCase;
+use mountix_app::usecase::tag::{TagCache, TagUseCase};
+use std::env;
 use std::sync::Arc;
 
 pub struct Modules {
     health_check_use_case: HealthCheckUseCase,
     mountain_use_case: MountainUseCase<RepositoriesModule>,
     surrounding_mountain_use_case: SurroundingMountainUseCase<RepositoriesModule>,
+    tag_use_case: TagUseCase<RepositoriesModule>,
+    prefecture_use_case: PrefectureUseCase<RepositoriesModule>,
+    area_use_case: AreaUseCase<RepositoriesModule>,
 }
 
 pub trait ModulesExt {
@@ -20,6 +28,9 @@ pub trait ModulesExt {
     fn surrounding_mountain_use_case(
         &self,
     ) -> &SurroundingMountainUseCase<Self::RepositoriesModule>;
+    fn tag_use_case(&self) -> &TagUseCase<Self::RepositoriesModule>;
+    fn prefecture_use_case(&self) -> &PrefectureUseCase<Self::RepositoriesModule>;
+    fn area_use_case(&self) -> &AreaUseCase<Self::RepositoriesModule>;
 }
 
 impl ModulesExt for Modules {
@@ -38,23 +49,86 @@ impl ModulesExt for Modules {
     ) -> &SurroundingMountainUseCase<Self::RepositoriesModule> {
         &self.surrounding_mountain_use_case
     }
+
+    fn tag_use_case(&self) -> &TagUseCase<Self::RepositoriesModule> {
+        &self.tag_use_case
+    }
+
+    fn prefecture_use_case(&self) -> &PrefectureUseCase<Self::RepositoriesModule> {
+        &self.prefecture_use_case
+    }
+
+    fn area_use_case(&self) -> &AreaUseCase<Self::RepositoriesModule> {
+        &self.area_use_case
+    }
 }
 
 impl Modules {
+    /// Returns modules backed by the database selected with `DATABASE_BACKEND`
+    ///
+    /// `DATABASE_BACKEND` で選択されたデータベース (`mongodb` または `memory`) を利用するモジュールを生成します
     pub async fn new() -> Modules {
-        let db = Db::new().await;
+        let backend = env::var("DATABASE_BACKEND").unwrap_or_else(|_| "mongodb".to_string());
+        match backend.as_str() {
+            "mongodb" => {
+                let db = Db::new().await;
+                // 読み込めない場合も関連度順の検索で再度構築する
+                if let Err(e) = db.load_search_index().await {
+                    tracing::warn!("Could not build the search index: {:?}", e);
+                }
+                Self::from_repositories(
+                    RepositoriesModule::new(db.clone()),
+                    HealthCheckRepository::new(db),
+                )
+                .await
+            }
+            "memory" => Self::new_in_memory(InMemoryDb::load()).await,
+            _ => panic!("DATABASE_BACKEND is invalid."),
+        }
+    }
+
+    /// Returns modules backed by the in-memory database
+    ///
+    /// インメモリデータベースを利用するモジュールを生成します
+    pub async fn new_in_memory(db: InMemoryDb) -> Modules {
+        Self::from_repositories(
+            RepositoriesModule::new_in_memory(db),
+            HealthCheckRepository::new_in_memory(),
+        )
+        .await
+    }
 
-        let repositories_module = Arc::new(RepositoriesModule::new(db.clone()));
+    /// Returns modules after loading the tags into the cache shared by the use cases
+    ///
+    /// ユースケース間で共有するタグのキャッシュを読み込んだうえでモジュールを生成します
+    async fn from_repositories(
+        repositories_module: RepositoriesModule,
+        health_check_repository: HealthCheckRepository,
+    ) -> Modules {
+        let repositories_module = Arc::new(repositories_module);
+        let tag_cache = Arc::new(TagCache::default());
 
-        let health_check_use_case = HealthCheckUseCase::new(HealthCheckRepository::new(db));
-        let mountain_use_case = MountainUseCase::new(repositories_module.clone());
+        let health_check_use_case = HealthCheckUseCase::new(health_check_repository);
+        let mountain_use_case =
+            MountainUseCase::new(repositories_module.clone(), tag_cache.clone());
         let surrounding_mountain_use_case =
-            SurroundingMountainUseCase::new(repositories_module.clone());
+            SurroundingMountainUseCase::new(repositories_module.clone(), tag_cache.clone());
+        let tag_use_case = TagUseCase::new(repositories_module.clone(), tag_cache);
+        let prefecture_use_case = PrefectureUseCase::new(repositories_module.clone());
+        let area_use_case = AreaUseCase::new(repositories_module.clone());
+
+        // 読み込めない場合もタグを必要とするリクエストで再読み込みする
+        if let Err(e) = tag_use_case.load_tags().await {
+            tracing::warn!("Could not load tags: {:?}", e);
+        }
 
         Self {
             health_check_use_case,
             mountain_use_case,
             surrounding_mountain_use_case,
+            tag_use_case,
+            prefecture_use_case,
+            area_use_case,
         }
     }
 }
@@ -0,0 +1,25 @@
+use crate::model::area::{AreaError, JsonAreasResponse};
+use crate::module::{Modules, ModulesExt};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use std::sync::Arc;
+use tracing::log::error;
+
+pub async fn find_areas(
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, AreaError> {
+    let res = modules.area_use_case().find().await;
+    match res {
+        Ok(result) => {
+            tracing::info!("Succeeded to find {} areas.", &result.areas.len());
+
+            let json: JsonAreasResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => {
+            error!("{:?}", find_ex);
+            Err(AreaError::ServerError)
+        }
+    }
+}
@@ -1,4 +1,7 @@
+pub mod area;
 pub mod health;
 pub mod information;
 pub mod mountain;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
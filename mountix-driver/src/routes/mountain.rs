@@ -1,14 +1,21 @@
 use crate::model::mountain::{
-    JsonBoxMountainsResponse, JsonMountain, JsonMountainsResponse, MountainBoxSearchQueryParam,
-    MountainError, MountainSearchQueryParam,
+    JsonBoxMountainsResponse, JsonMountain, JsonMountainSuggestionsResponse,
+    JsonMountainWriteRequest, JsonMountainsResponse, JsonPolygonGeometry,
+    MountainBoxSearchQueryParam, MountainError, MountainPolygonSearchQueryParam,
+    MountainSearchQueryParam, MountainSuggestQueryParam, MountainWriteError,
 };
 use crate::model::JsonErrorResponse;
 use crate::module::{Modules, ModulesExt};
+use axum::extract::rejection::JsonRejection;
 use axum::extract::{Path, Query};
-use axum::http::StatusCode;
+use axum::http::header::{ETAG, IF_MATCH, LOCATION};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::{Extension, Json};
-use mountix_app::model::mountain::MountainSearchQuery;
+use mountix_app::model::mountain::{
+    MountainPolygonSearchQuery, MountainSearchQuery, SearchedMountain,
+};
+use mountix_kernel::model::mountain::MountainWriteException;
 use mountix_kernel::model::ErrorCode;
 use std::sync::Arc;
 use tracing::log::error;
@@ -23,8 +30,9 @@ pub async fn get_mountain(
             Some(sm) => {
                 tracing::info!("Succeeded to get mountain by id ({}).", &sm.id);
 
+                let etag = etag(&sm);
                 let json: JsonMountain = sm.into();
-                Ok((StatusCode::OK, Json(json)))
+                Ok((StatusCode::OK, [(ETAG, etag)], Json(json)))
             }
             None => {
                 tracing::info!("Succeeded to get mountain by id (None).");
@@ -43,10 +51,10 @@ pub async fn get_mountain(
 }
 
 pub async fn find_mountains(
-    Query(query): Query<MountainSearchQueryParam>,
+    Query(pairs): Query<Vec<(String, String)>>,
     Extension(modules): Extension<Arc<Modules>>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
-    let search_query: MountainSearchQuery = query.into();
+    let search_query: MountainSearchQuery = MountainSearchQueryParam::from(pairs).into();
 
     let res = modules.mountain_use_case().find(search_query).await;
     match res {
@@ -69,11 +77,37 @@ pub async fn find_mountains(
     }
 }
 
+/// Suggests mountains whose names or aliases start with `q` for the search box
+///
+/// 検索ボックス向けに、山名または別名が `q` で始まる山岳の候補を返します
+pub async fn suggest_mountains(
+    Query(query): Query<MountainSuggestQueryParam>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let res = modules.mountain_use_case().suggest(query.into()).await;
+    match res {
+        Ok(suggested_mountains) => {
+            let json: JsonMountainSuggestionsResponse = suggested_mountains.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => {
+            error!("{:?}", find_ex);
+
+            let json = JsonErrorResponse::new(find_ex.messages);
+            if find_ex.error_code == ErrorCode::ServerError {
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json)))
+            } else {
+                Err((StatusCode::BAD_REQUEST, Json(json)))
+            }
+        }
+    }
+}
+
 pub async fn find_mountains_by_box(
-    Query(query): Query<MountainBoxSearchQueryParam>,
+    Query(pairs): Query<Vec<(String, String)>>,
     Extension(modules): Extension<Arc<Modules>>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
-    match query.try_into() {
+    match MountainBoxSearchQueryParam::from(pairs).try_into() {
         Ok(search_query) => {
             let res = modules.mountain_use_case().find_box(search_query).await;
             match res {
@@ -107,6 +141,186 @@ pub async fn find_mountains_by_box(
     }
 }
 
+pub async fn find_mountains_by_polygon(
+    Query(pairs): Query<Vec<(String, String)>>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let query = MountainPolygonSearchQueryParam::from(pairs);
+    search_by_polygon(&modules, query.into_search_query(None)).await
+}
+
+pub async fn find_mountains_by_polygon_body(
+    Query(pairs): Query<Vec<(String, String)>>,
+    Extension(modules): Extension<Arc<Modules>>,
+    body: Result<Json<JsonPolygonGeometry>, JsonRejection>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let query = MountainPolygonSearchQueryParam::from(pairs);
+    let search_query = body
+        .map_err(|rejection| {
+            vec![format!(
+                "リクエストボディが不正です。({})",
+                rejection.body_text()
+            )]
+        })
+        .and_then(|Json(geometry)| query.into_search_query(Some(geometry)));
+    search_by_polygon(&modules, search_query).await
+}
+
+async fn search_by_polygon(
+    modules: &Modules,
+    search_query: Result<MountainPolygonSearchQuery, Vec<String>>,
+) -> Result<(StatusCode, Json<JsonBoxMountainsResponse>), (StatusCode, Json<JsonErrorResponse>)> {
+    match search_query {
+        Ok(search_query) => {
+            let res = modules.mountain_use_case().find_polygon(search_query).await;
+            match res {
+                Ok(result) => {
+                    tracing::info!(
+                        "Succeeded to find {} mountains by polygon.",
+                        &result.mountains.len()
+                    );
+
+                    let json: JsonBoxMountainsResponse = result.into();
+                    Ok((StatusCode::OK, Json(json)))
+                }
+                Err(find_ex) => {
+                    error!("{:?}", find_ex);
+
+                    let json = JsonErrorResponse::new(find_ex.messages);
+                    if find_ex.error_code == ErrorCode::ServerError {
+                        Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json)))
+                    } else {
+                        Err((StatusCode::BAD_REQUEST, Json(json)))
+                    }
+                }
+            }
+        }
+        Err(messages) => {
+            error!("{:?}", messages);
+
+            let json = JsonErrorResponse::new(messages);
+            Err((StatusCode::BAD_REQUEST, Json(json)))
+        }
+    }
+}
+
+pub async fn create_mountain(
+    Extension(modules): Extension<Arc<Modules>>,
+    body: Result<Json<JsonMountainWriteRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, MountainWriteError> {
+    let Json(body) = body.map_err(invalid_request_body)?;
+
+    let res = modules.mountain_use_case().create(body.into()).await;
+    match res {
+        Ok(sm) => {
+            tracing::info!("Succeeded to create mountain ({}).", &sm.id);
+
+            let location = format!("/api/v1/mountains/{}", sm.id);
+            let etag = etag(&sm);
+            let json: JsonMountain = sm.into();
+            Ok((
+                StatusCode::CREATED,
+                [(LOCATION, location), (ETAG, etag)],
+                Json(json),
+            ))
+        }
+        Err(write_ex) => {
+            error!("{:?}", write_ex);
+            Err(MountainWriteError(write_ex))
+        }
+    }
+}
+
+pub async fn update_mountain(
+    Path(mountain_id): Path<String>,
+    headers: HeaderMap,
+    Extension(modules): Extension<Arc<Modules>>,
+    body: Result<Json<JsonMountainWriteRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, MountainWriteError> {
+    let Json(body) = body.map_err(invalid_request_body)?;
+
+    let res = modules
+        .mountain_use_case()
+        .update(mountain_id, body.into(), if_match(&headers))
+        .await;
+    updated_response(res)
+}
+
+pub async fn patch_mountain(
+    Path(mountain_id): Path<String>,
+    headers: HeaderMap,
+    Extension(modules): Extension<Arc<Modules>>,
+    body: Result<Json<JsonMountainWriteRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, MountainWriteError> {
+    let Json(body) = body.map_err(invalid_request_body)?;
+
+    let res = modules
+        .mountain_use_case()
+        .patch(mountain_id, body.into(), if_match(&headers))
+        .await;
+    updated_response(res)
+}
+
+pub async fn delete_mountain(
+    Path(mountain_id): Path<String>,
+    headers: HeaderMap,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, MountainWriteError> {
+    let res = modules
+        .mountain_use_case()
+        .delete(mountain_id, if_match(&headers))
+        .await;
+    match res {
+        Ok(()) => {
+            tracing::info!("Succeeded to delete mountain.");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(write_ex) => {
+            error!("{:?}", write_ex);
+            Err(MountainWriteError(write_ex))
+        }
+    }
+}
+
+fn updated_response(
+    res: Result<SearchedMountain, MountainWriteException>,
+) -> Result<impl IntoResponse, MountainWriteError> {
+    match res {
+        Ok(sm) => {
+            tracing::info!("Succeeded to update mountain ({}).", &sm.id);
+
+            let etag = etag(&sm);
+            let json: JsonMountain = sm.into();
+            Ok((StatusCode::OK, [(ETAG, etag)], Json(json)))
+        }
+        Err(write_ex) => {
+            error!("{:?}", write_ex);
+            Err(MountainWriteError(write_ex))
+        }
+    }
+}
+
+fn etag(sm: &SearchedMountain) -> String {
+    format!("\"{}\"", sm.version)
+}
+
+fn if_match(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(IF_MATCH)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string())
+}
+
+fn invalid_request_body(rejection: JsonRejection) -> MountainWriteError {
+    error!("{:?}", rejection);
+    MountainWriteError(MountainWriteException::new(
+        ErrorCode::InvalidRequestBody,
+        vec![format!(
+            "リクエストボディが不正です。({})",
+            rejection.body_text()
+        )],
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::model::mountain::JsonMountain;
@@ -129,6 +343,12 @@ mod tests {
                 gsi_url: "https://maps.gsi.go.jp/fuji".to_string(),
             },
             tags: vec!["百名山".to_string()],
+            aliases: vec![],
+            version: 0,
+            matched_variant: None,
+            matched_alias: None,
+            score: None,
+            highlights: vec![],
         }
     }
 
@@ -177,7 +397,6 @@ mod tests {
         let messages = vec!["Error message".to_string()];
         let _response = JsonErrorResponse::new(messages.clone());
         // Cannot test private field directly, but test that the object was created
-        assert!(true); // JsonErrorResponse was created successfully
     }
 
     #[test]
@@ -0,0 +1,64 @@
+use crate::model::mountain::{JsonMountainsResponse, MountainSearchQueryParam};
+use crate::model::prefecture::{JsonPrefecturesResponse, PrefectureError};
+use crate::model::JsonErrorResponse;
+use crate::module::{Modules, ModulesExt};
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use mountix_kernel::model::ErrorCode;
+use std::sync::Arc;
+use tracing::log::error;
+
+pub async fn find_prefectures(
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, PrefectureError> {
+    let res = modules.prefecture_use_case().find().await;
+    match res {
+        Ok(result) => {
+            tracing::info!(
+                "Succeeded to find {} prefectures.",
+                &result.prefectures.len()
+            );
+
+            let json: JsonPrefecturesResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => {
+            error!("{:?}", find_ex);
+            Err(PrefectureError::ServerError)
+        }
+    }
+}
+
+pub async fn find_prefecture_mountains(
+    Path(prefecture): Path<String>,
+    Query(pairs): Query<Vec<(String, String)>>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let res = modules
+        .mountain_use_case()
+        .find_by_prefecture(prefecture, MountainSearchQueryParam::from(pairs).into())
+        .await;
+    match res {
+        Ok(result) => {
+            tracing::info!(
+                "Succeeded to find {} mountains by prefecture.",
+                &result.mountains.len()
+            );
+
+            let json: JsonMountainsResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => {
+            error!("{:?}", find_ex);
+
+            let json = JsonErrorResponse::new(find_ex.messages);
+            match find_ex.error_code {
+                ErrorCode::NotFound => Err((StatusCode::NOT_FOUND, Json(json))),
+                ErrorCode::ServerError => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json))),
+                _ => Err((StatusCode::BAD_REQUEST, Json(json))),
+            }
+        }
+    }
+}
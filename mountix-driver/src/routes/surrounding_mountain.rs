@@ -1,13 +1,20 @@
 use crate::model::surrounding_mountain::{
-    JsonSurroundingMountainResponse, SurroundingMountainSearchQueryParam,
+    CorridorMountainSearchQueryParam, JsonCorridorMountainResponse, JsonLineStringGeometry,
+    JsonNearbyMountainResponse, JsonNearestMountainResponse, JsonSurroundingMountainResponse,
+    NearbyMountainSearchQueryParam, NearestMountainSearchQueryParam,
+    SurroundingMountainSearchQueryParam,
 };
 use crate::model::JsonErrorResponse;
 use crate::module::{Modules, ModulesExt};
+use axum::extract::rejection::JsonRejection;
 use axum::extract::{Path, Query};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::{Extension, Json};
-use mountix_app::model::surrounding_mountain::SurroundingMountainSearchQuery;
+use mountix_app::model::surrounding_mountain::{
+    CorridorMountainSearchQuery, SurroundingMountainSearchQuery,
+};
+use mountix_kernel::model::surrounding_mountain::SurroundingMountainFindException;
 use mountix_kernel::model::ErrorCode;
 use std::sync::Arc;
 use tracing::log::error;
@@ -46,6 +53,149 @@ pub async fn find_surroundings(
     }
 }
 
+pub async fn find_nearby(
+    Query(query): Query<NearbyMountainSearchQueryParam>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let res = modules
+        .surrounding_mountain_use_case()
+        .find_nearby(query.into())
+        .await;
+    match res {
+        Ok(result) => {
+            tracing::info!(
+                "Succeeded to find {} nearby mountains.",
+                &result.mountains.len()
+            );
+
+            let json: JsonNearbyMountainResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => {
+            error!("{:?}", find_ex);
+
+            let json = JsonErrorResponse::new(find_ex.messages);
+            if find_ex.error_code == ErrorCode::ServerError {
+                Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json)))
+            } else {
+                Err((StatusCode::BAD_REQUEST, Json(json)))
+            }
+        }
+    }
+}
+
+pub async fn find_nearest(
+    Query(query): Query<NearestMountainSearchQueryParam>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let res = modules
+        .surrounding_mountain_use_case()
+        .find_nearest(query.into())
+        .await;
+    match res {
+        Ok(result) => {
+            tracing::info!(
+                "Succeeded to find {} nearest mountains.",
+                &result.mountains.len()
+            );
+
+            let json: JsonNearestMountainResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => Err(nearest_error_response(find_ex)),
+    }
+}
+
+pub async fn find_nearest_to_mountain(
+    Path(mountain_id): Path<String>,
+    Query(query): Query<NearestMountainSearchQueryParam>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let res = modules
+        .surrounding_mountain_use_case()
+        .find_nearest_to_mountain(mountain_id, query.into())
+        .await;
+    match res {
+        Ok(result) => {
+            tracing::info!(
+                "Succeeded to find {} nearest mountains.",
+                &result.mountains.len()
+            );
+
+            let json: JsonNearestMountainResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => Err(nearest_error_response(find_ex)),
+    }
+}
+
+pub async fn find_corridor(
+    Query(query): Query<CorridorMountainSearchQueryParam>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    search_corridor(&modules, query.into_search_query(None)).await
+}
+
+pub async fn find_corridor_by_body(
+    Query(query): Query<CorridorMountainSearchQueryParam>,
+    Extension(modules): Extension<Arc<Modules>>,
+    body: Result<Json<JsonLineStringGeometry>, JsonRejection>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+    let search_query = body
+        .map_err(|rejection| {
+            vec![format!(
+                "リクエストボディが不正です。({})",
+                rejection.body_text()
+            )]
+        })
+        .and_then(|Json(geometry)| query.into_search_query(Some(geometry)));
+    search_corridor(&modules, search_query).await
+}
+
+async fn search_corridor(
+    modules: &Modules,
+    search_query: Result<CorridorMountainSearchQuery, Vec<String>>,
+) -> Result<(StatusCode, Json<JsonCorridorMountainResponse>), (StatusCode, Json<JsonErrorResponse>)>
+{
+    let search_query = search_query.map_err(|messages| {
+        error!("{:?}", messages);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(JsonErrorResponse::new(messages)),
+        )
+    })?;
+
+    match modules
+        .surrounding_mountain_use_case()
+        .find_corridor(search_query)
+        .await
+    {
+        Ok(result) => {
+            tracing::info!(
+                "Succeeded to find {} mountains along the line.",
+                &result.mountains.len()
+            );
+
+            let json: JsonCorridorMountainResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => Err(nearest_error_response(find_ex)),
+    }
+}
+
+fn nearest_error_response(
+    find_ex: SurroundingMountainFindException,
+) -> (StatusCode, Json<JsonErrorResponse>) {
+    error!("{:?}", find_ex);
+
+    let status_code = match find_ex.error_code {
+        ErrorCode::NotFound => StatusCode::NOT_FOUND,
+        ErrorCode::ServerError => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    (status_code, Json(JsonErrorResponse::new(find_ex.messages)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,8 +203,6 @@ mod tests {
     use mountix_app::model::surrounding_mountain::{
         SearchedSurroundingMountain, SearchedSurroundingMountainLocation,
     };
-    use mountix_kernel::model::surrounding_mountain::SurroundingMountainFindException;
-    use mountix_kernel::model::ErrorCode;
 
     fn create_test_searched_surrounding_mountain() -> SearchedSurroundingMountain {
         SearchedSurroundingMountain {
@@ -155,10 +303,19 @@ mod tests {
         // Test with distance parameter
         let query_param = SurroundingMountainSearchQueryParam {
             distance: Some("10000".to_string()),
+            prefecture: None,
+            tag: Some("1".to_string()),
+            elevation_min: None,
+            elevation_max: None,
+            offset: None,
+            limit: Some("10".to_string()),
+            sort: Some("distance.desc".to_string()),
         };
-        let _search_query: SurroundingMountainSearchQuery = query_param.into();
-        // The actual conversion logic would be tested here in a real implementation
-        assert!(true); // Placeholder - tests the structure exists
+        let search_query: SurroundingMountainSearchQuery = query_param.into();
+        assert_eq!(search_query.distance, Some("10000".to_string()));
+        assert_eq!(search_query.tag, Some("1".to_string()));
+        assert_eq!(search_query.limit, Some("10".to_string()));
+        assert_eq!(search_query.sort, Some("distance.desc".to_string()));
     }
 
     #[test]
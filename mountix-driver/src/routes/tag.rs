@@ -0,0 +1,54 @@
+use crate::model::tag::{JsonTag, JsonTagsResponse, TagError};
+use crate::module::{Modules, ModulesExt};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use mountix_kernel::model::ErrorCode;
+use std::sync::Arc;
+use tracing::log::error;
+
+pub async fn get_tag(
+    Path(tag_id): Path<String>,
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, TagError> {
+    let res = modules.tag_use_case().get(tag_id).await;
+    match res {
+        Ok(Some(st)) => {
+            tracing::info!("Succeeded to get tag by id ({}).", &st.id);
+
+            let json: JsonTag = st.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Ok(None) => {
+            tracing::info!("Succeeded to get tag by id (None).");
+            Err(TagError::NotFound)
+        }
+        Err(get_ex) => {
+            error!("{:?}", get_ex);
+            if get_ex.error_code == ErrorCode::InvalidId {
+                Err(TagError::NotFound)
+            } else {
+                Err(TagError::ServerError)
+            }
+        }
+    }
+}
+
+pub async fn find_tags(
+    Extension(modules): Extension<Arc<Modules>>,
+) -> Result<impl IntoResponse, TagError> {
+    let res = modules.tag_use_case().find().await;
+    match res {
+        Ok(result) => {
+            tracing::info!("Succeeded to find {} tags.", &result.tags.len());
+
+            let json: JsonTagsResponse = result.into();
+            Ok((StatusCode::OK, Json(json)))
+        }
+        Err(find_ex) => {
+            error!("{:?}", find_ex);
+            Err(TagError::ServerError)
+        }
+    }
+}
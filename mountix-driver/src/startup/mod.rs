@@ -1,8 +1,19 @@
 use crate::module::Modules;
+use crate::routes::area::find_areas;
 use crate::routes::health::{hc, hc_mongodb};
 use crate::routes::information::info;
-use crate::routes::mountain::{find_mountains, find_mountains_by_box, get_mountain};
-use crate::routes::surrounding_mountain::find_surroundings;
+use crate::routes::mountain::{
+    create_mountain, delete_mountain, find_mountains, find_mountains_by_box,
+    find_mountains_by_polygon, find_mountains_by_polygon_body, get_mountain, patch_mountain,
+    suggest_mountains, update_mountain,
+};
+use crate::routes::prefecture::{find_prefecture_mountains, find_prefectures};
+use crate::routes::surrounding_mountain::{
+    find_corridor, find_corridor_by_body, find_nearby, find_nearest, find_nearest_to_mountain,
+    find_surroundings,
+};
+use crate::routes::tag::{find_tags, get_tag};
+use axum::http::header::{CONTENT_TYPE, ETAG, IF_MATCH, LOCATION};
 use axum::http::Method;
 use axum::{routing::get, Extension, Router};
 use dotenvy::dotenv;
@@ -15,8 +26,36 @@ use tower_http::trace::TraceLayer;
 use tracing::Level;
 
 pub async fn startup(modules: Arc<Modules>) {
+    let app = create_router(modules);
+
+    let addr = SocketAddr::from(init_addr());
+    tracing::info!("Server listening on {}", addr);
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to bind to address {}: {:?}", addr, e));
+
+    axum::serve(listener, app)
+        .await
+        .unwrap_or_else(|_| panic!("Server cannot launch."));
+}
+
+/// Returns the API router
+///
+/// API のルーターを生成します
+pub fn create_router(modules: Arc<Modules>) -> Router {
     let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::OPTIONS, Method::HEAD])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+            Method::HEAD,
+        ])
+        .allow_headers([CONTENT_TYPE, IF_MATCH])
+        .expose_headers([ETAG, LOCATION])
         .allow_origin(Any);
 
     let hc_router = Router::new()
@@ -24,17 +63,45 @@ pub async fn startup(modules: Arc<Modules>) {
         .route("/mongo", get(hc_mongodb));
 
     let mountain_router = Router::new()
-        .route("/", get(find_mountains))
-        .route("/{id}", get(get_mountain))
+        .route("/", get(find_mountains).post(create_mountain))
+        .route(
+            "/{id}",
+            get(get_mountain)
+                .put(update_mountain)
+                .patch(patch_mountain)
+                .delete(delete_mountain),
+        )
         .route("/{id}/surroundings", get(find_surroundings))
-        .route("/geosearch", get(find_mountains_by_box));
+        .route("/{id}/nearest", get(find_nearest_to_mountain))
+        .route("/suggest", get(suggest_mountains))
+        .route("/geosearch", get(find_mountains_by_box))
+        .route(
+            "/geosearch/polygon",
+            get(find_mountains_by_polygon).post(find_mountains_by_polygon_body),
+        )
+        .route("/nearby", get(find_nearby))
+        .route("/nearest", get(find_nearest))
+        .route("/corridor", get(find_corridor).post(find_corridor_by_body));
+
+    let tag_router = Router::new()
+        .route("/", get(find_tags))
+        .route("/{id}", get(get_tag));
+
+    let prefecture_router = Router::new()
+        .route("/", get(find_prefectures))
+        .route("/{id}/mountains", get(find_prefecture_mountains));
+
+    let area_router = Router::new().route("/", get(find_areas));
 
     let info_router = Router::new().route("/", get(info));
 
-    let app = Router::new()
+    Router::new()
         .nest("/api/v1/", info_router)
         .nest("/api/v1/hc", hc_router)
         .nest("/api/v1/mountains", mountain_router)
+        .nest("/api/v1/tags", tag_router)
+        .nest("/api/v1/prefectures", prefecture_router)
+        .nest("/api/v1/areas", area_router)
         .layer(cors)
         .layer(Extension(modules))
         .layer(
@@ -57,18 +124,7 @@ pub async fn startup(modules: Arc<Modules>) {
                     tracing::span!(Level::INFO, "http-request")
                 }
             }),
-        );
-
-    let addr = SocketAddr::from(init_addr());
-    tracing::info!("Server listening on {}", addr);
-
-    let listener = TcpListener::bind(addr)
-        .await
-        .unwrap_or_else(|e| panic!("Failed to bind to address {}: {:?}", addr, e));
-
-    axum::serve(listener, app)
-        .await
-        .unwrap_or_else(|_| panic!("Server cannot launch."));
+        )
 }
 
 pub fn init_app() {
@@ -171,4 +227,1167 @@ mod tests {
 
     // Note: Full startup tests would require actual server initialization
     // These tests focus on individual components and configuration
+
+    mod in_memory {
+        use super::super::create_router;
+        use crate::module::Modules;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use mountix_adapter::persistence::in_memory::InMemoryDb;
+        use mountix_kernel::model::mountain::MountainPrefecture;
+        use std::fs::File;
+        use std::io::BufReader;
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        async fn create_test_router() -> axum::Router {
+            let data_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../migrations/data");
+            let mountains = File::open(format!("{}/mountix_db-mountains.json", data_dir)).unwrap();
+            let tags = File::open(format!("{}/mountix_db-tags.json", data_dir)).unwrap();
+            let db = InMemoryDb::from_reader(BufReader::new(mountains))
+                .unwrap()
+                .with_tags(BufReader::new(tags))
+                .unwrap();
+            create_router(Arc::new(Modules::new_in_memory(db).await))
+        }
+
+        async fn get_json(uri: &str) -> (StatusCode, serde_json::Value) {
+            let response = create_test_router()
+                .await
+                .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice(&body).unwrap())
+        }
+
+        async fn send(
+            router: &axum::Router,
+            request: Request<Body>,
+        ) -> (StatusCode, axum::http::HeaderMap, serde_json::Value) {
+            let response = router.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+            (status, headers, json)
+        }
+
+        fn json_request(
+            method: &str,
+            uri: &str,
+            if_match: Option<&str>,
+            body: &str,
+        ) -> Request<Body> {
+            let mut builder = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json");
+            if let Some(if_match) = if_match {
+                builder = builder.header("if-match", if_match);
+            }
+            builder.body(Body::from(body.to_string())).unwrap()
+        }
+
+        const TEST_MOUNTAIN_BODY: &str = r#"{"name":"テスト山","nameKana":"てすとやま","area":"富士山","prefectures":["静岡県"],"elevation":1000,"location":{"latitude":35.3,"longitude":138.8},"tags":["百名山"]}"#;
+
+        #[tokio::test]
+        async fn test_write_mountain_lifecycle() {
+            let router = create_test_router().await;
+
+            let (status, headers, json) = send(
+                &router,
+                json_request("POST", "/api/v1/mountains", None, TEST_MOUNTAIN_BODY),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(headers["location"], "/api/v1/mountains/1060");
+            assert_eq!(headers["etag"], "\"0\"");
+            assert_eq!(
+                json["location"]["gsiUrl"],
+                "https://maps.gsi.go.jp/#15/35.3/138.8"
+            );
+
+            // If-Match がない場合は更新できない
+            let (status, _, _) = send(
+                &router,
+                json_request(
+                    "PATCH",
+                    "/api/v1/mountains/1060",
+                    None,
+                    r#"{"elevation":1200}"#,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::PRECONDITION_REQUIRED);
+
+            let (status, headers, json) = send(
+                &router,
+                json_request(
+                    "PATCH",
+                    "/api/v1/mountains/1060",
+                    Some("\"0\""),
+                    r#"{"elevation":1200}"#,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(headers["etag"], "\"1\"");
+            assert_eq!(json["elevation"], 1200);
+            assert_eq!(json["name"], "テスト山");
+
+            // 古いバージョンでの更新・削除は競合として扱う
+            let (status, _, _) = send(
+                &router,
+                json_request(
+                    "PUT",
+                    "/api/v1/mountains/1060",
+                    Some("\"0\""),
+                    TEST_MOUNTAIN_BODY,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+            let (status, _, _) = send(
+                &router,
+                json_request("DELETE", "/api/v1/mountains/1060", Some("\"1\""), ""),
+            )
+            .await;
+            assert_eq!(status, StatusCode::NO_CONTENT);
+
+            let (status, _, _) = send(
+                &router,
+                Request::get("/api/v1/mountains/1060")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn test_create_mountain_invalid_body() {
+            let router = create_test_router().await;
+
+            let (status, _, json) = send(
+                &router,
+                json_request(
+                    "POST",
+                    "/api/v1/mountains",
+                    None,
+                    r#"{"name":"テスト山","nameKana":"てすとやま","area":"富士山","prefectures":["東京"],"elevation":1000,"location":{"latitude":10.0,"longitude":138.8},"tags":[]}"#,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(json["messages"].as_array().unwrap().len(), 2);
+
+            let (status, _, json) = send(
+                &router,
+                json_request(
+                    "POST",
+                    "/api/v1/mountains",
+                    None,
+                    r#"{"name":"テスト山","nameKana":"てすとやま","area":"富士山","prefectures":["東京都"],"elevation":1000,"location":{"latitude":35.0,"longitude":138.8},"tags":[],"aliases":[{"name":"別名山","nameKana":"べつめいやま","type":"nickname"}]}"#,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(json["messages"].as_array().unwrap().len(), 1);
+
+            let (status, _, _) = send(
+                &router,
+                json_request("POST", "/api/v1/mountains", None, "{"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_tags() {
+            let (status, json) = get_json("/api/v1/tags").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 2);
+            assert_eq!(json["tags"][0]["name"], "百名山");
+            assert_eq!(json["tags"][0]["mountainCount"], 100);
+            assert!(!json["tags"][1]["source"].as_str().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_get_tag() {
+            let (status, json) = get_json("/api/v1/tags/2").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["name"], "二百名山");
+            assert_eq!(json["mountainCount"], 100);
+
+            let (status, _) = get_json("/api/v1/tags/99").await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn test_find_prefectures() {
+            let (status, json) = get_json("/api/v1/prefectures").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 47);
+
+            let shizuoka = &json["prefectures"][21];
+            assert_eq!(shizuoka["name"], "静岡県");
+            assert_eq!(shizuoka["isoCode"], "JP-22");
+            assert_eq!(shizuoka["romaji"], "Shizuoka");
+            assert_eq!(shizuoka["region"], "中部");
+            assert!(shizuoka["mountainCount"].as_u64().unwrap() > 0);
+            assert_eq!(shizuoka["highestPeak"]["elevation"], 3776);
+        }
+
+        #[tokio::test]
+        async fn test_find_prefecture_mountains() {
+            let (status, json) =
+                get_json("/api/v1/prefectures/yamanashi/mountains?sort=elevation.desc&limit=1")
+                    .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"][0]["name"], "富士山<剣ヶ峯>");
+
+            let (_, by_code) = get_json("/api/v1/mountains?prefecture=JP-19").await;
+            assert_eq!(by_code["total"], json["total"]);
+
+            let (status, _) = get_json("/api/v1/prefectures/99/mountains").await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn test_find_areas() {
+            let (status, json) = get_json("/api/v1/areas").await;
+            assert_eq!(status, StatusCode::OK);
+
+            let areas = json["areas"].as_array().unwrap();
+            assert_eq!(json["total"].as_u64().unwrap() as usize, areas.len());
+            let total: u64 = areas
+                .iter()
+                .map(|area| area["mountainCount"].as_u64().unwrap())
+                .sum();
+            assert_eq!(total, 1059);
+
+            let fuji = areas
+                .iter()
+                .find(|area| area["name"] == "富士山とその周辺")
+                .unwrap();
+            assert_eq!(fuji["elevationMax"], 3776);
+            assert!(fuji["prefectures"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!("山梨県")));
+            assert!(
+                fuji["boundingBox"]["bottomLeft"]["latitude"]
+                    .as_f64()
+                    .unwrap()
+                    <= fuji["boundingBox"]["upperRight"]["latitude"]
+                        .as_f64()
+                        .unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_area() {
+            let (status, json) = get_json("/api/v1/mountains?area=%E5%AF%8C%E5%A3%AB%E5%B1%B1%E3%81%A8%E3%81%9D%E3%81%AE%E5%91%A8%E8%BE%BA").await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert!(!mountains.is_empty());
+            assert!(mountains.iter().all(|m| m["area"] == "富士山とその周辺"));
+
+            let (status, json) =
+                get_json("/api/v1/mountains/geosearch?box=(138.0,35.0),(139.5,36.0)&area=%E5%AF%8C%E5%A3%AB%E5%B1%B1%E3%81%A8%E3%81%9D%E3%81%AE%E5%91%A8%E8%BE%BA")
+                    .await;
+            assert_eq!(status, StatusCode::OK);
+            let box_mountains = json["mountains"].as_array().unwrap();
+            assert!(!box_mountains.is_empty());
+            assert!(box_mountains
+                .iter()
+                .all(|m| m["area"] == "富士山とその周辺"));
+        }
+
+        #[tokio::test]
+        async fn test_get_mountain() {
+            let (status, json) = get_json("/api/v1/mountains/5").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["name"], "羅臼岳");
+            assert!(!json["location"]["gsiUrl"].as_str().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_get_mountain_not_found() {
+            let (status, _) = get_json("/api/v1/mountains/99999").await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains() {
+            let (status, json) = get_json("/api/v1/mountains?tag=1&limit=10").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 100);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 10);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_with_cursor() {
+            let base_uri = "/api/v1/mountains?tag=1&sort=elevation.desc&limit=30";
+            let (status, first_page) = get_json(base_uri).await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(first_page["prevCursor"].is_null());
+
+            let mut ids: Vec<i64> = Vec::new();
+            let mut page = first_page.clone();
+            loop {
+                ids.extend(
+                    page["mountains"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|m| m["id"].as_i64().unwrap()),
+                );
+                let Some(next_cursor) = page["nextCursor"].as_str() else {
+                    break;
+                };
+                let (status, next_page) =
+                    get_json(&format!("{}&cursor={}", base_uri, next_cursor)).await;
+                assert_eq!(status, StatusCode::OK);
+                assert_eq!(next_page["total"], 100);
+                page = next_page;
+            }
+            assert_eq!(ids.len(), 100);
+            let mut unique_ids = ids.clone();
+            unique_ids.sort();
+            unique_ids.dedup();
+            assert_eq!(unique_ids.len(), 100);
+
+            // 最後のページから前のページに戻れる
+            let prev_cursor = page["prevCursor"].as_str().unwrap();
+            let (status, prev_page) =
+                get_json(&format!("{}&cursor={}", base_uri, prev_cursor)).await;
+            assert_eq!(status, StatusCode::OK);
+            let prev_ids: Vec<i64> = prev_page["mountains"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| m["id"].as_i64().unwrap())
+                .collect();
+            assert_eq!(prev_ids, ids[60..90]);
+
+            let (status, _) = get_json(&format!(
+                "/api/v1/mountains?sort=name.asc&cursor={}",
+                first_page["nextCursor"].as_str().unwrap()
+            ))
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_with_facets() {
+            let (status, json) =
+                get_json("/api/v1/mountains?tag=1&limit=5&facets=tag,elevation_band").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 5);
+            assert_eq!(json["facets"]["tag"][0]["value"], "百名山");
+            assert_eq!(json["facets"]["tag"][0]["count"], 100);
+            let band_total: u64 = json["facets"]["elevation_band"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|band| band["count"].as_u64().unwrap())
+                .sum();
+            assert_eq!(band_total, 100);
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)&tag=1&facets=prefecture",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let prefectures: Vec<&str> = json["facets"]["prefecture"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["value"].as_str().unwrap())
+                .collect();
+            assert_eq!(prefectures, ["山梨県", "静岡県"]);
+
+            let (status, json) = get_json("/api/v1/mountains?limit=1").await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json.get("facets").is_none());
+
+            let (status, _) = get_json("/api/v1/mountains?facets=name").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_with_name_match() {
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?name={}&match=prefix",
+                encode_query("富士")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 2);
+
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?name={}&match=exact",
+                encode_query("富士山<剣ヶ峯>")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 1);
+
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)&name={}&match=prefix",
+                encode_query("富士")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"][0]["name"], "富士山<剣ヶ峯>");
+
+            // 既定では正規表現のメタ文字をそのまま照合する
+            let (status, json) = get_json("/api/v1/mountains?name=.%2A").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 0);
+
+            let (status, _) = get_json("/api/v1/mountains?name=(a%2B)%2B&match=regex").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let (status, _) = get_json("/api/v1/mountains?name=a&match=fuzzy").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_kana_and_romaji() {
+            for name in ["フジサン", "ﾌｼﾞｻﾝ", "fujisan", "Huzisan"] {
+                let (status, json) =
+                    get_json(&format!("/api/v1/mountains?name={}", encode_query(name))).await;
+                assert_eq!(status, StatusCode::OK);
+                assert_eq!(json["mountains"][0]["name"], "富士山<剣ヶ峯>", "{}", name);
+            }
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/geosearch?box=(137.5,36.2),(137.8,36.5)&name=yarigatake",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"][0]["name"], "槍ヶ岳");
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_name_variant() {
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?name={}",
+                encode_query("槍ケ岳")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let mountain = json["mountains"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|m| m["name"] == "槍ヶ岳")
+                .unwrap();
+            assert_eq!(mountain["matchedVariant"], "槍ヶ岳");
+
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/geosearch?box=(137.3,35.8),(137.6,36.0)&name={}",
+                encode_query("御岳山")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"][0]["name"], "御嶽山<剣ヶ峰>");
+            assert_eq!(json["mountains"][0]["matchedVariant"], "御嶽山");
+
+            let (_, json) = get_json("/api/v1/mountains?limit=1").await;
+            assert!(json["mountains"][0].get("matchedVariant").is_none());
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_alias() {
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?name={}",
+                encode_query("伯耆大山")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 1);
+            let mountain = &json["mountains"][0];
+            assert_eq!(mountain["name"], "大山<剣ヶ峰>");
+            assert_eq!(mountain["aliases"].as_array().unwrap().len(), 2);
+            assert_eq!(mountain["matchedAlias"]["name"], "伯耆大山");
+            assert_eq!(mountain["matchedAlias"]["nameKana"], "ほうきだいせん");
+            assert_eq!(mountain["matchedAlias"]["type"], "local");
+            assert!(mountain.get("matchedVariant").is_none());
+
+            let (_, json) = get_json("/api/v1/mountains?name=shiribeshi").await;
+            assert_eq!(json["mountains"][0]["name"], "羊蹄山（蝦夷富士）");
+            assert_eq!(json["mountains"][0]["matchedAlias"]["type"], "historic");
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_filter_expression() {
+            let q = r#"elevation>=3000 AND (tag:百名山 OR tag:二百名山) AND pref:長野県 AND area:"飛騨山脈*""#;
+            let (status, json) =
+                get_json(&format!("/api/v1/mountains?q={}", encode_query(q))).await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert!(mountains.iter().any(|m| m["name"] == "槍ヶ岳"));
+            assert!(mountains
+                .iter()
+                .all(|m| m["elevation"].as_u64().unwrap() >= 3000));
+
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?q={}",
+                encode_query("tag:百名山 OR (pref:長野県")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(
+                json["messages"][0],
+                "クエリパラメータ q の 12 文字目: 括弧 ( に対応する ) がありません。"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_prefectures() {
+            let (status, json) =
+                get_json("/api/v1/mountains?prefecture=20&prefecture=16&prefecture_mode=all").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 20);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert!(mountains.iter().any(|m| m["name"] == "白馬岳"));
+
+            // カンマ区切りでも同じ結果になる
+            let (_, comma_json) =
+                get_json("/api/v1/mountains?prefecture=20,16&prefecture_mode=all").await;
+            assert_eq!(comma_json["total"], 20);
+
+            let (_, any_json) = get_json("/api/v1/mountains?prefecture=20,16").await;
+            let (_, nagano_json) = get_json("/api/v1/mountains?prefecture=20").await;
+            assert!(any_json["total"].as_u64() > nagano_json["total"].as_u64());
+
+            let (status, json) =
+                get_json("/api/v1/mountains?tag=1&tag=2&prefecture_mode=every").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(
+                json["messages"][0],
+                "クエリパラメータ prefecture_mode の値が不正です。"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_sorted_by_relevance() {
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?name={}&sort=relevance&limit=5",
+                encode_query("槍ヶ岳")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 2);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert_eq!(mountains[0]["name"], "槍ヶ岳");
+            assert_eq!(mountains[1]["name"], "鹿島槍ヶ岳");
+            assert!(mountains[0]["score"].as_f64() > mountains[1]["score"].as_f64());
+            let highlight = &mountains[1]["highlights"][0];
+            assert_eq!(highlight["field"], "name");
+            assert_eq!(highlight["spans"][0]["start"], 2);
+            assert_eq!(highlight["spans"][0]["end"], 5);
+            assert!(json["nextCursor"].is_null());
+
+            let (status, json) = get_json("/api/v1/mountains?name=fuji").await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json["mountains"][0].get("score").is_none());
+            assert!(json["mountains"][0].get("highlights").is_none());
+
+            let (status, _) = get_json("/api/v1/mountains?sort=relevance").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            let (status, _) = get_json(
+                "/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)&name=fuji&sort=relevance",
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_sorted_by_multiple_keys() {
+            let (status, json) =
+                get_json("/api/v1/mountains?tag=1&sort=prefecture.asc,elevation.desc&limit=3")
+                    .await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert_eq!(mountains[0]["prefectures"][0], "北海道");
+            assert!(mountains[0]["elevation"].as_u64() >= mountains[1]["elevation"].as_u64());
+
+            // 複数キーの並び替えでもカーソルで続きのページを offset と同じ順序で取得できる
+            let next_cursor = json["nextCursor"].as_str().unwrap().to_string();
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains?tag=1&sort=prefecture.asc,elevation.desc&limit=3&cursor={}",
+                next_cursor
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let (_, offset_json) = get_json(
+                "/api/v1/mountains?tag=1&sort=prefecture.asc,elevation.desc&limit=3&offset=3",
+            )
+            .await;
+            assert_eq!(json["mountains"], offset_json["mountains"]);
+            assert!(json["prevCursor"].is_string());
+
+            // 異なる並び順で取得したカーソルは指定できない
+            let (status, _) = get_json(&format!(
+                "/api/v1/mountains?tag=1&sort=elevation.desc,prefecture.asc&cursor={}",
+                next_cursor
+            ))
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)&sort=distance.asc&lat=35.3606&lng=138.7274",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"][0]["name"], "富士山<剣ヶ峯>");
+
+            let (status, json) = get_json("/api/v1/mountains?sort=distance.asc").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(json["messages"][0], "クエリパラメータ lat は必須です。");
+
+            let (status, _) = get_json("/api/v1/mountains?sort=elevation.asc,elevation.desc").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_suggest_mountains() {
+            let (status, json) = get_json("/api/v1/mountains/suggest?q=fuji&limit=2").await;
+            assert_eq!(status, StatusCode::OK);
+            let suggestions = json["suggestions"].as_array().unwrap();
+            assert_eq!(suggestions.len(), 2);
+            assert_eq!(suggestions[0]["name"], "富士山<剣ヶ峯>");
+            assert_eq!(suggestions[0]["nameKana"], "ふじさん<けんがみね>");
+            assert_eq!(suggestions[0]["prefectures"][0], "山梨県");
+            assert!(suggestions[0].get("location").is_none());
+
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/suggest?q={}",
+                encode_query("マッカリ")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["suggestions"][0]["name"], "羊蹄山（蝦夷富士）");
+
+            let (status, _) = get_json("/api/v1/mountains/suggest?q=&limit=100").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_box() {
+            let (status, json) =
+                get_json("/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)&tag=1").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 1);
+            assert_eq!(json["mountains"][0]["name"], "富士山<剣ヶ峯>");
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_box_sorted_by_multiple_keys() {
+            let (status, json) = get_json(
+                "/api/v1/mountains/geosearch?box=(138.0,35.0),(139.5,36.5)&sort=prefecture.asc,elevation.desc",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+
+            // 検索と同様に都道府県コードの昇順、同じ都道府県では標高の降順に並ぶ
+            let prefecture_names: Vec<String> = MountainPrefecture::all()
+                .into_iter()
+                .map(|prefecture| prefecture.name)
+                .collect();
+            let keys: Vec<(usize, u64)> = json["mountains"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|mountain| {
+                    let code = mountain["prefectures"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .filter_map(|name| prefecture_names.iter().position(|p| name == p))
+                        .min()
+                        .unwrap();
+                    (code, mountain["elevation"].as_u64().unwrap())
+                })
+                .collect();
+            assert!(keys.first().unwrap().0 < keys.last().unwrap().0);
+            assert!(keys
+                .windows(2)
+                .all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 >= w[1].1)));
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_box_with_tags() {
+            let box_uri = "/api/v1/mountains/geosearch?box=(138.0,35.0),(139.5,36.5)";
+            let (_, hyakumeizan) = get_json(&format!("{}&tag=1", box_uri)).await;
+            let (_, nihyakumeizan) = get_json(&format!("{}&tag=2", box_uri)).await;
+
+            // 検索と同様に複数のタグを指定できる
+            let (status, json) = get_json(&format!("{}&tag=1,2", box_uri)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(
+                json["total"].as_u64().unwrap(),
+                hyakumeizan["total"].as_u64().unwrap() + nihyakumeizan["total"].as_u64().unwrap()
+            );
+
+            // 繰り返し指定した tag はカンマ区切りと同じ結果になる
+            let (status, repeated) = get_json(&format!("{}&tag=1&tag=2", box_uri)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(repeated, json);
+
+            let (status, json) = get_json(&format!("{}&tag=1,2&tag_mode=all", box_uri)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 0);
+            let (status, json) = get_json(&format!("{}&tag=1&tag=2&tag_mode=all", box_uri)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 0);
+
+            let (status, _) = get_json(&format!("{}&tag=1&tag_mode=invalid", box_uri)).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_box_with_page() {
+            let (status, json) =
+                get_json("/api/v1/mountains/geosearch?box=(122.0,20.0),(154.0,46.0)").await;
+            assert_eq!(status, StatusCode::OK);
+            let total = json["total"].as_u64().unwrap();
+            assert!(total > 100);
+            assert_eq!(json["offset"], 0);
+            assert_eq!(json["limit"], 100);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 100);
+
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/geosearch?box=(122.0,20.0),(154.0,46.0)&offset={}&limit=50",
+                total - 10
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], total);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 10);
+
+            let (status, _) =
+                get_json("/api/v1/mountains/geosearch?box=(122.0,20.0),(154.0,46.0)&limit=101")
+                    .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_surroundings() {
+            let (status, json) = get_json("/api/v1/mountains/5/surroundings?distance=10000").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["distance"], 10000);
+            assert!(!json["mountains"].as_array().unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_find_surroundings_distance_and_bearing() {
+            let (status, json) =
+                get_json("/api/v1/mountains/396/surroundings?distance=20000").await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert!(mountains
+                .windows(2)
+                .all(|w| w[0]["distance"].as_u64() <= w[1]["distance"].as_u64()));
+
+            let kenashi = mountains.iter().find(|m| m["name"] == "毛無山").unwrap();
+            assert_eq!(kenashi["distance"], 17752);
+            assert_eq!(kenashi["compassPoint"], "西北西");
+            assert_eq!(kenashi["elevationDiff"], -1812);
+            assert!((290.0..300.0).contains(&kenashi["bearing"].as_f64().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_find_surroundings_with_page() {
+            let (status, json) =
+                get_json("/api/v1/mountains/396/surroundings?distance=30000").await;
+            assert_eq!(status, StatusCode::OK);
+            let total = json["total"].as_u64().unwrap();
+            assert_eq!(json["mountains"].as_array().unwrap().len() as u64, total);
+            assert!(total > 2);
+            let farthest = json["mountains"][total as usize - 1]["name"].clone();
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/396/surroundings?distance=30000&sort=distance.desc&offset=0&limit=2",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], total);
+            assert_eq!(json["offset"], 0);
+            assert_eq!(json["limit"], 2);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 2);
+            assert_eq!(json["mountains"][0]["name"], farthest);
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/396/surroundings?distance=30000&prefecture=19&sort=elevation.desc",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert!(mountains.iter().all(|m| m["prefectures"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::Value::from("山梨県"))));
+            assert!(mountains
+                .windows(2)
+                .all(|w| w[0]["elevation"].as_u64() >= w[1]["elevation"].as_u64()));
+
+            let (status, json) =
+                get_json("/api/v1/mountains/396/surroundings?sort=distance&tag=99").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(json["messages"].as_array().unwrap().len(), 2);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_elevation() {
+            let (status, json) = get_json("/api/v1/mountains?elevation_min=3000").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 23);
+
+            let (status, json) =
+                get_json("/api/v1/mountains?prefecture=13&elevation_max=999&sort=elevation.desc")
+                    .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 14);
+            assert!(json["mountains"][0]["elevation"].as_u64().unwrap() <= 999);
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)&elevation_min=1500&elevation_max=2500",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(json["mountains"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|m| (1500..=2500).contains(&m["elevation"].as_u64().unwrap())));
+
+            let (status, _) =
+                get_json("/api/v1/mountains?elevation_min=2500&elevation_max=1500").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_surroundings_by_elevation() {
+            let (status, json) =
+                get_json("/api/v1/mountains/396/surroundings?distance=20000&elevation_min=1700")
+                    .await;
+            assert_eq!(status, StatusCode::OK);
+            let names: Vec<&str> = json["mountains"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| m["name"].as_str().unwrap())
+                .collect();
+            assert_eq!(names, vec!["毛無山", "節刀ヶ岳"]);
+
+            let (status, _) =
+                get_json("/api/v1/mountains/396/surroundings?elevation_min=high").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_nearby() {
+            let (status, json) = get_json(
+                "/api/v1/mountains/nearby?lat=35.36083333333333&lng=138.7275&distance=20000",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["distance"], 20000);
+
+            let mountains = json["mountains"].as_array().unwrap();
+            assert_eq!(mountains[0]["name"], "富士山<剣ヶ峯>");
+            assert_eq!(mountains[0]["distance"], 0);
+            assert_eq!(mountains[1]["name"], "愛鷹山<越前岳>");
+            assert!(mountains
+                .windows(2)
+                .all(|w| w[0]["distance"].as_u64() <= w[1]["distance"].as_u64()));
+            assert!(mountains
+                .iter()
+                .all(|m| (0.0..360.0).contains(&m["bearing"].as_f64().unwrap())));
+
+            let (status, json) = get_json(
+                "/api/v1/mountains/nearby?lat=35.36083333333333&lng=138.7275&distance=20000&tag=1",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_find_nearby_invalid_params() {
+            let (status, json) = get_json("/api/v1/mountains/nearby?lng=138.7").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(json["messages"].as_array().unwrap().len(), 1);
+
+            let (status, _) =
+                get_json("/api/v1/mountains/nearby?lat=35.3&lng=138.7&distance=100001").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_nearest() {
+            // 距離の上限なしに k 件を返す
+            let (status, json) =
+                get_json("/api/v1/mountains/nearest?lat=35.36083333333333&lng=138.7275&k=3").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["k"], 3);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert_eq!(mountains.len(), 3);
+            assert_eq!(mountains[0]["name"], "富士山<剣ヶ峯>");
+            assert_eq!(mountains[1]["name"], "愛鷹山<越前岳>");
+
+            // 周辺に山岳のない地点でも k 件を返す
+            let (status, json) =
+                get_json("/api/v1/mountains/nearest?lat=30.0&lng=150.0&k=2&elevation_min=3000")
+                    .await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert_eq!(mountains.len(), 2);
+            assert!(mountains
+                .iter()
+                .all(|m| m["elevation"].as_u64().unwrap() >= 3000));
+            assert!(mountains[0]["distance"].as_u64() <= mountains[1]["distance"].as_u64());
+
+            let (status, json) = get_json("/api/v1/mountains/nearest?lat=35.3&lng=138.7").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["k"], 5);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 5);
+        }
+
+        #[tokio::test]
+        async fn test_find_nearest_to_mountain() {
+            let (status, json) = get_json("/api/v1/mountains/396/nearest?k=2&tag=1").await;
+            assert_eq!(status, StatusCode::OK);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert_eq!(mountains.len(), 2);
+            assert!(mountains.iter().all(|m| m["id"] != 396));
+            assert!(mountains.iter().all(|m| m["tags"]
+                .as_array()
+                .unwrap()
+                .contains(&serde_json::json!("百名山"))));
+
+            let (status, _) = get_json("/api/v1/mountains/99999/nearest").await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, _) = get_json("/api/v1/mountains/396/nearest?k=101").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let (status, _) = get_json("/api/v1/mountains/nearest?lat=35.3&k=3").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        fn encode_query(value: &str) -> String {
+            value
+                .bytes()
+                .map(|b| match b {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' => {
+                        (b as char).to_string()
+                    }
+                    _ => format!("%{:02X}", b),
+                })
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_polygon() {
+            // ボックス検索と同じ範囲の四角形
+            let geometry = r#"{"type":"Polygon","coordinates":[[[138.5,35.2],[138.9,35.2],[138.9,35.5],[138.5,35.5],[138.5,35.2]]]}"#;
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/geosearch/polygon?geometry={}&tag=1",
+                encode_query(geometry)
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 1);
+            assert_eq!(json["mountains"][0]["name"], "富士山<剣ヶ峯>");
+
+            let (_, box_json) =
+                get_json("/api/v1/mountains/geosearch?box=(138.5,35.2),(138.9,35.5)").await;
+            let box_total = box_json["total"].as_u64().unwrap();
+
+            let router = create_test_router().await;
+            let geometry = r#"{"type":"MultiPolygon","coordinates":[[[[138.5,35.2],[138.9,35.2],[138.9,35.5],[138.5,35.2]]]]}"#;
+            let (status, _, json) = send(
+                &router,
+                json_request(
+                    "POST",
+                    "/api/v1/mountains/geosearch/polygon?sort=elevation.desc",
+                    None,
+                    geometry,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let total = json["total"].as_u64().unwrap();
+            assert!(total > 0 && total < box_total);
+            let mountains = json["mountains"].as_array().unwrap();
+            assert!(mountains
+                .windows(2)
+                .all(|w| w[0]["elevation"].as_u64() >= w[1]["elevation"].as_u64()));
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_polygon_page() {
+            let geometry = r#"{"type":"Polygon","coordinates":[[[138.5,35.2],[138.9,35.2],[138.9,35.5],[138.5,35.5],[138.5,35.2]]]}"#;
+            let polygon_uri = |params: &str| {
+                format!(
+                    "/api/v1/mountains/geosearch/polygon?geometry={}&{}",
+                    encode_query(geometry),
+                    params
+                )
+            };
+
+            // 範囲検索と同様に繰り返し指定した tag はカンマ区切りと同じ結果になる
+            let (status, json) = get_json(&polygon_uri("tag=1,2")).await;
+            assert_eq!(status, StatusCode::OK);
+            let (status, repeated) = get_json(&polygon_uri("tag=1&tag=2")).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(repeated, json);
+
+            // 範囲検索と同様に取得範囲を切り出し、件数は取得範囲に関係なく数える
+            let (_, all_json) = get_json(&polygon_uri("sort=elevation.desc")).await;
+            let (status, json) =
+                get_json(&polygon_uri("sort=elevation.desc&offset=1&limit=2")).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], all_json["total"]);
+            assert_eq!(json["offset"], 1);
+            assert_eq!(json["limit"], 2);
+            let all_mountains = all_json["mountains"].as_array().unwrap();
+            assert_eq!(
+                json["mountains"].as_array().unwrap()[..],
+                all_mountains[1..3]
+            );
+
+            // 山名の一致方法を指定できる
+            let (status, json) = get_json(&polygon_uri(&format!(
+                "name={}&match=exact",
+                encode_query("富士山<剣ヶ峯>")
+            )))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["total"], 1);
+
+            let (status, _) = get_json(&polygon_uri("limit=0")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            let (status, _) = get_json(&polygon_uri("sort=relevance")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_mountains_by_polygon_invalid_geometry() {
+            let (status, _) = get_json("/api/v1/mountains/geosearch/polygon").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let (status, _) = get_json(&format!(
+                "/api/v1/mountains/geosearch/polygon?geometry={}",
+                encode_query(r#"{"type":"Point","coordinates":[138.7,35.3]}"#)
+            ))
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let router = create_test_router().await;
+            let geometry = r#"{"type":"Polygon","coordinates":[[[138.0,35.0],[139.0,36.0],[139.0,35.0],[138.0,36.0],[138.0,35.0]]]}"#;
+            let (status, _, json) = send(
+                &router,
+                json_request(
+                    "POST",
+                    "/api/v1/mountains/geosearch/polygon",
+                    None,
+                    geometry,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert!(json["messages"][0].as_str().unwrap().contains("自己交差"));
+
+            let (status, _, _) = send(
+                &router,
+                json_request("POST", "/api/v1/mountains/geosearch/polygon", None, "{"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn test_find_corridor() {
+            // 愛鷹山<越前岳> から富士山<剣ヶ峯> を経て毛無山へ向かうライン
+            let line = r#"{"type":"LineString","coordinates":[[138.80666666666667,35.2325],[138.7275,35.36083333333333],[138.5433333333333,35.41777777777778]]}"#;
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/corridor?line={}&buffer=1000",
+                encode_query(line)
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["buffer"], 1000);
+            let length = json["length"].as_u64().unwrap();
+
+            let mountains = json["mountains"].as_array().unwrap();
+            let names: Vec<&str> = mountains
+                .iter()
+                .map(|m| m["name"].as_str().unwrap())
+                .collect();
+            let fuji = names.iter().position(|n| *n == "富士山<剣ヶ峯>").unwrap();
+            assert!(names.iter().position(|n| n.starts_with("愛鷹山")).unwrap() < fuji);
+            assert!(names.iter().position(|n| *n == "毛無山").unwrap() > fuji);
+            assert!(mountains
+                .windows(2)
+                .all(|w| w[0]["alongTrack"].as_u64() <= w[1]["alongTrack"].as_u64()));
+            assert!(mountains
+                .iter()
+                .all(|m| m["crossTrack"].as_u64().unwrap() <= 1000
+                    && m["alongTrack"].as_u64().unwrap() <= length));
+
+            let router = create_test_router().await;
+            let (status, _, json) = send(
+                &router,
+                json_request(
+                    "POST",
+                    "/api/v1/mountains/corridor?buffer=1000&tag=1",
+                    None,
+                    line,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(json["mountains"].as_array().unwrap().len(), 1);
+            assert_eq!(json["mountains"][0]["crossTrack"], 0);
+        }
+
+        #[tokio::test]
+        async fn test_find_corridor_invalid_params() {
+            let (status, _) = get_json("/api/v1/mountains/corridor?buffer=1000").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let line = r#"{"type":"LineString","coordinates":[[138.7,35.3]]}"#;
+            let (status, json) = get_json(&format!(
+                "/api/v1/mountains/corridor?line={}&buffer=100001",
+                encode_query(line)
+            ))
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(json["messages"].as_array().unwrap().len(), 2);
+        }
+
+        #[tokio::test]
+        async fn test_hc_mongodb_without_mongodb() {
+            let response = create_test_router()
+                .await
+                .oneshot(
+                    Request::get("/api/v1/hc/mongo")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        }
+    }
 }
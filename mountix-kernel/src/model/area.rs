@@ -0,0 +1,97 @@
+use crate::model::mountain::{MountainBoxCoordinates, MountainPrefecture};
+use crate::model::ErrorCode;
+
+const ERR_MESSAGE_AREA_FIND_EXCEPTION: &str = "山域情報を検索中にエラーが発生しました。";
+
+/// Mountain area (山域) aggregated from mountains
+///
+/// 山岳情報から集計した山域
+#[derive(Debug)]
+pub struct Area {
+    pub name: String,
+    pub mountain_count: u64,
+    pub elevation_min: u32,
+    pub elevation_max: u32,
+    pub prefectures: Vec<String>,
+    pub bounding_box: MountainBoxCoordinates,
+}
+
+impl Area {
+    /// Returns an area with the prefectures deduplicated and ordered by prefecture code
+    ///
+    /// 都道府県を重複なく都道府県コード順に並べた山域を生成します
+    pub fn new(
+        name: String,
+        mountain_count: u64,
+        elevation_range: (u32, u32),
+        prefectures: Vec<String>,
+        bounding_box: MountainBoxCoordinates,
+    ) -> Self {
+        let mut prefectures = prefectures;
+        prefectures.sort_by_key(|name| {
+            MountainPrefecture::try_from(name.clone())
+                .map(|p| p.id)
+                .unwrap_or(u64::MAX)
+        });
+        prefectures.dedup();
+
+        Self {
+            name,
+            mountain_count,
+            elevation_min: elevation_range.0,
+            elevation_max: elevation_range.1,
+            prefectures,
+            bounding_box,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AreaFindException {
+    pub error_code: ErrorCode,
+    pub messages: Vec<String>,
+}
+
+impl AreaFindException {
+    /// Returns an error including exception error messages
+    ///
+    /// 検索時の例外エラーメッセージを含むエラーを生成します
+    ///
+    /// # Arguments
+    ///
+    /// - `error_code`: Error code
+    pub fn new_with_error_code(error_code: ErrorCode) -> Self {
+        let messages = vec![ERR_MESSAGE_AREA_FIND_EXCEPTION.to_string()];
+        Self {
+            error_code,
+            messages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_new_orders_prefectures() {
+        let area = Area::new(
+            "富士山".to_string(),
+            3,
+            (1504, 3776),
+            vec![
+                "静岡県".to_string(),
+                "山梨県".to_string(),
+                "静岡県".to_string(),
+            ],
+            MountainBoxCoordinates {
+                bottom_left: (138.5, 35.2),
+                upper_right: (138.9, 35.5),
+            },
+        );
+
+        assert_eq!(area.prefectures, vec!["山梨県", "静岡県"]);
+        assert_eq!(area.elevation_min, 1504);
+        assert_eq!(area.elevation_max, 3776);
+    }
+}
@@ -0,0 +1,507 @@
+/// Mean earth radius in meters, the same value MongoDB uses for spherical queries
+///
+/// 地球の半径 (m) で、MongoDB の球面クエリと同じ値
+pub const EARTH_RADIUS: f64 = 6_378_100.0;
+
+/// Point on the earth
+///
+/// 地球上の地点
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    /// Returns a point, or an error if the latitude or longitude is out of range
+    ///
+    /// 地点を生成します。緯度・経度が範囲外の場合はエラーを返します
+    pub fn new(latitude: f64, longitude: f64) -> anyhow::Result<Self> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(anyhow::anyhow!("Invalid latitude."));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(anyhow::anyhow!("Invalid longitude."));
+        }
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Returns the great-circle distance to the point in meters
+    ///
+    /// 指定した地点までの大圏距離 (m) を返します
+    pub fn distance_to(&self, to: &GeoPoint) -> f64 {
+        let (from_lat, to_lat) = (self.latitude.to_radians(), to.latitude.to_radians());
+        let delta_lat = to_lat - from_lat;
+        let delta_lng = (to.longitude - self.longitude).to_radians();
+
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + from_lat.cos() * to_lat.cos() * (delta_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+    }
+
+    /// Returns the initial bearing to the point in degrees clockwise from north (0 to 360)
+    ///
+    /// 指定した地点への方位角 (北を 0 とした時計回りの度数、0 以上 360 未満) を返します
+    pub fn bearing_to(&self, to: &GeoPoint) -> f64 {
+        let (from_lat, to_lat) = (self.latitude.to_radians(), to.latitude.to_radians());
+        let delta_lng = (to.longitude - self.longitude).to_radians();
+
+        let y = delta_lng.sin() * to_lat.cos();
+        let x = from_lat.cos() * to_lat.sin() - from_lat.sin() * to_lat.cos() * delta_lng.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+}
+
+/// Names of the 16 compass points clockwise from north
+///
+/// 北から時計回りの 16 方位の名称
+const COMPASS_POINTS: [&str; 16] = [
+    "北",
+    "北北東",
+    "北東",
+    "東北東",
+    "東",
+    "東南東",
+    "南東",
+    "南南東",
+    "南",
+    "南南西",
+    "南西",
+    "西南西",
+    "西",
+    "西北西",
+    "北西",
+    "北北西",
+];
+
+/// Returns the name of the 16-point compass direction nearest to the bearing
+///
+/// 方位角に最も近い 16 方位の名称 (北東など) を返します
+pub fn compass_point(bearing: f64) -> &'static str {
+    let index = (bearing.rem_euclid(360.0) / 22.5).round() as usize % COMPASS_POINTS.len();
+    COMPASS_POINTS[index]
+}
+
+/// Maximum number of positions in a polygon search geometry
+///
+/// ポリゴン検索で指定できる頂点数の上限
+pub const MAX_POLYGON_VERTICES: usize = 1000;
+
+/// GeoJSON `Polygon` or `MultiPolygon`, validated to be usable for `$geoWithin`
+///
+/// `$geoWithin` に使用できることを検証済みの GeoJSON の `Polygon` または `MultiPolygon`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoPolygon {
+    /// Polygons consisting of closed rings, where the first ring is the exterior and the rest are holes
+    ///
+    /// 閉じたリングからなるポリゴンで、最初のリングが外周、以降のリングが穴
+    pub polygons: Vec<Vec<Vec<GeoPoint>>>,
+}
+
+impl GeoPolygon {
+    /// Returns whether the geometry is a `MultiPolygon`
+    ///
+    /// `MultiPolygon` かどうかを返します
+    pub fn is_multi(&self) -> bool {
+        self.polygons.len() > 1
+    }
+
+    /// Returns whether the point is inside the polygons, treating edges as planar on longitude/latitude
+    ///
+    /// 地点がポリゴンの内側にあるかどうかを、辺を経度・緯度の平面上の線分とみなして判定します
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        self.polygons.iter().any(|rings| {
+            let mut rings = rings.iter();
+            match rings.next() {
+                Some(exterior) => {
+                    ring_contains(exterior, point) && !rings.any(|hole| ring_contains(hole, point))
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+impl TryFrom<Vec<Vec<Vec<[f64; 2]>>>> for GeoPolygon {
+    type Error = Vec<String>;
+
+    /// Validates polygons of GeoJSON positions ([longitude, latitude])
+    ///
+    /// GeoJSON の座標 ([経度, 緯度]) からなるポリゴンを検証します
+    fn try_from(polygons: Vec<Vec<Vec<[f64; 2]>>>) -> Result<Self, Self::Error> {
+        let mut errors: Vec<String> = Vec::new();
+
+        let vertices: usize = polygons.iter().flatten().map(|ring| ring.len()).sum();
+        if polygons.is_empty() || polygons.iter().any(|rings| rings.is_empty()) {
+            errors.push("geometry (ポリゴン) の座標を指定してください。".to_string());
+        } else if vertices > MAX_POLYGON_VERTICES {
+            errors.push(format!(
+                "geometry (ポリゴン) の頂点数は {} 以下にしてください。",
+                MAX_POLYGON_VERTICES
+            ));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut geo_polygons: Vec<Vec<Vec<GeoPoint>>> = Vec::new();
+        for rings in polygons {
+            let mut geo_rings: Vec<Vec<GeoPoint>> = Vec::new();
+            for ring in rings {
+                let points: Vec<GeoPoint> = match ring
+                    .iter()
+                    .map(|position| GeoPoint::new(position[1], position[0]))
+                    .collect()
+                {
+                    Ok(points) => points,
+                    Err(_) => {
+                        errors.push("geometry (ポリゴン) の座標が範囲外です。".to_string());
+                        continue;
+                    }
+                };
+
+                if points.len() < 4 {
+                    errors.push(
+                        "geometry (ポリゴン) のリングは 4 点以上の座標で構成してください。"
+                            .to_string(),
+                    );
+                } else if points.first() != points.last() {
+                    errors.push(
+                        "geometry (ポリゴン) のリングは始点と終点を同じ座標にして閉じてください。"
+                            .to_string(),
+                    );
+                } else if is_self_intersecting(&points) {
+                    errors.push("geometry (ポリゴン) のリングが自己交差しています。".to_string());
+                }
+                geo_rings.push(points);
+            }
+            geo_polygons.push(geo_rings);
+        }
+
+        if !errors.is_empty() {
+            errors.dedup();
+            return Err(errors);
+        }
+
+        Ok(Self {
+            polygons: geo_polygons,
+        })
+    }
+}
+
+/// Maximum number of positions in a line string
+///
+/// ラインで指定できる頂点数の上限
+pub const MAX_LINE_STRING_VERTICES: usize = 1000;
+
+/// GeoJSON `LineString` such as a route or a ridge traverse
+///
+/// 経路や縦走路などの GeoJSON の `LineString`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoLineString {
+    pub points: Vec<GeoPoint>,
+}
+
+/// Position of a point relative to a line
+///
+/// ラインに対する地点の位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoLineProjection {
+    /// Distance along the line from its start to the closest position in meters
+    ///
+    /// ラインの始点から最も近い位置までのライン上の距離 (m)
+    pub along_track: f64,
+    /// Distance from the line to the point in meters
+    ///
+    /// ラインから地点までの距離 (m)
+    pub cross_track: f64,
+}
+
+impl GeoLineString {
+    /// Returns the length of the line in meters
+    ///
+    /// ラインの長さ (m) を返します
+    pub fn length(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|segment| segment[0].distance_to(&segment[1]))
+            .sum()
+    }
+
+    /// Projects the point onto the closest segment of the line on the sphere
+    ///
+    /// 地点を球面上でラインの最も近い線分に投影します
+    pub fn project(&self, point: &GeoPoint) -> GeoLineProjection {
+        let mut closest = GeoLineProjection {
+            along_track: 0.0,
+            cross_track: f64::INFINITY,
+        };
+
+        let mut offset = 0.0;
+        for segment in self.points.windows(2) {
+            let (start, end) = (&segment[0], &segment[1]);
+            let length = start.distance_to(end);
+
+            let (along_track, cross_track) = if length == 0.0 {
+                (0.0, start.distance_to(point))
+            } else {
+                let distance = start.distance_to(point) / EARTH_RADIUS;
+                let angle = (start.bearing_to(point) - start.bearing_to(end)).to_radians();
+                let cross = (distance.sin() * angle.sin()).asin();
+                let along = (distance.cos() / cross.cos()).clamp(-1.0, 1.0).acos()
+                    * angle.cos().signum()
+                    * EARTH_RADIUS;
+
+                // 線分の外側に投影される場合は近い方の端点までの距離とする
+                if along < 0.0 {
+                    (0.0, start.distance_to(point))
+                } else if along > length {
+                    (length, end.distance_to(point))
+                } else {
+                    (along, cross.abs() * EARTH_RADIUS)
+                }
+            };
+
+            if cross_track < closest.cross_track {
+                closest = GeoLineProjection {
+                    along_track: offset + along_track,
+                    cross_track,
+                };
+            }
+            offset += length;
+        }
+
+        closest
+    }
+}
+
+impl TryFrom<Vec<[f64; 2]>> for GeoLineString {
+    type Error = Vec<String>;
+
+    /// Validates a line of GeoJSON positions ([longitude, latitude])
+    ///
+    /// GeoJSON の座標 ([経度, 緯度]) からなるラインを検証します
+    fn try_from(positions: Vec<[f64; 2]>) -> Result<Self, Self::Error> {
+        if positions.len() < 2 {
+            return Err(vec![
+                "line (ライン) は 2 点以上の座標で構成してください。".to_string()
+            ]);
+        }
+        if positions.len() > MAX_LINE_STRING_VERTICES {
+            return Err(vec![format!(
+                "line (ライン) の頂点数は {} 以下にしてください。",
+                MAX_LINE_STRING_VERTICES
+            )]);
+        }
+
+        let points = positions
+            .iter()
+            .map(|position| GeoPoint::new(position[1], position[0]))
+            .collect::<anyhow::Result<Vec<GeoPoint>>>()
+            .map_err(|_| vec!["line (ライン) の座標が範囲外です。".to_string()])?;
+
+        Ok(Self { points })
+    }
+}
+
+/// Returns whether the point is inside the closed ring by the even-odd rule
+fn ring_contains(ring: &[GeoPoint], point: &GeoPoint) -> bool {
+    let mut inside = false;
+    for edge in ring.windows(2) {
+        let (a, b) = (&edge[0], &edge[1]);
+        if (a.latitude > point.latitude) != (b.latitude > point.latitude) {
+            let longitude = a.longitude
+                + (point.latitude - a.latitude) / (b.latitude - a.latitude)
+                    * (b.longitude - a.longitude);
+            if point.longitude < longitude {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Returns whether any two non-adjacent edges of the closed ring intersect
+fn is_self_intersecting(ring: &[GeoPoint]) -> bool {
+    let edges = ring.len() - 1;
+    for i in 0..edges {
+        for j in (i + 1)..edges {
+            // 隣接する辺は端点を共有するため判定しない
+            if j == i + 1 || (i == 0 && j == edges - 1) {
+                continue;
+            }
+            if segments_intersect(&ring[i], &ring[i + 1], &ring[j], &ring[j + 1]) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn segments_intersect(p1: &GeoPoint, p2: &GeoPoint, q1: &GeoPoint, q2: &GeoPoint) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    (d1 == 0.0 && on_segment(q1, q2, p1))
+        || (d2 == 0.0 && on_segment(q1, q2, p2))
+        || (d3 == 0.0 && on_segment(p1, p2, q1))
+        || (d4 == 0.0 && on_segment(p1, p2, q2))
+}
+
+/// Returns the cross product of (b - a) and (c - a) on longitude/latitude
+fn orientation(a: &GeoPoint, b: &GeoPoint, c: &GeoPoint) -> f64 {
+    (b.longitude - a.longitude) * (c.latitude - a.latitude)
+        - (b.latitude - a.latitude) * (c.longitude - a.longitude)
+}
+
+/// Returns whether the collinear point `c` lies on the segment from `a` to `b`
+fn on_segment(a: &GeoPoint, b: &GeoPoint, c: &GeoPoint) -> bool {
+    c.longitude >= a.longitude.min(b.longitude)
+        && c.longitude <= a.longitude.max(b.longitude)
+        && c.latitude >= a.latitude.min(b.latitude)
+        && c.latitude <= a.latitude.max(b.latitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geo_point_new_out_of_range() {
+        assert!(GeoPoint::new(35.0, 138.0).is_ok());
+        assert!(GeoPoint::new(90.1, 138.0).is_err());
+        assert!(GeoPoint::new(35.0, -180.1).is_err());
+    }
+
+    #[test]
+    fn test_geo_point_distance_and_bearing() {
+        let fuji = GeoPoint::new(35.36083333333333, 138.7275).unwrap();
+        let kenashi = GeoPoint::new(35.41777777777778, 138.5433333333333).unwrap();
+
+        let distance = fuji.distance_to(&kenashi);
+        assert!((distance - 17_875.0).abs() < 1.0);
+        assert_eq!(fuji.distance_to(&fuji), 0.0);
+
+        // 毛無山は富士山の西北西にある
+        let bearing = fuji.bearing_to(&kenashi);
+        assert!((290.0..300.0).contains(&bearing));
+
+        let north = GeoPoint::new(36.0, 138.7275).unwrap();
+        assert!(fuji.bearing_to(&north).abs() < 1e-9);
+        let east = GeoPoint::new(35.36083333333333, 139.0).unwrap();
+        assert!((fuji.bearing_to(&east) - 90.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_compass_point() {
+        assert_eq!(compass_point(0.0), "北");
+        assert_eq!(compass_point(359.0), "北");
+        assert_eq!(compass_point(45.0), "北東");
+        assert_eq!(compass_point(101.0), "東");
+        assert_eq!(compass_point(190.0), "南");
+        assert_eq!(compass_point(292.5), "西北西");
+        assert_eq!(compass_point(-45.0), "北西");
+    }
+
+    fn square(min: f64, max: f64) -> Vec<[f64; 2]> {
+        vec![[min, min], [max, min], [max, max], [min, max], [min, min]]
+    }
+
+    #[test]
+    fn test_geo_polygon_try_from() {
+        let polygon =
+            GeoPolygon::try_from(vec![vec![square(0.0, 10.0), square(4.0, 6.0)]]).unwrap();
+        assert!(!polygon.is_multi());
+        assert!(polygon.contains(&GeoPoint::new(2.0, 2.0).unwrap()));
+        // 穴の内側は含まない
+        assert!(!polygon.contains(&GeoPoint::new(5.0, 5.0).unwrap()));
+        assert!(!polygon.contains(&GeoPoint::new(11.0, 5.0).unwrap()));
+
+        let polygon =
+            GeoPolygon::try_from(vec![vec![square(0.0, 1.0)], vec![square(2.0, 3.0)]]).unwrap();
+        assert!(polygon.is_multi());
+        assert!(polygon.contains(&GeoPoint::new(2.5, 2.5).unwrap()));
+    }
+
+    #[test]
+    fn test_geo_polygon_try_from_invalid() {
+        assert!(GeoPolygon::try_from(vec![]).is_err());
+
+        // 閉じていないリング
+        let mut ring = square(0.0, 1.0);
+        ring.pop();
+        ring.push([0.0, 0.5]);
+        let errors = GeoPolygon::try_from(vec![vec![ring]]).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("閉じて"));
+
+        // 自己交差する蝶ネクタイ型のリング
+        let ring = vec![[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+        let errors = GeoPolygon::try_from(vec![vec![ring]]).unwrap_err();
+        assert!(errors[0].contains("自己交差"));
+
+        let ring = vec![[0.0, 0.0], [1.0, 0.0], [0.0, 0.0]];
+        assert!(GeoPolygon::try_from(vec![vec![ring]]).is_err());
+
+        let ring = vec![[0.0, 0.0], [181.0, 0.0], [1.0, 1.0], [0.0, 0.0]];
+        assert!(GeoPolygon::try_from(vec![vec![ring]]).is_err());
+
+        // 頂点数の上限
+        let mut ring: Vec<[f64; 2]> = (0..MAX_POLYGON_VERTICES)
+            .map(|i| {
+                let angle = i as f64 / MAX_POLYGON_VERTICES as f64 * std::f64::consts::TAU;
+                [angle.cos(), angle.sin()]
+            })
+            .collect();
+        ring.push(ring[0]);
+        let errors = GeoPolygon::try_from(vec![vec![ring]]).unwrap_err();
+        assert!(errors[0].contains("頂点数"));
+    }
+
+    #[test]
+    fn test_geo_line_string_project() {
+        // 富士山から東へ向かうライン
+        let line =
+            GeoLineString::try_from(vec![[138.7, 35.3], [138.8, 35.3], [138.9, 35.3]]).unwrap();
+        let length = line.length();
+        assert!((length - 18_200.0).abs() < 100.0);
+
+        // ライン上の地点
+        let projection = line.project(&GeoPoint::new(35.3, 138.85).unwrap());
+        assert!((projection.along_track - length * 0.75).abs() < 10.0);
+        assert!(projection.cross_track < 10.0);
+
+        // ラインの北 (約 1.1 km) の地点
+        let projection = line.project(&GeoPoint::new(35.31, 138.75).unwrap());
+        assert!((projection.along_track - length * 0.25).abs() < 10.0);
+        assert!((projection.cross_track - 1_113.0).abs() < 5.0);
+
+        // 始点より手前の地点は始点からの距離になる
+        let point = GeoPoint::new(35.3, 138.6).unwrap();
+        let projection = line.project(&point);
+        assert_eq!(projection.along_track, 0.0);
+        assert_eq!(
+            projection.cross_track,
+            GeoPoint::new(35.3, 138.7).unwrap().distance_to(&point)
+        );
+    }
+
+    #[test]
+    fn test_geo_line_string_try_from_invalid() {
+        assert!(GeoLineString::try_from(vec![[138.7, 35.3]]).is_err());
+        assert!(GeoLineString::try_from(vec![[138.7, 35.3], [138.7, 95.0]]).is_err());
+        let positions = vec![[138.7, 35.3]; MAX_LINE_STRING_VERTICES + 1];
+        assert!(GeoLineString::try_from(positions).is_err());
+    }
+}
@@ -0,0 +1,612 @@
+/// Full-width katakana corresponding to the half-width katakana from U+FF61 to U+FF9F
+///
+/// U+FF61 から U+FF9F の半角カタカナに対応する全角カタカナ
+const HALF_WIDTH_KATAKANA: &str =
+    "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン゛゜";
+
+/// Katakana which take the voiced sound mark (dakuten), and which take the semi-voiced sound mark (handakuten)
+///
+/// 濁点を付けられるカタカナと半濁点を付けられるカタカナ
+const VOICEABLE_KATAKANA: &str = "カキクケコサシスセソタチツテトハヒフヘホ";
+const SEMI_VOICEABLE_KATAKANA: &str = "ハヒフヘホ";
+
+/// Romaji syllables of the Hepburn and Kunrei systems and the corresponding hiragana
+///
+/// ヘボン式・訓令式のローマ字の音節と対応するひらがな
+const ROMAJI_SYLLABLES: &[(&str, &str)] = &[
+    ("kya", "きゃ"),
+    ("kyu", "きゅ"),
+    ("kyo", "きょ"),
+    ("sha", "しゃ"),
+    ("shi", "し"),
+    ("shu", "しゅ"),
+    ("she", "しぇ"),
+    ("sho", "しょ"),
+    ("sya", "しゃ"),
+    ("syu", "しゅ"),
+    ("syo", "しょ"),
+    ("cha", "ちゃ"),
+    ("chi", "ち"),
+    ("chu", "ちゅ"),
+    ("che", "ちぇ"),
+    ("cho", "ちょ"),
+    ("tya", "ちゃ"),
+    ("tyu", "ちゅ"),
+    ("tyo", "ちょ"),
+    ("tsu", "つ"),
+    ("nya", "にゃ"),
+    ("nyu", "にゅ"),
+    ("nyo", "にょ"),
+    ("hya", "ひゃ"),
+    ("hyu", "ひゅ"),
+    ("hyo", "ひょ"),
+    ("mya", "みゃ"),
+    ("myu", "みゅ"),
+    ("myo", "みょ"),
+    ("rya", "りゃ"),
+    ("ryu", "りゅ"),
+    ("ryo", "りょ"),
+    ("gya", "ぎゃ"),
+    ("gyu", "ぎゅ"),
+    ("gyo", "ぎょ"),
+    ("zya", "じゃ"),
+    ("zyu", "じゅ"),
+    ("zyo", "じょ"),
+    ("dya", "ぢゃ"),
+    ("dyu", "ぢゅ"),
+    ("dyo", "ぢょ"),
+    ("bya", "びゃ"),
+    ("byu", "びゅ"),
+    ("byo", "びょ"),
+    ("pya", "ぴゃ"),
+    ("pyu", "ぴゅ"),
+    ("pyo", "ぴょ"),
+    ("ka", "か"),
+    ("ki", "き"),
+    ("ku", "く"),
+    ("ke", "け"),
+    ("ko", "こ"),
+    ("sa", "さ"),
+    ("si", "し"),
+    ("su", "す"),
+    ("se", "せ"),
+    ("so", "そ"),
+    ("ta", "た"),
+    ("ti", "ち"),
+    ("tu", "つ"),
+    ("te", "て"),
+    ("to", "と"),
+    ("na", "な"),
+    ("ni", "に"),
+    ("nu", "ぬ"),
+    ("ne", "ね"),
+    ("no", "の"),
+    ("ha", "は"),
+    ("hi", "ひ"),
+    ("hu", "ふ"),
+    ("fu", "ふ"),
+    ("he", "へ"),
+    ("ho", "ほ"),
+    ("ma", "ま"),
+    ("mi", "み"),
+    ("mu", "む"),
+    ("me", "め"),
+    ("mo", "も"),
+    ("ya", "や"),
+    ("yu", "ゆ"),
+    ("yo", "よ"),
+    ("ra", "ら"),
+    ("ri", "り"),
+    ("ru", "る"),
+    ("re", "れ"),
+    ("ro", "ろ"),
+    ("wa", "わ"),
+    ("wo", "を"),
+    ("ga", "が"),
+    ("gi", "ぎ"),
+    ("gu", "ぐ"),
+    ("ge", "げ"),
+    ("go", "ご"),
+    ("za", "ざ"),
+    ("zi", "じ"),
+    ("zu", "ず"),
+    ("ze", "ぜ"),
+    ("zo", "ぞ"),
+    ("ja", "じゃ"),
+    ("ji", "じ"),
+    ("ju", "じゅ"),
+    ("je", "じぇ"),
+    ("jo", "じょ"),
+    ("da", "だ"),
+    ("di", "ぢ"),
+    ("du", "づ"),
+    ("de", "で"),
+    ("do", "ど"),
+    ("ba", "ば"),
+    ("bi", "び"),
+    ("bu", "ぶ"),
+    ("be", "べ"),
+    ("bo", "ぼ"),
+    ("pa", "ぱ"),
+    ("pi", "ぴ"),
+    ("pu", "ぷ"),
+    ("pe", "ぺ"),
+    ("po", "ぽ"),
+    ("fa", "ふぁ"),
+    ("fi", "ふぃ"),
+    ("fe", "ふぇ"),
+    ("fo", "ふぉ"),
+    ("va", "ゔぁ"),
+    ("vi", "ゔぃ"),
+    ("vu", "ゔ"),
+    ("ve", "ゔぇ"),
+    ("vo", "ゔぉ"),
+    ("a", "あ"),
+    ("i", "い"),
+    ("u", "う"),
+    ("e", "え"),
+    ("o", "お"),
+];
+
+/// Hiragana and the corresponding Hepburn romaji, where contracted sounds come first
+///
+/// ひらがなと対応するヘボン式のローマ字 (拗音を先に照合します)
+const KANA_SYLLABLES: &[(&str, &str)] = &[
+    ("きゃ", "kya"),
+    ("きゅ", "kyu"),
+    ("きょ", "kyo"),
+    ("しゃ", "sha"),
+    ("しゅ", "shu"),
+    ("しぇ", "she"),
+    ("しょ", "sho"),
+    ("ちゃ", "cha"),
+    ("ちゅ", "chu"),
+    ("ちぇ", "che"),
+    ("ちょ", "cho"),
+    ("にゃ", "nya"),
+    ("にゅ", "nyu"),
+    ("にょ", "nyo"),
+    ("ひゃ", "hya"),
+    ("ひゅ", "hyu"),
+    ("ひょ", "hyo"),
+    ("みゃ", "mya"),
+    ("みゅ", "myu"),
+    ("みょ", "myo"),
+    ("りゃ", "rya"),
+    ("りゅ", "ryu"),
+    ("りょ", "ryo"),
+    ("ぎゃ", "gya"),
+    ("ぎゅ", "gyu"),
+    ("ぎょ", "gyo"),
+    ("じゃ", "ja"),
+    ("じゅ", "ju"),
+    ("じぇ", "je"),
+    ("じょ", "jo"),
+    ("ぢゃ", "ja"),
+    ("ぢゅ", "ju"),
+    ("ぢょ", "jo"),
+    ("びゃ", "bya"),
+    ("びゅ", "byu"),
+    ("びょ", "byo"),
+    ("ぴゃ", "pya"),
+    ("ぴゅ", "pyu"),
+    ("ぴょ", "pyo"),
+    ("ふぁ", "fa"),
+    ("ふぃ", "fi"),
+    ("ふぇ", "fe"),
+    ("ふぉ", "fo"),
+    ("ゔぁ", "va"),
+    ("ゔぃ", "vi"),
+    ("ゔぇ", "ve"),
+    ("ゔぉ", "vo"),
+    ("あ", "a"),
+    ("い", "i"),
+    ("う", "u"),
+    ("え", "e"),
+    ("お", "o"),
+    ("か", "ka"),
+    ("き", "ki"),
+    ("く", "ku"),
+    ("け", "ke"),
+    ("こ", "ko"),
+    ("さ", "sa"),
+    ("し", "shi"),
+    ("す", "su"),
+    ("せ", "se"),
+    ("そ", "so"),
+    ("た", "ta"),
+    ("ち", "chi"),
+    ("つ", "tsu"),
+    ("て", "te"),
+    ("と", "to"),
+    ("な", "na"),
+    ("に", "ni"),
+    ("ぬ", "nu"),
+    ("ね", "ne"),
+    ("の", "no"),
+    ("は", "ha"),
+    ("ひ", "hi"),
+    ("ふ", "fu"),
+    ("へ", "he"),
+    ("ほ", "ho"),
+    ("ま", "ma"),
+    ("み", "mi"),
+    ("む", "mu"),
+    ("め", "me"),
+    ("も", "mo"),
+    ("や", "ya"),
+    ("ゆ", "yu"),
+    ("よ", "yo"),
+    ("ら", "ra"),
+    ("り", "ri"),
+    ("る", "ru"),
+    ("れ", "re"),
+    ("ろ", "ro"),
+    ("わ", "wa"),
+    ("ゐ", "i"),
+    ("ゑ", "e"),
+    ("を", "o"),
+    ("ん", "n"),
+    ("が", "ga"),
+    ("ぎ", "gi"),
+    ("ぐ", "gu"),
+    ("げ", "ge"),
+    ("ご", "go"),
+    ("ざ", "za"),
+    ("じ", "ji"),
+    ("ず", "zu"),
+    ("ぜ", "ze"),
+    ("ぞ", "zo"),
+    ("だ", "da"),
+    ("ぢ", "ji"),
+    ("づ", "zu"),
+    ("で", "de"),
+    ("ど", "do"),
+    ("ば", "ba"),
+    ("び", "bi"),
+    ("ぶ", "bu"),
+    ("べ", "be"),
+    ("ぼ", "bo"),
+    ("ぱ", "pa"),
+    ("ぴ", "pi"),
+    ("ぷ", "pu"),
+    ("ぺ", "pe"),
+    ("ぽ", "po"),
+    ("ゔ", "vu"),
+    ("ぁ", "a"),
+    ("ぃ", "i"),
+    ("ぅ", "u"),
+    ("ぇ", "e"),
+    ("ぉ", "o"),
+    ("ゃ", "ya"),
+    ("ゅ", "yu"),
+    ("ょ", "yo"),
+    ("ゎ", "wa"),
+];
+
+/// Orthographic variants in mountain names and the characters they are folded into
+///
+/// 山名の表記ゆれと、まとめる先の文字
+const NAME_VARIANTS: &[(char, char)] = &[
+    ('ケ', 'ヶ'),
+    ('が', 'ヶ'),
+    ('ガ', 'ヶ'),
+    ('嶽', '岳'),
+    ('峯', '峰'),
+    ('澤', '沢'),
+    ('嶋', '島'),
+    ('嶌', '島'),
+    ('﨑', '崎'),
+    ('嵜', '崎'),
+    ('龍', '竜'),
+    ('邊', '辺'),
+    ('邉', '辺'),
+    ('髙', '高'),
+    ('冨', '富'),
+    ('濱', '浜'),
+    ('瀧', '滝'),
+    ('國', '国'),
+    ('藏', '蔵'),
+    ('眞', '真'),
+    ('舘', '館'),
+    ('の', 'ノ'),
+];
+
+/// Folds the orthographic variants in the name (`槍ケ岳` and `槍が岳` into `槍ヶ岳`, `御嶽山` into `御岳山`),
+/// together with the width and the case of alphanumerics
+///
+/// 山名の表記ゆれ (`槍ケ岳`・`槍が岳` は `槍ヶ岳`、`御嶽山` は `御岳山`) と英数字の全角・大文字をまとめます
+pub fn fold_name_variants(value: &str) -> String {
+    fold_width(value).chars().map(fold_variant_char).collect()
+}
+
+/// Folds a character of the name, which keeps the number of characters unlike `fold_name_variants`
+///
+/// 山名の 1 文字の表記ゆれをまとめます。`fold_name_variants` と異なり文字数は変わりません
+pub(crate) fn fold_variant_char(c: char) -> char {
+    let c = match c {
+        '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' => {
+            char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)
+        }
+        _ => c,
+    };
+    NAME_VARIANTS
+        .iter()
+        .find(|(variant, _)| *variant == c)
+        .map(|(_, folded)| *folded)
+        .unwrap_or(c.to_ascii_lowercase())
+}
+
+/// Folds full-width alphanumerics into ASCII and half-width katakana into full-width,
+/// converts katakana into hiragana and lowercases the value
+///
+/// 全角英数字を半角に、半角カタカナを全角に揃え、カタカナをひらがなに、英字を小文字に変換します
+pub fn normalize_kana(value: &str) -> String {
+    fold_width(value)
+        .chars()
+        .map(|c| match c {
+            // ァ (U+30A1) から ヴ (U+30F4) をひらがなに変換する。ヵ・ヶ は地名で「か」「が」などと読むため残す
+            'ァ'..='ヴ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// Converts Hepburn or Kunrei romaji into hiragana, or returns `None` if the value is not romaji
+///
+/// ヘボン式または訓令式のローマ字をひらがなに変換します。ローマ字でない場合は `None` を返します
+pub fn romaji_to_hiragana(value: &str) -> Option<String> {
+    let romaji = fold_romaji(value)?;
+    let chars: Vec<char> = romaji.chars().collect();
+
+    let mut kana = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '-' || c == '\'' || c == ' ' {
+            i += 1;
+            continue;
+        }
+
+        // 撥音: 母音・y が続かない n、訓令式・IME 入力の nn、ヘボン式で b・m・p の前の m
+        if c == 'n' && !next.is_some_and(|n| is_vowel(n) || n == 'y') {
+            let after_next = chars.get(i + 2).copied();
+            let is_double_n =
+                next == Some('n') && !after_next.is_some_and(|n| is_vowel(n) || n == 'y');
+            kana.push('ん');
+            i += if is_double_n { 2 } else { 1 };
+            continue;
+        }
+        if c == 'm' && matches!(next, Some('b' | 'm' | 'p')) {
+            kana.push('ん');
+            i += 1;
+            continue;
+        }
+
+        // 促音: 同じ子音の連続、ヘボン式の tch
+        if !is_vowel(c) && (next == Some(c) || (c == 't' && next == Some('c'))) {
+            kana.push('っ');
+            i += 1;
+            continue;
+        }
+
+        let syllable = (1..=3).rev().find_map(|len| {
+            let candidate: String = chars.get(i..i + len)?.iter().collect();
+            ROMAJI_SYLLABLES
+                .iter()
+                .find(|(romaji, _)| *romaji == candidate)
+                .map(|(_, hiragana)| (len, *hiragana))
+        });
+        let (len, hiragana) = syllable?;
+        kana.push_str(hiragana);
+        i += len;
+    }
+    Some(kana)
+}
+
+/// Converts hiragana into Hepburn romaji without long vowels (`りょうかみ` into `ryokami`),
+/// leaving the characters other than hiragana as they are
+///
+/// ひらがなを長音を省略したヘボン式のローマ字 (`りょうかみ` は `ryokami`) に変換します。
+/// ひらがな以外の文字はそのまま残します
+pub fn hiragana_to_romaji(value: &str) -> String {
+    let kana = fold_width(value);
+
+    let mut romaji = String::new();
+    let mut sokuon = false;
+    let mut rest = kana.as_str();
+    while let Some(c) = rest.chars().next() {
+        if c == 'っ' || c == 'ー' {
+            sokuon = c == 'っ';
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let Some((hiragana, syllable)) = KANA_SYLLABLES
+            .iter()
+            .find(|(hiragana, _)| rest.starts_with(hiragana))
+        else {
+            romaji.push(c);
+            sokuon = false;
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+        rest = &rest[hiragana.len()..];
+
+        // 長音の「おう」「おお」「うう」は母音を重ねない
+        if (*syllable == "u" && (romaji.ends_with('o') || romaji.ends_with('u')))
+            || (*syllable == "o" && romaji.ends_with('o'))
+        {
+            continue;
+        }
+
+        if std::mem::take(&mut sokuon) {
+            if syllable.starts_with("ch") {
+                romaji.push('t');
+            } else if let Some(consonant) = syllable.chars().next().filter(|c| !is_vowel(*c)) {
+                romaji.push(consonant);
+            }
+        }
+        romaji.push_str(syllable);
+    }
+    romaji
+}
+
+/// Returns the romaji value in the same form as `hiragana_to_romaji`, or `None` if the value is not romaji
+///
+/// ローマ字を `hiragana_to_romaji` と同じ表記に揃えます。ローマ字でない場合は `None` を返します
+pub fn normalize_romaji(value: &str) -> Option<String> {
+    let romaji = fold_romaji(value)?;
+    match romaji_to_hiragana(&romaji) {
+        Some(hiragana) => Some(hiragana_to_romaji(&hiragana)),
+        // 入力途中の子音 (fuj など) はそのまま照合する
+        None => Some(romaji.replace(['-', '\'', ' '], "")),
+    }
+}
+
+/// Folds the width and the case of romaji and expands macrons and circumflexes,
+/// or returns `None` if the value contains characters other than romaji
+///
+/// ローマ字の全角・大文字を揃え、長音記号を母音の連続に展開します。ローマ字以外を含む場合は `None` を返します
+fn fold_romaji(value: &str) -> Option<String> {
+    let mut romaji = String::new();
+    for c in fold_width(value).to_lowercase().chars() {
+        match c {
+            'a'..='z' | '-' | '\'' | ' ' => romaji.push(c),
+            'ā' | 'â' => romaji.push_str("aa"),
+            'ī' | 'î' => romaji.push_str("ii"),
+            'ū' | 'û' => romaji.push_str("uu"),
+            'ē' | 'ê' => romaji.push_str("ee"),
+            'ō' | 'ô' => romaji.push_str("ou"),
+            _ => return None,
+        }
+    }
+    romaji
+        .chars()
+        .any(|c| c.is_ascii_alphabetic())
+        .then_some(romaji)
+}
+
+/// Folds full-width alphanumerics and spaces into ASCII and half-width katakana into full-width katakana
+///
+/// 全角英数字・空白を半角に、半角カタカナを全角に変換します
+fn fold_width(value: &str) -> String {
+    let mut folded = String::new();
+    for c in value.chars() {
+        match c {
+            '\u{3000}' => folded.push(' '),
+            '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' => {
+                folded.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c));
+            }
+            '\u{FF61}'..='\u{FF9F}' => {
+                let katakana = HALF_WIDTH_KATAKANA
+                    .chars()
+                    .nth((c as u32 - 0xFF61) as usize)
+                    .unwrap_or(c);
+                // 半角の濁点・半濁点は直前の文字と合成する
+                let composed = match (folded.chars().last(), katakana) {
+                    (Some('ウ'), '゛') => Some('ヴ'),
+                    (Some(last), '゛') if VOICEABLE_KATAKANA.contains(last) => {
+                        char::from_u32(last as u32 + 1)
+                    }
+                    (Some(last), '゜') if SEMI_VOICEABLE_KATAKANA.contains(last) => {
+                        char::from_u32(last as u32 + 2)
+                    }
+                    _ => None,
+                };
+                match composed {
+                    Some(composed) => {
+                        folded.pop();
+                        folded.push(composed);
+                    }
+                    None => folded.push(katakana),
+                }
+            }
+            _ => folded.push(c),
+        }
+    }
+    folded
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_kana() {
+        assert_eq!(normalize_kana("フジサン"), "ふじさん");
+        assert_eq!(normalize_kana("ﾌｼﾞｻﾝ"), "ふじさん");
+        assert_eq!(normalize_kana("ﾊﾟﾝｹ"), "ぱんけ");
+        assert_eq!(normalize_kana("ｳﾞ"), "ゔ");
+        assert_eq!(normalize_kana("ＦＵＪＩ　ｻﾝ"), "fuji さん");
+        assert_eq!(normalize_kana("富士山（剣ヶ峯）"), "富士山（剣ヶ峯）");
+    }
+
+    #[test]
+    fn test_fold_name_variants() {
+        assert_eq!(fold_name_variants("槍ケ岳"), "槍ヶ岳");
+        assert_eq!(fold_name_variants("槍が岳"), "槍ヶ岳");
+        assert_eq!(fold_name_variants("ﾔﾘｶﾞ"), "ヤリヶ");
+        assert_eq!(fold_name_variants("御嶽山"), "御岳山");
+        assert_eq!(fold_name_variants("赤石岳（峯）"), "赤石岳（峰）");
+        assert_eq!(fold_name_variants("丹澤"), "丹沢");
+        assert_eq!(fold_name_variants("仙の倉山"), "仙ノ倉山");
+        assert_eq!(fold_name_variants("Ｐ１"), "p1");
+    }
+
+    #[test]
+    fn test_romaji_to_hiragana() {
+        let kana = |romaji: &str| romaji_to_hiragana(romaji);
+
+        assert_eq!(kana("fujisan").unwrap(), "ふじさん");
+        assert_eq!(kana("huzisan").unwrap(), "ふじさん");
+        assert_eq!(kana("Yarigatake").unwrap(), "やりがたけ");
+        assert_eq!(kana("ｙａｒｉｇａｔａｋｅ").unwrap(), "やりがたけ");
+        assert_eq!(kana("tyokaisan").unwrap(), "ちょかいさん");
+        assert_eq!(kana("chōkaisan").unwrap(), "ちょうかいさん");
+        assert_eq!(kana("hakkoda-san").unwrap(), "はっこださん");
+        assert_eq!(kana("matchi").unwrap(), "まっち");
+        assert_eq!(kana("konna").unwrap(), "こんな");
+        assert_eq!(kana("kan'ei").unwrap(), "かんえい");
+        assert_eq!(kana("kannei").unwrap(), "かんねい");
+        assert_eq!(kana("shimbashi").unwrap(), "しんばし");
+        assert_eq!(kana("kinpuzan").unwrap(), "きんぷざん");
+
+        assert!(kana("富士山").is_none());
+        assert!(kana("fuj").is_none());
+        assert!(kana("--").is_none());
+    }
+
+    #[test]
+    fn test_hiragana_to_romaji() {
+        assert_eq!(hiragana_to_romaji("ふじさん"), "fujisan");
+        assert_eq!(hiragana_to_romaji("やりがたけ"), "yarigatake");
+        assert_eq!(hiragana_to_romaji("りょうかみさん"), "ryokamisan");
+        assert_eq!(hiragana_to_romaji("おおやま"), "oyama");
+        assert_eq!(hiragana_to_romaji("はっこうださん"), "hakkodasan");
+        assert_eq!(hiragana_to_romaji("はっちょうやま"), "hatchoyama");
+        assert_eq!(
+            hiragana_to_romaji("あとさぬぷり（いおうざん）"),
+            "atosanupuri（iozan）"
+        );
+        assert_eq!(
+            hiragana_to_romaji("ふじさん<けんがみね>"),
+            "fujisan<kengamine>"
+        );
+    }
+
+    #[test]
+    fn test_normalize_romaji() {
+        assert_eq!(normalize_romaji("Fuji-san").unwrap(), "fujisan");
+        assert_eq!(normalize_romaji("huzisan").unwrap(), "fujisan");
+        assert_eq!(normalize_romaji("Ryōkami").unwrap(), "ryokami");
+        assert_eq!(normalize_romaji("fuj").unwrap(), "fuj");
+        assert!(normalize_romaji("ふじ").is_none());
+    }
+}
@@ -1,7 +1,13 @@
 use std::marker::PhantomData;
 
+pub mod area;
+pub mod geo;
+pub mod kana;
 pub mod mountain;
+pub mod mountain_filter;
+pub mod prefecture;
 pub mod surrounding_mountain;
+pub mod tag;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Id<T> {
@@ -18,6 +24,12 @@ impl<T> Id<T> {
     }
 }
 
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.value)
+    }
+}
+
 impl<T> From<i32> for Id<T> {
     fn from(value: i32) -> Self {
         Self {
@@ -42,5 +54,9 @@ impl<T> TryFrom<String> for Id<T> {
 pub enum ErrorCode {
     InvalidId,
     InvalidQueryParam,
+    InvalidRequestBody,
+    NotFound,
+    VersionConflict,
+    PreconditionRequired,
     ServerError,
 }
@@ -1,9 +1,34 @@
+use crate::model::geo::{GeoPoint, GeoPolygon};
+use crate::model::kana::{
+    fold_name_variants, fold_variant_char, hiragana_to_romaji, normalize_kana, normalize_romaji,
+    romaji_to_hiragana,
+};
+use crate::model::mountain_filter::MountainFilterExpr;
+use crate::model::tag::{Tag, TagRegistry};
 use crate::model::{ErrorCode, Id};
-use regex::Regex;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use regex::{Regex, RegexBuilder};
+use regex_syntax::ast::parse::Parser;
+use regex_syntax::ast::Ast;
+use std::collections::BTreeMap;
 
 const ERR_MESSAGE_FIND_EXCEPTION: &str = "山岳情報を検索中にエラーが発生しました。";
+const ERR_MESSAGE_WRITE_EXCEPTION: &str = "山岳情報を更新中にエラーが発生しました。";
 
-#[derive(Debug)]
+/// Upper limits of the length and the number of repetitions of the `regex` name pattern
+///
+/// 正規表現で山名を検索する場合のパターンの長さと繰り返しの数の上限
+const MAX_NAME_REGEX_LENGTH: usize = 64;
+const MAX_NAME_REGEX_REPETITIONS: usize = 5;
+
+/// Approximate bounds of Japan (latitude, longitude)
+///
+/// 日本の領域の概略範囲 (沖ノ鳥島・南鳥島・与那国島・択捉島を含む)
+const JAPAN_LATITUDE_RANGE: (f64, f64) = (20.0, 46.0);
+const JAPAN_LONGITUDE_RANGE: (f64, f64) = (122.0, 154.0);
+
+#[derive(Debug, Clone)]
 pub struct Mountain {
     pub id: Id<Mountain>,
     pub name: String,
@@ -13,15 +38,18 @@ pub struct Mountain {
     pub elevation: u32,
     pub location: MountainLocation,
     pub tags: Vec<String>,
+    pub aliases: Vec<MountainAlias>,
+    pub version: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MountainLocation {
     pub latitude: f64,
     pub longitude: f64,
     pub gsi_url: String,
 }
 
+#[derive(Debug, Clone)]
 pub struct MountainData {
     pub name: String,
     pub name_kana: String,
@@ -30,6 +58,57 @@ pub struct MountainData {
     pub elevation: u32,
     pub location: MountainLocation,
     pub tags: Vec<String>,
+    pub aliases: Vec<MountainAlias>,
+}
+
+/// Kind of the alternate name of a mountain
+///
+/// 山の別名の種類
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MountainAliasType {
+    /// Official name other than the one in the data, such as the name on the topographic map
+    Official,
+    /// Local name, such as 蝦夷富士
+    Local,
+    /// Former name
+    Historic,
+    /// Name in Ainu
+    Ainu,
+}
+
+impl MountainAliasType {
+    pub fn to_key(&self) -> String {
+        match self {
+            MountainAliasType::Official => "official".to_string(),
+            MountainAliasType::Local => "local".to_string(),
+            MountainAliasType::Historic => "historic".to_string(),
+            MountainAliasType::Ainu => "ainu".to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for MountainAliasType {
+    type Error = anyhow::Error;
+
+    fn try_from(alias_type: String) -> Result<Self, Self::Error> {
+        match alias_type.as_str() {
+            "official" => Ok(MountainAliasType::Official),
+            "local" => Ok(MountainAliasType::Local),
+            "historic" => Ok(MountainAliasType::Historic),
+            "ainu" => Ok(MountainAliasType::Ainu),
+            _ => Err(anyhow::anyhow!("Invalid alias type.")),
+        }
+    }
+}
+
+/// Alternate name of a mountain with its reading
+///
+/// 山の別名とその読み
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainAlias {
+    pub name: String,
+    pub name_kana: String,
+    pub alias_type: MountainAliasType,
 }
 
 impl Mountain {
@@ -43,8 +122,74 @@ impl Mountain {
             elevation: data.elevation,
             location: data.location,
             tags: data.tags,
+            aliases: data.aliases,
+            version: 0,
         }
     }
+
+    /// Returns the mountain with the version used for optimistic locking
+    ///
+    /// 楽観的排他制御に用いるバージョンを設定します
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+}
+
+impl MountainData {
+    /// Validates the invariants of mountain data, resolving the tags against the registry
+    ///
+    /// 山岳情報の不変条件を検証し、違反している場合はエラーメッセージを返します (タグはタグレジストリから解決します)
+    pub fn validate(&self, tags: &TagRegistry) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push("name (山名) は必須です。".to_string());
+        }
+        if self.name_kana.trim().is_empty() {
+            errors.push("nameKana (山名かな) は必須です。".to_string());
+        }
+        if self.area.trim().is_empty() {
+            errors.push("area (山域) は必須です。".to_string());
+        }
+
+        if self.prefectures.is_empty() {
+            errors.push("prefectures (都道府県) は必須です。".to_string());
+        }
+        for prefecture in &self.prefectures {
+            if !MountainPrefecture::is_valid_name(prefecture) {
+                errors.push(format!(
+                    "prefectures (都道府県) の値 {} が不正です。",
+                    prefecture
+                ));
+            }
+        }
+
+        for tag in &self.tags {
+            if !tags.contains_name(tag) {
+                errors.push(format!("tags (タグ) の値 {} が不正です。", tag));
+            }
+        }
+
+        for alias in &self.aliases {
+            if alias.name.trim().is_empty() || alias.name_kana.trim().is_empty() {
+                errors.push("aliases (別名) の name、nameKana は必須です。".to_string());
+            }
+        }
+
+        if self.elevation == 0 {
+            errors.push("elevation (標高) は 0 より大きい値を指定してください。".to_string());
+        }
+
+        if !self.location.is_in_japan() {
+            errors.push("location (位置情報) は日本国内の座標を指定してください。".to_string());
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
 }
 
 impl MountainLocation {
@@ -55,219 +200,1298 @@ impl MountainLocation {
             gsi_url,
         }
     }
+
+    /// Returns a location with the GSI map URL derived from its coordinates
+    ///
+    /// 座標から地理院地図の URL を生成して位置情報を生成します
+    pub fn from_coordinates(latitude: f64, longitude: f64) -> Self {
+        let gsi_url = format!("https://maps.gsi.go.jp/#15/{}/{}", latitude, longitude);
+        Self::new(latitude, longitude, gsi_url)
+    }
+
+    fn is_in_japan(&self) -> bool {
+        (JAPAN_LATITUDE_RANGE.0..=JAPAN_LATITUDE_RANGE.1).contains(&self.latitude)
+            && (JAPAN_LONGITUDE_RANGE.0..=JAPAN_LONGITUDE_RANGE.1).contains(&self.longitude)
+    }
+}
+
+/// How the name is matched against the name and the kana name of mountains
+///
+/// 山名と山名かなに対する山名の照合方法
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum MountainNameMatchType {
+    #[default]
+    Contains,
+    Prefix,
+    Exact,
+    Regex,
+}
+
+impl TryFrom<String> for MountainNameMatchType {
+    type Error = anyhow::Error;
+
+    fn try_from(match_param: String) -> Result<Self, Self::Error> {
+        match match_param.as_str() {
+            "contains" => Ok(MountainNameMatchType::Contains),
+            "prefix" => Ok(MountainNameMatchType::Prefix),
+            "exact" => Ok(MountainNameMatchType::Exact),
+            "regex" => Ok(MountainNameMatchType::Regex),
+            _ => Err(anyhow::anyhow!("Invalid match value.")),
+        }
+    }
+}
+
+/// Name to search mountains by, which is matched case-insensitively
+///
+/// 山岳を検索する山名。大文字小文字を区別せずに照合します
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainNameCondition {
+    pub value: String,
+    pub match_type: MountainNameMatchType,
+}
+
+impl MountainNameCondition {
+    /// Returns a name condition, or an error if the `regex` pattern is invalid or too complex
+    ///
+    /// 山名の条件を生成します。正規表現が不正または複雑すぎる場合はエラーを返します
+    pub fn new(value: String, match_type: MountainNameMatchType) -> anyhow::Result<Self> {
+        if match_type == MountainNameMatchType::Regex {
+            validate_name_regex(&value)?;
+        }
+        Ok(Self { value, match_type })
+    }
+
+    /// Returns a condition matching the names containing the value literally
+    ///
+    /// 値を文字どおりに含む山名に一致する条件を生成します
+    pub fn contains(value: String) -> Self {
+        Self {
+            value,
+            match_type: MountainNameMatchType::Contains,
+        }
+    }
+
+    /// Returns the regular expression, where the value is escaped except for the `regex` match
+    ///
+    /// 照合に用いる正規表現を返します。`regex` 以外では値をエスケープします
+    pub fn to_pattern(&self) -> String {
+        self.literal_pattern(&self.value)
+    }
+
+    /// Returns the regular expressions for the name, the kana name and the romaji name,
+    /// where the value is normalized into hiragana and romaji except for the `regex` match
+    ///
+    /// 山名、山名かな、山名ローマ字に対する正規表現を返します。`regex` 以外では値をひらがな・ローマ字に正規化します
+    pub fn to_patterns(&self) -> MountainNamePatterns {
+        if self.match_type == MountainNameMatchType::Regex {
+            return MountainNamePatterns {
+                name: self.value.clone(),
+                folded: None,
+                kana: self.value.clone(),
+                romaji: None,
+            };
+        }
+
+        let kana = romaji_to_hiragana(&self.value).unwrap_or_else(|| normalize_kana(&self.value));
+        MountainNamePatterns {
+            name: self.to_pattern(),
+            folded: Some(self.literal_pattern(&fold_name_variants(&self.value))),
+            kana: self.literal_pattern(&kana),
+            romaji: normalize_romaji(&self.value).map(|romaji| self.literal_pattern(&romaji)),
+        }
+    }
+
+    /// Returns the part of the name which the condition matched, where the orthographic variants are folded,
+    /// or `None` if the name does not match
+    ///
+    /// 山名のうち条件に一致した部分を返します。表記ゆれはまとめて照合し、一致しない場合は `None` を返します
+    pub fn matched_variant(&self, name: &str) -> Option<String> {
+        let query: Vec<char> = fold_name_variants(&self.value).chars().collect();
+        let folded_name: Vec<char> = name.chars().map(fold_variant_char).collect();
+        let start = match self.match_type {
+            MountainNameMatchType::Regex => {
+                let regex = RegexBuilder::new(&self.value)
+                    .case_insensitive(true)
+                    .build()
+                    .ok()?;
+                return regex
+                    .find(name)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| m.as_str().to_string());
+            }
+            _ if query.is_empty() => return None,
+            MountainNameMatchType::Contains => folded_name
+                .windows(query.len())
+                .position(|chars| chars == query)?,
+            MountainNameMatchType::Prefix => folded_name.starts_with(&query).then_some(0)?,
+            MountainNameMatchType::Exact => (folded_name == query).then_some(0)?,
+        };
+        Some(name.chars().skip(start).take(query.len()).collect())
+    }
+
+    fn literal_pattern(&self, value: &str) -> String {
+        let escaped = regex::escape(value);
+        match self.match_type {
+            MountainNameMatchType::Contains => escaped,
+            MountainNameMatchType::Prefix => format!("^{}", escaped),
+            MountainNameMatchType::Exact => format!("^{}$", escaped),
+            MountainNameMatchType::Regex => value.to_string(),
+        }
+    }
+}
+
+/// Regular expressions matched against the name, the kana name and the romaji name of mountains
+///
+/// 山名、山名かな、山名ローマ字に対する正規表現
+#[derive(Debug, PartialEq)]
+pub struct MountainNamePatterns {
+    pub name: String,
+    /// Pattern for the name whose orthographic variants are folded, which is absent for the `regex` match
+    pub folded: Option<String>,
+    pub kana: String,
+    /// Pattern for the romaji name, which is absent unless the value is romaji
+    pub romaji: Option<String>,
+}
+
+/// Name condition compiled into regular expressions, which matches names case-insensitively like `$regex`
+///
+/// 正規表現に変換した山名の条件。`$regex` と同様に大文字小文字を区別せずに照合します
+pub struct MountainNameMatcher {
+    name: Regex,
+    folded: Option<Regex>,
+    kana: Regex,
+    romaji: Option<Regex>,
+}
+
+impl MountainNameMatcher {
+    pub fn new(condition: &MountainNameCondition) -> anyhow::Result<Self> {
+        let patterns = condition.to_patterns();
+        let build = |pattern: &str| RegexBuilder::new(pattern).case_insensitive(true).build();
+        Ok(Self {
+            name: build(&patterns.name)?,
+            folded: patterns.folded.as_deref().map(build).transpose()?,
+            kana: build(&patterns.kana)?,
+            romaji: patterns.romaji.as_deref().map(build).transpose()?,
+        })
+    }
+
+    /// Returns whether the name, the folded name, the kana name or the romaji name matches
+    ///
+    /// 山名、表記ゆれをまとめた山名、山名かな、山名ローマ字のいずれかが一致するかを返します
+    pub fn is_match(
+        &self,
+        name: &str,
+        name_folded: &str,
+        name_kana: &str,
+        name_romaji: &str,
+    ) -> bool {
+        self.name.is_match(name)
+            || self
+                .folded
+                .as_ref()
+                .is_some_and(|folded| folded.is_match(name_folded))
+            || self.kana.is_match(name_kana)
+            || self
+                .romaji
+                .as_ref()
+                .is_some_and(|romaji| romaji.is_match(name_romaji))
+    }
+
+    /// Returns whether the name or the kana name matches, deriving the folded name and the romaji name
+    ///
+    /// 表記ゆれをまとめた山名と山名ローマ字を導出し、山名または山名かなが一致するかを返します
+    pub fn is_name_match(&self, name: &str, name_kana: &str) -> bool {
+        self.is_match(
+            name,
+            &fold_name_variants(name),
+            name_kana,
+            &hiragana_to_romaji(name_kana),
+        )
+    }
+}
+
+/// Rejects patterns which are too long, have too many repetitions or nest repetitions,
+/// since they may backtrack catastrophically in MongoDB
+///
+/// MongoDB で過剰なバックトラックを起こしうる長いパターン、繰り返しの多いパターン、入れ子の繰り返しを拒否します
+fn validate_name_regex(pattern: &str) -> anyhow::Result<()> {
+    if pattern.chars().count() > MAX_NAME_REGEX_LENGTH {
+        return Err(anyhow::anyhow!("The name pattern is too long."));
+    }
+
+    // 後方参照や先読みなど MongoDB のみで有効な構文もここで拒否される
+    let ast = Parser::new()
+        .parse(pattern)
+        .map_err(|_| anyhow::anyhow!("Invalid name pattern."))?;
+    Regex::new(pattern).map_err(|_| anyhow::anyhow!("Invalid name pattern."))?;
+
+    fn count_repetitions(ast: &Ast, in_repetition: bool) -> anyhow::Result<usize> {
+        match ast {
+            Ast::Repetition(repetition) => {
+                if in_repetition {
+                    return Err(anyhow::anyhow!("The name pattern nests repetitions."));
+                }
+                Ok(1 + count_repetitions(&repetition.ast, true)?)
+            }
+            Ast::Group(group) => count_repetitions(&group.ast, in_repetition),
+            Ast::Alternation(alternation) => alternation
+                .asts
+                .iter()
+                .map(|ast| count_repetitions(ast, in_repetition))
+                .sum(),
+            Ast::Concat(concat) => concat
+                .asts
+                .iter()
+                .map(|ast| count_repetitions(ast, in_repetition))
+                .sum(),
+            _ => Ok(0),
+        }
+    }
+
+    if count_repetitions(&ast, false)? > MAX_NAME_REGEX_REPETITIONS {
+        return Err(anyhow::anyhow!(
+            "The name pattern has too many repetitions."
+        ));
+    }
+    Ok(())
+}
+
+/// How the values of a multi-valued condition are combined
+///
+/// 複数の値を指定した条件の結合方法
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum MountainValuesMode {
+    /// Matches the mountains having any of the values
+    #[default]
+    Any,
+    /// Matches the mountains having all of the values
+    All,
+}
+
+impl TryFrom<String> for MountainValuesMode {
+    type Error = anyhow::Error;
+
+    fn try_from(mode_param: String) -> Result<Self, Self::Error> {
+        match mode_param.as_str() {
+            "any" => Ok(MountainValuesMode::Any),
+            "all" => Ok(MountainValuesMode::All),
+            _ => Err(anyhow::anyhow!("Invalid mode value.")),
+        }
+    }
+}
+
+/// Values to search mountains by and how they are combined, which matches every mountain if empty
+///
+/// 山岳を検索する値の一覧とその結合方法。値が空の場合はすべての山岳に一致します
+#[derive(Debug, Clone)]
+pub struct MountainValuesCondition<T> {
+    pub values: Vec<T>,
+    pub mode: MountainValuesMode,
+}
+
+impl<T> MountainValuesCondition<T> {
+    pub fn new(values: Vec<T>, mode: MountainValuesMode) -> Self {
+        Self { values, mode }
+    }
+
+    /// Returns a condition matching the mountains having the value
+    ///
+    /// 値を持つ山岳に一致する条件を生成します
+    pub fn single(value: T) -> Self {
+        Self::new(vec![value], Default::default())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the condition with the values converted by `f` and the same mode
+    ///
+    /// 値を `f` で変換し、結合方法はそのままの条件を返します
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> MountainValuesCondition<U> {
+        MountainValuesCondition::new(self.values.iter().map(f).collect(), self.mode)
+    }
+
+    /// Returns whether the values match according to the mode, where `matches` tests a single value
+    ///
+    /// 結合方法に従って値の一覧が一致するかを返します。`matches` は値 1 つを照合します
+    pub fn matches(&self, matches: impl Fn(&T) -> bool) -> bool {
+        match self.mode {
+            MountainValuesMode::Any => self.is_empty() || self.values.iter().any(matches),
+            MountainValuesMode::All => self.values.iter().all(matches),
+        }
+    }
+}
+
+impl<T> Default for MountainValuesCondition<T> {
+    fn default() -> Self {
+        Self::new(Vec::new(), Default::default())
+    }
+}
+
+impl<T> From<Option<T>> for MountainValuesCondition<T> {
+    fn from(value: Option<T>) -> Self {
+        Self::new(value.into_iter().collect(), Default::default())
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct MountainSearchCondition {
-    pub name: Option<String>,
-    pub prefecture: Option<MountainPrefecture>,
-    pub tag: Option<MountainTag>,
+    pub name: Option<MountainNameCondition>,
+    pub prefecture: MountainValuesCondition<MountainPrefecture>,
+    pub tag: MountainValuesCondition<MountainTag>,
+    pub area: Option<String>,
+    pub elevation: MountainElevationRange,
     pub skip: u64,
     pub limit: Option<i64>,
-    pub sort: MountainSortCondition,
+    pub sort: MountainSort,
+    pub cursor: Option<MountainCursor>,
+    pub facets: Vec<MountainFacetKey>,
+    /// Filter expression combined with the other conditions by AND
+    pub filter: Option<MountainFilterExpr>,
+}
+
+/// Page of the searched mountains and the number of all the mountains matching the condition
+///
+/// 検索した山岳のページと、取得範囲に関係なく検索条件に一致した山岳の件数
+#[derive(Debug, Clone)]
+pub struct MountainPage {
+    pub mountains: Vec<Mountain>,
+    pub total: u64,
+    pub facets: Vec<MountainFacet>,
+    /// Relevance of the mountains keyed by the id, which is empty unless sorted by relevance
+    pub relevance: BTreeMap<i32, MountainRelevance>,
+}
+
+/// Field of a mountain which the relevance search matches the name condition against
+///
+/// 関連度順の検索で山名の条件と照合する山岳の項目
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MountainSearchField {
+    Name,
+    NameKana,
+    Area,
+    Prefecture,
+}
+
+impl MountainSearchField {
+    /// Returns the name of the property of the mountain in the response
+    ///
+    /// レスポンスの山岳のプロパティ名を返します
+    pub fn to_key(&self) -> String {
+        match self {
+            MountainSearchField::Name => "name".to_string(),
+            MountainSearchField::NameKana => "nameKana".to_string(),
+            MountainSearchField::Area => "area".to_string(),
+            MountainSearchField::Prefecture => "prefectures".to_string(),
+        }
+    }
+}
+
+/// Range of characters of a field value which matched the name condition, where `end` is exclusive
+///
+/// 項目の値のうち山名の条件に一致した文字の範囲 (`end` は含まない文字数単位の位置)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MountainHighlightSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Field value of a mountain with the ranges which matched the name condition
+///
+/// 山名の条件に一致した範囲を持つ山岳の項目の値
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainHighlight {
+    pub field: MountainSearchField,
+    pub value: String,
+    pub spans: Vec<MountainHighlightSpan>,
+}
+
+/// Relevance score of a mountain to the name condition and the highlighted field values
+///
+/// 山名の条件に対する山岳の関連度のスコアと、一致した項目の値
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainRelevance {
+    pub score: f64,
+    pub highlights: Vec<MountainHighlight>,
+}
+
+/// Width of the bands of the `elevation_band` facet in meters
+///
+/// 標高帯ファセットの標高帯の幅 (m)
+pub const ELEVATION_BAND_WIDTH: u32 = 500;
+
+/// Field to count the matched mountains by
+///
+/// 検索条件に一致した山岳の件数を集計する項目
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MountainFacetKey {
+    Prefecture,
+    Tag,
+    Area,
+    ElevationBand,
+}
+
+impl MountainFacetKey {
+    pub fn to_key(&self) -> String {
+        match self {
+            MountainFacetKey::Prefecture => "prefecture".to_string(),
+            MountainFacetKey::Tag => "tag".to_string(),
+            MountainFacetKey::Area => "area".to_string(),
+            MountainFacetKey::ElevationBand => "elevation_band".to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for MountainFacetKey {
+    type Error = anyhow::Error;
+
+    fn try_from(facet_param: String) -> Result<Self, Self::Error> {
+        match facet_param.trim() {
+            "prefecture" => Ok(MountainFacetKey::Prefecture),
+            "tag" => Ok(MountainFacetKey::Tag),
+            "area" => Ok(MountainFacetKey::Area),
+            "elevation_band" => Ok(MountainFacetKey::ElevationBand),
+            _ => Err(anyhow::anyhow!("Invalid facet value.")),
+        }
+    }
+}
+
+/// Number of the matched mountains for a value of the facet
+///
+/// ファセットの値ごとの検索条件に一致した山岳の件数
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainFacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+impl MountainFacetCount {
+    /// Returns the count of the elevation band starting at the lower bound, labeled like `1500-1999`
+    ///
+    /// 下限から始まる標高帯の件数を生成します。値は `1500-1999` のような標高帯の表記です
+    pub fn elevation_band(lower_bound: u32, count: u64) -> Self {
+        Self {
+            value: format!("{}-{}", lower_bound, lower_bound + ELEVATION_BAND_WIDTH - 1),
+            count,
+        }
+    }
+}
+
+/// Counts of the matched mountains per value of the facet
+///
+/// ファセットの値ごとの検索条件に一致した山岳の件数の一覧
+#[derive(Debug, Clone)]
+pub struct MountainFacet {
+    pub key: MountainFacetKey,
+    pub counts: Vec<MountainFacetCount>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MountainPrefecture {
+    pub id: u64,
+    pub name: String,
+    pub romaji: String,
+    pub region: String,
+}
+
+impl MountainPrefecture {
+    /// (id, name, romaji, region)
+    ///
+    /// (都道府県コード, 都道府県名, ローマ字表記, 地方区分)
+    const PREFECTURES: [(u64, &'static str, &'static str, &'static str); 47] = [
+        (1, "北海道", "Hokkaido", "北海道"),
+        (2, "青森県", "Aomori", "東北"),
+        (3, "岩手県", "Iwate", "東北"),
+        (4, "宮城県", "Miyagi", "東北"),
+        (5, "秋田県", "Akita", "東北"),
+        (6, "山形県", "Yamagata", "東北"),
+        (7, "福島県", "Fukushima", "東北"),
+        (8, "茨城県", "Ibaraki", "関東"),
+        (9, "栃木県", "Tochigi", "関東"),
+        (10, "群馬県", "Gunma", "関東"),
+        (11, "埼玉県", "Saitama", "関東"),
+        (12, "千葉県", "Chiba", "関東"),
+        (13, "東京都", "Tokyo", "関東"),
+        (14, "神奈川県", "Kanagawa", "関東"),
+        (15, "新潟県", "Niigata", "中部"),
+        (16, "富山県", "Toyama", "中部"),
+        (17, "石川県", "Ishikawa", "中部"),
+        (18, "福井県", "Fukui", "中部"),
+        (19, "山梨県", "Yamanashi", "中部"),
+        (20, "長野県", "Nagano", "中部"),
+        (21, "岐阜県", "Gifu", "中部"),
+        (22, "静岡県", "Shizuoka", "中部"),
+        (23, "愛知県", "Aichi", "中部"),
+        (24, "三重県", "Mie", "近畿"),
+        (25, "滋賀県", "Shiga", "近畿"),
+        (26, "京都府", "Kyoto", "近畿"),
+        (27, "大阪府", "Osaka", "近畿"),
+        (28, "兵庫県", "Hyogo", "近畿"),
+        (29, "奈良県", "Nara", "近畿"),
+        (30, "和歌山県", "Wakayama", "近畿"),
+        (31, "鳥取県", "Tottori", "中国"),
+        (32, "島根県", "Shimane", "中国"),
+        (33, "岡山県", "Okayama", "中国"),
+        (34, "広島県", "Hiroshima", "中国"),
+        (35, "山口県", "Yamaguchi", "中国"),
+        (36, "徳島県", "Tokushima", "四国"),
+        (37, "香川県", "Kagawa", "四国"),
+        (38, "愛媛県", "Ehime", "四国"),
+        (39, "高知県", "Kochi", "四国"),
+        (40, "福岡県", "Fukuoka", "九州"),
+        (41, "佐賀県", "Saga", "九州"),
+        (42, "長崎県", "Nagasaki", "九州"),
+        (43, "熊本県", "Kumamoto", "九州"),
+        (44, "大分県", "Oita", "九州"),
+        (45, "宮崎県", "Miyazaki", "九州"),
+        (46, "鹿児島県", "Kagoshima", "九州"),
+        (47, "沖縄県", "Okinawa", "九州"),
+    ];
+
+    fn new(pref: (u64, &'static str, &'static str, &'static str)) -> Self {
+        Self {
+            id: pref.0,
+            name: pref.1.to_string(),
+            romaji: pref.2.to_string(),
+            region: pref.3.to_string(),
+        }
+    }
+
+    /// Returns all prefectures in prefecture code order
+    ///
+    /// 全都道府県を都道府県コード順に返します
+    pub fn all() -> Vec<MountainPrefecture> {
+        MountainPrefecture::PREFECTURES
+            .into_iter()
+            .map(MountainPrefecture::new)
+            .collect()
+    }
+
+    /// Returns the ISO 3166-2 code (e.g. `JP-13`)
+    ///
+    /// ISO 3166-2 のコード (例: `JP-13`) を返します
+    pub fn iso_code(&self) -> String {
+        format!("JP-{:02}", self.id)
+    }
+
+    pub fn is_valid_name(name: &str) -> bool {
+        MountainPrefecture::PREFECTURES
+            .iter()
+            .any(|pref| pref.1 == name)
+    }
+
+    /// Returns the smallest code of the prefectures, where unknown names are 0 and no prefecture is 0
+    ///
+    /// 都道府県の最小のコードを返します。不明な都道府県名と都道府県がない場合は 0 とします
+    pub fn min_code(names: &[String]) -> u64 {
+        names
+            .iter()
+            .map(|name| MountainPrefecture::code_of(name).unwrap_or(0))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Returns the prefecture code of the prefecture name
+    ///
+    /// 都道府県名の都道府県コードを返します
+    pub fn code_of(name: &str) -> Option<u64> {
+        MountainPrefecture::PREFECTURES
+            .iter()
+            .find(|pref| pref.1 == name)
+            .map(|pref| pref.0)
+    }
+
+    fn find_by_param(param: &str) -> Option<(u64, &'static str, &'static str, &'static str)> {
+        let param = param.trim();
+
+        let id = match param.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("JP-") => param[3..].parse::<u64>().ok(),
+            _ => param.parse::<u64>().ok(),
+        };
+        if let Some(id) = id {
+            return MountainPrefecture::PREFECTURES
+                .into_iter()
+                .find(|pref| pref.0 == id);
+        }
+
+        MountainPrefecture::PREFECTURES.into_iter().find(|pref| {
+            // 「東京」のように都・府・県を省略した名称も受け付ける
+            pref.1 == param
+                || pref
+                    .1
+                    .strip_suffix(['都', '府', '県'])
+                    .is_some_and(|name| name == param)
+                || pref.2.eq_ignore_ascii_case(param)
+        })
+    }
+}
+
+impl TryFrom<String> for MountainPrefecture {
+    type Error = anyhow::Error;
+
+    /// Resolves the prefecture code, `JP-xx` code, kanji name or romaji name
+    ///
+    /// 都道府県コード、`JP-xx` 形式のコード、都道府県名またはローマ字表記から都道府県を解決します
+    fn try_from(prefecture_param: String) -> Result<Self, Self::Error> {
+        MountainPrefecture::find_by_param(&prefecture_param)
+            .map(MountainPrefecture::new)
+            .ok_or(Self::Error::msg("Invalid prefecture value."))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MountainTag {
+    pub id: u64,
+    pub name: String,
+}
+
+impl TryFrom<(String, &TagRegistry)> for MountainTag {
+    type Error = anyhow::Error;
+
+    /// Resolves the tag id against the tag registry
+    ///
+    /// タグ ID をタグレジストリから解決します
+    fn try_from((tag_param, tags): (String, &TagRegistry)) -> Result<Self, Self::Error> {
+        tag_param
+            .parse::<i32>()
+            .ok()
+            .and_then(|id| tags.find_by_id(id))
+            .cloned()
+            .map(MountainTag::from)
+            .ok_or(Self::Error::msg("Invalid tag value."))
+    }
+}
+
+impl From<Tag> for MountainTag {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id.value as u64,
+            name: tag.name,
+        }
+    }
+}
+
+/// Elevation range in meters, where each bound is inclusive
+///
+/// 標高の範囲 (m) で、上限・下限ともに指定した値を含む
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MountainElevationRange {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl MountainElevationRange {
+    /// Returns an elevation range, or an error if the lower bound exceeds the upper bound
+    ///
+    /// 標高の範囲を生成します。下限が上限を超える場合はエラーを返します
+    pub fn new(min: Option<u32>, max: Option<u32>) -> anyhow::Result<Self> {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(anyhow::anyhow!(
+                    "The minimum elevation exceeds the maximum elevation."
+                ));
+            }
+        }
+        Ok(Self { min, max })
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn contains(&self, elevation: u32) -> bool {
+        self.min.is_none_or(|min| min <= elevation) && self.max.is_none_or(|max| elevation <= max)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MountainSortKey {
+    Id,
+    Elevation,
+    Name,
+    /// Relevance score to the name condition, which is not stored and is ranked by the search index
+    Relevance,
+    /// Smallest code of the prefectures of the mountain
+    Prefecture,
+    Area,
+    Latitude,
+    /// Distance from the point of the sort
+    Distance,
+}
+
+impl MountainSortKey {
+    pub fn to_key(&self) -> String {
+        match self {
+            MountainSortKey::Id => "_id".to_string(),
+            MountainSortKey::Elevation => "elevation".to_string(),
+            MountainSortKey::Name => "name_kana".to_string(),
+            MountainSortKey::Relevance => "score".to_string(),
+            MountainSortKey::Prefecture => "prefecture_code".to_string(),
+            MountainSortKey::Area => "area".to_string(),
+            MountainSortKey::Latitude => "latitude".to_string(),
+            MountainSortKey::Distance => "distance".to_string(),
+        }
+    }
+
+    /// Returns whether the key is a field stored in the documents, which every search can sort by
+    ///
+    /// ドキュメントに保存された項目で、どの検索でも並び替えに使用できるキーかを返します
+    pub fn is_stored(&self) -> bool {
+        matches!(
+            self,
+            MountainSortKey::Id
+                | MountainSortKey::Elevation
+                | MountainSortKey::Name
+                | MountainSortKey::Area
+        )
+    }
+}
+
+impl TryFrom<&str> for MountainSortKey {
+    type Error = ();
+
+    fn try_from(key_param: &str) -> Result<Self, Self::Error> {
+        match key_param {
+            "id" => Ok(MountainSortKey::Id),
+            "elevation" => Ok(MountainSortKey::Elevation),
+            "name" => Ok(MountainSortKey::Name),
+            "prefecture" => Ok(MountainSortKey::Prefecture),
+            "area" => Ok(MountainSortKey::Area),
+            "latitude" => Ok(MountainSortKey::Latitude),
+            "distance" => Ok(MountainSortKey::Distance),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MountainOrderType {
+    Asc,
+    Desc,
+}
+
+impl MountainOrderType {
+    pub fn to_value(&self) -> i64 {
+        match self {
+            MountainOrderType::Asc => 1,
+            MountainOrderType::Desc => -1,
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        match self {
+            MountainOrderType::Asc => MountainOrderType::Desc,
+            MountainOrderType::Desc => MountainOrderType::Asc,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct MountainSortCondition {
+    pub key: MountainSortKey,
+    pub order: MountainOrderType,
+}
+
+impl Default for MountainSortCondition {
+    fn default() -> Self {
+        Self {
+            key: MountainSortKey::Id,
+            order: MountainOrderType::Asc,
+        }
+    }
+}
+
+impl TryFrom<String> for MountainSortCondition {
+    type Error = ();
+
+    /// Parses `key.asc` or `key.desc`, or `relevance` which is always in descending order
+    ///
+    /// `キー.asc` または `キー.desc` を解析します。`relevance` は常に降順です
+    fn try_from(sort_param: String) -> Result<Self, Self::Error> {
+        if sort_param == "relevance" {
+            return Ok(MountainSortCondition {
+                key: MountainSortKey::Relevance,
+                order: MountainOrderType::Desc,
+            });
+        }
+
+        let (key_param, order_param) = sort_param.split_once('.').ok_or(())?;
+        let key = MountainSortKey::try_from(key_param)?;
+        let order = match order_param {
+            "asc" => MountainOrderType::Asc,
+            "desc" => MountainOrderType::Desc,
+            _ => return Err(()),
+        };
+        Ok(MountainSortCondition { key, order })
+    }
+}
+
+/// Sort keys applied in order, where each key sorts the mountains tied on the keys before it
+///
+/// 並び替えのキーの一覧。前のキーの値が同じ山岳を後のキーで並び替えます
+#[derive(Debug, Clone)]
+pub struct MountainSort {
+    pub conditions: Vec<MountainSortCondition>,
+    /// Point to sort by the distance from, which is set if any key is the distance
+    pub point: Option<GeoPoint>,
+}
+
+impl MountainSort {
+    /// Returns the first key, which decides the order of the tiebreaker `_id`
+    ///
+    /// 最初のキーを返します。並び順が一意に定まるよう _id をこのキーと同じ順序で並べます
+    pub fn primary(&self) -> MountainSortCondition {
+        self.conditions.first().copied().unwrap_or_default()
+    }
+
+    pub fn contains(&self, key: MountainSortKey) -> bool {
+        self.conditions.iter().any(|condition| condition.key == key)
+    }
+
+    /// Returns whether cursors can page the sort, which holds for every sort except by the relevance
+    ///
+    /// カーソルで取得範囲を指定できるかを返します。関連度順以外のすべての並び順で指定できます
+    pub fn has_cursor(&self) -> bool {
+        !self.contains(MountainSortKey::Relevance)
+    }
+
+    /// Returns whether the cursor holds the values of the sort keys in order
+    ///
+    /// カーソルが並び替えのキーの値を同じ順序で保持しているかを返します
+    pub fn matches_cursor(&self, cursor: &MountainCursor) -> bool {
+        self.has_cursor()
+            && cursor.values.len() == self.conditions.len()
+            && cursor
+                .values
+                .iter()
+                .zip(&self.conditions)
+                .all(|(value, condition)| value.key() == condition.key)
+    }
+
+    /// Returns the sort in the reverse order of every key, used to fetch the previous page
+    ///
+    /// すべてのキーを逆順にした並び順を返します。前のページの取得に使用します
+    pub fn reverse(&self) -> Self {
+        Self {
+            conditions: self
+                .conditions
+                .iter()
+                .map(|condition| MountainSortCondition {
+                    key: condition.key,
+                    order: condition.order.reverse(),
+                })
+                .collect(),
+            point: self.point,
+        }
+    }
+
+    pub fn with_point(mut self, point: GeoPoint) -> Self {
+        self.point = Some(point);
+        self
+    }
+}
+
+impl Default for MountainSort {
+    fn default() -> Self {
+        MountainSortCondition::default().into()
+    }
+}
+
+impl From<MountainSortCondition> for MountainSort {
+    fn from(condition: MountainSortCondition) -> Self {
+        Self {
+            conditions: vec![condition],
+            point: None,
+        }
+    }
+}
+
+impl TryFrom<String> for MountainSort {
+    type Error = ();
+
+    /// Parses comma-separated sort keys, rejecting duplicated keys and `relevance` with other keys
+    ///
+    /// カンマ区切りの並び替えのキーを解析します。重複したキーと、他のキーと組み合わせた `relevance` はエラーにします
+    fn try_from(sort_param: String) -> Result<Self, Self::Error> {
+        let mut conditions: Vec<MountainSortCondition> = Vec::new();
+        for condition_param in sort_param.split(',') {
+            let condition = MountainSortCondition::try_from(condition_param.trim().to_string())?;
+            if conditions.iter().any(|c| c.key == condition.key) {
+                return Err(());
+            }
+            conditions.push(condition);
+        }
+
+        let sort = Self {
+            conditions,
+            point: None,
+        };
+        // 関連度は他のキーと比較できる値ではないため、単独でのみ指定できる
+        if sort.contains(MountainSortKey::Relevance) && sort.conditions.len() > 1 {
+            return Err(());
+        }
+        Ok(sort)
+    }
+}
+
+/// Direction to page in from the mountain a cursor points at
+///
+/// カーソルが指す山岳から前後どちらのページを取得するか
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MountainCursorDirection {
+    Next,
+    Prev,
+}
+
+/// Sort key value of the mountain a cursor points at
+///
+/// カーソルが指す山岳のソートキーの値
+#[derive(Debug, Clone, PartialEq)]
+pub enum MountainCursorValue {
+    Id,
+    Elevation(u32),
+    Name(String),
+    Prefecture(u64),
+    Area(String),
+    Latitude(f64),
+    Distance(f64),
+}
+
+impl MountainCursorValue {
+    pub fn key(&self) -> MountainSortKey {
+        match self {
+            MountainCursorValue::Id => MountainSortKey::Id,
+            MountainCursorValue::Elevation(_) => MountainSortKey::Elevation,
+            MountainCursorValue::Name(_) => MountainSortKey::Name,
+            MountainCursorValue::Prefecture(_) => MountainSortKey::Prefecture,
+            MountainCursorValue::Area(_) => MountainSortKey::Area,
+            MountainCursorValue::Latitude(_) => MountainSortKey::Latitude,
+            MountainCursorValue::Distance(_) => MountainSortKey::Distance,
+        }
+    }
+
+    /// Encodes the value with the character of its key, where the text is Base64-encoded not to contain `|`
+    ///
+    /// 値をキーの文字とともに変換します。文字列は区切り文字 `|` を含まないよう Base64 にします
+    fn encode(&self) -> String {
+        match self {
+            MountainCursorValue::Id => "i".to_string(),
+            MountainCursorValue::Elevation(elevation) => format!("e{}", elevation),
+            MountainCursorValue::Name(name) => format!("n{}", URL_SAFE_NO_PAD.encode(name)),
+            MountainCursorValue::Prefecture(code) => format!("p{}", code),
+            MountainCursorValue::Area(area) => format!("a{}", URL_SAFE_NO_PAD.encode(area)),
+            MountainCursorValue::Latitude(latitude) => format!("l{}", latitude),
+            MountainCursorValue::Distance(distance) => format!("d{}", distance),
+        }
+    }
+
+    fn decode(value_param: &str) -> Option<Self> {
+        let decode_text = |text: &str| {
+            URL_SAFE_NO_PAD
+                .decode(text)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+        };
+        let (key, value) = value_param.split_at_checked(1)?;
+        match key {
+            "i" if value.is_empty() => Some(MountainCursorValue::Id),
+            "e" => value.parse().ok().map(MountainCursorValue::Elevation),
+            "n" => decode_text(value).map(MountainCursorValue::Name),
+            "p" => value.parse().ok().map(MountainCursorValue::Prefecture),
+            "a" => decode_text(value).map(MountainCursorValue::Area),
+            "l" => value.parse().ok().map(MountainCursorValue::Latitude),
+            "d" => value.parse().ok().map(MountainCursorValue::Distance),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct MountainPrefecture {
-    pub id: u64,
-    pub name: String,
+/// Keyset pagination cursor holding the values of all the sort keys and `_id` of a page boundary
+///
+/// ページ境界の山岳のすべてのソートキーの値と `_id` を保持するキーセットページネーション用のカーソル
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainCursor {
+    pub direction: MountainCursorDirection,
+    /// Values of the sort keys in the order of `MountainSort::conditions`
+    pub values: Vec<MountainCursorValue>,
+    pub id: i32,
 }
 
-impl MountainPrefecture {
-    const PREFECTURES: [(u64, &'static str); 47] = [
-        (1, "北海道"),
-        (2, "青森県"),
-        (3, "岩手県"),
-        (4, "宮城県"),
-        (5, "秋田県"),
-        (6, "山形県"),
-        (7, "福島県"),
-        (8, "茨城県"),
-        (9, "栃木県"),
-        (10, "群馬県"),
-        (11, "埼玉県"),
-        (12, "千葉県"),
-        (13, "東京都"),
-        (14, "神奈川県"),
-        (15, "新潟県"),
-        (16, "富山県"),
-        (17, "石川県"),
-        (18, "福井県"),
-        (19, "山梨県"),
-        (20, "長野県"),
-        (21, "岐阜県"),
-        (22, "静岡県"),
-        (23, "愛知県"),
-        (24, "三重県"),
-        (25, "滋賀県"),
-        (26, "京都府"),
-        (27, "大阪府"),
-        (28, "兵庫県"),
-        (29, "奈良県"),
-        (30, "和歌山県"),
-        (31, "鳥取県"),
-        (32, "島根県"),
-        (33, "岡山県"),
-        (34, "広島県"),
-        (35, "山口県"),
-        (36, "徳島県"),
-        (37, "香川県"),
-        (38, "愛媛県"),
-        (39, "高知県"),
-        (40, "福岡県"),
-        (41, "佐賀県"),
-        (42, "長崎県"),
-        (43, "熊本県"),
-        (44, "大分県"),
-        (45, "宮崎県"),
-        (46, "鹿児島県"),
-        (47, "沖縄県"),
-    ];
+impl MountainCursor {
+    /// Returns a cursor pointing at the mountain for the sort, or `None` if the sort has no cursor
+    ///
+    /// 並び順に応じて山岳を指すカーソルを生成します。カーソルを使用できない並び順の場合は `None` を返します
+    pub fn new(
+        direction: MountainCursorDirection,
+        sort: &MountainSort,
+        mountain: &Mountain,
+    ) -> Option<Self> {
+        let mut values: Vec<MountainCursorValue> = Vec::new();
+        for condition in &sort.conditions {
+            let value = match condition.key {
+                MountainSortKey::Id => MountainCursorValue::Id,
+                MountainSortKey::Elevation => MountainCursorValue::Elevation(mountain.elevation),
+                MountainSortKey::Name => MountainCursorValue::Name(mountain.name_kana.clone()),
+                MountainSortKey::Prefecture => MountainCursorValue::Prefecture(
+                    MountainPrefecture::min_code(&mountain.prefectures),
+                ),
+                MountainSortKey::Area => MountainCursorValue::Area(mountain.area.clone()),
+                MountainSortKey::Latitude => {
+                    MountainCursorValue::Latitude(mountain.location.latitude)
+                }
+                MountainSortKey::Distance => {
+                    let point = GeoPoint {
+                        latitude: mountain.location.latitude,
+                        longitude: mountain.location.longitude,
+                    };
+                    MountainCursorValue::Distance(sort.point?.distance_to(&point))
+                }
+                // 関連度は検索条件ごとに変わるためカーソルを使用できない
+                MountainSortKey::Relevance => return None,
+            };
+            values.push(value);
+        }
+        Some(Self {
+            direction,
+            values,
+            id: mountain.id.value,
+        })
+    }
 
-    fn new(id: u64, name: String) -> Self {
-        Self { id, name }
+    /// Encodes the cursor into an opaque URL-safe token
+    ///
+    /// カーソルを URL に使用できる不透明なトークンに変換します
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            MountainCursorDirection::Next => "n",
+            MountainCursorDirection::Prev => "p",
+        };
+        let mut parts = vec![direction.to_string(), self.id.to_string()];
+        parts.extend(self.values.iter().map(MountainCursorValue::encode));
+        URL_SAFE_NO_PAD.encode(parts.join("|"))
     }
 }
 
-impl TryFrom<String> for MountainPrefecture {
+impl TryFrom<String> for MountainCursor {
     type Error = anyhow::Error;
 
-    fn try_from(prefecture_param: String) -> Result<Self, Self::Error> {
-        match prefecture_param.parse::<u64>() {
-            Ok(prefecture_id) => {
-                for pref in MountainPrefecture::PREFECTURES {
-                    if pref.0 == prefecture_id {
-                        return Ok(MountainPrefecture::new(pref.0, pref.1.to_string()));
-                    }
-                }
-                Err(Self::Error::msg("Invalid prefecture value."))
-            }
-            Err(_) => Err(Self::Error::msg("Invalid prefecture value.")),
-        }
+    /// Decodes a token returned by [`MountainCursor::encode`]
+    ///
+    /// [`MountainCursor::encode`] で生成したトークンからカーソルを復元します
+    fn try_from(cursor_param: String) -> Result<Self, Self::Error> {
+        let invalid = || anyhow::anyhow!("Invalid cursor value.");
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(cursor_param.trim())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(invalid)?;
+
+        let mut parts = payload.split('|');
+        let (Some(direction), Some(id)) = (parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+
+        let direction = match direction {
+            "n" => MountainCursorDirection::Next,
+            "p" => MountainCursorDirection::Prev,
+            _ => return Err(invalid()),
+        };
+        let id = id.parse::<i32>().map_err(|_| invalid())?;
+        let values = parts
+            .map(MountainCursorValue::decode)
+            .collect::<Option<Vec<_>>>()
+            .filter(|values| !values.is_empty())
+            .ok_or_else(invalid)?;
+
+        Ok(Self {
+            direction,
+            values,
+            id,
+        })
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct MountainTag {
-    pub id: u64,
-    pub name: String,
+pub struct MountainBoxSearchCondition {
+    pub box_coordinates: MountainBoxCoordinates,
+    pub name: Option<MountainNameCondition>,
+    pub tag: MountainValuesCondition<MountainTag>,
+    pub area: Option<String>,
+    pub elevation: MountainElevationRange,
+    pub skip: u64,
+    pub limit: Option<i64>,
+    pub sort: MountainSort,
+    pub facets: Vec<MountainFacetKey>,
 }
 
-impl MountainTag {
-    const TAGS: [(u64, &'static str); 2] = [(1, "百名山"), (2, "二百名山")];
-
-    fn new(id: u64, name: String) -> Self {
-        Self { id, name }
-    }
+/// Condition to search mountains inside a GeoJSON polygon
+///
+/// GeoJSON のポリゴンの内側にある山岳を検索する条件
+pub struct MountainPolygonSearchCondition {
+    pub polygon: GeoPolygon,
+    pub name: Option<MountainNameCondition>,
+    pub tag: MountainValuesCondition<MountainTag>,
+    pub area: Option<String>,
+    pub elevation: MountainElevationRange,
+    pub skip: u64,
+    pub limit: Option<i64>,
+    pub sort: MountainSort,
 }
 
-impl TryFrom<String> for MountainTag {
-    type Error = anyhow::Error;
+/// Name of the tag ranked first among suggestions of the same match quality
+///
+/// 一致の度合いが同じ候補のうち優先する山岳のタグ名
+pub const SUGGEST_PREFERRED_TAG: &str = "百名山";
 
-    fn try_from(tag_param: String) -> Result<Self, Self::Error> {
-        match tag_param.parse::<u64>() {
-            Ok(tag_id) => {
-                for tag in MountainTag::TAGS {
-                    if tag.0 == tag_id {
-                        return Ok(MountainTag::new(tag.0, tag.1.to_string()));
-                    }
-                }
-                Err(Self::Error::msg("Invalid tag value."))
-            }
-            Err(_) => Err(Self::Error::msg("Invalid tag value.")),
-        }
-    }
+/// Brackets which start the peak name following the mountain name, such as `富士山<剣ヶ峯>`
+///
+/// 山名に続く山頂名の始まりを表す括弧
+pub const SUGGEST_PEAK_BRACKETS: [char; 3] = ['<', '（', '('];
+
+/// Condition to suggest mountains whose names, kana names, romaji names or aliases start with the query
+///
+/// 山名、山名かな、山名ローマ字、別名のいずれかが入力値で始まる山岳を候補として返す条件
+#[derive(Debug, Clone)]
+pub struct MountainSuggestCondition {
+    pub query: String,
+    pub limit: usize,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub enum MountainSortKey {
-    Id,
-    Elevation,
-    Name,
+/// Mountain suggested for the query, which has only the fields shown in the search box
+///
+/// 入力値に対する候補の山岳。検索ボックスに表示する項目のみを持ちます
+#[derive(Debug, Clone)]
+pub struct MountainSuggestion {
+    pub id: Id<Mountain>,
+    pub name: String,
+    pub name_kana: String,
+    pub prefectures: Vec<String>,
 }
 
-impl MountainSortKey {
-    pub fn to_key(&self) -> String {
-        match self {
-            MountainSortKey::Id => "_id".to_string(),
-            MountainSortKey::Elevation => "elevation".to_string(),
-            MountainSortKey::Name => "name_kana".to_string(),
+impl From<Mountain> for MountainSuggestion {
+    fn from(mountain: Mountain) -> Self {
+        Self {
+            id: mountain.id,
+            name: mountain.name,
+            name_kana: mountain.name_kana,
+            prefectures: mountain.prefectures,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub enum MountainOrderType {
-    Asc,
-    Desc,
+/// Normalized prefixes matched against the folded name, the kana name and the romaji name
+///
+/// 表記ゆれをまとめた山名、山名かな、山名ローマ字に対して照合する正規化済みの前方一致の値
+#[derive(Debug, PartialEq)]
+pub struct MountainSuggestPrefixes {
+    pub folded: String,
+    pub kana: String,
+    /// Prefix for the romaji name, which is absent unless the query is romaji
+    pub romaji: Option<String>,
 }
 
-impl MountainOrderType {
-    pub fn to_value(&self) -> i64 {
-        match self {
-            MountainOrderType::Asc => 1,
-            MountainOrderType::Desc => -1,
-        }
-    }
+/// Quality of the match of a suggestion, where the smaller one is ranked first
+///
+/// 候補の一致の度合いで、小さいほど上位に並べます
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MountainSuggestMatch {
+    /// The name matches the whole query, ignoring the peak in brackets
+    Name,
+    /// An alias matches the whole query
+    Alias,
+    /// The name starts with the query
+    NamePrefix,
+    /// An alias starts with the query
+    AliasPrefix,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct MountainSortCondition {
-    pub key: MountainSortKey,
-    pub order: MountainOrderType,
-}
+impl MountainSuggestCondition {
+    pub fn new(query: String, limit: usize) -> Self {
+        Self { query, limit }
+    }
 
-impl Default for MountainSortCondition {
-    fn default() -> Self {
-        Self {
-            key: MountainSortKey::Id,
-            order: MountainOrderType::Asc,
+    /// Returns the prefixes normalized in the same way as the stored search names
+    ///
+    /// 保存されている検索用の山名と同じ方法で正規化した前方一致の値を返します
+    pub fn prefixes(&self) -> MountainSuggestPrefixes {
+        MountainSuggestPrefixes {
+            folded: fold_name_variants(&self.query),
+            kana: romaji_to_hiragana(&self.query).unwrap_or_else(|| normalize_kana(&self.query)),
+            romaji: normalize_romaji(&self.query),
         }
     }
-}
-
-impl TryFrom<String> for MountainSortCondition {
-    type Error = ();
 
-    fn try_from(sort_param: String) -> Result<Self, Self::Error> {
-        match sort_param.as_str() {
-            "id.asc" => Ok(MountainSortCondition {
-                key: MountainSortKey::Id,
-                order: MountainOrderType::Asc,
-            }),
-            "id.desc" => Ok(MountainSortCondition {
-                key: MountainSortKey::Id,
-                order: MountainOrderType::Desc,
-            }),
-            "elevation.asc" => Ok(MountainSortCondition {
-                key: MountainSortKey::Elevation,
-                order: MountainOrderType::Asc,
-            }),
-            "elevation.desc" => Ok(MountainSortCondition {
-                key: MountainSortKey::Elevation,
-                order: MountainOrderType::Desc,
-            }),
-            "name.asc" => Ok(MountainSortCondition {
-                key: MountainSortKey::Name,
-                order: MountainOrderType::Asc,
-            }),
-            "name.desc" => Ok(MountainSortCondition {
-                key: MountainSortKey::Name,
-                order: MountainOrderType::Desc,
-            }),
-            _ => Err(()),
+    /// Returns the quality of the match, or `None` if neither the name nor the aliases start with the query
+    ///
+    /// 一致の度合いを返します。山名、別名のいずれも入力値で始まらない場合は `None` を返します
+    pub fn match_quality(&self, mountain: &Mountain) -> Option<MountainSuggestMatch> {
+        let prefixes = self.prefixes();
+        let name = prefixes.match_name(&mountain.name, &mountain.name_kana);
+        let alias = mountain
+            .aliases
+            .iter()
+            .filter_map(|alias| prefixes.match_name(&alias.name, &alias.name_kana))
+            .min();
+
+        match (name, alias) {
+            (Some(true), _) => Some(MountainSuggestMatch::Name),
+            (_, Some(true)) => Some(MountainSuggestMatch::Alias),
+            (Some(false), _) => Some(MountainSuggestMatch::NamePrefix),
+            (_, Some(false)) => Some(MountainSuggestMatch::AliasPrefix),
+            (None, None) => None,
         }
     }
+
+    /// Ranks the candidates by the match quality, 百名山 membership and elevation, and returns up to `limit` of them
+    ///
+    /// 候補を一致の度合い、百名山かどうか、標高の順に並べ、`limit` 件まで返します
+    pub fn rank(&self, candidates: Vec<Mountain>) -> Vec<Mountain> {
+        let mut ranked: Vec<(MountainSuggestMatch, Mountain)> = candidates
+            .into_iter()
+            .filter_map(|mountain| Some((self.match_quality(&mountain)?, mountain)))
+            .collect();
+        ranked.sort_by(|(a_match, a), (b_match, b)| {
+            let preferred = |m: &Mountain| !m.tags.iter().any(|tag| tag == SUGGEST_PREFERRED_TAG);
+            a_match
+                .cmp(b_match)
+                .then(preferred(a).cmp(&preferred(b)))
+                .then(b.elevation.cmp(&a.elevation))
+                .then(a.id.value.cmp(&b.id.value))
+        });
+        ranked
+            .into_iter()
+            .take(self.limit)
+            .map(|(_, mountain)| mountain)
+            .collect()
+    }
 }
 
-pub struct MountainBoxSearchCondition {
-    pub box_coordinates: MountainBoxCoordinates,
-    pub name: Option<String>,
-    pub tag: Option<MountainTag>,
-    pub sort: MountainSortCondition,
+impl MountainSuggestPrefixes {
+    /// Returns whether the name matches the whole query, or `None` if the name does not start with the query
+    ///
+    /// 山名が入力値全体に一致するかを返します。入力値で始まらない場合は `None` を返します
+    fn match_name(&self, name: &str, name_kana: &str) -> Option<bool> {
+        let romaji = hiragana_to_romaji(name_kana);
+        [
+            Some((fold_name_variants(name), &self.folded)),
+            Some((normalize_kana(name_kana), &self.kana)),
+            self.romaji.as_ref().map(|prefix| (romaji, prefix)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|(_, prefix)| !prefix.is_empty())
+        .filter_map(|(value, prefix)| {
+            value
+                .strip_prefix(prefix.as_str())
+                .map(|rest| rest.is_empty() || rest.starts_with(SUGGEST_PEAK_BRACKETS))
+        })
+        .max()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MountainBoxCoordinates {
     pub bottom_left: (f64, f64),
     pub upper_right: (f64, f64),
@@ -369,10 +1593,58 @@ impl MountainFindException {
     }
 }
 
+#[derive(Debug)]
+pub struct MountainWriteException {
+    pub error_code: ErrorCode,
+    pub messages: Vec<String>,
+}
+
+impl MountainWriteException {
+    pub fn new(error_code: ErrorCode, messages: Vec<String>) -> Self {
+        Self {
+            error_code,
+            messages,
+        }
+    }
+
+    /// Returns an error including exception error messages
+    ///
+    /// 登録・更新・削除時の例外エラーメッセージを含むエラーを生成します
+    ///
+    /// # Arguments
+    ///
+    /// - `error_code`: Error code
+    pub fn new_with_error_code(error_code: ErrorCode) -> Self {
+        let messages = vec![ERR_MESSAGE_WRITE_EXCEPTION.to_string()];
+        Self {
+            error_code,
+            messages,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn create_tag_registry() -> TagRegistry {
+        TagRegistry::new(vec![
+            Tag::new(
+                Id::new(1),
+                "百名山".to_string(),
+                "深田久弥が選定した日本百名山".to_string(),
+                "深田久弥『日本百名山』".to_string(),
+            ),
+            Tag::new(
+                Id::new(2),
+                "二百名山".to_string(),
+                "深田クラブが選定した日本二百名山".to_string(),
+                "深田クラブ『日本二百名山』".to_string(),
+            ),
+        ])
+        .unwrap()
+    }
+
     #[test]
     fn test_mountain_prefecture_try_from_valid_id() {
         let result = MountainPrefecture::try_from("1".to_string());
@@ -412,9 +1684,42 @@ mod tests {
         assert_eq!(result.unwrap_err().to_string(), "Invalid prefecture value.");
     }
 
+    #[test]
+    fn test_mountain_prefecture_try_from_iso_code() {
+        let prefecture = MountainPrefecture::try_from("JP-13".to_string()).unwrap();
+        assert_eq!(prefecture.name, "東京都");
+        assert_eq!(prefecture.iso_code(), "JP-13");
+
+        let prefecture = MountainPrefecture::try_from("jp-01".to_string()).unwrap();
+        assert_eq!(prefecture.name, "北海道");
+        assert!(MountainPrefecture::try_from("JP-48".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_mountain_prefecture_try_from_names() {
+        let prefecture = MountainPrefecture::try_from("山梨県".to_string()).unwrap();
+        assert_eq!(prefecture.id, 19);
+
+        let prefecture = MountainPrefecture::try_from("京都".to_string()).unwrap();
+        assert_eq!(prefecture.id, 26);
+
+        let prefecture = MountainPrefecture::try_from("nagano".to_string()).unwrap();
+        assert_eq!(prefecture.name, "長野県");
+        assert_eq!(prefecture.romaji, "Nagano");
+        assert_eq!(prefecture.region, "中部");
+    }
+
+    #[test]
+    fn test_mountain_prefecture_all() {
+        let prefectures = MountainPrefecture::all();
+        assert_eq!(prefectures.len(), 47);
+        assert_eq!(prefectures[46].iso_code(), "JP-47");
+        assert_eq!(prefectures[46].region, "九州");
+    }
+
     #[test]
     fn test_mountain_tag_try_from_valid_hyakumeizan() {
-        let result = MountainTag::try_from("1".to_string());
+        let result = MountainTag::try_from(("1".to_string(), &create_tag_registry()));
         assert!(result.is_ok());
         let tag = result.unwrap();
         assert_eq!(tag.id, 1);
@@ -423,7 +1728,7 @@ mod tests {
 
     #[test]
     fn test_mountain_tag_try_from_valid_nihyakumeizan() {
-        let result = MountainTag::try_from("2".to_string());
+        let result = MountainTag::try_from(("2".to_string(), &create_tag_registry()));
         assert!(result.is_ok());
         let tag = result.unwrap();
         assert_eq!(tag.id, 2);
@@ -432,25 +1737,43 @@ mod tests {
 
     #[test]
     fn test_mountain_tag_try_from_invalid_id() {
-        let result = MountainTag::try_from("3".to_string());
+        let result = MountainTag::try_from(("3".to_string(), &create_tag_registry()));
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "Invalid tag value.");
     }
 
     #[test]
     fn test_mountain_tag_try_from_invalid_string() {
-        let result = MountainTag::try_from("invalid".to_string());
+        let result = MountainTag::try_from(("invalid".to_string(), &create_tag_registry()));
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "Invalid tag value.");
     }
 
     #[test]
     fn test_mountain_tag_try_from_zero() {
-        let result = MountainTag::try_from("0".to_string());
+        let result = MountainTag::try_from(("0".to_string(), &create_tag_registry()));
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "Invalid tag value.");
     }
 
+    #[test]
+    fn test_mountain_tag_try_from_registry_only_tag() {
+        // 組み込みの百名山・二百名山以外のタグもレジストリに登録されていれば解決できる
+        let registry = TagRegistry::new(vec![Tag::new(
+            Id::new(3),
+            "花の百名山".to_string(),
+            "田中澄江が選定した花の百名山".to_string(),
+            "田中澄江『花の百名山』".to_string(),
+        )])
+        .unwrap();
+        let tag = MountainTag::try_from(("3".to_string(), &registry)).unwrap();
+        assert_eq!(tag.id, 3);
+        assert_eq!(tag.name, "花の百名山");
+
+        let result = MountainTag::try_from(("1".to_string(), &registry));
+        assert_eq!(result.unwrap_err().to_string(), "Invalid tag value.");
+    }
+
     #[test]
     fn test_mountain_sort_condition_try_from_id_asc() {
         let result = MountainSortCondition::try_from("id.asc".to_string());
@@ -487,6 +1810,14 @@ mod tests {
         assert_eq!(sort.order.to_value(), -1);
     }
 
+    #[test]
+    fn test_mountain_sort_condition_try_from_relevance() {
+        let sort = MountainSortCondition::try_from("relevance".to_string()).unwrap();
+        assert_eq!(sort.key, MountainSortKey::Relevance);
+        assert_eq!(sort.order.to_value(), -1);
+        assert!(MountainSortCondition::try_from("relevance.asc".to_string()).is_err());
+    }
+
     #[test]
     fn test_mountain_sort_condition_try_from_invalid() {
         let result = MountainSortCondition::try_from("invalid".to_string());
@@ -500,6 +1831,344 @@ mod tests {
         assert_eq!(sort.order.to_value(), 1);
     }
 
+    #[test]
+    fn test_mountain_sort_try_from_multiple_keys() {
+        let sort = MountainSort::try_from("prefecture.asc, elevation.desc".to_string()).unwrap();
+        let keys: Vec<(MountainSortKey, MountainOrderType)> =
+            sort.conditions.iter().map(|c| (c.key, c.order)).collect();
+        assert_eq!(
+            keys,
+            vec![
+                (MountainSortKey::Prefecture, MountainOrderType::Asc),
+                (MountainSortKey::Elevation, MountainOrderType::Desc),
+            ]
+        );
+        assert!(sort.has_cursor());
+        assert_eq!(sort.reverse().primary().order, MountainOrderType::Desc);
+
+        let sort = MountainSort::try_from("relevance".to_string()).unwrap();
+        assert!(!sort.has_cursor());
+
+        assert!(MountainSort::try_from("elevation.asc,elevation.desc".to_string()).is_err());
+        assert!(MountainSort::try_from("relevance,elevation.desc".to_string()).is_err());
+        assert!(MountainSort::try_from("area.asc,".to_string()).is_err());
+        assert!(MountainSort::try_from("distance".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_mountain_prefecture_code_of() {
+        assert_eq!(MountainPrefecture::code_of("長野県"), Some(20));
+        assert_eq!(MountainPrefecture::code_of("長野"), None);
+
+        let names = vec!["静岡県".to_string(), "山梨県".to_string()];
+        assert_eq!(MountainPrefecture::min_code(&names), 19);
+        assert_eq!(MountainPrefecture::min_code(&["不明".to_string()]), 0);
+        assert_eq!(MountainPrefecture::min_code(&[]), 0);
+    }
+
+    #[test]
+    fn test_mountain_cursor_encode_and_decode() {
+        let cursor = MountainCursor {
+            direction: MountainCursorDirection::Next,
+            values: vec![MountainCursorValue::Name("ふじさん|けんがみね".to_string())],
+            id: 396,
+        };
+        let token = cursor.encode();
+        assert!(token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let decoded = MountainCursor::try_from(token).unwrap();
+        assert_eq!(decoded, cursor);
+        assert_eq!(decoded.values[0].key(), MountainSortKey::Name);
+
+        let cursor = MountainCursor {
+            direction: MountainCursorDirection::Prev,
+            values: vec![
+                MountainCursorValue::Prefecture(19),
+                MountainCursorValue::Area("富士山|周辺".to_string()),
+                MountainCursorValue::Latitude(35.36083333333333),
+                MountainCursorValue::Distance(1234.5678901234),
+                MountainCursorValue::Elevation(3776),
+                MountainCursorValue::Id,
+            ],
+            id: 396,
+        };
+        assert_eq!(MountainCursor::try_from(cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_mountain_cursor_new() {
+        let mountain = Mountain {
+            id: Id::new(396),
+            name: "富士山<剣ヶ峯>".to_string(),
+            name_kana: "ふじさん<けんがみね>".to_string(),
+            area: "富士山".to_string(),
+            prefectures: vec!["山梨県".to_string(), "静岡県".to_string()],
+            elevation: 3776,
+            location: MountainLocation::from_coordinates(35.3608, 138.7275),
+            tags: vec![],
+            aliases: vec![],
+            version: 0,
+        };
+
+        let point = GeoPoint::new(35.3606, 138.7274).unwrap();
+        let sort = MountainSort::try_from("prefecture.asc,distance.asc,elevation.desc".to_string())
+            .unwrap()
+            .with_point(point);
+        let cursor = MountainCursor::new(MountainCursorDirection::Next, &sort, &mountain).unwrap();
+        assert_eq!(cursor.id, 396);
+        assert_eq!(
+            cursor.values,
+            vec![
+                MountainCursorValue::Prefecture(19),
+                MountainCursorValue::Distance(point.distance_to(&GeoPoint {
+                    latitude: 35.3608,
+                    longitude: 138.7275,
+                })),
+                MountainCursorValue::Elevation(3776),
+            ]
+        );
+        assert!(sort.matches_cursor(&cursor));
+
+        // キーの順序が異なる並び順には使用できない
+        let other = MountainSort::try_from("elevation.desc,prefecture.asc".to_string()).unwrap();
+        assert!(!other.matches_cursor(&cursor));
+
+        let sort = MountainSort::try_from("relevance".to_string()).unwrap();
+        assert!(MountainCursor::new(MountainCursorDirection::Next, &sort, &mountain).is_none());
+    }
+
+    #[test]
+    fn test_mountain_cursor_try_from_invalid() {
+        assert!(MountainCursor::try_from("invalid!".to_string()).is_err());
+        assert!(MountainCursor::try_from(URL_SAFE_NO_PAD.encode("n|396|ehigh")).is_err());
+        assert!(MountainCursor::try_from(URL_SAFE_NO_PAD.encode("x|396|i")).is_err());
+        assert!(MountainCursor::try_from(URL_SAFE_NO_PAD.encode("n|396")).is_err());
+        assert!(MountainCursor::try_from(URL_SAFE_NO_PAD.encode("n|396|x1")).is_err());
+    }
+
+    #[test]
+    fn test_mountain_name_condition_to_pattern() {
+        let name = MountainNameCondition::contains("富士山(剣ヶ峯).*".to_string());
+        assert_eq!(name.to_pattern(), r"富士山\(剣ヶ峯\)\.\*");
+
+        let name =
+            MountainNameCondition::new("ふじ".to_string(), MountainNameMatchType::Prefix).unwrap();
+        assert_eq!(name.to_pattern(), "^ふじ");
+
+        let name = MountainNameCondition::new("ふじさん".to_string(), MountainNameMatchType::Exact)
+            .unwrap();
+        assert_eq!(name.to_pattern(), "^ふじさん$");
+
+        let name =
+            MountainNameCondition::new("^(富士|浅間)山$".to_string(), MountainNameMatchType::Regex)
+                .unwrap();
+        assert_eq!(name.to_pattern(), "^(富士|浅間)山$");
+    }
+
+    #[test]
+    fn test_mountain_name_condition_to_patterns() {
+        let patterns = MountainNameCondition::contains("ﾌｼﾞｻﾝ".to_string()).to_patterns();
+        assert_eq!(patterns.name, "ﾌｼﾞｻﾝ");
+        assert_eq!(patterns.kana, "ふじさん");
+        assert!(patterns.romaji.is_none());
+
+        let patterns =
+            MountainNameCondition::new("Huzi".to_string(), MountainNameMatchType::Prefix)
+                .unwrap()
+                .to_patterns();
+        assert_eq!(patterns.name, "^Huzi");
+        assert_eq!(patterns.kana, "^ふじ");
+        assert_eq!(patterns.romaji.unwrap(), "^fuji");
+
+        let patterns =
+            MountainNameCondition::new("^ふじ.*".to_string(), MountainNameMatchType::Regex)
+                .unwrap()
+                .to_patterns();
+        assert_eq!(patterns.kana, "^ふじ.*");
+        assert!(patterns.romaji.is_none());
+    }
+
+    #[test]
+    fn test_mountain_name_condition_matched_variant() {
+        let name = MountainNameCondition::contains("槍ケ岳".to_string());
+        assert_eq!(name.to_patterns().folded.unwrap(), "槍ヶ岳");
+        assert_eq!(name.matched_variant("槍ヶ岳").unwrap(), "槍ヶ岳");
+        assert!(name.matched_variant("穂高岳").is_none());
+
+        let name = MountainNameCondition::contains("御岳".to_string());
+        assert_eq!(name.matched_variant("木曽御嶽山").unwrap(), "御嶽");
+
+        let name =
+            MountainNameCondition::new("赤石岳".to_string(), MountainNameMatchType::Exact).unwrap();
+        assert!(name.matched_variant("赤石岳<峯>").is_none());
+
+        let name =
+            MountainNameCondition::new("^丹.".to_string(), MountainNameMatchType::Regex).unwrap();
+        assert_eq!(name.matched_variant("丹沢山").unwrap(), "丹沢");
+    }
+
+    #[test]
+    fn test_mountain_alias_type_try_from() {
+        for key in ["official", "local", "historic", "ainu"] {
+            let alias_type = MountainAliasType::try_from(key.to_string()).unwrap();
+            assert_eq!(alias_type.to_key(), key);
+        }
+        assert!(MountainAliasType::try_from("nickname".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_mountain_name_matcher_is_name_match() {
+        let matcher =
+            MountainNameMatcher::new(&MountainNameCondition::contains("伯耆".to_string())).unwrap();
+        assert!(matcher.is_name_match("伯耆大山", "ほうきだいせん"));
+        assert!(!matcher.is_name_match("大山", "だいせん"));
+
+        let matcher =
+            MountainNameMatcher::new(&MountainNameCondition::contains("Makkari".to_string()))
+                .unwrap();
+        assert!(matcher.is_name_match("マッカリヌプリ", "まっかりぬぷり"));
+
+        let matcher =
+            MountainNameMatcher::new(&MountainNameCondition::contains("御岳".to_string())).unwrap();
+        assert!(matcher.is_name_match("木曽御嶽山", "きそおんたけさん"));
+    }
+
+    #[test]
+    fn test_mountain_suggest_condition_prefixes() {
+        let condition = MountainSuggestCondition::new("ヤリガ".to_string(), 10);
+        assert_eq!(
+            condition.prefixes(),
+            MountainSuggestPrefixes {
+                folded: "ヤリヶ".to_string(),
+                kana: "やりが".to_string(),
+                romaji: None,
+            }
+        );
+
+        let prefixes = MountainSuggestCondition::new("Fuji".to_string(), 10).prefixes();
+        assert_eq!(prefixes.kana, "ふじ");
+        assert_eq!(prefixes.romaji.unwrap(), "fuji");
+
+        // 入力途中の子音はそのまま前方一致させる
+        let prefixes = MountainSuggestCondition::new("Fuj".to_string(), 10).prefixes();
+        assert_eq!(prefixes.kana, "fuj");
+        assert_eq!(prefixes.romaji.unwrap(), "fuj");
+    }
+
+    #[test]
+    fn test_mountain_suggest_condition_rank() {
+        let mountain = |id: i32, name: &str, name_kana: &str, elevation: u32, tags: &[&str]| {
+            let mut data = create_test_mountain_data();
+            data.name = name.to_string();
+            data.name_kana = name_kana.to_string();
+            data.elevation = elevation;
+            data.tags = tags.iter().map(|tag| tag.to_string()).collect();
+            Mountain::new(Id::new(id), data)
+        };
+        let mut aliased = mountain(4, "大山<剣ヶ峰>", "だいせん<けんがみね>", 1729, &[]);
+        aliased.aliases = vec![MountainAlias {
+            name: "伯耆大山".to_string(),
+            name_kana: "ほうきだいせん".to_string(),
+            alias_type: MountainAliasType::Local,
+        }];
+        let candidates = vec![
+            mountain(1, "宝篋山", "ほうきょうさん", 461, &[]),
+            mountain(2, "ほうき山", "ほうきやま", 500, &[]),
+            mountain(3, "伯耆山", "ほうき", 300, &[]),
+            aliased,
+            mountain(5, "朝日岳", "あさひだけ", 2418, &["百名山"]),
+        ];
+
+        let condition = MountainSuggestCondition::new("ほうき".to_string(), 10);
+        assert_eq!(
+            condition.match_quality(&candidates[2]),
+            Some(MountainSuggestMatch::Name)
+        );
+        assert_eq!(
+            condition.match_quality(&candidates[3]),
+            Some(MountainSuggestMatch::AliasPrefix)
+        );
+        assert_eq!(condition.match_quality(&candidates[4]), None);
+
+        let ids: Vec<i32> = condition
+            .rank(candidates.clone())
+            .iter()
+            .map(|m| m.id.value)
+            .collect();
+        assert_eq!(ids, vec![3, 2, 1, 4]);
+
+        let condition = MountainSuggestCondition::new("ほうき".to_string(), 2);
+        assert_eq!(condition.rank(candidates).len(), 2);
+    }
+
+    #[test]
+    fn test_mountain_name_condition_rejects_complex_regex() {
+        let regex = |pattern: &str| {
+            MountainNameCondition::new(pattern.to_string(), MountainNameMatchType::Regex)
+        };
+
+        assert!(regex("(").is_err());
+        assert!(regex("(a+)+$").is_err());
+        assert!(regex("(?:a*b?)*").is_err());
+        assert!(regex(r"(a)\1").is_err());
+        assert!(regex("a+b+c+d+e+f+").is_err());
+        assert!(regex(&"a".repeat(65)).is_err());
+        assert!(regex("a+b+c+d+e+").is_ok());
+
+        // エスケープされる照合方法では検証しない
+        assert!(
+            MountainNameCondition::new("(".to_string(), MountainNameMatchType::Contains).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_mountain_facet_key_try_from() {
+        assert_eq!(
+            MountainFacetKey::try_from("elevation_band".to_string()).unwrap(),
+            MountainFacetKey::ElevationBand
+        );
+        assert_eq!(
+            MountainFacetKey::try_from(" prefecture ".to_string()).unwrap(),
+            MountainFacetKey::Prefecture
+        );
+        assert!(MountainFacetKey::try_from("elevation".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_mountain_facet_count_elevation_band() {
+        let count = MountainFacetCount::elevation_band(1500, 3);
+        assert_eq!(count.value, "1500-1999");
+        assert_eq!(count.count, 3);
+    }
+
+    #[test]
+    fn test_mountain_values_condition_matches() {
+        let prefectures = ["長野県", "富山県"];
+
+        let any = MountainValuesCondition::new(vec!["長野県", "岐阜県"], MountainValuesMode::Any);
+        assert!(any.matches(|p| prefectures.contains(p)));
+
+        let all = MountainValuesCondition::new(vec!["長野県", "岐阜県"], MountainValuesMode::All);
+        assert!(!all.matches(|p| prefectures.contains(p)));
+
+        let all = MountainValuesCondition::new(vec!["長野県", "富山県"], MountainValuesMode::All);
+        assert!(all.matches(|p| prefectures.contains(p)));
+
+        // 値が空の場合はどちらの結合方法でもすべてに一致する
+        let empty: MountainValuesCondition<&str> = Default::default();
+        assert!(empty.matches(|_| false));
+        let empty = MountainValuesCondition::<&str>::new(Vec::new(), MountainValuesMode::All);
+        assert!(empty.matches(|_| false));
+
+        assert_eq!(
+            MountainValuesMode::try_from("all".to_string()).unwrap(),
+            MountainValuesMode::All
+        );
+        assert!(MountainValuesMode::try_from("both".to_string()).is_err());
+    }
+
     #[test]
     fn test_mountain_box_coordinates_try_from_valid() {
         let box_param = "(139.0,35.0),(140.0,36.0)".to_string();
@@ -577,4 +2246,83 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "Invalid box parameter.");
     }
+
+    fn create_test_mountain_data() -> MountainData {
+        MountainData {
+            name: "富士山".to_string(),
+            name_kana: "ふじさん".to_string(),
+            area: "富士山とその周辺".to_string(),
+            prefectures: vec!["山梨県".to_string(), "静岡県".to_string()],
+            elevation: 3776,
+            location: MountainLocation::from_coordinates(35.36083333333333, 138.7275),
+            tags: vec!["百名山".to_string()],
+            aliases: vec![],
+        }
+    }
+
+    #[test]
+    fn test_mountain_location_from_coordinates() {
+        let location = MountainLocation::from_coordinates(35.36083333333333, 138.7275);
+        assert_eq!(
+            location.gsi_url,
+            "https://maps.gsi.go.jp/#15/35.36083333333333/138.7275"
+        );
+    }
+
+    #[test]
+    fn test_mountain_data_validate_valid() {
+        let data = create_test_mountain_data();
+        assert!(data.validate(&create_tag_registry()).is_ok());
+    }
+
+    #[test]
+    fn test_mountain_data_validate_invalid_prefecture_and_tag() {
+        let mut data = create_test_mountain_data();
+        data.prefectures = vec!["山梨".to_string()];
+        data.tags = vec!["千名山".to_string()];
+
+        let errors = data.validate(&create_tag_registry()).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("山梨"));
+        assert!(errors[1].contains("千名山"));
+    }
+
+    #[test]
+    fn test_mountain_data_validate_zero_elevation() {
+        let mut data = create_test_mountain_data();
+        data.elevation = 0;
+        assert_eq!(data.validate(&create_tag_registry()).unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_mountain_data_validate_outside_japan() {
+        let mut data = create_test_mountain_data();
+        // エベレスト
+        data.location = MountainLocation::from_coordinates(27.988056, 86.925278);
+        assert_eq!(data.validate(&create_tag_registry()).unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_mountain_data_validate_required_fields() {
+        let mut data = create_test_mountain_data();
+        data.name = " ".to_string();
+        data.prefectures = vec![];
+        assert_eq!(data.validate(&create_tag_registry()).unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn test_mountain_elevation_range() {
+        let range = MountainElevationRange::new(Some(1500), Some(2500)).unwrap();
+        assert!(range.contains(1500));
+        assert!(range.contains(2500));
+        assert!(!range.contains(1499));
+        assert!(!range.contains(2501));
+
+        let range = MountainElevationRange::new(Some(3000), None).unwrap();
+        assert!(range.contains(3776));
+        assert!(!range.is_unbounded());
+        assert!(MountainElevationRange::default().is_unbounded());
+
+        assert!(MountainElevationRange::new(Some(2000), Some(1000)).is_err());
+    }
 }
@@ -0,0 +1,682 @@
+use crate::model::mountain::{
+    MountainNameCondition, MountainNameMatchType, MountainPrefecture, MountainTag,
+};
+use crate::model::tag::TagRegistry;
+
+/// Upper limits of the length, the nesting of parentheses and the number of conditions of the expression
+///
+/// フィルタ式の長さ、括弧の入れ子の深さ、条件の数の上限
+const MAX_FILTER_LENGTH: usize = 256;
+const MAX_FILTER_DEPTH: usize = 8;
+const MAX_FILTER_CONDITIONS: usize = 20;
+
+/// Filter expression given by the `q` parameter, parsed into a tree of conditions
+///
+/// `q` パラメータで指定されたフィルタ式を条件の木に変換したもの
+#[derive(Debug, Clone, PartialEq)]
+pub enum MountainFilterExpr {
+    And(Vec<MountainFilterExpr>),
+    Or(Vec<MountainFilterExpr>),
+    Not(Box<MountainFilterExpr>),
+    Condition(MountainFilterCondition),
+}
+
+/// Condition on a field of mountains in the filter expression
+///
+/// フィルタ式に含まれる山岳の項目に対する条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum MountainFilterCondition {
+    Elevation(MountainFilterComparison, u32),
+    /// Tag name, which is resolved from the tag id or name
+    Tag(String),
+    /// Prefecture name, which is resolved from the code, name or romaji name
+    Prefecture(String),
+    Area(MountainFilterText),
+    Name(MountainNameCondition),
+}
+
+/// Comparison of the elevation with the value in the filter expression
+///
+/// フィルタ式の値と標高の比較
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MountainFilterComparison {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl MountainFilterComparison {
+    pub fn matches(&self, elevation: u32, value: u32) -> bool {
+        match self {
+            MountainFilterComparison::Eq => elevation == value,
+            MountainFilterComparison::Gt => elevation > value,
+            MountainFilterComparison::Gte => elevation >= value,
+            MountainFilterComparison::Lt => elevation < value,
+            MountainFilterComparison::Lte => elevation <= value,
+        }
+    }
+}
+
+/// Text value in the filter expression, which matches as a prefix when it ends with `*`
+///
+/// フィルタ式の文字列の値で、末尾が `*` の場合は前方一致で照合します
+#[derive(Debug, Clone, PartialEq)]
+pub enum MountainFilterText {
+    Exact(String),
+    Prefix(String),
+}
+
+impl MountainFilterText {
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            MountainFilterText::Exact(text) => value == text,
+            MountainFilterText::Prefix(prefix) => value.starts_with(prefix.as_str()),
+        }
+    }
+
+    /// Returns the regular expression matching the value
+    ///
+    /// 値に一致する正規表現を返します
+    pub fn to_pattern(&self) -> String {
+        match self {
+            MountainFilterText::Exact(text) => format!("^{}$", regex::escape(text)),
+            MountainFilterText::Prefix(prefix) => format!("^{}", regex::escape(prefix)),
+        }
+    }
+}
+
+/// Error in the filter expression at the position counted in characters from 1
+///
+/// フィルタ式の誤りと、その位置 (1 から数えた文字数)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountainFilterError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl MountainFilterError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Operator(Operator),
+    Word(String),
+    Quoted(String),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Operator {
+    Colon,
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+/// Splits the expression into tokens, or returns an error for an unterminated quoted value
+///
+/// フィルタ式を字句に分割します。引用符が閉じられていない場合はエラーを返します
+fn tokenize(expression: &str) -> Result<Vec<Token>, MountainFilterError> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let position = index + 1;
+        let c = chars[index];
+        let kind = match c {
+            _ if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            ':' => TokenKind::Operator(Operator::Colon),
+            '=' => TokenKind::Operator(Operator::Eq),
+            '>' | '<' => {
+                let with_eq = chars.get(index + 1) == Some(&'=');
+                if with_eq {
+                    index += 1;
+                }
+                TokenKind::Operator(match (c, with_eq) {
+                    ('>', true) => Operator::Gte,
+                    ('>', false) => Operator::Gt,
+                    (_, true) => Operator::Lte,
+                    (_, false) => Operator::Lt,
+                })
+            }
+            '"' => {
+                let mut value = String::new();
+                let mut closed = false;
+                index += 1;
+                while index < chars.len() {
+                    match chars[index] {
+                        '\\' if index + 1 < chars.len() => {
+                            value.push(chars[index + 1]);
+                            index += 2;
+                        }
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        other => {
+                            value.push(other);
+                            index += 1;
+                        }
+                    }
+                }
+                if !closed {
+                    return Err(MountainFilterError::new(
+                        position,
+                        "引用符 \" が閉じられていません。",
+                    ));
+                }
+                TokenKind::Quoted(value)
+            }
+            _ => {
+                let start = index;
+                while index + 1 < chars.len() && !is_delimiter(chars[index + 1]) {
+                    index += 1;
+                }
+                let word: String = chars[start..=index].iter().collect();
+                match word.as_str() {
+                    "AND" => TokenKind::And,
+                    "OR" => TokenKind::Or,
+                    "NOT" => TokenKind::Not,
+                    _ => TokenKind::Word(word),
+                }
+            }
+        };
+        tokens.push(Token { kind, position });
+        index += 1;
+    }
+    Ok(tokens)
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '"' | ':' | '=' | '<' | '>')
+}
+
+/// Part of a condition which an error is reported at
+///
+/// 誤りを報告する条件の部分
+enum ConditionPart {
+    Field,
+    Operator,
+    Value,
+}
+
+/// Recursive descent parser of the filter expression, which stops at the first syntax error
+/// and collects the errors of the field values
+///
+/// フィルタ式の再帰下降パーサー。構文の誤りは最初の 1 件で解析を止め、項目の値の誤りはすべて集めます
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    tags: &'a TagRegistry,
+    index: usize,
+    /// Position following the last character, reported when the expression ends unexpectedly
+    end: usize,
+    conditions: usize,
+    errors: Vec<MountainFilterError>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn position(&self) -> usize {
+        self.peek().map(|token| token.position).unwrap_or(self.end)
+    }
+
+    fn parse_or(&mut self, depth: usize) -> Result<MountainFilterExpr, MountainFilterError> {
+        let mut operands = vec![self.parse_and(depth)?];
+        while self.peek().is_some_and(|token| token.kind == TokenKind::Or) {
+            self.next();
+            operands.push(self.parse_and(depth)?);
+        }
+        Ok(match operands.len() {
+            1 => operands.remove(0),
+            _ => MountainFilterExpr::Or(operands),
+        })
+    }
+
+    fn parse_and(&mut self, depth: usize) -> Result<MountainFilterExpr, MountainFilterError> {
+        let mut operands = vec![self.parse_not(depth)?];
+        while self
+            .peek()
+            .is_some_and(|token| token.kind == TokenKind::And)
+        {
+            self.next();
+            operands.push(self.parse_not(depth)?);
+        }
+        Ok(match operands.len() {
+            1 => operands.remove(0),
+            _ => MountainFilterExpr::And(operands),
+        })
+    }
+
+    fn parse_not(&mut self, depth: usize) -> Result<MountainFilterExpr, MountainFilterError> {
+        if self
+            .peek()
+            .is_some_and(|token| token.kind == TokenKind::Not)
+        {
+            self.next();
+            let operand = self.parse_not(depth)?;
+            return Ok(MountainFilterExpr::Not(Box::new(operand)));
+        }
+        self.parse_primary(depth)
+    }
+
+    fn parse_primary(&mut self, depth: usize) -> Result<MountainFilterExpr, MountainFilterError> {
+        let position = self.position();
+        match self.next().map(|token| token.kind) {
+            Some(TokenKind::LParen) => {
+                if depth >= MAX_FILTER_DEPTH {
+                    return Err(MountainFilterError::new(
+                        position,
+                        format!("括弧の入れ子は {} 段までにしてください。", MAX_FILTER_DEPTH),
+                    ));
+                }
+                let expr = self.parse_or(depth + 1)?;
+                match self.next().map(|token| token.kind) {
+                    Some(TokenKind::RParen) => Ok(expr),
+                    _ => Err(MountainFilterError::new(
+                        position,
+                        "括弧 ( に対応する ) がありません。",
+                    )),
+                }
+            }
+            Some(TokenKind::Word(field)) => self.parse_condition(field, position),
+            _ => Err(MountainFilterError::new(
+                position,
+                "条件 (例: elevation>=3000) を指定してください。",
+            )),
+        }
+    }
+
+    fn parse_condition(
+        &mut self,
+        field: String,
+        position: usize,
+    ) -> Result<MountainFilterExpr, MountainFilterError> {
+        self.conditions += 1;
+        if self.conditions > MAX_FILTER_CONDITIONS {
+            return Err(MountainFilterError::new(
+                position,
+                format!("条件は {} 個までにしてください。", MAX_FILTER_CONDITIONS),
+            ));
+        }
+
+        let operator_position = self.position();
+        let Some(TokenKind::Operator(operator)) = self.next().map(|token| token.kind) else {
+            return Err(MountainFilterError::new(
+                operator_position,
+                format!(
+                    "{} の後に演算子 (:、=、>、>=、<、<=) を指定してください。",
+                    field
+                ),
+            ));
+        };
+        let value_position = self.position();
+        let value = match self.next().map(|token| token.kind) {
+            Some(TokenKind::Word(value)) | Some(TokenKind::Quoted(value)) if !value.is_empty() => {
+                value
+            }
+            _ => {
+                return Err(MountainFilterError::new(
+                    value_position,
+                    format!("{} の値を指定してください。", field),
+                ))
+            }
+        };
+
+        let condition = match self.to_condition(&field, operator, value) {
+            Ok(condition) => condition,
+            Err((part, message)) => {
+                let position = match part {
+                    ConditionPart::Field => position,
+                    ConditionPart::Operator => operator_position,
+                    ConditionPart::Value => value_position,
+                };
+                self.errors
+                    .push(MountainFilterError::new(position, message));
+                // 値の誤りは集めて返すため、解析は続ける
+                return Ok(MountainFilterExpr::And(vec![]));
+            }
+        };
+        Ok(MountainFilterExpr::Condition(condition))
+    }
+
+    /// Returns the condition, or the wrong part of it with the message
+    ///
+    /// 条件を返します。誤りがある場合は誤った部分とメッセージを返します
+    fn to_condition(
+        &self,
+        field: &str,
+        operator: Operator,
+        value: String,
+    ) -> Result<MountainFilterCondition, (ConditionPart, String)> {
+        if field == "elevation" {
+            let comparison = match operator {
+                Operator::Colon | Operator::Eq => MountainFilterComparison::Eq,
+                Operator::Gt => MountainFilterComparison::Gt,
+                Operator::Gte => MountainFilterComparison::Gte,
+                Operator::Lt => MountainFilterComparison::Lt,
+                Operator::Lte => MountainFilterComparison::Lte,
+            };
+            let elevation = value.parse::<u32>().map_err(|_| {
+                (
+                    ConditionPart::Value,
+                    "elevation には 0 以上の整数を指定してください。".to_string(),
+                )
+            })?;
+            return Ok(MountainFilterCondition::Elevation(comparison, elevation));
+        }
+
+        if !matches!(field, "tag" | "pref" | "area" | "name") {
+            return Err((
+                ConditionPart::Field,
+                format!(
+                    "{} は指定できない項目です。elevation、tag、pref、area、name のいずれかを指定してください。",
+                    field
+                ),
+            ));
+        }
+        if !matches!(operator, Operator::Colon | Operator::Eq) {
+            return Err((
+                ConditionPart::Operator,
+                format!("{} には : または = を指定してください。", field),
+            ));
+        }
+
+        let prefix = value.strip_suffix('*');
+        let text = prefix.unwrap_or(&value);
+        let allows_prefix = matches!(field, "area" | "name");
+        if text.contains('*') || (prefix.is_some() && !allows_prefix) || text.is_empty() {
+            return Err((
+                ConditionPart::Value,
+                "* は area と name の値の末尾にのみ指定できます。".to_string(),
+            ));
+        }
+
+        match field {
+            "tag" => {
+                if self.tags.contains_name(text) {
+                    return Ok(MountainFilterCondition::Tag(text.to_string()));
+                }
+                MountainTag::try_from((text.to_string(), self.tags))
+                    .map(|tag| MountainFilterCondition::Tag(tag.name))
+                    .map_err(|_| {
+                        (
+                            ConditionPart::Value,
+                            format!("タグ {} が見つかりません。", text),
+                        )
+                    })
+            }
+            "pref" => MountainPrefecture::try_from(text.to_string())
+                .map(|prefecture| MountainFilterCondition::Prefecture(prefecture.name))
+                .map_err(|_| {
+                    (
+                        ConditionPart::Value,
+                        format!("都道府県 {} が見つかりません。", text),
+                    )
+                }),
+            "area" => Ok(MountainFilterCondition::Area(match prefix {
+                Some(prefix) => MountainFilterText::Prefix(prefix.to_string()),
+                None => MountainFilterText::Exact(value),
+            })),
+            _ => {
+                let match_type = match prefix {
+                    Some(_) => MountainNameMatchType::Prefix,
+                    None => MountainNameMatchType::Contains,
+                };
+                Ok(MountainFilterCondition::Name(MountainNameCondition {
+                    value: text.to_string(),
+                    match_type,
+                }))
+            }
+        }
+    }
+}
+
+impl MountainFilterExpr {
+    /// Parses the filter expression, resolving the tags against the registry and returning all
+    /// the errors found with their positions
+    ///
+    /// フィルタ式を解析します。タグはタグレジストリから解決し、誤りがある場合は見つかったすべての誤りを位置とともに返します
+    pub fn parse(expression: &str, tags: &TagRegistry) -> Result<Self, Vec<MountainFilterError>> {
+        let length = expression.chars().count();
+        if length > MAX_FILTER_LENGTH {
+            return Err(vec![MountainFilterError::new(
+                MAX_FILTER_LENGTH + 1,
+                format!("{} 文字以下で指定してください。", MAX_FILTER_LENGTH),
+            )]);
+        }
+
+        let tokens = tokenize(expression).map_err(|error| vec![error])?;
+        let mut parser = Parser {
+            tokens,
+            tags,
+            index: 0,
+            end: length + 1,
+            conditions: 0,
+            errors: Vec::new(),
+        };
+        let expr = parser.parse_or(0).map_err(|error| vec![error])?;
+        if let Some(token) = parser.peek() {
+            let message = match token.kind {
+                TokenKind::RParen => "括弧 ) に対応する ( がありません。",
+                _ => "条件の間に AND または OR を指定してください。",
+            };
+            return Err(vec![MountainFilterError::new(token.position, message)]);
+        }
+
+        if !parser.errors.is_empty() {
+            return Err(parser.errors);
+        }
+        Ok(expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::tag::Tag;
+    use crate::model::Id;
+
+    fn create_tag_registry() -> TagRegistry {
+        TagRegistry::new(vec![
+            Tag::new(
+                Id::new(1),
+                "百名山".to_string(),
+                "深田久弥が選定した日本百名山".to_string(),
+                "深田久弥『日本百名山』".to_string(),
+            ),
+            Tag::new(
+                Id::new(2),
+                "二百名山".to_string(),
+                "深田クラブが選定した日本二百名山".to_string(),
+                "深田クラブ『日本二百名山』".to_string(),
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn parse_errors(expression: &str) -> Vec<(usize, String)> {
+        MountainFilterExpr::parse(expression, &create_tag_registry())
+            .unwrap_err()
+            .into_iter()
+            .map(|error| (error.position, error.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_mountain_filter_expr_parse() {
+        let expr = MountainFilterExpr::parse(
+            r#"elevation>=3000 AND (tag:百名山 OR tag:2) AND pref:長野県 AND area:"飛騨山脈*""#,
+            &create_tag_registry(),
+        )
+        .unwrap();
+        assert_eq!(
+            expr,
+            MountainFilterExpr::And(vec![
+                MountainFilterExpr::Condition(MountainFilterCondition::Elevation(
+                    MountainFilterComparison::Gte,
+                    3000
+                )),
+                MountainFilterExpr::Or(vec![
+                    MountainFilterExpr::Condition(MountainFilterCondition::Tag(
+                        "百名山".to_string()
+                    )),
+                    MountainFilterExpr::Condition(MountainFilterCondition::Tag(
+                        "二百名山".to_string()
+                    )),
+                ]),
+                MountainFilterExpr::Condition(MountainFilterCondition::Prefecture(
+                    "長野県".to_string()
+                )),
+                MountainFilterExpr::Condition(MountainFilterCondition::Area(
+                    MountainFilterText::Prefix("飛騨山脈".to_string())
+                )),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_mountain_filter_expr_parse_precedence() {
+        // AND は OR より先に結合し、NOT はさらに先に結合する
+        let expr = MountainFilterExpr::parse(
+            "name:槍* OR NOT pref:nagano AND elevation<1000",
+            &create_tag_registry(),
+        )
+        .unwrap();
+        assert_eq!(
+            expr,
+            MountainFilterExpr::Or(vec![
+                MountainFilterExpr::Condition(MountainFilterCondition::Name(
+                    MountainNameCondition {
+                        value: "槍".to_string(),
+                        match_type: MountainNameMatchType::Prefix,
+                    }
+                )),
+                MountainFilterExpr::And(vec![
+                    MountainFilterExpr::Not(Box::new(MountainFilterExpr::Condition(
+                        MountainFilterCondition::Prefecture("長野県".to_string())
+                    ))),
+                    MountainFilterExpr::Condition(MountainFilterCondition::Elevation(
+                        MountainFilterComparison::Lt,
+                        1000
+                    )),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_mountain_filter_expr_parse_syntax_errors() {
+        assert_eq!(
+            parse_errors("elevation>=3000 AND (tag:百名山"),
+            vec![(21, "括弧 ( に対応する ) がありません。".to_string())]
+        );
+        assert_eq!(
+            parse_errors("tag:百名山 pref:長野県"),
+            vec![(
+                9,
+                "条件の間に AND または OR を指定してください。".to_string()
+            )]
+        );
+        assert_eq!(
+            parse_errors("elevation>=3000 AND"),
+            vec![(
+                20,
+                "条件 (例: elevation>=3000) を指定してください。".to_string()
+            )]
+        );
+        assert_eq!(
+            parse_errors(r#"area:"飛騨"#),
+            vec![(6, "引用符 \" が閉じられていません。".to_string())]
+        );
+        assert_eq!(
+            parse_errors("elevation 3000"),
+            vec![(
+                11,
+                "elevation の後に演算子 (:、=、>、>=、<、<=) を指定してください。".to_string()
+            )]
+        );
+        assert_eq!(parse_errors("tag:百名山)")[0].0, 8);
+    }
+
+    #[test]
+    fn test_mountain_filter_expr_parse_value_errors() {
+        // 値の誤りはすべて位置とともに返す
+        assert_eq!(
+            parse_errors("elevation>=high OR height:3000 OR pref:火星 OR tag>1 OR pref:長野*"),
+            vec![
+                (
+                    12,
+                    "elevation には 0 以上の整数を指定してください。".to_string()
+                ),
+                (
+                    20,
+                    "height は指定できない項目です。elevation、tag、pref、area、name のいずれかを指定してください。".to_string()
+                ),
+                (40, "都道府県 火星 が見つかりません。".to_string()),
+                (49, "tag には : または = を指定してください。".to_string()),
+                (
+                    60,
+                    "* は area と name の値の末尾にのみ指定できます。".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mountain_filter_expr_parse_limits() {
+        let nested = format!("{}tag:1{}", "(".repeat(9), ")".repeat(9));
+        assert_eq!(parse_errors(&nested)[0].0, 9);
+
+        let conditions = vec!["tag:1"; 21].join(" OR ");
+        assert_eq!(parse_errors(&conditions)[0].0, 181);
+
+        let long = "a".repeat(257);
+        assert_eq!(parse_errors(&long)[0].0, 257);
+    }
+
+    #[test]
+    fn test_mountain_filter_text_matches() {
+        let text = MountainFilterText::Prefix("飛騨山脈".to_string());
+        assert!(text.matches("飛騨山脈南部"));
+        assert!(!text.matches("木曽山脈"));
+        assert_eq!(text.to_pattern(), "^飛騨山脈");
+
+        let text = MountainFilterText::Exact("a.b".to_string());
+        assert!(!text.matches("a.bc"));
+        assert_eq!(text.to_pattern(), r"^a\.b$");
+    }
+}
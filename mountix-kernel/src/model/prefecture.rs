@@ -0,0 +1,44 @@
+use crate::model::mountain::Mountain;
+use crate::model::{ErrorCode, Id};
+
+const ERR_MESSAGE_PREFECTURE_FIND_EXCEPTION: &str = "都道府県情報を検索中にエラーが発生しました。";
+
+/// Aggregated mountains of a prefecture
+///
+/// 都道府県ごとの山岳の集計結果
+#[derive(Debug)]
+pub struct PrefectureMountainStats {
+    pub name: String,
+    pub mountain_count: u64,
+    pub highest_peak: Option<PrefectureHighestPeak>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefectureHighestPeak {
+    pub id: Id<Mountain>,
+    pub name: String,
+    pub elevation: u32,
+}
+
+#[derive(Debug)]
+pub struct PrefectureFindException {
+    pub error_code: ErrorCode,
+    pub messages: Vec<String>,
+}
+
+impl PrefectureFindException {
+    /// Returns an error including exception error messages
+    ///
+    /// 検索時の例外エラーメッセージを含むエラーを生成します
+    ///
+    /// # Arguments
+    ///
+    /// - `error_code`: Error code
+    pub fn new_with_error_code(error_code: ErrorCode) -> Self {
+        let messages = vec![ERR_MESSAGE_PREFECTURE_FIND_EXCEPTION.to_string()];
+        Self {
+            error_code,
+            messages,
+        }
+    }
+}
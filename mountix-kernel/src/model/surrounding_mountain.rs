@@ -1,4 +1,8 @@
-use crate::model::mountain::Mountain;
+use crate::model::geo::{GeoLineString, GeoPoint};
+use crate::model::mountain::{
+    Mountain, MountainElevationRange, MountainNameCondition, MountainOrderType, MountainPrefecture,
+    MountainSortCondition, MountainSortKey, MountainTag,
+};
 use crate::model::{ErrorCode, Id};
 use std::env;
 use std::ffi::OsString;
@@ -58,8 +62,16 @@ impl SurroundingMountainLocation {
             gsi_url,
         }
     }
+
+    pub fn point(&self) -> GeoPoint {
+        GeoPoint {
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct SurroundingMountainSearchDistance(pub u32);
 
 impl SurroundingMountainSearchDistance {
@@ -82,17 +94,162 @@ impl Default for SurroundingMountainSearchDistance {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct SurroundingMountainSearchCondition {
     pub mountain: Mountain,
     pub distance: SurroundingMountainSearchDistance,
+    pub prefecture: Option<MountainPrefecture>,
+    pub tag: Option<MountainTag>,
+    pub elevation: MountainElevationRange,
+    pub skip: u64,
+    pub limit: Option<i64>,
+    pub sort: SurroundingMountainSortCondition,
 }
 
 impl SurroundingMountainSearchCondition {
     pub fn new(mountain: Mountain, distance: SurroundingMountainSearchDistance) -> Self {
-        Self { mountain, distance }
+        Self {
+            mountain,
+            distance,
+            prefecture: None,
+            tag: None,
+            elevation: MountainElevationRange::default(),
+            skip: 0,
+            limit: None,
+            sort: SurroundingMountainSortCondition::default(),
+        }
+    }
+
+    /// Narrows the surroundings down to the prefecture and the tag
+    ///
+    /// 周辺の山岳を都道府県とタグで絞り込みます
+    pub fn with_filter(
+        mut self,
+        prefecture: Option<MountainPrefecture>,
+        tag: Option<MountainTag>,
+    ) -> Self {
+        self.prefecture = prefecture;
+        self.tag = tag;
+        self
+    }
+
+    /// Sets the page and the sort order of the surroundings
+    ///
+    /// 周辺の山岳の取得範囲と並び順を設定します
+    pub fn with_page(
+        mut self,
+        skip: u64,
+        limit: Option<i64>,
+        sort: SurroundingMountainSortCondition,
+    ) -> Self {
+        self.skip = skip;
+        self.limit = limit;
+        self.sort = sort;
+        self
+    }
+
+    /// Narrows the surroundings down to the elevation range
+    ///
+    /// 周辺の山岳を標高の範囲で絞り込みます
+    pub fn with_elevation(mut self, elevation: MountainElevationRange) -> Self {
+        self.elevation = elevation;
+        self
+    }
+}
+
+/// Sort key of the surroundings, which can also be the distance from the center mountain
+///
+/// 周辺の山岳の並び替えのキーで、中心の山岳からの距離も指定できる
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SurroundingMountainSortKey {
+    Distance,
+    Mountain(MountainSortKey),
+}
+
+impl SurroundingMountainSortKey {
+    pub fn to_key(&self) -> String {
+        match self {
+            SurroundingMountainSortKey::Distance => "distance".to_string(),
+            SurroundingMountainSortKey::Mountain(key) => key.to_key(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct SurroundingMountainSortCondition {
+    pub key: SurroundingMountainSortKey,
+    pub order: MountainOrderType,
+}
+
+impl Default for SurroundingMountainSortCondition {
+    fn default() -> Self {
+        Self {
+            key: SurroundingMountainSortKey::Distance,
+            order: MountainOrderType::Asc,
+        }
+    }
+}
+
+impl TryFrom<String> for SurroundingMountainSortCondition {
+    type Error = ();
+
+    fn try_from(sort_param: String) -> Result<Self, Self::Error> {
+        match sort_param.as_str() {
+            "distance.asc" => Ok(SurroundingMountainSortCondition {
+                key: SurroundingMountainSortKey::Distance,
+                order: MountainOrderType::Asc,
+            }),
+            "distance.desc" => Ok(SurroundingMountainSortCondition {
+                key: SurroundingMountainSortKey::Distance,
+                order: MountainOrderType::Desc,
+            }),
+            // 周辺の山岳は $geoNear の後に並べ替えるため、ドキュメントに保存された項目のキーのみ指定できる
+            _ => match MountainSortCondition::try_from(sort_param)? {
+                sort if !sort.key.is_stored() => Err(()),
+                sort => Ok(SurroundingMountainSortCondition {
+                    key: SurroundingMountainSortKey::Mountain(sort.key),
+                    order: sort.order,
+                }),
+            },
+        }
     }
 }
 
+/// Condition to search mountains around an arbitrary point
+///
+/// 任意の地点の周辺の山岳を検索する条件
+pub struct NearbyMountainSearchCondition {
+    pub point: GeoPoint,
+    pub distance: SurroundingMountainSearchDistance,
+    pub name: Option<MountainNameCondition>,
+    pub tag: Option<MountainTag>,
+}
+
+/// Condition to search the k nearest mountains to a point, without a distance limit
+///
+/// 任意の地点に最も近い k 件の山岳を距離の制限なしで検索する条件
+pub struct NearestMountainSearchCondition {
+    pub point: GeoPoint,
+    pub k: u32,
+    pub tag: Option<MountainTag>,
+    pub elevation: MountainElevationRange,
+    /// Mountain excluded from the results, e.g. the mountain used as the point
+    ///
+    /// 検索結果から除外する山岳 (基準にした山岳など)
+    pub exclude_id: Option<Id<Mountain>>,
+}
+
+/// Condition to search mountains within the buffer distance of a line
+///
+/// ラインから指定した距離の範囲内にある山岳を検索する条件
+pub struct CorridorMountainSearchCondition {
+    pub line: GeoLineString,
+    pub buffer: SurroundingMountainSearchDistance,
+    pub name: Option<MountainNameCondition>,
+    pub tag: Option<MountainTag>,
+    pub elevation: MountainElevationRange,
+}
+
 #[derive(Debug)]
 pub struct SurroundingMountainFindException {
     pub error_code: ErrorCode,
@@ -140,6 +297,7 @@ mod tests {
             elevation: 3776,
             location,
             tags: vec!["百名山".to_string()],
+            aliases: vec![],
         };
         Mountain::new(id, data)
     }
@@ -174,6 +332,22 @@ mod tests {
 
         assert_eq!(condition.mountain.id.value, 1);
         assert_eq!(condition.distance.0, 15000);
+        assert_eq!(condition.skip, 0);
+        assert_eq!(condition.sort.key, SurroundingMountainSortKey::Distance);
+    }
+
+    #[test]
+    fn test_surrounding_mountain_sort_condition_try_from() {
+        let sort = SurroundingMountainSortCondition::try_from("distance.desc".to_string()).unwrap();
+        assert_eq!(sort.key.to_key(), "distance");
+        assert_eq!(sort.order.to_value(), -1);
+
+        let sort = SurroundingMountainSortCondition::try_from("elevation.asc".to_string()).unwrap();
+        assert_eq!(sort.key.to_key(), "elevation");
+        assert_eq!(sort.order.to_value(), 1);
+
+        assert!(SurroundingMountainSortCondition::try_from("distance".to_string()).is_err());
+        assert!(SurroundingMountainSortCondition::try_from("relevance".to_string()).is_err());
     }
 
     #[test]
@@ -0,0 +1,124 @@
+use crate::model::{ErrorCode, Id};
+
+const ERR_MESSAGE_TAG_FIND_EXCEPTION: &str = "タグ情報を検索中にエラーが発生しました。";
+
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub id: Id<Tag>,
+    pub name: String,
+    pub description: String,
+    pub source: String,
+}
+
+impl Tag {
+    pub fn new(id: Id<Tag>, name: String, description: String, source: String) -> Self {
+        Self {
+            id,
+            name,
+            description,
+            source,
+        }
+    }
+}
+
+/// Snapshot of the tag collection used to resolve tags without accessing the repository
+///
+/// リポジトリにアクセスせずにタグを解決するための、タグコレクションのスナップショット
+#[derive(Debug, Clone)]
+pub struct TagRegistry {
+    tags: Vec<Tag>,
+}
+
+impl TagRegistry {
+    /// Returns a registry of the tags, or an error if there are no tags
+    ///
+    /// タグの一覧からレジストリを生成します (タグが 1 件もない場合はエラー)
+    pub fn new(tags: Vec<Tag>) -> anyhow::Result<Self> {
+        if tags.is_empty() {
+            return Err(anyhow::anyhow!("The tag collection is empty."));
+        }
+        Ok(Self { tags })
+    }
+
+    pub fn find_by_id(&self, id: i32) -> Option<&Tag> {
+        self.tags.iter().find(|tag| tag.id.value == id)
+    }
+
+    pub fn contains_name(&self, name: &str) -> bool {
+        self.tags.iter().any(|tag| tag.name == name)
+    }
+}
+
+#[derive(Debug)]
+pub struct TagGetException {
+    pub error_code: ErrorCode,
+}
+
+impl TagGetException {
+    pub fn new(error_code: ErrorCode) -> Self {
+        Self { error_code }
+    }
+}
+
+#[derive(Debug)]
+pub struct TagFindException {
+    pub error_code: ErrorCode,
+    pub messages: Vec<String>,
+}
+
+impl TagFindException {
+    /// Returns an error including exception error messages
+    ///
+    /// 検索時の例外エラーメッセージを含むエラーを生成します
+    ///
+    /// # Arguments
+    ///
+    /// - `error_code`: Error code
+    pub fn new_with_error_code(error_code: ErrorCode) -> Self {
+        let messages = vec![ERR_MESSAGE_TAG_FIND_EXCEPTION.to_string()];
+        Self {
+            error_code,
+            messages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_registry() {
+        let registry = TagRegistry::new(vec![
+            Tag::new(
+                Id::new(1),
+                "百名山".to_string(),
+                "深田久弥が選定した日本百名山".to_string(),
+                "深田久弥『日本百名山』".to_string(),
+            ),
+            Tag::new(
+                Id::new(2),
+                "二百名山".to_string(),
+                "深田クラブが選定した日本二百名山".to_string(),
+                "深田クラブ『日本二百名山』".to_string(),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(registry.find_by_id(2).unwrap().name, "二百名山");
+        assert!(registry.contains_name("百名山"));
+        assert!(!registry.contains_name("三百名山"));
+        assert!(registry.find_by_id(0).is_none());
+    }
+
+    #[test]
+    fn test_tag_registry_empty() {
+        assert!(TagRegistry::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_tag_find_exception_with_error_code() {
+        let exception = TagFindException::new_with_error_code(ErrorCode::ServerError);
+        assert_eq!(exception.error_code, ErrorCode::ServerError);
+        assert_eq!(exception.messages, vec![ERR_MESSAGE_TAG_FIND_EXCEPTION]);
+    }
+}
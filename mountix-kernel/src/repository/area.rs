@@ -0,0 +1,10 @@
+use crate::model::area::Area;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait AreaRepository {
+    /// Returns all areas ordered by name
+    ///
+    /// 全ての山域を名称順に返します
+    async fn find(&self) -> anyhow::Result<Vec<Area>>;
+}
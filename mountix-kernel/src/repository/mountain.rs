@@ -1,18 +1,54 @@
 use crate::model::Id;
 use async_trait::async_trait;
 
-use crate::model::mountain::{Mountain, MountainBoxSearchCondition, MountainSearchCondition};
+use crate::model::mountain::{
+    Mountain, MountainBoxSearchCondition, MountainData, MountainPage,
+    MountainPolygonSearchCondition, MountainSearchCondition, MountainSuggestCondition,
+    MountainSuggestion,
+};
 
 #[async_trait]
 pub trait MountainRepository {
     async fn get(&self, id: Id<Mountain>) -> anyhow::Result<Option<Mountain>>;
     async fn get_count(&self, search_condition: MountainSearchCondition) -> anyhow::Result<u64>;
-    async fn find(
-        &self,
-        search_condition: MountainSearchCondition,
-    ) -> anyhow::Result<Vec<Mountain>>;
+    /// Returns a page of mountains together with the number and the facet counts of all the matched mountains
+    ///
+    /// 取得範囲の山岳と、取得範囲に関係なく条件に一致した山岳の件数およびファセットごとの件数を 1 回の検索で返します
+    async fn find(&self, search_condition: MountainSearchCondition)
+        -> anyhow::Result<MountainPage>;
+    /// Returns a page of mountains inside the box together with the number and the facet counts of all of them
+    ///
+    /// 取得範囲のボックス内の山岳と、取得範囲に関係なくボックス内で条件に一致した山岳の件数およびファセットごとの件数を 1 回の検索で返します
     async fn find_box(
         &self,
         search_condition: MountainBoxSearchCondition,
-    ) -> anyhow::Result<Vec<Mountain>>;
+    ) -> anyhow::Result<MountainPage>;
+    /// Returns a page of mountains inside the polygon together with the number of all of them
+    ///
+    /// 取得範囲のポリゴン内の山岳と、取得範囲に関係なくポリゴン内で条件に一致した山岳の件数を 1 回の検索で返します
+    async fn find_polygon(
+        &self,
+        search_condition: MountainPolygonSearchCondition,
+    ) -> anyhow::Result<MountainPage>;
+    /// Returns up to `limit` mountains whose names or aliases start with the query, in the ranked order
+    ///
+    /// 山名、山名かな、山名ローマ字、別名のいずれかが入力値で始まる山岳を、順位の順に最大 `limit` 件返します
+    async fn suggest(
+        &self,
+        search_condition: MountainSuggestCondition,
+    ) -> anyhow::Result<Vec<MountainSuggestion>>;
+    async fn create(&self, data: MountainData) -> anyhow::Result<Mountain>;
+    /// Replaces the mountain only when its current version matches, returning `None` otherwise
+    ///
+    /// 現在のバージョンが一致する場合のみ山岳情報を置き換えます。一致しない場合は `None` を返します
+    async fn update(
+        &self,
+        id: Id<Mountain>,
+        data: MountainData,
+        version: u32,
+    ) -> anyhow::Result<Option<Mountain>>;
+    /// Deletes the mountain only when its current version matches, returning whether it was deleted
+    ///
+    /// 現在のバージョンが一致する場合のみ山岳情報を削除し、削除したかどうかを返します
+    async fn delete(&self, id: Id<Mountain>, version: u32) -> anyhow::Result<bool>;
 }
@@ -0,0 +1,10 @@
+use crate::model::prefecture::PrefectureMountainStats;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait PrefectureRepository {
+    /// Returns the mountain count and highest peak of each prefecture that has mountains
+    ///
+    /// 山岳が存在する都道府県ごとに、山岳の件数と最高峰を返します
+    async fn find_stats(&self) -> anyhow::Result<Vec<PrefectureMountainStats>>;
+}
@@ -1,10 +1,45 @@
-use crate::model::surrounding_mountain::{SurroundingMountain, SurroundingMountainSearchCondition};
+use crate::model::surrounding_mountain::{
+    CorridorMountainSearchCondition, NearbyMountainSearchCondition, NearestMountainSearchCondition,
+    SurroundingMountain, SurroundingMountainSearchCondition,
+};
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait SurroundingMountainRepository {
+    /// Returns the number of mountains around the center mountain, regardless of the page
+    ///
+    /// 取得範囲に関係なく中心の山岳の周辺の山岳の件数を返します
+    async fn get_count(
+        &self,
+        search_condition: SurroundingMountainSearchCondition,
+    ) -> anyhow::Result<u64>;
+
     async fn find(
         &self,
         search_condition: SurroundingMountainSearchCondition,
     ) -> anyhow::Result<Vec<SurroundingMountain>>;
+
+    /// Returns mountains within the distance from the point, ordered by distance
+    ///
+    /// 指定した地点から距離の範囲内にある山岳を距離の近い順に返します
+    async fn find_nearby(
+        &self,
+        search_condition: NearbyMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>>;
+
+    /// Returns the k nearest mountains to the point, ordered by distance
+    ///
+    /// 指定した地点に最も近い k 件の山岳を距離の近い順に返します
+    async fn find_nearest(
+        &self,
+        search_condition: NearestMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>>;
+
+    /// Returns mountains within the buffer distance of the line, ordered by the position along the line
+    ///
+    /// ラインから指定した距離の範囲内にある山岳をライン上の位置の順に返します
+    async fn find_corridor(
+        &self,
+        search_condition: CorridorMountainSearchCondition,
+    ) -> anyhow::Result<Vec<SurroundingMountain>>;
 }
@@ -0,0 +1,15 @@
+use crate::model::tag::Tag;
+use crate::model::Id;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+
+#[async_trait]
+pub trait TagRepository {
+    async fn get(&self, id: Id<Tag>) -> anyhow::Result<Option<Tag>>;
+    async fn find(&self) -> anyhow::Result<Vec<Tag>>;
+
+    /// Returns the mountain count of each tag name that has mountains
+    ///
+    /// 山岳が存在するタグ名ごとに、山岳の件数を返します
+    async fn count_mountains(&self) -> anyhow::Result<BTreeMap<String, u64>>;
+}